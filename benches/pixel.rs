@@ -0,0 +1,141 @@
+#[macro_use]
+extern crate criterion;
+extern crate decklink;
+
+use criterion::{BenchmarkId, Criterion, Throughput};
+use decklink::pixel::{Isa, Kernels};
+use decklink::PixelFormat;
+use std::hint::black_box;
+
+const SIZES: [(usize, usize); 2] = [(1920, 1080), (3840, 2160)];
+
+fn kernels() -> Vec<Kernels> {
+    Isa::ALL
+        .iter()
+        .filter_map(|isa| Kernels::new(*isa))
+        .collect()
+}
+
+fn id(k: &Kernels, width: usize, height: usize) -> BenchmarkId {
+    BenchmarkId::new(format!("{:?}", k.isa()), format!("{}x{}", width, height))
+}
+
+fn uyvy(c: &mut Criterion) {
+    let mut group = c.benchmark_group("uyvy_to_i420");
+    for &(width, height) in SIZES.iter() {
+        let src = vec![0x80u8; width * 2 * height];
+        let mut dst = vec![0u8; width * height * 3 / 2];
+        group.throughput(Throughput::Elements((width * height) as u64));
+        for k in kernels() {
+            group.bench_function(id(&k, width, height), |b| {
+                b.iter(|| k.uyvy_to_i420(black_box(&src), width * 2, width, height, &mut dst))
+            });
+        }
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("uyvy_to_nv12");
+    for &(width, height) in SIZES.iter() {
+        let src = vec![0x80u8; width * 2 * height];
+        let mut dst = vec![0u8; width * height * 3 / 2];
+        group.throughput(Throughput::Elements((width * height) as u64));
+        for k in kernels() {
+            group.bench_function(id(&k, width, height), |b| {
+                b.iter(|| k.uyvy_to_nv12(black_box(&src), width * 2, width, height, &mut dst))
+            });
+        }
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("i420_to_uyvy");
+    for &(width, height) in SIZES.iter() {
+        let src = vec![0x80u8; width * height * 3 / 2];
+        let mut dst = vec![0u8; width * 2 * height];
+        group.throughput(Throughput::Elements((width * height) as u64));
+        for k in kernels() {
+            group.bench_function(id(&k, width, height), |b| {
+                b.iter(|| k.i420_to_uyvy(black_box(&src), width, height, &mut dst, width * 2))
+            });
+        }
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("nv12_to_uyvy");
+    for &(width, height) in SIZES.iter() {
+        let src = vec![0x80u8; width * height * 3 / 2];
+        let mut dst = vec![0u8; width * 2 * height];
+        group.throughput(Throughput::Elements((width * height) as u64));
+        for k in kernels() {
+            group.bench_function(id(&k, width, height), |b| {
+                b.iter(|| k.nv12_to_uyvy(black_box(&src), width, height, &mut dst, width * 2))
+            });
+        }
+    }
+    group.finish();
+}
+
+fn bgra(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bgra_to_uyvy");
+    for &(width, height) in SIZES.iter() {
+        let src = vec![0x80u8; width * 4 * height];
+        let mut dst = vec![0u8; width * 2 * height];
+        group.throughput(Throughput::Elements((width * height) as u64));
+        for k in kernels() {
+            group.bench_function(id(&k, width, height), |b| {
+                b.iter(|| {
+                    k.bgra_to_uyvy(
+                        black_box(&src),
+                        width * 4,
+                        width,
+                        height,
+                        &mut dst,
+                        width * 2,
+                    )
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+fn v210(c: &mut Criterion) {
+    let mut group = c.benchmark_group("v210_to_p210");
+    for &(width, height) in SIZES.iter() {
+        let row_bytes = PixelFormat::FORMAT_10BIT_YUV
+            .row_bytes(width as i32)
+            .unwrap() as usize;
+        let src = vec![0x80u8; row_bytes * height];
+        let (mut y, mut uv) = (vec![0u16; width * height], vec![0u16; width * height]);
+        group.throughput(Throughput::Elements((width * height) as u64));
+        for k in kernels() {
+            group.bench_function(id(&k, width, height), |b| {
+                b.iter(|| {
+                    k.v210_to_p210(black_box(&src), row_bytes, width, height, &mut y, &mut uv)
+                })
+            });
+        }
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("p210_to_v210");
+    for &(width, height) in SIZES.iter() {
+        let row_bytes = PixelFormat::FORMAT_10BIT_YUV
+            .row_bytes(width as i32)
+            .unwrap() as usize;
+        let (y, uv) = (
+            vec![0x8000u16; width * height],
+            vec![0x8000u16; width * height],
+        );
+        let mut dst = vec![0u8; row_bytes * height];
+        group.throughput(Throughput::Elements((width * height) as u64));
+        for k in kernels() {
+            group.bench_function(id(&k, width, height), |b| {
+                b.iter(|| k.p210_to_v210(black_box(&y), &uv, width, height, &mut dst, row_bytes))
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, uyvy, bgra, v210);
+criterion_main!(benches);
@@ -1,3 +1,4 @@
+#[cfg(feature = "regenerate-bindings")]
 extern crate bindgen;
 extern crate cc;
 
@@ -5,8 +6,6 @@ use std::env;
 use std::path::PathBuf;
 
 fn main() {
-    let bindings;
-
     #[cfg(target_os = "macos")]
     {
         println!("cargo:rustc-link-lib=c++");
@@ -18,7 +17,34 @@ fn main() {
             .file("src/lib.cpp")
             .file("vendor/Mac/include/DeckLinkAPIDispatch_v10_8.cpp")
             .compile("decklink");
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        println!("cargo:rustc-link-lib=stdc++");
+
+        cc::Build::new()
+            .include("vendor/Linux/include")
+            .file("src/lib.cpp")
+            .file("vendor/Linux/include/DeckLinkAPIDispatch_v10_8.cpp")
+            .compile("decklink");
+    }
 
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("bindings.rs");
+
+    #[cfg(feature = "regenerate-bindings")]
+    generate_bindings(&out_path);
+
+    #[cfg(not(feature = "regenerate-bindings"))]
+    copy_prebuilt_bindings(&out_path);
+}
+
+#[cfg(feature = "regenerate-bindings")]
+fn generate_bindings(out_path: &PathBuf) {
+    let bindings;
+
+    #[cfg(target_os = "macos")]
+    {
         let sdk_root = std::process::Command::new("xcrun")
             .args(["--sdk", "macosx", "--show-sdk-path"])
             .stderr(std::process::Stdio::inherit())
@@ -36,29 +62,43 @@ fn main() {
 
     #[cfg(target_os = "linux")]
     {
-        println!("cargo:rustc-link-lib=stdc++");
-
-        cc::Build::new()
-            .include("vendor/Linux/include")
-            .file("src/lib.cpp")
-            .file("vendor/Linux/include/DeckLinkAPIDispatch_v10_8.cpp")
-            .compile("decklink");
-
         bindings = bindgen::Builder::default().clang_arg("-Ivendor/Linux/include")
     }
 
-    let bindings = bindings
+    bindings
         .header("src/lib.hpp")
         .allowlist_function("buffer_.+")
         .allowlist_function("unknown_.+")
         .allowlist_function(".*decklink_.+")
         .allowlist_type("_BMD.+")
+        .allowlist_var("BLACKMAGIC_DECKLINK_API_VERSION.*")
         .layout_tests(false)
         .generate()
-        .expect("unable to generate bindings");
-
-    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
-    bindings
-        .write_to_file(out_path.join("bindings.rs"))
+        .expect("unable to generate bindings")
+        .write_to_file(out_path)
         .expect("unable to write bindings");
 }
+
+#[cfg(not(feature = "regenerate-bindings"))]
+fn copy_prebuilt_bindings(out_path: &PathBuf) {
+    #[cfg(not(feature = "prebuilt-bindings"))]
+    compile_error!("enable either the `prebuilt-bindings` or the `regenerate-bindings` feature");
+
+    #[cfg(target_os = "macos")]
+    let prebuilt = "src/bindings/macos.rs";
+
+    #[cfg(target_os = "linux")]
+    let prebuilt = "src/bindings/linux.rs";
+
+    #[cfg(target_os = "macos")]
+    let include = "vendor/Mac/include";
+
+    #[cfg(target_os = "linux")]
+    let include = "vendor/Linux/include";
+
+    println!("cargo:rerun-if-changed={}", prebuilt);
+    println!("cargo:rerun-if-changed={}", include);
+    println!("cargo:rerun-if-changed=src/lib.hpp");
+    println!("cargo:rerun-if-changed=src/lib.cpp");
+    std::fs::copy(prebuilt, out_path).expect("unable to copy prebuilt bindings");
+}
@@ -0,0 +1,487 @@
+//! Linear timecode carried on an audio channel.
+//!
+//! LTC, as SMPTE ST 12-1 defines it, sends one 80-bit word per frame in biphase mark code: every
+//! bit starts with a transition, and a one has a second transition halfway through. Each word
+//! holds the time as BCD, eight groups of user bits and a sync word that marks its end. Above 30
+//! frames per second, a word covers a pair of frames, or four frames at high frame rates, and
+//! counts the pairs.
+//!
+//! An `LtcDecoder` reads the words from a channel of captured audio and follows changes in speed.
+//! An `LtcEncoder` writes them into a channel of an output buffer.
+
+use simple_error::SimpleError;
+
+use super::{AudioPacket, Sample};
+use {AudioSampleType, DisplayMode, FrameTimecode, SmpteTimecode, TimecodeFlags, TimecodeRate};
+
+const BITS_PER_WORD: usize = 80;
+// Bits 64 to 79, in the order they're sent.
+const SYNC_WORD: u128 = 0xbffc;
+
+/// A word decoded from LTC.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LtcFrame {
+    /// The time as carried, with the `DROP_FRAME` and `COLOR_FRAME` flags. Above 30 frames per
+    /// second, it counts pairs of frames, and can be passed to `TimecodeGenerator::jam_sync` to
+    /// count every frame.
+    pub timecode: FrameTimecode,
+    /// The eight groups of user bits, with the first group in the lowest 4 bits.
+    pub user_bits: u32,
+    /// When the word started, in samples on the clock of the packets it was decoded from. The
+    /// start of a word lines up with the start of the frame it labels.
+    pub time: i64,
+}
+
+fn bits(word: u128, first: usize, count: usize) -> u32 {
+    ((word >> first) & ((1 << count) - 1)) as u32
+}
+
+fn set_bits(word: &mut u128, first: usize, count: usize, value: u32) {
+    *word |= ((value & ((1 << count) - 1)) as u128) << first;
+}
+
+// The bit that keeps an even number of zeros in each word, so every word starts on the same
+// polarity.
+fn polarity_bit(frames_per_second: u32) -> usize {
+    if frames_per_second == 25 {
+        59
+    } else {
+        27
+    }
+}
+
+fn encode_word(timecode: &FrameTimecode, user_bits: u32, frames_per_second: u32) -> u128 {
+    let mut word = 0;
+    let fields = [
+        (0, 4, timecode.frames % 10),
+        (8, 2, timecode.frames / 10),
+        (16, 4, timecode.seconds % 10),
+        (24, 3, timecode.seconds / 10),
+        (32, 4, timecode.minutes % 10),
+        (40, 3, timecode.minutes / 10),
+        (48, 4, timecode.hours % 10),
+        (56, 2, timecode.hours / 10),
+    ];
+    for &(first, count, value) in fields.iter() {
+        set_bits(&mut word, first, count, value as u32);
+    }
+    if timecode.flags.contains(TimecodeFlags::DROP_FRAME) {
+        set_bits(&mut word, 10, 1, 1);
+    }
+    if timecode.flags.contains(TimecodeFlags::COLOR_FRAME) {
+        set_bits(&mut word, 11, 1, 1);
+    }
+    for group in 0..8 {
+        set_bits(&mut word, 4 + 8 * group, 4, user_bits >> (4 * group));
+    }
+    word |= SYNC_WORD << 64;
+    if word.count_ones() % 2 == 1 {
+        set_bits(&mut word, polarity_bit(frames_per_second), 1, 1);
+    }
+    word
+}
+
+fn decode_word(word: u128, time: i64) -> Option<LtcFrame> {
+    let digit = |first, count, max| Some(bits(word, first, count)).filter(|d| *d <= max);
+    let frames = digit(8, 2, 2)? * 10 + digit(0, 4, 9)?;
+    let seconds = digit(24, 3, 5)? * 10 + digit(16, 4, 9)?;
+    let minutes = digit(40, 3, 5)? * 10 + digit(32, 4, 9)?;
+    let hours = digit(56, 2, 2)? * 10 + digit(48, 4, 9)?;
+    if hours > 23 {
+        return None;
+    }
+    let mut flags = TimecodeFlags::DEFAULT;
+    if bits(word, 10, 1) == 1 {
+        flags |= TimecodeFlags::DROP_FRAME;
+    }
+    if bits(word, 11, 1) == 1 {
+        flags |= TimecodeFlags::COLOR_FRAME;
+    }
+    let user_bits = (0..8).fold(0, |bits_so_far, group| {
+        bits_so_far | bits(word, 4 + 8 * group, 4) << (4 * group)
+    });
+    Some(LtcFrame {
+        timecode: FrameTimecode {
+            hours: hours as u8,
+            minutes: minutes as u8,
+            seconds: seconds as u8,
+            frames: frames as u8,
+            flags,
+        },
+        user_bits,
+        time,
+    })
+}
+
+/// Decodes LTC from one channel of captured audio.
+///
+/// The decoder locks on to any rate from 24 to 30 frames per second, and follows LTC played
+/// forwards at up to half as fast or half as fast again. Words are found by their sync word, so
+/// decoding starts within two words of a clean signal and recovers the same way after a dropout.
+#[derive(Clone, Debug)]
+pub struct LtcDecoder {
+    sample_rate: u32,
+    // The time the next packet should start at, to notice packets that were dropped.
+    next_time: Option<i64>,
+    // The envelope the slicing threshold follows, and how fast it decays per sample.
+    peak: f64,
+    decay: f64,
+    high: bool,
+    previous: f64,
+    // When the signal last crossed zero, and the last transition it was sliced into.
+    zero_crossing: f64,
+    last_transition: Option<f64>,
+    // The length of a bit in samples, as currently measured.
+    bit_period: f64,
+    // Where a one started, once its first half has been seen.
+    half_start: Option<f64>,
+    word: u128,
+    // Bits received since the last sync word, and where each of the last 80 started.
+    bits: usize,
+    starts: Vec<f64>,
+    slot: usize,
+}
+
+impl LtcDecoder {
+    pub fn new(sample_rate: u32) -> Result<Self, SimpleError> {
+        if sample_rate == 0 {
+            return Err(SimpleError::new("sample rate must be non-zero"));
+        }
+        let mut decoder = Self {
+            sample_rate,
+            next_time: None,
+            peak: 0.0,
+            decay: (-50.0 / sample_rate as f64).exp(),
+            high: false,
+            previous: 0.0,
+            zero_crossing: f64::NEG_INFINITY,
+            last_transition: None,
+            bit_period: 0.0,
+            half_start: None,
+            word: 0,
+            bits: 0,
+            starts: vec![0.0; BITS_PER_WORD],
+            slot: 0,
+        };
+        decoder.reset();
+        Ok(decoder)
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Forgets the signal, as if starting on a new one.
+    pub fn reset(&mut self) {
+        self.next_time = None;
+        self.peak = 0.0;
+        self.high = false;
+        self.previous = 0.0;
+        self.zero_crossing = f64::NEG_INFINITY;
+        // Halfway between 24 and 30 frames per second.
+        self.bit_period = self.sample_rate as f64 / (BITS_PER_WORD as f64 * 27.0);
+        self.lose_sync();
+    }
+
+    /// Decodes the LTC on one channel of a packet, returning the words that ended in it. Packets
+    /// must be passed in order; a gap between them drops the word in progress. Fails if the
+    /// packet's sample rate differs from the decoder's or the channel doesn't exist.
+    pub fn process(
+        &mut self,
+        packet: &AudioPacket,
+        channel: usize,
+    ) -> Result<Vec<LtcFrame>, SimpleError> {
+        let format = packet.format();
+        if format.sample_rate != self.sample_rate {
+            return Err(SimpleError::new(
+                "packet sample rate doesn't match the decoder",
+            ));
+        }
+        let samples = packet
+            .channel(channel)
+            .ok_or_else(|| SimpleError::new("channel out of range"))?;
+        let scale = match format.sample_type {
+            AudioSampleType::I16 => 1.0 / i16::FULL_SCALE,
+            AudioSampleType::I32 => 1.0 / i32::FULL_SCALE,
+        };
+        let time = packet.time(self.sample_rate as i64);
+        if self.next_time != Some(time) {
+            self.lose_sync();
+        }
+        self.next_time = Some(time + packet.sample_frame_count() as i64);
+
+        let mut frames = Vec::new();
+        for (i, sample) in samples.enumerate() {
+            let t = (time + i as i64) as f64;
+            if let Some(frame) = self.sample(sample as f64 * scale, t) {
+                frames.push(frame);
+            }
+        }
+        Ok(frames)
+    }
+
+    fn lose_sync(&mut self) {
+        self.last_transition = None;
+        self.half_start = None;
+        self.bits = 0;
+    }
+
+    fn sample(&mut self, x: f64, t: f64) -> Option<LtcFrame> {
+        if (x >= 0.0) != (self.previous >= 0.0) {
+            self.zero_crossing = t - 1.0 + self.previous / (self.previous - x);
+        }
+        self.previous = x;
+        self.peak = x.abs().max(self.peak * self.decay);
+        // Slicing with hysteresis keeps noise near zero from adding transitions.
+        let threshold = (self.peak / 4.0).max(1e-4);
+        if (self.high && x < -threshold) || (!self.high && x > threshold) {
+            self.high = !self.high;
+            let crossing = if t - self.zero_crossing < 2.0 {
+                self.zero_crossing
+            } else {
+                t
+            };
+            return self.transition(crossing);
+        }
+        None
+    }
+
+    fn transition(&mut self, t: f64) -> Option<LtcFrame> {
+        let last = self.last_transition.replace(t)?;
+        let interval = t - last;
+        if interval > 2.0 * self.bit_period {
+            self.lose_sync();
+            self.last_transition = Some(t);
+            return None;
+        }
+        let bit = if interval < 0.75 * self.bit_period {
+            self.bit_period += (2.0 * interval - self.bit_period) / 8.0;
+            match self.half_start.take() {
+                Some(start) => Some((true, start)),
+                None => {
+                    self.half_start = Some(last);
+                    None
+                }
+            }
+        } else {
+            self.bit_period += (interval - self.bit_period) / 8.0;
+            // A lone half bit means the halves were paired up wrongly.
+            if self.half_start.take().is_some() {
+                self.bits = 0;
+            }
+            Some((false, last))
+        };
+        let rate = self.sample_rate as f64 / BITS_PER_WORD as f64;
+        self.bit_period = self.bit_period.clamp(rate / 45.0, rate / 12.0);
+        let (bit, start) = bit?;
+        self.push(bit, start)
+    }
+
+    fn push(&mut self, bit: bool, start: f64) -> Option<LtcFrame> {
+        self.word = (self.word >> 1) | (bit as u128) << (BITS_PER_WORD - 1);
+        self.starts[self.slot] = start;
+        self.slot = (self.slot + 1) % BITS_PER_WORD;
+        self.bits += 1;
+        if self.bits < BITS_PER_WORD || self.word >> 64 != SYNC_WORD {
+            return None;
+        }
+        self.bits = 0;
+        // The oldest start is the one the next bit replaces.
+        decode_word(self.word, self.starts[self.slot].round() as i64)
+    }
+}
+
+/// Encodes LTC into one channel of an output buffer.
+///
+/// The encoder counts frames like `TimecodeGenerator`, writing a word for each frame, or each pair
+/// or group of four frames above 30 frames per second. The first word starts on the first sample
+/// written, which should line up with the start of a frame. Words are timed exactly against the
+/// sample rate, so at 1000/1001 rates they don't take a whole number of samples.
+#[derive(Clone, Debug)]
+pub struct LtcEncoder {
+    rate: TimecodeRate,
+    group: u32,
+    sample_rate: u32,
+    // Half bits per second are `half_bits / half_bits_scale`.
+    half_bits: u64,
+    half_bits_scale: u64,
+    amplitude: f64,
+    user_bits: u32,
+    color_frame: bool,
+    // The frame the next word labels.
+    next_frame: u32,
+    word: u128,
+    // Samples written so far, and the next half bit to start.
+    position: u64,
+    half_bit: u64,
+    high: bool,
+}
+
+impl LtcEncoder {
+    /// Encodes LTC for a display mode, starting with the word for `start`. Like the labels of
+    /// `TimecodeGenerator`, `start` counts every frame of the mode; its drop frame flag chooses
+    /// between drop and non-drop frame.
+    pub fn new(
+        display_mode: DisplayMode,
+        sample_rate: u32,
+        start: SmpteTimecode,
+    ) -> Result<Self, SimpleError> {
+        let spec = display_mode
+            .info()
+            .ok_or_else(|| SimpleError::new("unknown display mode"))?;
+        Self::from_frame_rate(spec.frame_duration, spec.time_scale, sample_rate, start)
+    }
+
+    /// Encodes LTC for a frame rate as returned by `DisplayModeInfo::get_frame_rate`, which must
+    /// be an integer or 1000/1001 times one.
+    pub fn from_frame_rate(
+        frame_duration: i64,
+        time_scale: i64,
+        sample_rate: u32,
+        start: SmpteTimecode,
+    ) -> Result<Self, SimpleError> {
+        if sample_rate == 0 {
+            return Err(SimpleError::new("sample rate must be non-zero"));
+        }
+        let nominal = TimecodeRate::from_frame_rate(frame_duration, time_scale)
+            .ok_or_else(|| SimpleError::new("frame rate has no timecode rate"))?;
+        let fractional = time_scale != nominal.frames_per_second() as i64 * frame_duration;
+        if start.drop_frame() && !fractional {
+            return Err(SimpleError::new(
+                "drop frame timecode needs a 1000/1001 rate",
+            ));
+        }
+        let rate = TimecodeRate::new(nominal.frames_per_second(), start.drop_frame())?;
+        let group = rate.group();
+        let mut encoder = Self {
+            rate,
+            group,
+            sample_rate,
+            half_bits: 2 * BITS_PER_WORD as u64 * (rate.frames_per_second() / group) as u64 * 1000,
+            half_bits_scale: if fractional { 1001 } else { 1000 },
+            amplitude: 0.0,
+            user_bits: 0,
+            color_frame: false,
+            next_frame: 0,
+            word: 0,
+            position: 0,
+            half_bit: 0,
+            high: false,
+        };
+        encoder.set_level(-18.0);
+        encoder.seek(start)?;
+        Ok(encoder)
+    }
+
+    pub fn rate(&self) -> TimecodeRate {
+        self.rate
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// The label of the first frame the next word covers.
+    pub fn peek(&self) -> SmpteTimecode {
+        SmpteTimecode::from_frames(self.next_frame as i64, self.rate)
+    }
+
+    /// Continues from `timecode` with the next word. Above 30 frames per second, the word covers
+    /// the pair or group of frames that `timecode` falls in.
+    pub fn seek(&mut self, timecode: SmpteTimecode) -> Result<(), SimpleError> {
+        let frame = timecode.to_frames(self.rate)?;
+        self.next_frame = frame - frame % self.group;
+        Ok(())
+    }
+
+    pub fn user_bits(&self) -> u32 {
+        self.user_bits
+    }
+
+    /// Sets the user bits sent from the next word, with the first group in the lowest 4 bits.
+    pub fn set_user_bits(&mut self, user_bits: u32) {
+        self.user_bits = user_bits;
+    }
+
+    /// Sets the color frame flag sent from the next word.
+    pub fn set_color_frame(&mut self, color_frame: bool) {
+        self.color_frame = color_frame;
+    }
+
+    /// Sets the peak level of the signal. It starts at -18 dBFS.
+    pub fn set_level(&mut self, level_dbfs: f64) {
+        self.amplitude = 10f64.powf(level_dbfs / 20.0);
+    }
+
+    fn next_word(&mut self) {
+        let label = SmpteTimecode::from_frames(self.next_frame as i64, self.rate);
+        let mut timecode = FrameTimecode {
+            frames: label.frames() / self.group as u8,
+            ..FrameTimecode::from(label)
+        };
+        if self.color_frame {
+            timecode.flags |= TimecodeFlags::COLOR_FRAME;
+        }
+        let frames_per_second = self.rate.frames_per_second() / self.group;
+        self.word = encode_word(&timecode, self.user_bits, frames_per_second);
+        self.next_frame = (self.next_frame + self.group) % self.rate.frames_per_day();
+    }
+
+    // Starts the next half bit, which has a transition at the start of every bit and in the
+    // middle of a one.
+    fn advance(&mut self) {
+        let n = self.half_bit;
+        self.half_bit += 1;
+        let bit = (n / 2) as usize % BITS_PER_WORD;
+        if n.is_multiple_of(2) {
+            if bit == 0 {
+                self.next_word();
+            }
+            self.high = !self.high;
+        } else if (self.word >> bit) & 1 == 1 {
+            self.high = !self.high;
+        }
+    }
+
+    /// Writes the signal into one channel of an interleaved buffer, leaving the other channels
+    /// alone. The buffer's length must be a multiple of the channel count.
+    pub fn fill<S: Sample>(
+        &mut self,
+        buffer: &mut [S],
+        channels: usize,
+        channel: usize,
+    ) -> Result<(), SimpleError> {
+        if channel >= channels {
+            return Err(SimpleError::new("channel out of range"));
+        }
+        if !buffer.len().is_multiple_of(channels) {
+            return Err(SimpleError::new(
+                "buffer length must be a multiple of the channel count",
+            ));
+        }
+        // Half bit n starts at sample n * scale / half_bits, where sample i covers the time from
+        // i - 1/2 to i + 1/2. Times are kept as multiples of 1 / (2 * half_bits) to stay exact.
+        let scale = self.sample_rate as i128 * self.half_bits_scale as i128;
+        let unit = 2 * self.half_bits as i128;
+        for frame in buffer.chunks_mut(channels) {
+            let start = (2 * self.position as i128 - 1) * self.half_bits as i128;
+            let end = start + unit;
+            // Averaging the square wave over each sample keeps transitions between samples.
+            let (mut sum, mut t) = (0, start);
+            loop {
+                let next = self.half_bit as i128 * 2 * scale;
+                if next >= end {
+                    break;
+                }
+                sum += if self.high { next - t } else { t - next };
+                t = next;
+                self.advance();
+            }
+            sum += if self.high { end - t } else { t - end };
+            let value = self.amplitude * sum as f64 / unit as f64;
+            frame[channel] = S::from_f64(value * S::FULL_SCALE);
+            self.position += 1;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,394 @@
+//! Level and loudness metering.
+//!
+//! `Meter` measures per-channel sample peak, true peak and RMS, and the programme loudness of all
+//! channels together as specified by ITU-R BS.1770-4 and EBU R128: momentary (400 ms), short-term
+//! (3 s) and gated integrated loudness, and loudness range as defined in EBU Tech 3342.
+
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+
+use simple_error::SimpleError;
+
+use super::{AudioPacket, Sample};
+
+const ABSOLUTE_GATE: f64 = -70.0;
+const INTEGRATED_RELATIVE_GATE: f64 = -10.0;
+const RANGE_RELATIVE_GATE: f64 = -20.0;
+
+// Loudness is computed from 100 ms sub-blocks: 4 make a momentary block and 30 a short-term one.
+const MOMENTARY_BLOCKS: usize = 4;
+const SHORT_TERM_BLOCKS: usize = 30;
+
+// The true peak interpolator: a 4x polyphase windowed sinc with 12 taps per phase.
+const OVERSAMPLING: usize = 4;
+const TAPS_PER_PHASE: usize = 12;
+
+/// Converts a linear level, where 1.0 is full scale, to dBFS.
+pub fn to_dbfs(level: f64) -> f64 {
+    20.0 * level.log10()
+}
+
+fn loudness(energy: f64) -> f64 {
+    -0.691 + 10.0 * energy.log10()
+}
+
+fn energy(loudness: f64) -> f64 {
+    10f64.powf((loudness + 0.691) / 10.0)
+}
+
+/// Levels of one channel since they were last taken. All are linear, with 1.0 as full scale.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ChannelLevels {
+    pub sample_peak: f64,
+    /// The peak of the signal reconstructed at 4x the sample rate, which catches inter-sample
+    /// peaks.
+    pub true_peak: f64,
+    pub rms: f64,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 3],
+    z: [f64; 2],
+}
+
+impl Biquad {
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b[0] * x + self.z[0];
+        self.z[0] = self.b[1] * x - self.a[1] * y + self.z[1];
+        self.z[1] = self.b[2] * x - self.a[2] * y;
+        y
+    }
+}
+
+// The two stages of the K-weighting filter, a high shelf and a high pass, derived for any sample
+// rate so that they match the 48 kHz coefficients of BS.1770.
+fn k_weighting(sample_rate: f64) -> [Biquad; 2] {
+    let f0 = 1681.974450955533;
+    let gain = 3.999843853973347;
+    let q = 0.7071752369554196;
+    let k = (PI * f0 / sample_rate).tan();
+    let vh = 10f64.powf(gain / 20.0);
+    let vb = vh.powf(0.4996667741545416);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = Biquad {
+        b: [
+            (vh + vb * k / q + k * k) / a0,
+            2.0 * (k * k - vh) / a0,
+            (vh - vb * k / q + k * k) / a0,
+        ],
+        a: [1.0, 2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        z: [0.0; 2],
+    };
+
+    let f0 = 38.13547087602444;
+    let q = 0.5003270373238773;
+    let k = (PI * f0 / sample_rate).tan();
+    let a0 = 1.0 + k / q + k * k;
+    let high_pass = Biquad {
+        b: [1.0, -2.0, 1.0],
+        a: [1.0, 2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        z: [0.0; 2],
+    };
+    [shelf, high_pass]
+}
+
+// The interpolating phases of the filter. The first phase is left out: it reproduces the input
+// samples, delayed, so the sample peak already covers it.
+type Phases = [[f64; TAPS_PER_PHASE]; OVERSAMPLING - 1];
+
+fn interpolation_filter() -> Phases {
+    let len = OVERSAMPLING * TAPS_PER_PHASE;
+    let half = (TAPS_PER_PHASE / 2) as f64 + 0.25;
+    let mut phases = [[0.0; TAPS_PER_PHASE]; OVERSAMPLING - 1];
+    for n in 0..len {
+        let t = (n as f64 - (len / 2) as f64) / OVERSAMPLING as f64;
+        let sinc = if t == 0.0 {
+            1.0
+        } else {
+            (PI * t).sin() / (PI * t)
+        };
+        let window = (PI * t / (2.0 * half)).cos().powi(2);
+        if n % OVERSAMPLING != 0 {
+            phases[n % OVERSAMPLING - 1][n / OVERSAMPLING] = sinc * window;
+        }
+    }
+    phases
+}
+
+#[derive(Clone, Debug)]
+struct ChannelState {
+    filter: [Biquad; 2],
+    weight: f64,
+    // The K-weighted sum of squares in the current sub-block.
+    block_sum: f64,
+    // The last `TAPS_PER_PHASE` samples, newest first from `history_pos`. They are stored twice
+    // so that they can always be read as one slice.
+    history: [f64; 2 * TAPS_PER_PHASE],
+    history_pos: usize,
+    sample_peak: f64,
+    true_peak: f64,
+    sum_squares: f64,
+    samples: u64,
+}
+
+impl ChannelState {
+    fn process(&mut self, x: f64, phases: &Phases) {
+        let level = x.abs();
+        self.sample_peak = self.sample_peak.max(level);
+        self.sum_squares += x * x;
+        self.samples += 1;
+
+        let pos = match self.history_pos {
+            0 => TAPS_PER_PHASE - 1,
+            pos => pos - 1,
+        };
+        self.history[pos] = x;
+        self.history[pos + TAPS_PER_PHASE] = x;
+        self.history_pos = pos;
+        let history = &self.history[pos..pos + TAPS_PER_PHASE];
+        let mut true_peak = self.true_peak.max(level);
+        for phase in phases.iter() {
+            let mut y = 0.0;
+            for j in 0..TAPS_PER_PHASE {
+                y += phase[j] * history[j];
+            }
+            true_peak = true_peak.max(y.abs());
+        }
+        self.true_peak = true_peak;
+
+        let y = self.filter[0].process(x);
+        let y = self.filter[1].process(y);
+        self.block_sum += y * y;
+    }
+}
+
+/// Measures the levels and loudness of a multichannel audio stream.
+///
+/// Packets of either sample type can be fed to `process` as long as their sample rate and channel
+/// count match the meter's. Every channel has a loudness weight of 1.0 by default; for surround
+/// layouts, set the LFE channel's weight to 0.0 and the surround channels' to 1.41 with
+/// `set_channel_weight`.
+#[derive(Clone, Debug)]
+pub struct Meter {
+    sample_rate: u32,
+    channels: Vec<ChannelState>,
+    phases: Phases,
+    block_len: usize,
+    block_pos: usize,
+    // The energies of the most recent sub-blocks, oldest first.
+    blocks: VecDeque<f64>,
+    momentary_energies: Vec<f64>,
+    short_term_energies: Vec<f64>,
+}
+
+impl Meter {
+    pub fn new(sample_rate: u32, channel_count: usize) -> Result<Self, SimpleError> {
+        if sample_rate < 10 {
+            return Err(SimpleError::new("sample rate is too low to meter"));
+        }
+        if channel_count == 0 {
+            return Err(SimpleError::new("channel count must be non-zero"));
+        }
+        let channel = ChannelState {
+            filter: k_weighting(sample_rate as f64),
+            weight: 1.0,
+            block_sum: 0.0,
+            history: [0.0; 2 * TAPS_PER_PHASE],
+            history_pos: 0,
+            sample_peak: 0.0,
+            true_peak: 0.0,
+            sum_squares: 0.0,
+            samples: 0,
+        };
+        Ok(Self {
+            sample_rate,
+            channels: vec![channel; channel_count],
+            phases: interpolation_filter(),
+            block_len: (sample_rate / 10) as usize,
+            block_pos: 0,
+            blocks: VecDeque::with_capacity(SHORT_TERM_BLOCKS),
+            momentary_energies: Vec::new(),
+            short_term_energies: Vec::new(),
+        })
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn channel_count(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// Sets the weight a channel contributes to the loudness measurements.
+    pub fn set_channel_weight(&mut self, channel: usize, weight: f64) -> Result<(), SimpleError> {
+        match self.channels.get_mut(channel) {
+            Some(state) => {
+                state.weight = weight;
+                Ok(())
+            }
+            None => Err(SimpleError::new("channel out of range")),
+        }
+    }
+
+    /// Measures a packet. Fails if its sample rate or channel count differ from the meter's.
+    pub fn process(&mut self, packet: &AudioPacket) -> Result<(), SimpleError> {
+        let format = packet.format();
+        if format.sample_rate != self.sample_rate {
+            return Err(SimpleError::new(
+                "packet sample rate doesn't match the meter",
+            ));
+        }
+        if packet.channel_count() != self.channels.len() {
+            return Err(SimpleError::new(
+                "packet channel count doesn't match the meter",
+            ));
+        }
+        let samples: Vec<f64> = match (packet.samples_i16(), packet.samples_i32()) {
+            (Some(samples), _) => samples
+                .iter()
+                .map(|s| s.to_f64() / i16::FULL_SCALE)
+                .collect(),
+            (_, Some(samples)) => samples
+                .iter()
+                .map(|s| s.to_f64() / i32::FULL_SCALE)
+                .collect(),
+            _ => Vec::new(),
+        };
+        self.process_interleaved(&samples);
+        Ok(())
+    }
+
+    fn process_interleaved(&mut self, samples: &[f64]) {
+        for frame in samples.chunks(self.channels.len()) {
+            for (channel, x) in self.channels.iter_mut().zip(frame) {
+                channel.process(*x, &self.phases);
+            }
+            self.block_pos += 1;
+            if self.block_pos == self.block_len {
+                self.end_block();
+            }
+        }
+    }
+
+    fn end_block(&mut self) {
+        let len = self.block_len as f64;
+        let energy = self
+            .channels
+            .iter_mut()
+            .map(|c| c.weight * std::mem::replace(&mut c.block_sum, 0.0) / len)
+            .sum();
+        self.block_pos = 0;
+
+        if self.blocks.len() == SHORT_TERM_BLOCKS {
+            self.blocks.pop_front();
+        }
+        self.blocks.push_back(energy);
+
+        // Gating blocks overlap by 75%, so one completes with every sub-block.
+        if let Some(energy) = self.window_energy(MOMENTARY_BLOCKS) {
+            if loudness(energy) > ABSOLUTE_GATE {
+                self.momentary_energies.push(energy);
+            }
+        }
+        if let Some(energy) = self.window_energy(SHORT_TERM_BLOCKS) {
+            if loudness(energy) > ABSOLUTE_GATE {
+                self.short_term_energies.push(energy);
+            }
+        }
+    }
+
+    fn window_energy(&self, blocks: usize) -> Option<f64> {
+        if self.blocks.len() < blocks {
+            return None;
+        }
+        Some(self.blocks.iter().rev().take(blocks).sum::<f64>() / blocks as f64)
+    }
+
+    /// The loudness of the last 400 ms in LUFS, or `None` until that much has been measured.
+    pub fn momentary(&self) -> Option<f64> {
+        self.window_energy(MOMENTARY_BLOCKS).map(loudness)
+    }
+
+    /// The loudness of the last 3 s in LUFS, or `None` until that much has been measured.
+    pub fn short_term(&self) -> Option<f64> {
+        self.window_energy(SHORT_TERM_BLOCKS).map(loudness)
+    }
+
+    /// The gated loudness of everything measured since the meter was created or reset, in LUFS.
+    /// `None` if nothing has passed the absolute gate.
+    pub fn integrated(&self) -> Option<f64> {
+        let threshold = mean(&self.momentary_energies).map(loudness)? + INTEGRATED_RELATIVE_GATE;
+        let threshold = energy(threshold);
+        let gated: Vec<f64> = self
+            .momentary_energies
+            .iter()
+            .cloned()
+            .filter(|e| *e > threshold)
+            .collect();
+        mean(&gated).map(loudness)
+    }
+
+    /// The loudness range in LU: the spread between the 10th and 95th percentiles of the gated
+    /// short-term loudness. `None` if nothing has passed the absolute gate.
+    pub fn loudness_range(&self) -> Option<f64> {
+        let threshold = mean(&self.short_term_energies).map(loudness)? + RANGE_RELATIVE_GATE;
+        let threshold = energy(threshold);
+        let mut gated: Vec<f64> = self
+            .short_term_energies
+            .iter()
+            .cloned()
+            .filter(|e| *e > threshold)
+            .map(loudness)
+            .collect();
+        gated.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let percentile = |p: f64| gated[((gated.len() - 1) as f64 * p).round() as usize];
+        Some(percentile(0.95) - percentile(0.10))
+    }
+
+    /// Returns each channel's levels since the last call and starts measuring them afresh.
+    pub fn take_levels(&mut self) -> Vec<ChannelLevels> {
+        self.channels
+            .iter_mut()
+            .map(|c| {
+                let levels = ChannelLevels {
+                    sample_peak: c.sample_peak,
+                    true_peak: c.true_peak,
+                    rms: match c.samples {
+                        0 => 0.0,
+                        n => (c.sum_squares / n as f64).sqrt(),
+                    },
+                };
+                c.sample_peak = 0.0;
+                c.true_peak = 0.0;
+                c.sum_squares = 0.0;
+                c.samples = 0;
+                levels
+            })
+            .collect()
+    }
+
+    /// Discards everything measured, keeping the channel weights.
+    pub fn reset(&mut self) {
+        let filter = k_weighting(self.sample_rate as f64);
+        for c in self.channels.iter_mut() {
+            c.filter = filter;
+            c.block_sum = 0.0;
+            c.history = [0.0; 2 * TAPS_PER_PHASE];
+        }
+        self.take_levels();
+        self.block_pos = 0;
+        self.blocks.clear();
+        self.momentary_energies.clear();
+        self.short_term_energies.clear();
+    }
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    match values.len() {
+        0 => None,
+        n => Some(values.iter().sum::<f64>() / n as f64),
+    }
+}
@@ -0,0 +1,293 @@
+//! Typed access to captured audio.
+//!
+//! The SDK hands out audio as an untyped buffer of interleaved samples whose layout is whatever was
+//! passed to `Input::enable_audio_input`. `AudioPacket` pairs the samples with that `AudioFormat`
+//! so they can be read per channel or converted without unsafe code.
+
+use simple_error::SimpleError;
+
+use AudioSampleType;
+
+pub mod ltc;
+pub mod meter;
+pub mod playout;
+pub mod router;
+pub mod tone;
+
+/// The layout of an audio stream, as passed to `Input::enable_audio_input`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AudioFormat {
+    pub sample_rate: u32,
+    pub sample_type: AudioSampleType,
+    pub channel_count: u32,
+}
+
+impl AudioFormat {
+    pub fn new(sample_rate: u32, sample_type: AudioSampleType, channel_count: u32) -> Self {
+        Self {
+            sample_rate,
+            sample_type,
+            channel_count,
+        }
+    }
+
+    /// The size of one sample frame: one sample for every channel.
+    pub fn bytes_per_frame(&self) -> usize {
+        self.sample_type.bytes() * self.channel_count as usize
+    }
+}
+
+/// A sample type that audio can be processed in.
+pub trait Sample: Copy + Default {
+    /// The magnitude of a full-scale sample.
+    const FULL_SCALE: f64;
+
+    fn to_f64(self) -> f64;
+
+    /// Converts from `f64` at the same scale, rounding and saturating to the type's range.
+    fn from_f64(value: f64) -> Self;
+}
+
+impl Sample for i16 {
+    const FULL_SCALE: f64 = 32_768.0;
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value.round() as i16
+    }
+}
+
+impl Sample for i32 {
+    const FULL_SCALE: f64 = 2_147_483_648.0;
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value.round() as i32
+    }
+}
+
+impl Sample for f32 {
+    const FULL_SCALE: f64 = 1.0;
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+/// A sample type the SDK can play out.
+pub trait PcmSample: Sample {
+    const SAMPLE_TYPE: AudioSampleType;
+}
+
+impl PcmSample for i16 {
+    const SAMPLE_TYPE: AudioSampleType = AudioSampleType::I16;
+}
+
+impl PcmSample for i32 {
+    const SAMPLE_TYPE: AudioSampleType = AudioSampleType::I32;
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Samples<'a> {
+    I16(&'a [i16]),
+    I32(&'a [i32]),
+}
+
+/// A packet of interleaved audio samples along with its format and timing.
+///
+/// Packets from the SDK are obtained with `AudioInputPacket::packet`. They can also be built from
+/// ordinary slices, e.g. to feed recorded audio through the same processing.
+#[derive(Clone, Copy, Debug)]
+pub struct AudioPacket<'a> {
+    format: AudioFormat,
+    samples: Samples<'a>,
+    time: i64,
+}
+
+impl<'a> AudioPacket<'a> {
+    /// Wraps interleaved 16-bit samples. `time` is the time of the first sample frame in units of
+    /// the sample rate.
+    pub fn from_i16(
+        sample_rate: u32,
+        channel_count: u32,
+        samples: &'a [i16],
+        time: i64,
+    ) -> Result<Self, SimpleError> {
+        let format = AudioFormat::new(sample_rate, AudioSampleType::I16, channel_count);
+        Self::new(format, Samples::I16(samples), samples.len(), time)
+    }
+
+    /// Wraps interleaved 32-bit samples. `time` is the time of the first sample frame in units of
+    /// the sample rate.
+    pub fn from_i32(
+        sample_rate: u32,
+        channel_count: u32,
+        samples: &'a [i32],
+        time: i64,
+    ) -> Result<Self, SimpleError> {
+        let format = AudioFormat::new(sample_rate, AudioSampleType::I32, channel_count);
+        Self::new(format, Samples::I32(samples), samples.len(), time)
+    }
+
+    fn new(
+        format: AudioFormat,
+        samples: Samples<'a>,
+        len: usize,
+        time: i64,
+    ) -> Result<Self, SimpleError> {
+        if format.sample_rate == 0 {
+            return Err(SimpleError::new("sample rate must be non-zero"));
+        }
+        if format.channel_count == 0 {
+            return Err(SimpleError::new("channel count must be non-zero"));
+        }
+        if !len.is_multiple_of(format.channel_count as usize) {
+            return Err(SimpleError::new(
+                "sample count must be a multiple of the channel count",
+            ));
+        }
+        Ok(Self {
+            format,
+            samples,
+            time,
+        })
+    }
+
+    pub(crate) fn from_sdk_samples(
+        format: AudioFormat,
+        bytes: &'a [u8],
+        time: i64,
+    ) -> Option<Self> {
+        // The SDK's buffers are aligned for their sample type; anything else is rejected rather
+        // than copied.
+        let samples = match format.sample_type {
+            AudioSampleType::I16 => match unsafe { bytes.align_to::<i16>() } {
+                (&[], samples, &[]) => Samples::I16(samples),
+                _ => return None,
+            },
+            AudioSampleType::I32 => match unsafe { bytes.align_to::<i32>() } {
+                (&[], samples, &[]) => Samples::I32(samples),
+                _ => return None,
+            },
+        };
+        let len = bytes.len() / format.sample_type.bytes();
+        Self::new(format, samples, len, time).ok()
+    }
+
+    pub fn format(&self) -> AudioFormat {
+        self.format
+    }
+
+    pub fn channel_count(&self) -> usize {
+        self.format.channel_count as usize
+    }
+
+    pub fn sample_frame_count(&self) -> usize {
+        let len = match self.samples {
+            Samples::I16(s) => s.len(),
+            Samples::I32(s) => s.len(),
+        };
+        len / self.channel_count()
+    }
+
+    /// The interleaved samples, if the packet holds 16-bit samples.
+    pub fn samples_i16(&self) -> Option<&'a [i16]> {
+        match self.samples {
+            Samples::I16(s) => Some(s),
+            Samples::I32(_) => None,
+        }
+    }
+
+    /// The interleaved samples, if the packet holds 32-bit samples.
+    pub fn samples_i32(&self) -> Option<&'a [i32]> {
+        match self.samples {
+            Samples::I32(s) => Some(s),
+            Samples::I16(_) => None,
+        }
+    }
+
+    /// Iterates over the samples of one channel, or returns `None` if the channel doesn't exist.
+    /// Samples are yielded at their native width, so 16-bit samples range over `i16`.
+    pub fn channel(&self, n: usize) -> Option<Channel<'a>> {
+        if n >= self.channel_count() {
+            return None;
+        }
+        Some(Channel {
+            samples: self.samples,
+            next: n,
+            stride: self.channel_count(),
+        })
+    }
+
+    /// Deinterleaves the samples into one buffer per channel, scaled to [-1.0, 1.0]. The top of
+    /// the 32-bit range rounds to exactly 1.0 in f32.
+    pub fn to_planar_f32(&self) -> Vec<Vec<f32>> {
+        let scale = match self.samples {
+            Samples::I16(_) => (1.0 / i16::FULL_SCALE) as f32,
+            Samples::I32(_) => (1.0 / i32::FULL_SCALE) as f32,
+        };
+        (0..self.channel_count())
+            .map(|n| self.channel(n).unwrap().map(|s| s as f32 * scale).collect())
+            .collect()
+    }
+
+    /// The time of the first sample frame in the given time scale, rounded down.
+    pub fn time(&self, time_scale: i64) -> i64 {
+        rescale(self.time, self.format.sample_rate, time_scale)
+    }
+
+    /// The duration of the packet in the given time scale, rounded down.
+    pub fn duration(&self, time_scale: i64) -> i64 {
+        rescale(
+            self.sample_frame_count() as i64,
+            self.format.sample_rate,
+            time_scale,
+        )
+    }
+}
+
+fn rescale(samples: i64, sample_rate: u32, time_scale: i64) -> i64 {
+    (samples as i128 * time_scale as i128).div_euclid(sample_rate as i128) as i64
+}
+
+/// An iterator over the samples of one channel of an `AudioPacket`.
+#[derive(Clone, Debug)]
+pub struct Channel<'a> {
+    samples: Samples<'a>,
+    next: usize,
+    stride: usize,
+}
+
+impl<'a> Iterator for Channel<'a> {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<i32> {
+        let sample = match self.samples {
+            Samples::I16(s) => s.get(self.next).map(|s| *s as i32),
+            Samples::I32(s) => s.get(self.next).cloned(),
+        };
+        self.next += self.stride;
+        sample
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match self.samples {
+            Samples::I16(s) => s.len(),
+            Samples::I32(s) => s.len(),
+        };
+        let remaining = len.saturating_sub(self.next).div_ceil(self.stride);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> ExactSizeIterator for Channel<'a> {}
@@ -0,0 +1,337 @@
+//! Keeping played-out audio locked to video.
+//!
+//! Audio produced against a different clock than the card's drifts against the video it is played
+//! with: the driver's buffer slowly fills or drains. `AudioPlayout` measures how far audio leads
+//! playback, and a PI controller trims the ratio of an adaptive `Resampler` so that the lead holds
+//! at a target. The driver's own resampling is left off, so correction happens in one place.
+
+use simple_error::SimpleError;
+
+use super::{AudioFormat, PcmSample, Sample};
+use {AudioOutputStreamType, Error, Output};
+
+// History frames kept between calls for the interpolator's four-point window.
+const HISTORY: usize = 3;
+
+/// Resamples interleaved audio by a ratio that can be changed between calls, using cubic
+/// interpolation. At a ratio of exactly 1.0 samples pass through unchanged, delayed by two frames.
+#[derive(Clone, Debug)]
+pub struct Resampler {
+    channels: usize,
+    ratio: f64,
+    // The position of the next output frame in `buffer`, in input frames.
+    position: f64,
+    buffer: Vec<f64>,
+}
+
+impl Resampler {
+    pub fn new(channels: usize) -> Result<Self, SimpleError> {
+        if channels == 0 {
+            return Err(SimpleError::new("channel count must be non-zero"));
+        }
+        Ok(Self {
+            channels,
+            ratio: 1.0,
+            position: 1.0,
+            buffer: vec![0.0; HISTORY * channels],
+        })
+    }
+
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// The output rate divided by the input rate.
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    pub fn set_ratio(&mut self, ratio: f64) -> Result<(), SimpleError> {
+        if !(ratio > 0.0 && ratio.is_finite()) {
+            return Err(SimpleError::new("resampling ratio must be positive"));
+        }
+        self.ratio = ratio;
+        Ok(())
+    }
+
+    /// Resamples interleaved `input`, appending the result to `output`. Roughly `ratio` output
+    /// frames are produced per input frame; the fraction left over carries into the next call.
+    pub fn process<S: Sample>(
+        &mut self,
+        input: &[S],
+        output: &mut Vec<S>,
+    ) -> Result<(), SimpleError> {
+        let channels = self.channels;
+        if !input.len().is_multiple_of(channels) {
+            return Err(SimpleError::new(
+                "input length must be a multiple of the channel count",
+            ));
+        }
+        self.buffer.extend(input.iter().map(|s| s.to_f64()));
+        let frames = self.buffer.len() / channels;
+        let step = 1.0 / self.ratio;
+
+        while self.position < (frames - 2) as f64 {
+            let i = self.position as usize;
+            let f = self.position - i as f64;
+            let window = &self.buffer[(i - 1) * channels..(i + 3) * channels];
+            for c in 0..channels {
+                let x = [
+                    window[c],
+                    window[channels + c],
+                    window[2 * channels + c],
+                    window[3 * channels + c],
+                ];
+                output.push(S::from_f64(interpolate(x, f)));
+            }
+            self.position += step;
+        }
+
+        self.buffer.drain(..(frames - HISTORY) * channels);
+        self.position -= (frames - HISTORY) as f64;
+        Ok(())
+    }
+
+    /// Discards the history, as after a discontinuity.
+    pub fn reset(&mut self) {
+        self.position = 1.0;
+        self.buffer.clear();
+        self.buffer.resize(HISTORY * self.channels, 0.0);
+    }
+}
+
+// Catmull-Rom interpolation between x[1] and x[2].
+fn interpolate(x: [f64; 4], f: f64) -> f64 {
+    let c1 = 0.5 * (x[2] - x[0]);
+    let c2 = x[0] - 2.5 * x[1] + 2.0 * x[2] - 0.5 * x[3];
+    let c3 = 0.5 * (x[3] - x[0]) + 1.5 * (x[1] - x[2]);
+    ((c3 * f + c2) * f + c1) * f + x[1]
+}
+
+/// A proportional-integral controller.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PiController {
+    pub kp: f64,
+    pub ki: f64,
+    /// The largest magnitude `update` returns.
+    pub limit: f64,
+    integral: f64,
+}
+
+impl PiController {
+    pub fn new(kp: f64, ki: f64, limit: f64) -> Self {
+        Self {
+            kp,
+            ki,
+            limit,
+            integral: 0.0,
+        }
+    }
+
+    /// Takes the current error and the time since the last update, and returns the correction.
+    pub fn update(&mut self, error: f64, dt: f64) -> f64 {
+        let integral = self.integral + error * dt;
+        let output = self.kp * error + self.ki * integral;
+        // The integral only accumulates while the output isn't limited, so that it doesn't wind
+        // up during large errors.
+        if output.abs() <= self.limit {
+            self.integral = integral;
+        }
+        output.clamp(-self.limit, self.limit)
+    }
+
+    pub fn reset(&mut self) {
+        self.integral = 0.0;
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PlayoutMode {
+    /// Samples are written as they come and play back to back. The lead is the driver's buffered
+    /// sample count.
+    ContinuousDontResample,
+    /// Samples are scheduled at consecutive stream times. The lead is the time of the next sample
+    /// relative to the scheduled video stream time.
+    Timestamped,
+}
+
+impl PlayoutMode {
+    pub fn stream_type(self) -> AudioOutputStreamType {
+        match self {
+            PlayoutMode::ContinuousDontResample => AudioOutputStreamType::ContinuousDontResample,
+            PlayoutMode::Timestamped => AudioOutputStreamType::Timestamped,
+        }
+    }
+}
+
+/// Plays audio out with drift compensation.
+///
+/// The default controller settles in about a minute and corrects by at most 0.5%, which
+/// tracks any realistic clock difference without audible pitch modulation.
+#[derive(Clone, Debug)]
+pub struct AudioPlayout {
+    format: AudioFormat,
+    mode: PlayoutMode,
+    target_lead: f64,
+    controller: PiController,
+    resampler: Resampler,
+    // The stream time of the next sample to schedule, in sample frames.
+    next_time: i64,
+    dropped_frames: u64,
+}
+
+impl AudioPlayout {
+    /// Creates a playout that holds audio `target_lead` sample frames ahead of playback.
+    pub fn new(
+        format: AudioFormat,
+        mode: PlayoutMode,
+        target_lead: u32,
+    ) -> Result<Self, SimpleError> {
+        if format.sample_rate == 0 {
+            return Err(SimpleError::new("sample rate must be non-zero"));
+        }
+        Ok(Self {
+            format,
+            mode,
+            target_lead: target_lead as f64,
+            controller: PiController::new(0.14, 0.01, 0.005),
+            resampler: Resampler::new(format.channel_count as usize)?,
+            next_time: 0,
+            dropped_frames: 0,
+        })
+    }
+
+    pub fn format(&self) -> AudioFormat {
+        self.format
+    }
+
+    pub fn mode(&self) -> PlayoutMode {
+        self.mode
+    }
+
+    /// Enables audio output on `output` in the playout's format and mode.
+    pub fn enable(&self, output: &mut Output) -> Result<(), Error> {
+        output.enable_audio_output(
+            self.format.sample_rate,
+            self.format.sample_type,
+            self.format.channel_count,
+            self.mode.stream_type(),
+        )
+    }
+
+    /// The controller, whose gains act on the lead error in seconds and return the ratio
+    /// correction.
+    pub fn controller_mut(&mut self) -> &mut PiController {
+        &mut self.controller
+    }
+
+    /// The current resampling ratio.
+    pub fn ratio(&self) -> f64 {
+        self.resampler.ratio()
+    }
+
+    /// Sample frames the driver refused, because its buffer was full.
+    pub fn dropped_frames(&self) -> u64 {
+        self.dropped_frames
+    }
+
+    /// The stream time the next scheduled sample plays at, in sample frames.
+    pub fn next_time(&self) -> i64 {
+        self.next_time
+    }
+
+    /// Sets the stream time the next scheduled sample plays at, in timestamped mode.
+    pub fn set_next_time(&mut self, stream_time: i64, time_scale: i64) {
+        self.next_time = ((stream_time as i128 * self.format.sample_rate as i128)
+            .div_euclid(time_scale as i128)) as i64;
+    }
+
+    /// Measures how far audio leads playback, in sample frames.
+    pub fn measure_lead(&self, output: &mut Output) -> Result<f64, Error> {
+        Ok(match self.mode {
+            PlayoutMode::ContinuousDontResample => {
+                output.get_buffered_audio_sample_frame_count()? as f64
+            }
+            PlayoutMode::Timestamped => {
+                let now = output.get_scheduled_stream_time(self.format.sample_rate as i64)?;
+                self.lead_at(now.stream_time)
+            }
+        })
+    }
+
+    /// The lead in timestamped mode when the scheduled stream time is `stream_time`, in sample
+    /// frames.
+    pub fn lead_at(&self, stream_time: i64) -> f64 {
+        (self.next_time - stream_time) as f64
+    }
+
+    /// Feeds the controller a lead measurement taken `dt` seconds after the previous one, and
+    /// returns the new resampling ratio.
+    pub fn update(&mut self, lead: f64, dt: f64) -> f64 {
+        let error = (lead - self.target_lead) / self.format.sample_rate as f64;
+        // A limit of 1 or more could otherwise stop or reverse playback.
+        let ratio = (1.0 - self.controller.update(error, dt)).max(0.5);
+        self.resampler.ratio = ratio;
+        ratio
+    }
+
+    /// Resamples interleaved samples at the current ratio, appending them to `output`.
+    pub fn process<S: Sample>(
+        &mut self,
+        samples: &[S],
+        output: &mut Vec<S>,
+    ) -> Result<(), SimpleError> {
+        self.resampler.process(samples, output)
+    }
+
+    /// Measures the lead, updates the ratio, and plays interleaved samples out: written in
+    /// continuous mode, scheduled at the next stream time in timestamped mode. Returns the number
+    /// of sample frames the driver accepted.
+    pub fn write<S: PcmSample>(
+        &mut self,
+        output: &mut Output,
+        samples: &[S],
+    ) -> Result<usize, Error> {
+        let lead = self.measure_lead(output)?;
+        let resampled = self.prepare(lead, samples).map_err(|_| Error::new())?;
+        let written = match self.mode {
+            PlayoutMode::ContinuousDontResample => output.write_audio_samples_sync(&resampled)?,
+            PlayoutMode::Timestamped => output.schedule_audio_samples(
+                &resampled,
+                self.next_time,
+                self.format.sample_rate as i64,
+            )?,
+        };
+        self.advance(
+            resampled.len() / self.format.channel_count as usize,
+            written,
+        );
+        Ok(written)
+    }
+
+    /// Updates the ratio from `lead` and resamples interleaved samples for playout, as `write`
+    /// does before handing them to the driver.
+    pub fn prepare<S: Sample>(&mut self, lead: f64, samples: &[S]) -> Result<Vec<S>, SimpleError> {
+        let channels = self.format.channel_count as usize;
+        let dt = (samples.len() / channels) as f64 / self.format.sample_rate as f64;
+        self.update(lead, dt);
+        let mut resampled = Vec::with_capacity(samples.len() + 4 * channels);
+        self.process(samples, &mut resampled)?;
+        Ok(resampled)
+    }
+
+    /// Accounts for `frames` sample frames handed to the driver, of which it accepted `accepted`.
+    /// The next stream time moves past all of them, so that later audio stays in sync, and the
+    /// rest count as dropped.
+    pub fn advance(&mut self, frames: usize, accepted: usize) {
+        self.next_time += frames as i64;
+        self.dropped_frames += (frames - accepted.min(frames)) as u64;
+    }
+
+    /// Resets the controller and resampler, e.g. after flushing the driver's buffer.
+    pub fn reset(&mut self) {
+        self.controller.reset();
+        self.resampler.reset();
+        self.resampler.ratio = 1.0;
+    }
+}
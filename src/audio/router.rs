@@ -0,0 +1,253 @@
+//! Channel routing and mixing.
+//!
+//! An `AudioRouter` maps interleaved audio with one channel count to another. Each output channel
+//! is the sum of any input channels, each with its own gain, followed by the output's gain, polarity
+//! and mute. With the `serde` feature a router can be loaded from a configuration file.
+
+use simple_error::SimpleError;
+
+use super::{AudioPacket, Sample, Samples};
+
+/// Converts a gain in decibels to a linear factor.
+pub fn db_to_gain(db: f64) -> f64 {
+    10f64.powf(db / 20.0)
+}
+
+#[cfg(feature = "serde")]
+fn unity() -> f64 {
+    1.0
+}
+
+/// An input channel feeding an output channel.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RouteSource {
+    pub channel: usize,
+    /// A linear factor.
+    #[cfg_attr(feature = "serde", serde(default = "unity"))]
+    pub gain: f64,
+}
+
+impl RouteSource {
+    pub fn new(channel: usize, gain: f64) -> Self {
+        Self { channel, gain }
+    }
+}
+
+/// One channel of a router's output.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OutputChannel {
+    /// The input channels summed into this one. With none, the channel is silent.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sources: Vec<RouteSource>,
+    /// A linear factor applied to the sum of the sources.
+    #[cfg_attr(feature = "serde", serde(default = "unity"))]
+    pub gain: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub invert: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub mute: bool,
+}
+
+impl Default for OutputChannel {
+    fn default() -> Self {
+        Self {
+            sources: Vec::new(),
+            gain: 1.0,
+            invert: false,
+            mute: false,
+        }
+    }
+}
+
+impl OutputChannel {
+    /// An output channel carrying one input channel unchanged.
+    pub fn from_input(channel: usize) -> Self {
+        Self::mix(&[(channel, 1.0)])
+    }
+
+    /// An output channel mixing the given input channels with linear gains.
+    pub fn mix(sources: &[(usize, f64)]) -> Self {
+        Self {
+            sources: sources
+                .iter()
+                .map(|&(channel, gain)| RouteSource::new(channel, gain))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn set_gain_db(&mut self, db: f64) {
+        self.gain = db_to_gain(db);
+    }
+
+    fn render<S: Sample>(&self, frame: &[S]) -> S {
+        if self.mute {
+            return S::default();
+        }
+        let sum: f64 = self
+            .sources
+            .iter()
+            .map(|s| s.gain * frame[s.channel].to_f64())
+            .sum();
+        let gain = if self.invert { -self.gain } else { self.gain };
+        S::from_f64(sum * gain)
+    }
+}
+
+/// Standard downmixes, with the input channels in SMPTE order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Downmix {
+    /// L and R at -6 dB each.
+    StereoToMono,
+    /// L, R, C, LFE, Ls and Rs to Lo and Ro per ITU-R BS.775: C and the surrounds at -3 dB, LFE
+    /// discarded.
+    Surround51ToStereo,
+    /// The stereo downmix of 5.1, summed to mono at -6 dB.
+    Surround51ToMono,
+}
+
+impl Downmix {
+    /// The number of input channels the downmix reads.
+    pub fn input_channels(self) -> usize {
+        match self {
+            Downmix::StereoToMono => 2,
+            Downmix::Surround51ToStereo | Downmix::Surround51ToMono => 6,
+        }
+    }
+
+    // The output channels as (input channel, gain) pairs, relative to the first input channel.
+    fn sources(self) -> Vec<Vec<(usize, f64)>> {
+        let minus_3db = std::f64::consts::FRAC_1_SQRT_2;
+        match self {
+            Downmix::StereoToMono => vec![vec![(0, 0.5), (1, 0.5)]],
+            Downmix::Surround51ToStereo => vec![
+                vec![(0, 1.0), (2, minus_3db), (4, minus_3db)],
+                vec![(1, 1.0), (2, minus_3db), (5, minus_3db)],
+            ],
+            Downmix::Surround51ToMono => vec![vec![
+                (0, 0.5),
+                (1, 0.5),
+                (2, minus_3db),
+                (4, minus_3db / 2.0),
+                (5, minus_3db / 2.0),
+            ]],
+        }
+    }
+}
+
+/// An N-to-M channel routing and gain matrix for interleaved audio.
+///
+/// Routers are plain data, so they can be built up directly or from the presets, and loaded from
+/// configuration with the `serde` feature. They are validated when audio is routed.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AudioRouter {
+    pub input_channels: usize,
+    pub outputs: Vec<OutputChannel>,
+}
+
+impl AudioRouter {
+    /// A router that passes every channel through unchanged.
+    pub fn identity(channels: usize) -> Self {
+        Self::select(channels, &(0..channels).collect::<Vec<_>>())
+    }
+
+    /// A router that picks the given input channels, in the given order. Channels may be repeated.
+    pub fn select(input_channels: usize, channels: &[usize]) -> Self {
+        Self {
+            input_channels,
+            outputs: channels
+                .iter()
+                .map(|c| OutputChannel::from_input(*c))
+                .collect(),
+        }
+    }
+
+    /// A router that copies one input channel to both channels of a stereo pair.
+    pub fn mono_to_stereo(input_channels: usize, channel: usize) -> Self {
+        Self::select(input_channels, &[channel, channel])
+    }
+
+    /// A router applying a downmix to the input channels starting at `first_channel`.
+    pub fn downmix(input_channels: usize, first_channel: usize, downmix: Downmix) -> Self {
+        Self {
+            input_channels,
+            outputs: downmix
+                .sources()
+                .iter()
+                .map(|sources| {
+                    let sources: Vec<_> = sources
+                        .iter()
+                        .map(|&(channel, gain)| (first_channel + channel, gain))
+                        .collect();
+                    OutputChannel::mix(&sources)
+                })
+                .collect(),
+        }
+    }
+
+    pub fn output_channels(&self) -> usize {
+        self.outputs.len()
+    }
+
+    /// Checks that the router has inputs and outputs, and that every source is an input channel.
+    pub fn validate(&self) -> Result<(), SimpleError> {
+        if self.input_channels == 0 || self.outputs.is_empty() {
+            return Err(SimpleError::new("router needs input and output channels"));
+        }
+        let in_range = |s: &RouteSource| s.channel < self.input_channels;
+        if !self.outputs.iter().all(|o| o.sources.iter().all(in_range)) {
+            return Err(SimpleError::new("router source channel out of range"));
+        }
+        Ok(())
+    }
+
+    /// Routes interleaved samples from `src` into `dst`, which must hold the same number of sample
+    /// frames at the output channel count.
+    pub fn route<S: Sample>(&self, src: &[S], dst: &mut [S]) -> Result<(), SimpleError> {
+        self.validate()?;
+        if !src.len().is_multiple_of(self.input_channels) {
+            return Err(SimpleError::new(
+                "source length must be a multiple of the input channel count",
+            ));
+        }
+        if dst.len() != src.len() / self.input_channels * self.outputs.len() {
+            return Err(SimpleError::new(
+                "destination length doesn't match the source",
+            ));
+        }
+        let frames = src
+            .chunks(self.input_channels)
+            .zip(dst.chunks_mut(self.outputs.len()));
+        for (src, dst) in frames {
+            for (sample, output) in dst.iter_mut().zip(self.outputs.iter()) {
+                *sample = output.render(src);
+            }
+        }
+        Ok(())
+    }
+
+    /// Routes a captured packet, returning interleaved 32-bit samples whatever the packet holds.
+    /// 16-bit samples come back left-justified, shifted into the top half of the `i32`. Mixing
+    /// is the same either way: as in `route`, each output is summed in f64 and saturates after
+    /// its gain.
+    pub fn route_packet(&self, packet: &AudioPacket) -> Result<Vec<i32>, SimpleError> {
+        if packet.channel_count() != self.input_channels {
+            return Err(SimpleError::new(
+                "packet channel count doesn't match the router",
+            ));
+        }
+        let mut dst = vec![0; packet.sample_frame_count() * self.outputs.len()];
+        match packet.samples {
+            Samples::I16(samples) => {
+                let widened: Vec<i32> = samples.iter().map(|s| (*s as i32) << 16).collect();
+                self.route(&widened, &mut dst)?;
+            }
+            Samples::I32(samples) => self.route(samples, &mut dst)?,
+        }
+        Ok(dst)
+    }
+}
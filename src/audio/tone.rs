@@ -0,0 +1,226 @@
+//! Audio lineup and test signals.
+//!
+//! A `ToneGenerator` fills interleaved buffers of any sample type and channel count with one of
+//! the `Signal`s, continuing seamlessly from one buffer to the next.
+
+use std::f64::consts::PI;
+
+use simple_error::SimpleError;
+
+use super::Sample;
+
+/// The EBU R68 alignment level.
+pub const EBU_LINEUP_DBFS: f64 = -18.0;
+/// The SMPTE RP 155 alignment level.
+pub const SMPTE_LINEUP_DBFS: f64 = -20.0;
+
+// The RMS of the pink noise filter's output for uniform white noise at full scale.
+const PINK_NOISE_RMS: f64 = 1.745;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Signal {
+    /// A sine wave in every channel, with its peak at the given level.
+    Sine {
+        frequency: f64,
+        level_dbfs: f64,
+    },
+    /// BBC GLITS: 1 kHz at -18 dBFS in a 4 s cycle. The left channel of each pair is interrupted
+    /// for 250 ms at the start of the cycle, and the right channel twice for 250 ms from 500 ms.
+    Glits,
+    /// 1 kHz at -18 dBFS beeps identifying each channel: channel n beeps n + 1 times, for 100 ms
+    /// with 100 ms gaps, followed by a second of silence.
+    ChannelIdent,
+    /// Independent pink noise in every channel, with its RMS at the given level.
+    PinkNoise {
+        level_dbfs: f64,
+    },
+    Silence,
+}
+
+impl Signal {
+    /// A 1 kHz sine at the given level, as used for lineup.
+    pub fn lineup(level_dbfs: f64) -> Self {
+        Signal::Sine {
+            frequency: 1000.0,
+            level_dbfs,
+        }
+    }
+}
+
+fn amplitude(dbfs: f64) -> f64 {
+    10f64.powf(dbfs / 20.0)
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct PinkNoise {
+    state: u64,
+    b: [f64; 7],
+}
+
+impl PinkNoise {
+    fn new(seed: u64) -> Self {
+        Self {
+            state: seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1,
+            ..Default::default()
+        }
+    }
+
+    fn white(&mut self) -> f64 {
+        // xorshift64*
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        let x = self.state.wrapping_mul(0x2545_f491_4f6c_dd1d);
+        (x >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+    }
+
+    // Paul Kellet's refined pink noise filter.
+    fn next(&mut self) -> f64 {
+        let w = self.white();
+        let b = &mut self.b;
+        b[0] = 0.99886 * b[0] + w * 0.0555179;
+        b[1] = 0.99332 * b[1] + w * 0.0750759;
+        b[2] = 0.96900 * b[2] + w * 0.1538520;
+        b[3] = 0.86650 * b[3] + w * 0.3104856;
+        b[4] = 0.55000 * b[4] + w * 0.5329522;
+        b[5] = -0.7616 * b[5] - w * 0.0168980;
+        let pink = b.iter().sum::<f64>() + w * 0.5362;
+        b[6] = w * 0.115926;
+        pink / PINK_NOISE_RMS
+    }
+}
+
+/// Generates a `Signal` into interleaved sample buffers.
+#[derive(Clone, Debug)]
+pub struct ToneGenerator {
+    signal: Signal,
+    sample_rate: u32,
+    channels: usize,
+    // Sample frames generated so far.
+    position: u64,
+    // The phase of the sine, in cycles.
+    phase: f64,
+    noise: Vec<PinkNoise>,
+}
+
+impl ToneGenerator {
+    pub fn new(signal: Signal, sample_rate: u32, channels: usize) -> Result<Self, SimpleError> {
+        if sample_rate == 0 {
+            return Err(SimpleError::new("sample rate must be non-zero"));
+        }
+        if channels == 0 {
+            return Err(SimpleError::new("channel count must be non-zero"));
+        }
+        Ok(Self {
+            signal,
+            sample_rate,
+            channels,
+            position: 0,
+            phase: 0.0,
+            noise: (0..channels as u64).map(PinkNoise::new).collect(),
+        })
+    }
+
+    pub fn signal(&self) -> Signal {
+        self.signal
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Switches to another signal, restarting its cycle.
+    pub fn set_signal(&mut self, signal: Signal) {
+        self.signal = signal;
+        self.reset();
+    }
+
+    /// Restarts the signal from its beginning.
+    pub fn reset(&mut self) {
+        self.position = 0;
+        self.phase = 0.0;
+        self.noise = (0..self.channels as u64).map(PinkNoise::new).collect();
+    }
+
+    /// Fills an interleaved buffer, whose length must be a multiple of the channel count.
+    pub fn fill<S: Sample>(&mut self, buffer: &mut [S]) -> Result<(), SimpleError> {
+        if !buffer.len().is_multiple_of(self.channels) {
+            return Err(SimpleError::new(
+                "buffer length must be a multiple of the channel count",
+            ));
+        }
+        for frame in buffer.chunks_mut(self.channels) {
+            self.next_frame(frame);
+        }
+        Ok(())
+    }
+
+    fn next_frame<S: Sample>(&mut self, frame: &mut [S]) {
+        let rate = self.sample_rate as f64;
+        // Milliseconds into the cycle of the gated signals.
+        let ms = |cycle_ms: u64| (self.position * 1000 / self.sample_rate as u64) % cycle_ms;
+        let sine = (2.0 * PI * self.phase).sin();
+        let lineup = amplitude(EBU_LINEUP_DBFS) * sine;
+
+        match self.signal {
+            Signal::Sine {
+                frequency,
+                level_dbfs,
+            } => {
+                let x = amplitude(level_dbfs) * sine;
+                fill(frame, |_| x);
+                self.advance_phase(frequency / rate);
+            }
+            Signal::Glits => {
+                let t = ms(4000);
+                fill(frame, |c| {
+                    let gap = match c % 2 {
+                        0 => t < 250,
+                        _ => (500..750).contains(&t) || (1000..1250).contains(&t),
+                    };
+                    if gap {
+                        0.0
+                    } else {
+                        lineup
+                    }
+                });
+                self.advance_phase(1000.0 / rate);
+            }
+            Signal::ChannelIdent => {
+                let t = ms(self.channels as u64 * 200 + 1000);
+                fill(frame, |c| {
+                    let beep = t < (c as u64 + 1) * 200 && t % 200 < 100;
+                    if beep {
+                        lineup
+                    } else {
+                        0.0
+                    }
+                });
+                self.advance_phase(1000.0 / rate);
+            }
+            Signal::PinkNoise { level_dbfs } => {
+                let gain = amplitude(level_dbfs);
+                for (sample, noise) in frame.iter_mut().zip(self.noise.iter_mut()) {
+                    let x = (gain * noise.next()).clamp(-1.0, 1.0);
+                    *sample = S::from_f64(x * S::FULL_SCALE);
+                }
+            }
+            Signal::Silence => fill(frame, |_| 0.0),
+        }
+        self.position += 1;
+    }
+
+    fn advance_phase(&mut self, cycles: f64) {
+        self.phase = (self.phase + cycles).fract();
+    }
+}
+
+fn fill<S: Sample, F: Fn(usize) -> f64>(frame: &mut [S], f: F) {
+    for (c, sample) in frame.iter_mut().enumerate() {
+        *sample = S::from_f64(f(c) * S::FULL_SCALE);
+    }
+}
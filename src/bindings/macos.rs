@@ -0,0 +1,1670 @@
+/* Pre-generated bindings for src/lib.hpp. To refresh, build with `--features regenerate-bindings` and copy `$OUT_DIR/bindings.rs` over this file. */
+
+pub type UInt8 = ::std::os::raw::c_uchar;
+pub type UInt32 = ::std::os::raw::c_uint;
+pub type SInt32 = ::std::os::raw::c_int;
+pub type HRESULT = SInt32;
+pub type ULONG = UInt32;
+pub type LPVOID = *mut ::std::os::raw::c_void;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct CFUUIDBytes {
+    pub byte0: UInt8,
+    pub byte1: UInt8,
+    pub byte2: UInt8,
+    pub byte3: UInt8,
+    pub byte4: UInt8,
+    pub byte5: UInt8,
+    pub byte6: UInt8,
+    pub byte7: UInt8,
+    pub byte8: UInt8,
+    pub byte9: UInt8,
+    pub byte10: UInt8,
+    pub byte11: UInt8,
+    pub byte12: UInt8,
+    pub byte13: UInt8,
+    pub byte14: UInt8,
+    pub byte15: UInt8,
+}
+pub type REFIID = CFUUIDBytes;
+#[repr(C)]
+pub struct IUnknown__bindgen_vtable(::std::os::raw::c_void);
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IUnknown {
+    pub vtable_: *const IUnknown__bindgen_vtable,
+}
+pub const BLACKMAGIC_DECKLINK_API_VERSION: u32 = 168494080;
+pub const BLACKMAGIC_DECKLINK_API_VERSION_STRING: &[u8; 8] = b"10.11.4\0";
+pub const _BMDTimecodeFlags_bmdTimecodeFlagDefault: _BMDTimecodeFlags = 0;
+pub const _BMDTimecodeFlags_bmdTimecodeIsDropFrame: _BMDTimecodeFlags = 1;
+pub const _BMDTimecodeFlags_bmdTimecodeFieldMark: _BMDTimecodeFlags = 2;
+pub const _BMDTimecodeFlags_bmdTimecodeColorFrame: _BMDTimecodeFlags = 4;
+pub type _BMDTimecodeFlags = ::std::os::raw::c_uint;
+pub const _BMDVideoConnection_bmdVideoConnectionSDI: _BMDVideoConnection = 1;
+pub const _BMDVideoConnection_bmdVideoConnectionHDMI: _BMDVideoConnection = 2;
+pub const _BMDVideoConnection_bmdVideoConnectionOpticalSDI: _BMDVideoConnection = 4;
+pub const _BMDVideoConnection_bmdVideoConnectionComponent: _BMDVideoConnection = 8;
+pub const _BMDVideoConnection_bmdVideoConnectionComposite: _BMDVideoConnection = 16;
+pub const _BMDVideoConnection_bmdVideoConnectionSVideo: _BMDVideoConnection = 32;
+pub type _BMDVideoConnection = ::std::os::raw::c_uint;
+pub const _BMDAudioConnection_bmdAudioConnectionEmbedded: _BMDAudioConnection = 1;
+pub const _BMDAudioConnection_bmdAudioConnectionAESEBU: _BMDAudioConnection = 2;
+pub const _BMDAudioConnection_bmdAudioConnectionAnalog: _BMDAudioConnection = 4;
+pub const _BMDAudioConnection_bmdAudioConnectionAnalogXLR: _BMDAudioConnection = 8;
+pub const _BMDAudioConnection_bmdAudioConnectionAnalogRCA: _BMDAudioConnection = 16;
+pub const _BMDAudioConnection_bmdAudioConnectionMicrophone: _BMDAudioConnection = 32;
+pub const _BMDAudioConnection_bmdAudioConnectionHeadphones: _BMDAudioConnection = 64;
+pub type _BMDAudioConnection = ::std::os::raw::c_uint;
+pub const _BMDDeckControlConnection_bmdDeckControlConnectionRS422Remote1:
+    _BMDDeckControlConnection = 1;
+pub const _BMDDeckControlConnection_bmdDeckControlConnectionRS422Remote2:
+    _BMDDeckControlConnection = 2;
+pub type _BMDDeckControlConnection = ::std::os::raw::c_uint;
+pub const _BMDDisplayMode_bmdModeNTSC: _BMDDisplayMode = 1853125475;
+pub const _BMDDisplayMode_bmdModeNTSC2398: _BMDDisplayMode = 1853108787;
+pub const _BMDDisplayMode_bmdModePAL: _BMDDisplayMode = 1885432864;
+pub const _BMDDisplayMode_bmdModeNTSCp: _BMDDisplayMode = 1853125488;
+pub const _BMDDisplayMode_bmdModePALp: _BMDDisplayMode = 1885432944;
+pub const _BMDDisplayMode_bmdModeHD1080p2398: _BMDDisplayMode = 842231923;
+pub const _BMDDisplayMode_bmdModeHD1080p24: _BMDDisplayMode = 842297459;
+pub const _BMDDisplayMode_bmdModeHD1080p25: _BMDDisplayMode = 1215312437;
+pub const _BMDDisplayMode_bmdModeHD1080p2997: _BMDDisplayMode = 1215312441;
+pub const _BMDDisplayMode_bmdModeHD1080p30: _BMDDisplayMode = 1215312688;
+pub const _BMDDisplayMode_bmdModeHD1080p50: _BMDDisplayMode = 1215313200;
+pub const _BMDDisplayMode_bmdModeHD1080p5994: _BMDDisplayMode = 1215313209;
+pub const _BMDDisplayMode_bmdModeHD1080p6000: _BMDDisplayMode = 1215313456;
+pub const _BMDDisplayMode_bmdModeHD1080i50: _BMDDisplayMode = 1214854448;
+pub const _BMDDisplayMode_bmdModeHD1080i5994: _BMDDisplayMode = 1214854457;
+pub const _BMDDisplayMode_bmdModeHD1080i6000: _BMDDisplayMode = 1214854704;
+pub const _BMDDisplayMode_bmdModeHD720p50: _BMDDisplayMode = 1752184112;
+pub const _BMDDisplayMode_bmdModeHD720p5994: _BMDDisplayMode = 1752184121;
+pub const _BMDDisplayMode_bmdModeHD720p60: _BMDDisplayMode = 1752184368;
+pub const _BMDDisplayMode_bmdMode2k2398: _BMDDisplayMode = 845886003;
+pub const _BMDDisplayMode_bmdMode2k24: _BMDDisplayMode = 845886004;
+pub const _BMDDisplayMode_bmdMode2k25: _BMDDisplayMode = 845886005;
+pub const _BMDDisplayMode_bmdMode2kDCI2398: _BMDDisplayMode = 845427251;
+pub const _BMDDisplayMode_bmdMode2kDCI24: _BMDDisplayMode = 845427252;
+pub const _BMDDisplayMode_bmdMode2kDCI25: _BMDDisplayMode = 845427253;
+pub const _BMDDisplayMode_bmdMode2kDCI2997: _BMDDisplayMode = 845427257;
+pub const _BMDDisplayMode_bmdMode2kDCI30: _BMDDisplayMode = 845427504;
+pub const _BMDDisplayMode_bmdMode2kDCI50: _BMDDisplayMode = 845428016;
+pub const _BMDDisplayMode_bmdMode2kDCI5994: _BMDDisplayMode = 845428025;
+pub const _BMDDisplayMode_bmdMode2kDCI60: _BMDDisplayMode = 845428272;
+pub const _BMDDisplayMode_bmdMode4K2160p2398: _BMDDisplayMode = 879440435;
+pub const _BMDDisplayMode_bmdMode4K2160p24: _BMDDisplayMode = 879440436;
+pub const _BMDDisplayMode_bmdMode4K2160p25: _BMDDisplayMode = 879440437;
+pub const _BMDDisplayMode_bmdMode4K2160p2997: _BMDDisplayMode = 879440441;
+pub const _BMDDisplayMode_bmdMode4K2160p30: _BMDDisplayMode = 879440688;
+pub const _BMDDisplayMode_bmdMode4K2160p50: _BMDDisplayMode = 879441200;
+pub const _BMDDisplayMode_bmdMode4K2160p5994: _BMDDisplayMode = 879441209;
+pub const _BMDDisplayMode_bmdMode4K2160p60: _BMDDisplayMode = 879441456;
+pub const _BMDDisplayMode_bmdMode4kDCI2398: _BMDDisplayMode = 878981683;
+pub const _BMDDisplayMode_bmdMode4kDCI24: _BMDDisplayMode = 878981684;
+pub const _BMDDisplayMode_bmdMode4kDCI25: _BMDDisplayMode = 878981685;
+pub const _BMDDisplayMode_bmdMode4kDCI2997: _BMDDisplayMode = 878981689;
+pub const _BMDDisplayMode_bmdMode4kDCI30: _BMDDisplayMode = 878981936;
+pub const _BMDDisplayMode_bmdMode4kDCI50: _BMDDisplayMode = 878982448;
+pub const _BMDDisplayMode_bmdMode4kDCI5994: _BMDDisplayMode = 878982457;
+pub const _BMDDisplayMode_bmdMode4kDCI60: _BMDDisplayMode = 878982704;
+pub const _BMDDisplayMode_bmdMode8K4320p2398: _BMDDisplayMode = 946549299;
+pub const _BMDDisplayMode_bmdMode8K4320p24: _BMDDisplayMode = 946549300;
+pub const _BMDDisplayMode_bmdMode8K4320p25: _BMDDisplayMode = 946549301;
+pub const _BMDDisplayMode_bmdMode8K4320p2997: _BMDDisplayMode = 946549305;
+pub const _BMDDisplayMode_bmdMode8K4320p30: _BMDDisplayMode = 946549552;
+pub const _BMDDisplayMode_bmdMode8K4320p50: _BMDDisplayMode = 946550064;
+pub const _BMDDisplayMode_bmdMode8K4320p5994: _BMDDisplayMode = 946550073;
+pub const _BMDDisplayMode_bmdMode8K4320p60: _BMDDisplayMode = 946550320;
+pub const _BMDDisplayMode_bmdMode8kDCI2398: _BMDDisplayMode = 946090547;
+pub const _BMDDisplayMode_bmdMode8kDCI24: _BMDDisplayMode = 946090548;
+pub const _BMDDisplayMode_bmdMode8kDCI25: _BMDDisplayMode = 946090549;
+pub const _BMDDisplayMode_bmdMode8kDCI2997: _BMDDisplayMode = 946090553;
+pub const _BMDDisplayMode_bmdMode8kDCI30: _BMDDisplayMode = 946090800;
+pub const _BMDDisplayMode_bmdMode8kDCI50: _BMDDisplayMode = 946091312;
+pub const _BMDDisplayMode_bmdMode8kDCI5994: _BMDDisplayMode = 946091321;
+pub const _BMDDisplayMode_bmdMode8kDCI60: _BMDDisplayMode = 946091568;
+pub const _BMDDisplayMode_bmdModeCintelRAW: _BMDDisplayMode = 1920426857;
+pub const _BMDDisplayMode_bmdModeCintelCompressedRAW: _BMDDisplayMode = 1920426851;
+pub const _BMDDisplayMode_bmdModeUnknown: _BMDDisplayMode = 1769303659;
+pub type _BMDDisplayMode = ::std::os::raw::c_uint;
+pub const _BMDFieldDominance_bmdUnknownFieldDominance: _BMDFieldDominance = 0;
+pub const _BMDFieldDominance_bmdLowerFieldFirst: _BMDFieldDominance = 1819244402;
+pub const _BMDFieldDominance_bmdUpperFieldFirst: _BMDFieldDominance = 1970303090;
+pub const _BMDFieldDominance_bmdProgressiveFrame: _BMDFieldDominance = 1886547815;
+pub const _BMDFieldDominance_bmdProgressiveSegmentedFrame: _BMDFieldDominance = 1886610976;
+pub type _BMDFieldDominance = ::std::os::raw::c_uint;
+pub const _BMDPixelFormat_bmdFormat8BitYUV: _BMDPixelFormat = 846624121;
+pub const _BMDPixelFormat_bmdFormat10BitYUV: _BMDPixelFormat = 1983000880;
+pub const _BMDPixelFormat_bmdFormat8BitARGB: _BMDPixelFormat = 32;
+pub const _BMDPixelFormat_bmdFormat8BitBGRA: _BMDPixelFormat = 1111970369;
+pub const _BMDPixelFormat_bmdFormat10BitRGB: _BMDPixelFormat = 1915892016;
+pub const _BMDPixelFormat_bmdFormat12BitRGB: _BMDPixelFormat = 1378955842;
+pub const _BMDPixelFormat_bmdFormat12BitRGBLE: _BMDPixelFormat = 1378955852;
+pub const _BMDPixelFormat_bmdFormat10BitRGBXLE: _BMDPixelFormat = 1378955372;
+pub const _BMDPixelFormat_bmdFormat10BitRGBX: _BMDPixelFormat = 1378955362;
+pub const _BMDPixelFormat_bmdFormatH265: _BMDPixelFormat = 1751479857;
+pub const _BMDPixelFormat_bmdFormatDNxHR: _BMDPixelFormat = 1096180840;
+pub const _BMDPixelFormat_bmdFormat12BitRAWGRBG: _BMDPixelFormat = 1915826800;
+pub const _BMDPixelFormat_bmdFormat12BitRAWJPEG: _BMDPixelFormat = 1915827824;
+pub type _BMDPixelFormat = ::std::os::raw::c_uint;
+pub const _BMDDisplayModeFlags_bmdDisplayModeSupports3D: _BMDDisplayModeFlags = 1;
+pub const _BMDDisplayModeFlags_bmdDisplayModeColorspaceRec601: _BMDDisplayModeFlags = 2;
+pub const _BMDDisplayModeFlags_bmdDisplayModeColorspaceRec709: _BMDDisplayModeFlags = 4;
+pub const _BMDDisplayModeFlags_bmdDisplayModeColorspaceRec2020: _BMDDisplayModeFlags = 8;
+pub type _BMDDisplayModeFlags = ::std::os::raw::c_uint;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigSwapSerialRxTx: _BMDDeckLinkConfigurationID =
+    1936945780;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigHDMI3DPackingFormat:
+    _BMDDeckLinkConfigurationID = 862220390;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigBypass: _BMDDeckLinkConfigurationID =
+    1652125811;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigClockTimingAdjustment:
+    _BMDDeckLinkConfigurationID = 1668571492;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigDuplexMode: _BMDDeckLinkConfigurationID =
+    1685418104;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigAnalogAudioConsumerLevels:
+    _BMDDeckLinkConfigurationID = 1633772396;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigFieldFlickerRemoval:
+    _BMDDeckLinkConfigurationID = 1717855858;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigHD1080p24ToHD1080i5994Conversion:
+    _BMDDeckLinkConfigurationID = 1953445177;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfig444SDIVideoOutput:
+    _BMDDeckLinkConfigurationID = 875836527;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigBlackVideoOutputDuringCapture:
+    _BMDDeckLinkConfigurationID = 1651928931;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigLowLatencyVideoOutput:
+    _BMDDeckLinkConfigurationID = 1819047535;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigDownConversionOnAllAnalogOutput:
+    _BMDDeckLinkConfigurationID = 1667326319;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigSMPTELevelAOutput:
+    _BMDDeckLinkConfigurationID = 1936553057;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigRec2020Output: _BMDDeckLinkConfigurationID =
+    1919247154;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigQuadLinkSDIVideoOutputSquareDivisionSplit:
+    _BMDDeckLinkConfigurationID = 1396986195;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigOutput1080pAsPsF:
+    _BMDDeckLinkConfigurationID = 1885761650;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVideoOutputConnection:
+    _BMDDeckLinkConfigurationID = 1987011438;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVideoOutputConversionMode:
+    _BMDDeckLinkConfigurationID = 1987011437;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigAnalogVideoOutputFlags:
+    _BMDDeckLinkConfigurationID = 1635151718;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigReferenceInputTimingOffset:
+    _BMDDeckLinkConfigurationID = 1735159668;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVideoOutputIdleOperation:
+    _BMDDeckLinkConfigurationID = 1987012975;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigDefaultVideoOutputMode:
+    _BMDDeckLinkConfigurationID = 1685483373;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigDefaultVideoOutputModeFlags:
+    _BMDDeckLinkConfigurationID = 1685483366;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigSDIOutputLinkConfiguration:
+    _BMDDeckLinkConfigurationID = 1936682083;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVideoOutputComponentLumaGain:
+    _BMDDeckLinkConfigurationID = 1868786791;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVideoOutputComponentChromaBlueGain:
+    _BMDDeckLinkConfigurationID = 1868784482;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVideoOutputComponentChromaRedGain:
+    _BMDDeckLinkConfigurationID = 1868784498;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVideoOutputCompositeLumaGain:
+    _BMDDeckLinkConfigurationID = 1869180007;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVideoOutputCompositeChromaGain:
+    _BMDDeckLinkConfigurationID = 1869177703;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVideoOutputSVideoLumaGain:
+    _BMDDeckLinkConfigurationID = 1869835367;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVideoOutputSVideoChromaGain:
+    _BMDDeckLinkConfigurationID = 1869833063;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVideoInputScanning:
+    _BMDDeckLinkConfigurationID = 1986622307;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigUseDedicatedLTCInput:
+    _BMDDeckLinkConfigurationID = 1684829283;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigSDIInput3DPayloadOverride:
+    _BMDDeckLinkConfigurationID = 862217331;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigCapture1080pAsPsF:
+    _BMDDeckLinkConfigurationID = 1667657842;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVideoInputConnection:
+    _BMDDeckLinkConfigurationID = 1986618222;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigAnalogVideoInputFlags:
+    _BMDDeckLinkConfigurationID = 1635150182;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVideoInputConversionMode:
+    _BMDDeckLinkConfigurationID = 1986618221;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfig32PulldownSequenceInitialTimecodeFrame:
+    _BMDDeckLinkConfigurationID = 1885628774;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVANCSourceLine1Mapping:
+    _BMDDeckLinkConfigurationID = 1987275825;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVANCSourceLine2Mapping:
+    _BMDDeckLinkConfigurationID = 1987275826;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVANCSourceLine3Mapping:
+    _BMDDeckLinkConfigurationID = 1987275827;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigCapturePassThroughMode:
+    _BMDDeckLinkConfigurationID = 1668314221;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVideoInputComponentLumaGain:
+    _BMDDeckLinkConfigurationID = 1768123495;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVideoInputComponentChromaBlueGain:
+    _BMDDeckLinkConfigurationID = 1768121186;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVideoInputComponentChromaRedGain:
+    _BMDDeckLinkConfigurationID = 1768121202;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVideoInputCompositeLumaGain:
+    _BMDDeckLinkConfigurationID = 1768516711;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVideoInputCompositeChromaGain:
+    _BMDDeckLinkConfigurationID = 1768514407;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVideoInputSVideoLumaGain:
+    _BMDDeckLinkConfigurationID = 1769172071;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigVideoInputSVideoChromaGain:
+    _BMDDeckLinkConfigurationID = 1769169767;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigMicrophonePhantomPower:
+    _BMDDeckLinkConfigurationID = 1836083312;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigAudioInputConnection:
+    _BMDDeckLinkConfigurationID = 1634296686;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigAnalogAudioInputScaleChannel1:
+    _BMDDeckLinkConfigurationID = 1634300721;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigAnalogAudioInputScaleChannel2:
+    _BMDDeckLinkConfigurationID = 1634300722;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigAnalogAudioInputScaleChannel3:
+    _BMDDeckLinkConfigurationID = 1634300723;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigAnalogAudioInputScaleChannel4:
+    _BMDDeckLinkConfigurationID = 1634300724;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigDigitalAudioInputScale:
+    _BMDDeckLinkConfigurationID = 1684105587;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigMicrophoneInputGain:
+    _BMDDeckLinkConfigurationID = 1835623271;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigAudioOutputAESAnalogSwitch:
+    _BMDDeckLinkConfigurationID = 1634689377;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigAnalogAudioOutputScaleChannel1:
+    _BMDDeckLinkConfigurationID = 1634693937;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigAnalogAudioOutputScaleChannel2:
+    _BMDDeckLinkConfigurationID = 1634693938;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigAnalogAudioOutputScaleChannel3:
+    _BMDDeckLinkConfigurationID = 1634693939;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigAnalogAudioOutputScaleChannel4:
+    _BMDDeckLinkConfigurationID = 1634693940;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigDigitalAudioOutputScale:
+    _BMDDeckLinkConfigurationID = 1684107123;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigHeadphoneVolume:
+    _BMDDeckLinkConfigurationID = 1752592236;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigDeviceInformationLabel:
+    _BMDDeckLinkConfigurationID = 1684630625;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigDeviceInformationSerialNumber:
+    _BMDDeckLinkConfigurationID = 1684632430;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigDeviceInformationCompany:
+    _BMDDeckLinkConfigurationID = 1684628335;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigDeviceInformationPhone:
+    _BMDDeckLinkConfigurationID = 1684631656;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigDeviceInformationEmail:
+    _BMDDeckLinkConfigurationID = 1684628845;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigDeviceInformationDate:
+    _BMDDeckLinkConfigurationID = 1684628577;
+pub const _BMDDeckLinkConfigurationID_bmdDeckLinkConfigDeckControlConnection:
+    _BMDDeckLinkConfigurationID = 1684235119;
+pub type _BMDDeckLinkConfigurationID = ::std::os::raw::c_uint;
+pub const _BMDDeckLinkEncoderConfigurationID_bmdDeckLinkEncoderConfigPreferredBitDepth:
+    _BMDDeckLinkEncoderConfigurationID = 1701864050;
+pub const _BMDDeckLinkEncoderConfigurationID_bmdDeckLinkEncoderConfigFrameCodingMode:
+    _BMDDeckLinkEncoderConfigurationID = 1701208941;
+pub const _BMDDeckLinkEncoderConfigurationID_bmdDeckLinkEncoderConfigH265TargetBitrate:
+    _BMDDeckLinkEncoderConfigurationID = 1752457842;
+pub const _BMDDeckLinkEncoderConfigurationID_bmdDeckLinkEncoderConfigDNxHRCompressionID:
+    _BMDDeckLinkEncoderConfigurationID = 1684236644;
+pub const _BMDDeckLinkEncoderConfigurationID_bmdDeckLinkEncoderConfigDNxHRLevel:
+    _BMDDeckLinkEncoderConfigurationID = 1684825462;
+pub const _BMDDeckLinkEncoderConfigurationID_bmdDeckLinkEncoderConfigMPEG4SampleDescription:
+    _BMDDeckLinkEncoderConfigurationID = 1937011525;
+pub const _BMDDeckLinkEncoderConfigurationID_bmdDeckLinkEncoderConfigMPEG4CodecSpecificDesc:
+    _BMDDeckLinkEncoderConfigurationID = 1702061171;
+pub type _BMDDeckLinkEncoderConfigurationID = ::std::os::raw::c_uint;
+pub const _BMDDeckControlMode_bmdDeckControlNotOpened: _BMDDeckControlMode = 1853124464;
+pub const _BMDDeckControlMode_bmdDeckControlVTRControlMode: _BMDDeckControlMode = 1987342947;
+pub const _BMDDeckControlMode_bmdDeckControlExportMode: _BMDDeckControlMode = 1702391917;
+pub const _BMDDeckControlMode_bmdDeckControlCaptureMode: _BMDDeckControlMode = 1667330157;
+pub type _BMDDeckControlMode = ::std::os::raw::c_uint;
+pub const _BMDDeckControlEvent_bmdDeckControlAbortedEvent: _BMDDeckControlEvent = 1633842277;
+pub const _BMDDeckControlEvent_bmdDeckControlPrepareForExportEvent: _BMDDeckControlEvent =
+    1885758821;
+pub const _BMDDeckControlEvent_bmdDeckControlExportCompleteEvent: _BMDDeckControlEvent = 1702388581;
+pub const _BMDDeckControlEvent_bmdDeckControlPrepareForCaptureEvent: _BMDDeckControlEvent =
+    1885758309;
+pub const _BMDDeckControlEvent_bmdDeckControlCaptureCompleteEvent: _BMDDeckControlEvent =
+    1667458422;
+pub type _BMDDeckControlEvent = ::std::os::raw::c_uint;
+pub const _BMDDeckControlVTRControlState_bmdDeckControlNotInVTRControlMode:
+    _BMDDeckControlVTRControlState = 1853252461;
+pub const _BMDDeckControlVTRControlState_bmdDeckControlVTRControlPlaying:
+    _BMDDeckControlVTRControlState = 1987342960;
+pub const _BMDDeckControlVTRControlState_bmdDeckControlVTRControlRecording:
+    _BMDDeckControlVTRControlState = 1987342962;
+pub const _BMDDeckControlVTRControlState_bmdDeckControlVTRControlStill:
+    _BMDDeckControlVTRControlState = 1987342945;
+pub const _BMDDeckControlVTRControlState_bmdDeckControlVTRControlShuttleForward:
+    _BMDDeckControlVTRControlState = 1987343206;
+pub const _BMDDeckControlVTRControlState_bmdDeckControlVTRControlShuttleReverse:
+    _BMDDeckControlVTRControlState = 1987343218;
+pub const _BMDDeckControlVTRControlState_bmdDeckControlVTRControlJogForward:
+    _BMDDeckControlVTRControlState = 1987340902;
+pub const _BMDDeckControlVTRControlState_bmdDeckControlVTRControlJogReverse:
+    _BMDDeckControlVTRControlState = 1987340914;
+pub const _BMDDeckControlVTRControlState_bmdDeckControlVTRControlStopped:
+    _BMDDeckControlVTRControlState = 1987342959;
+pub type _BMDDeckControlVTRControlState = ::std::os::raw::c_uint;
+pub const _BMDDeckControlStatusFlags_bmdDeckControlStatusDeckConnected: _BMDDeckControlStatusFlags =
+    1;
+pub const _BMDDeckControlStatusFlags_bmdDeckControlStatusRemoteMode: _BMDDeckControlStatusFlags = 2;
+pub const _BMDDeckControlStatusFlags_bmdDeckControlStatusRecordInhibited:
+    _BMDDeckControlStatusFlags = 4;
+pub const _BMDDeckControlStatusFlags_bmdDeckControlStatusCassetteOut: _BMDDeckControlStatusFlags =
+    8;
+pub type _BMDDeckControlStatusFlags = ::std::os::raw::c_uint;
+pub const _BMDDeckControlExportModeOpsFlags_bmdDeckControlExportModeInsertVideo:
+    _BMDDeckControlExportModeOpsFlags = 1;
+pub const _BMDDeckControlExportModeOpsFlags_bmdDeckControlExportModeInsertAudio1:
+    _BMDDeckControlExportModeOpsFlags = 2;
+pub const _BMDDeckControlExportModeOpsFlags_bmdDeckControlExportModeInsertAudio2:
+    _BMDDeckControlExportModeOpsFlags = 4;
+pub const _BMDDeckControlExportModeOpsFlags_bmdDeckControlExportModeInsertAudio3:
+    _BMDDeckControlExportModeOpsFlags = 8;
+pub const _BMDDeckControlExportModeOpsFlags_bmdDeckControlExportModeInsertAudio4:
+    _BMDDeckControlExportModeOpsFlags = 16;
+pub const _BMDDeckControlExportModeOpsFlags_bmdDeckControlExportModeInsertAudio5:
+    _BMDDeckControlExportModeOpsFlags = 32;
+pub const _BMDDeckControlExportModeOpsFlags_bmdDeckControlExportModeInsertAudio6:
+    _BMDDeckControlExportModeOpsFlags = 64;
+pub const _BMDDeckControlExportModeOpsFlags_bmdDeckControlExportModeInsertAudio7:
+    _BMDDeckControlExportModeOpsFlags = 128;
+pub const _BMDDeckControlExportModeOpsFlags_bmdDeckControlExportModeInsertAudio8:
+    _BMDDeckControlExportModeOpsFlags = 256;
+pub const _BMDDeckControlExportModeOpsFlags_bmdDeckControlExportModeInsertAudio9:
+    _BMDDeckControlExportModeOpsFlags = 512;
+pub const _BMDDeckControlExportModeOpsFlags_bmdDeckControlExportModeInsertAudio10:
+    _BMDDeckControlExportModeOpsFlags = 1024;
+pub const _BMDDeckControlExportModeOpsFlags_bmdDeckControlExportModeInsertAudio11:
+    _BMDDeckControlExportModeOpsFlags = 2048;
+pub const _BMDDeckControlExportModeOpsFlags_bmdDeckControlExportModeInsertAudio12:
+    _BMDDeckControlExportModeOpsFlags = 4096;
+pub const _BMDDeckControlExportModeOpsFlags_bmdDeckControlExportModeInsertTimeCode:
+    _BMDDeckControlExportModeOpsFlags = 8192;
+pub const _BMDDeckControlExportModeOpsFlags_bmdDeckControlExportModeInsertAssemble:
+    _BMDDeckControlExportModeOpsFlags = 16384;
+pub const _BMDDeckControlExportModeOpsFlags_bmdDeckControlExportModeInsertPreview:
+    _BMDDeckControlExportModeOpsFlags = 32768;
+pub const _BMDDeckControlExportModeOpsFlags_bmdDeckControlUseManualExport:
+    _BMDDeckControlExportModeOpsFlags = 65536;
+pub type _BMDDeckControlExportModeOpsFlags = ::std::os::raw::c_uint;
+pub const _BMDDeckControlError_bmdDeckControlNoError: _BMDDeckControlError = 1852794226;
+pub const _BMDDeckControlError_bmdDeckControlModeError: _BMDDeckControlError = 1836017010;
+pub const _BMDDeckControlError_bmdDeckControlMissedInPointError: _BMDDeckControlError = 1835623794;
+pub const _BMDDeckControlError_bmdDeckControlDeckTimeoutError: _BMDDeckControlError = 1685349746;
+pub const _BMDDeckControlError_bmdDeckControlCommandFailedError: _BMDDeckControlError = 1667655026;
+pub const _BMDDeckControlError_bmdDeckControlDeviceAlreadyOpenedError: _BMDDeckControlError =
+    1684106351;
+pub const _BMDDeckControlError_bmdDeckControlFailedToOpenDeviceError: _BMDDeckControlError =
+    1717855602;
+pub const _BMDDeckControlError_bmdDeckControlInLocalModeError: _BMDDeckControlError = 1819108722;
+pub const _BMDDeckControlError_bmdDeckControlEndOfTapeError: _BMDDeckControlError = 1702126962;
+pub const _BMDDeckControlError_bmdDeckControlUserAbortError: _BMDDeckControlError = 1969317234;
+pub const _BMDDeckControlError_bmdDeckControlNoTapeInDeckError: _BMDDeckControlError = 1853121906;
+pub const _BMDDeckControlError_bmdDeckControlNoVideoFromCardError: _BMDDeckControlError =
+    1853253219;
+pub const _BMDDeckControlError_bmdDeckControlNoCommunicationError: _BMDDeckControlError =
+    1852010349;
+pub const _BMDDeckControlError_bmdDeckControlBufferTooSmallError: _BMDDeckControlError = 1651798893;
+pub const _BMDDeckControlError_bmdDeckControlBadChecksumError: _BMDDeckControlError = 1667787635;
+pub const _BMDDeckControlError_bmdDeckControlUnknownError: _BMDDeckControlError = 1970169202;
+pub type _BMDDeckControlError = ::std::os::raw::c_uint;
+pub const _BMDVideoOutputFlags_bmdVideoOutputFlagDefault: _BMDVideoOutputFlags = 0;
+pub const _BMDVideoOutputFlags_bmdVideoOutputVANC: _BMDVideoOutputFlags = 1;
+pub const _BMDVideoOutputFlags_bmdVideoOutputVITC: _BMDVideoOutputFlags = 2;
+pub const _BMDVideoOutputFlags_bmdVideoOutputRP188: _BMDVideoOutputFlags = 4;
+pub const _BMDVideoOutputFlags_bmdVideoOutputDualStream3D: _BMDVideoOutputFlags = 16;
+pub type _BMDVideoOutputFlags = ::std::os::raw::c_uint;
+pub const _BMDPacketType_bmdPacketTypeStreamInterruptedMarker: _BMDPacketType = 1936289396;
+pub const _BMDPacketType_bmdPacketTypeStreamData: _BMDPacketType = 1935958388;
+pub type _BMDPacketType = ::std::os::raw::c_uint;
+pub const _BMDFrameFlags_bmdFrameFlagDefault: _BMDFrameFlags = 0;
+pub const _BMDFrameFlags_bmdFrameFlagFlipVertical: _BMDFrameFlags = 1;
+pub const _BMDFrameFlags_bmdFrameContainsHDRMetadata: _BMDFrameFlags = 2;
+pub const _BMDFrameFlags_bmdFrameContainsCintelMetadata: _BMDFrameFlags = 4;
+pub const _BMDFrameFlags_bmdFrameCapturedAsPsF: _BMDFrameFlags = 1073741824;
+pub const _BMDFrameFlags_bmdFrameHasNoInputSource: _BMDFrameFlags = -2147483648;
+pub type _BMDFrameFlags = ::std::os::raw::c_int;
+pub const _BMDVideoInputFlags_bmdVideoInputFlagDefault: _BMDVideoInputFlags = 0;
+pub const _BMDVideoInputFlags_bmdVideoInputEnableFormatDetection: _BMDVideoInputFlags = 1;
+pub const _BMDVideoInputFlags_bmdVideoInputDualStream3D: _BMDVideoInputFlags = 2;
+pub type _BMDVideoInputFlags = ::std::os::raw::c_uint;
+pub const _BMDVideoInputFormatChangedEvents_bmdVideoInputDisplayModeChanged:
+    _BMDVideoInputFormatChangedEvents = 1;
+pub const _BMDVideoInputFormatChangedEvents_bmdVideoInputFieldDominanceChanged:
+    _BMDVideoInputFormatChangedEvents = 2;
+pub const _BMDVideoInputFormatChangedEvents_bmdVideoInputColorspaceChanged:
+    _BMDVideoInputFormatChangedEvents = 4;
+pub type _BMDVideoInputFormatChangedEvents = ::std::os::raw::c_uint;
+pub const _BMDDetectedVideoInputFormatFlags_bmdDetectedVideoInputYCbCr422:
+    _BMDDetectedVideoInputFormatFlags = 1;
+pub const _BMDDetectedVideoInputFormatFlags_bmdDetectedVideoInputRGB444:
+    _BMDDetectedVideoInputFormatFlags = 2;
+pub const _BMDDetectedVideoInputFormatFlags_bmdDetectedVideoInputDualStream3D:
+    _BMDDetectedVideoInputFormatFlags = 4;
+pub type _BMDDetectedVideoInputFormatFlags = ::std::os::raw::c_uint;
+pub const _BMDDeckLinkCapturePassthroughMode_bmdDeckLinkCapturePassthroughModeDisabled:
+    _BMDDeckLinkCapturePassthroughMode = 1885628787;
+pub const _BMDDeckLinkCapturePassthroughMode_bmdDeckLinkCapturePassthroughModeDirect:
+    _BMDDeckLinkCapturePassthroughMode = 1885628786;
+pub const _BMDDeckLinkCapturePassthroughMode_bmdDeckLinkCapturePassthroughModeCleanSwitch:
+    _BMDDeckLinkCapturePassthroughMode = 1885564014;
+pub type _BMDDeckLinkCapturePassthroughMode = ::std::os::raw::c_uint;
+pub const _BMDOutputFrameCompletionResult_bmdOutputFrameCompleted: _BMDOutputFrameCompletionResult =
+    0;
+pub const _BMDOutputFrameCompletionResult_bmdOutputFrameDisplayedLate:
+    _BMDOutputFrameCompletionResult = 1;
+pub const _BMDOutputFrameCompletionResult_bmdOutputFrameDropped: _BMDOutputFrameCompletionResult =
+    2;
+pub const _BMDOutputFrameCompletionResult_bmdOutputFrameFlushed: _BMDOutputFrameCompletionResult =
+    3;
+pub type _BMDOutputFrameCompletionResult = ::std::os::raw::c_uint;
+pub const _BMDReferenceStatus_bmdReferenceNotSupportedByHardware: _BMDReferenceStatus = 1;
+pub const _BMDReferenceStatus_bmdReferenceLocked: _BMDReferenceStatus = 2;
+pub type _BMDReferenceStatus = ::std::os::raw::c_uint;
+pub const _BMDAudioFormat_bmdAudioFormatPCM: _BMDAudioFormat = 1819304813;
+pub type _BMDAudioFormat = ::std::os::raw::c_uint;
+pub const _BMDAudioSampleRate_bmdAudioSampleRate48kHz: _BMDAudioSampleRate = 48000;
+pub type _BMDAudioSampleRate = ::std::os::raw::c_uint;
+pub const _BMDAudioSampleType_bmdAudioSampleType16bitInteger: _BMDAudioSampleType = 16;
+pub const _BMDAudioSampleType_bmdAudioSampleType32bitInteger: _BMDAudioSampleType = 32;
+pub type _BMDAudioSampleType = ::std::os::raw::c_uint;
+pub const _BMDAudioOutputStreamType_bmdAudioOutputStreamContinuous: _BMDAudioOutputStreamType = 0;
+pub const _BMDAudioOutputStreamType_bmdAudioOutputStreamContinuousDontResample:
+    _BMDAudioOutputStreamType = 1;
+pub const _BMDAudioOutputStreamType_bmdAudioOutputStreamTimestamped: _BMDAudioOutputStreamType = 2;
+pub type _BMDAudioOutputStreamType = ::std::os::raw::c_uint;
+pub const _BMDDisplayModeSupport_bmdDisplayModeNotSupported: _BMDDisplayModeSupport = 0;
+pub const _BMDDisplayModeSupport_bmdDisplayModeSupported: _BMDDisplayModeSupport = 1;
+pub const _BMDDisplayModeSupport_bmdDisplayModeSupportedWithConversion: _BMDDisplayModeSupport = 2;
+pub type _BMDDisplayModeSupport = ::std::os::raw::c_uint;
+pub const _BMDAncillaryPacketFormat_bmdAncillaryPacketFormatUInt8: _BMDAncillaryPacketFormat =
+    1969827896;
+pub const _BMDAncillaryPacketFormat_bmdAncillaryPacketFormatUInt16: _BMDAncillaryPacketFormat =
+    1969828150;
+pub const _BMDAncillaryPacketFormat_bmdAncillaryPacketFormatYCbCr10: _BMDAncillaryPacketFormat =
+    1983000880;
+pub type _BMDAncillaryPacketFormat = ::std::os::raw::c_uint;
+pub const _BMDTimecodeFormat_bmdTimecodeRP188VITC1: _BMDTimecodeFormat = 1919972913;
+pub const _BMDTimecodeFormat_bmdTimecodeRP188VITC2: _BMDTimecodeFormat = 1919955250;
+pub const _BMDTimecodeFormat_bmdTimecodeRP188LTC: _BMDTimecodeFormat = 1919970420;
+pub const _BMDTimecodeFormat_bmdTimecodeRP188HighFrameRate: _BMDTimecodeFormat = 1919969394;
+pub const _BMDTimecodeFormat_bmdTimecodeRP188Any: _BMDTimecodeFormat = 1919955256;
+pub const _BMDTimecodeFormat_bmdTimecodeVITC: _BMDTimecodeFormat = 1986622563;
+pub const _BMDTimecodeFormat_bmdTimecodeVITCField2: _BMDTimecodeFormat = 1986622514;
+pub const _BMDTimecodeFormat_bmdTimecodeSerial: _BMDTimecodeFormat = 1936028265;
+pub type _BMDTimecodeFormat = ::std::os::raw::c_uint;
+pub const _BMDAnalogVideoFlags_bmdAnalogVideoFlagCompositeSetup75: _BMDAnalogVideoFlags = 1;
+pub const _BMDAnalogVideoFlags_bmdAnalogVideoFlagComponentBetacamLevels: _BMDAnalogVideoFlags = 2;
+pub type _BMDAnalogVideoFlags = ::std::os::raw::c_uint;
+pub const _BMDAudioOutputAnalogAESSwitch_bmdAudioOutputSwitchAESEBU:
+    _BMDAudioOutputAnalogAESSwitch = 1634038560;
+pub const _BMDAudioOutputAnalogAESSwitch_bmdAudioOutputSwitchAnalog:
+    _BMDAudioOutputAnalogAESSwitch = 1634626663;
+pub type _BMDAudioOutputAnalogAESSwitch = ::std::os::raw::c_uint;
+pub const _BMDVideoOutputConversionMode_bmdNoVideoOutputConversion: _BMDVideoOutputConversionMode =
+    1852796517;
+pub const _BMDVideoOutputConversionMode_bmdVideoOutputLetterboxDownconversion:
+    _BMDVideoOutputConversionMode = 1819566712;
+pub const _BMDVideoOutputConversionMode_bmdVideoOutputAnamorphicDownconversion:
+    _BMDVideoOutputConversionMode = 1634562152;
+pub const _BMDVideoOutputConversionMode_bmdVideoOutputHD720toHD1080Conversion:
+    _BMDVideoOutputConversionMode = 926036067;
+pub const _BMDVideoOutputConversionMode_bmdVideoOutputHardwareLetterboxDownconversion:
+    _BMDVideoOutputConversionMode = 1213688930;
+pub const _BMDVideoOutputConversionMode_bmdVideoOutputHardwareAnamorphicDownconversion:
+    _BMDVideoOutputConversionMode = 1213686125;
+pub const _BMDVideoOutputConversionMode_bmdVideoOutputHardwareCenterCutDownconversion:
+    _BMDVideoOutputConversionMode = 1213686627;
+pub const _BMDVideoOutputConversionMode_bmdVideoOutputHardware720p1080pCrossconversion:
+    _BMDVideoOutputConversionMode = 2019778928;
+pub const _BMDVideoOutputConversionMode_bmdVideoOutputHardwareAnamorphic720pUpconversion:
+    _BMDVideoOutputConversionMode = 1969305456;
+pub const _BMDVideoOutputConversionMode_bmdVideoOutputHardwareAnamorphic1080iUpconversion:
+    _BMDVideoOutputConversionMode = 1969303913;
+pub const _BMDVideoOutputConversionMode_bmdVideoOutputHardwareAnamorphic149To720pUpconversion:
+    _BMDVideoOutputConversionMode = 1966356336;
+pub const _BMDVideoOutputConversionMode_bmdVideoOutputHardwareAnamorphic149To1080iUpconversion:
+    _BMDVideoOutputConversionMode = 1966354793;
+pub const _BMDVideoOutputConversionMode_bmdVideoOutputHardwarePillarbox720pUpconversion:
+    _BMDVideoOutputConversionMode = 1970288496;
+pub const _BMDVideoOutputConversionMode_bmdVideoOutputHardwarePillarbox1080iUpconversion:
+    _BMDVideoOutputConversionMode = 1970286953;
+pub type _BMDVideoOutputConversionMode = ::std::os::raw::c_uint;
+pub const _BMDVideoInputConversionMode_bmdNoVideoInputConversion: _BMDVideoInputConversionMode =
+    1852796517;
+pub const _BMDVideoInputConversionMode_bmdVideoInputLetterboxDownconversionFromHD1080:
+    _BMDVideoInputConversionMode = 825257058;
+pub const _BMDVideoInputConversionMode_bmdVideoInputAnamorphicDownconversionFromHD1080:
+    _BMDVideoInputConversionMode = 825254253;
+pub const _BMDVideoInputConversionMode_bmdVideoInputLetterboxDownconversionFromHD720:
+    _BMDVideoInputConversionMode = 926051426;
+pub const _BMDVideoInputConversionMode_bmdVideoInputAnamorphicDownconversionFromHD720:
+    _BMDVideoInputConversionMode = 926048621;
+pub const _BMDVideoInputConversionMode_bmdVideoInputLetterboxUpconversion:
+    _BMDVideoInputConversionMode = 1818391920;
+pub const _BMDVideoInputConversionMode_bmdVideoInputAnamorphicUpconversion:
+    _BMDVideoInputConversionMode = 1634563440;
+pub type _BMDVideoInputConversionMode = ::std::os::raw::c_uint;
+pub const _BMDVideo3DPackingFormat_bmdVideo3DPackingSidebySideHalf: _BMDVideo3DPackingFormat =
+    1935831912;
+pub const _BMDVideo3DPackingFormat_bmdVideo3DPackingLinebyLine: _BMDVideo3DPackingFormat =
+    1818392940;
+pub const _BMDVideo3DPackingFormat_bmdVideo3DPackingTopAndBottom: _BMDVideo3DPackingFormat =
+    1952539247;
+pub const _BMDVideo3DPackingFormat_bmdVideo3DPackingFramePacking: _BMDVideo3DPackingFormat =
+    1718775915;
+pub const _BMDVideo3DPackingFormat_bmdVideo3DPackingLeftOnly: _BMDVideo3DPackingFormat = 1818584692;
+pub const _BMDVideo3DPackingFormat_bmdVideo3DPackingRightOnly: _BMDVideo3DPackingFormat =
+    1919510376;
+pub type _BMDVideo3DPackingFormat = ::std::os::raw::c_uint;
+pub const _BMDIdleVideoOutputOperation_bmdIdleVideoOutputBlack: _BMDIdleVideoOutputOperation =
+    1651269987;
+pub const _BMDIdleVideoOutputOperation_bmdIdleVideoOutputLastFrame: _BMDIdleVideoOutputOperation =
+    1818322529;
+pub type _BMDIdleVideoOutputOperation = ::std::os::raw::c_uint;
+pub const _BMDVideoEncoderFrameCodingMode_bmdVideoEncoderFrameCodingModeInter:
+    _BMDVideoEncoderFrameCodingMode = 1768846437;
+pub const _BMDVideoEncoderFrameCodingMode_bmdVideoEncoderFrameCodingModeIntra:
+    _BMDVideoEncoderFrameCodingMode = 1768846450;
+pub type _BMDVideoEncoderFrameCodingMode = ::std::os::raw::c_uint;
+pub const _BMDDNxHRLevel_bmdDNxHRLevelSQ: _BMDDNxHRLevel = 1684960113;
+pub const _BMDDNxHRLevel_bmdDNxHRLevelLB: _BMDDNxHRLevel = 1684958306;
+pub const _BMDDNxHRLevel_bmdDNxHRLevelHQ: _BMDDNxHRLevel = 1684957297;
+pub const _BMDDNxHRLevel_bmdDNxHRLevelHQX: _BMDDNxHRLevel = 1684566392;
+pub const _BMDDNxHRLevel_bmdDNxHRLevel444: _BMDDNxHRLevel = 1681142836;
+pub type _BMDDNxHRLevel = ::std::os::raw::c_uint;
+pub const _BMDLinkConfiguration_bmdLinkConfigurationSingleLink: _BMDLinkConfiguration = 1818456940;
+pub const _BMDLinkConfiguration_bmdLinkConfigurationDualLink: _BMDLinkConfiguration = 1818453100;
+pub const _BMDLinkConfiguration_bmdLinkConfigurationQuadLink: _BMDLinkConfiguration = 1818456428;
+pub type _BMDLinkConfiguration = ::std::os::raw::c_uint;
+pub const _BMDDeviceInterface_bmdDeviceInterfacePCI: _BMDDeviceInterface = 1885563168;
+pub const _BMDDeviceInterface_bmdDeviceInterfaceUSB: _BMDDeviceInterface = 1970496032;
+pub const _BMDDeviceInterface_bmdDeviceInterfaceThunderbolt: _BMDDeviceInterface = 1953002862;
+pub type _BMDDeviceInterface = ::std::os::raw::c_uint;
+pub const _BMDColorspace_bmdColorspaceRec601: _BMDColorspace = 1916153905;
+pub const _BMDColorspace_bmdColorspaceRec709: _BMDColorspace = 1916219449;
+pub const _BMDColorspace_bmdColorspaceRec2020: _BMDColorspace = 842019376;
+pub type _BMDColorspace = ::std::os::raw::c_uint;
+pub const _BMDDynamicRange_bmdDynamicRangeSDR: _BMDDynamicRange = 0;
+pub const _BMDDynamicRange_bmdDynamicRangeHDRStaticPQ: _BMDDynamicRange = 536870912;
+pub const _BMDDynamicRange_bmdDynamicRangeHDRStaticHLG: _BMDDynamicRange = 1073741824;
+pub type _BMDDynamicRange = ::std::os::raw::c_uint;
+pub const _BMDDeckLinkHDMIInputEDIDID_bmdDeckLinkHDMIInputEDIDDynamicRange:
+    _BMDDeckLinkHDMIInputEDIDID = 1212761209;
+pub type _BMDDeckLinkHDMIInputEDIDID = ::std::os::raw::c_uint;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataColorspace:
+    _BMDDeckLinkFrameMetadataID = 1668509795;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataHDRElectroOpticalTransferFunc:
+    _BMDDeckLinkFrameMetadataID = 1701803110;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelFilmType:
+    _BMDDeckLinkFrameMetadataID = 1667658873;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelFilmGauge:
+    _BMDDeckLinkFrameMetadataID = 1667655521;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelOffsetDetectedHorizontal:
+    _BMDDeckLinkFrameMetadataID = 1868850792;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelOffsetDetectedVertical:
+    _BMDDeckLinkFrameMetadataID = 1868850806;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelKeykodeLow:
+    _BMDDeckLinkFrameMetadataID = 1667984236;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelKeykodeHigh:
+    _BMDDeckLinkFrameMetadataID = 1667984232;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelTile1Size:
+    _BMDDeckLinkFrameMetadataID = 1668559219;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelTile2Size:
+    _BMDDeckLinkFrameMetadataID = 1668559475;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelTile3Size:
+    _BMDDeckLinkFrameMetadataID = 1668559731;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelTile4Size:
+    _BMDDeckLinkFrameMetadataID = 1668559987;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelImageWidth:
+    _BMDDeckLinkFrameMetadataID = 1230459000;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelImageHeight:
+    _BMDDeckLinkFrameMetadataID = 1229475960;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLinearMaskingRedInRed:
+    _BMDDeckLinkFrameMetadataID = 1836214642;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLinearMaskingGreenInRed:
+    _BMDDeckLinkFrameMetadataID = 1835493746;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLinearMaskingBlueInRed:
+    _BMDDeckLinkFrameMetadataID = 1835166066;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLinearMaskingRedInGreen:
+    _BMDDeckLinkFrameMetadataID = 1836214631;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLinearMaskingGreenInGreen:
+    _BMDDeckLinkFrameMetadataID = 1835493735;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLinearMaskingBlueInGreen:
+    _BMDDeckLinkFrameMetadataID = 1835166055;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLinearMaskingRedInBlue:
+    _BMDDeckLinkFrameMetadataID = 1836214626;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLinearMaskingGreenInBlue:
+    _BMDDeckLinkFrameMetadataID = 1835493730;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLinearMaskingBlueInBlue:
+    _BMDDeckLinkFrameMetadataID = 1835166050;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLogMaskingRedInRed:
+    _BMDDeckLinkFrameMetadataID = 1835823730;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLogMaskingGreenInRed:
+    _BMDDeckLinkFrameMetadataID = 1835820914;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLogMaskingBlueInRed:
+    _BMDDeckLinkFrameMetadataID = 1835819634;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLogMaskingRedInGreen:
+    _BMDDeckLinkFrameMetadataID = 1835823719;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLogMaskingGreenInGreen:
+    _BMDDeckLinkFrameMetadataID = 1835820903;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLogMaskingBlueInGreen:
+    _BMDDeckLinkFrameMetadataID = 1835819623;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLogMaskingRedInBlue:
+    _BMDDeckLinkFrameMetadataID = 1835823714;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLogMaskingGreenInBlue:
+    _BMDDeckLinkFrameMetadataID = 1835820898;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLogMaskingBlueInBlue:
+    _BMDDeckLinkFrameMetadataID = 1835819618;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelFilmFrameRate:
+    _BMDDeckLinkFrameMetadataID = 1667655282;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataHDRDisplayPrimariesRedX:
+    _BMDDeckLinkFrameMetadataID = 1751413368;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataHDRDisplayPrimariesRedY:
+    _BMDDeckLinkFrameMetadataID = 1751413369;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataHDRDisplayPrimariesGreenX:
+    _BMDDeckLinkFrameMetadataID = 1751410552;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataHDRDisplayPrimariesGreenY:
+    _BMDDeckLinkFrameMetadataID = 1751410553;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataHDRDisplayPrimariesBlueX:
+    _BMDDeckLinkFrameMetadataID = 1751409272;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataHDRDisplayPrimariesBlueY:
+    _BMDDeckLinkFrameMetadataID = 1751409273;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataHDRWhitePointX:
+    _BMDDeckLinkFrameMetadataID = 1751414648;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataHDRWhitePointY:
+    _BMDDeckLinkFrameMetadataID = 1751414649;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataHDRMaxDisplayMasteringLuminance:
+    _BMDDeckLinkFrameMetadataID = 1751412076;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataHDRMinDisplayMasteringLuminance:
+    _BMDDeckLinkFrameMetadataID = 1752000876;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataHDRMaximumContentLightLevel:
+    _BMDDeckLinkFrameMetadataID = 1835232364;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataHDRMaximumFrameAverageLightLevel:
+    _BMDDeckLinkFrameMetadataID = 1717660780;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelOffsetToApplyHorizontal:
+    _BMDDeckLinkFrameMetadataID = 1869898088;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelOffsetToApplyVertical:
+    _BMDDeckLinkFrameMetadataID = 1869898102;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelGainRed:
+    _BMDDeckLinkFrameMetadataID = 1281774180;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelGainGreen:
+    _BMDDeckLinkFrameMetadataID = 1281771378;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelGainBlue:
+    _BMDDeckLinkFrameMetadataID = 1281770092;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLiftRed:
+    _BMDDeckLinkFrameMetadataID = 1198412388;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLiftGreen:
+    _BMDDeckLinkFrameMetadataID = 1198409586;
+pub const _BMDDeckLinkFrameMetadataID_bmdDeckLinkFrameMetadataCintelLiftBlue:
+    _BMDDeckLinkFrameMetadataID = 1198408300;
+pub type _BMDDeckLinkFrameMetadataID = ::std::os::raw::c_uint;
+pub const _BMDDuplexMode_bmdDuplexModeFull: _BMDDuplexMode = 1717859696;
+pub const _BMDDuplexMode_bmdDuplexModeHalf: _BMDDuplexMode = 1751414128;
+pub type _BMDDuplexMode = ::std::os::raw::c_uint;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkSupportsInternalKeying: _BMDDeckLinkAttributeID =
+    1801812329;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkSupportsExternalKeying: _BMDDeckLinkAttributeID =
+    1801812325;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkSupportsHDKeying: _BMDDeckLinkAttributeID = 1801812328;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkSupportsInputFormatDetection: _BMDDeckLinkAttributeID =
+    1768842852;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkHasReferenceInput: _BMDDeckLinkAttributeID =
+    1752328558;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkHasSerialPort: _BMDDeckLinkAttributeID = 1752395892;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkHasAnalogVideoOutputGain: _BMDDeckLinkAttributeID =
+    1635151719;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkCanOnlyAdjustOverallVideoOutputGain:
+    _BMDDeckLinkAttributeID = 1870032743;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkHasVideoInputAntiAliasingFilter:
+    _BMDDeckLinkAttributeID = 1633773164;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkHasBypass: _BMDDeckLinkAttributeID = 1652125811;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkSupportsClockTimingAdjustment:
+    _BMDDeckLinkAttributeID = 1668571492;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkSupportsFullDuplex: _BMDDeckLinkAttributeID =
+    1717859696;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkSupportsFullFrameReferenceInputTimingOffset:
+    _BMDDeckLinkAttributeID = 1718774126;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkSupportsSMPTELevelAOutput: _BMDDeckLinkAttributeID =
+    1819700321;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkSupportsDualLinkSDI: _BMDDeckLinkAttributeID =
+    1935961203;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkSupportsQuadLinkSDI: _BMDDeckLinkAttributeID =
+    1936813171;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkSupportsIdleOutput: _BMDDeckLinkAttributeID =
+    1768189813;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkHasLTCTimecodeInput: _BMDDeckLinkAttributeID =
+    1751938147;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkSupportsDuplexModeConfiguration:
+    _BMDDeckLinkAttributeID = 1685418104;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkSupportsHDRMetadata: _BMDDeckLinkAttributeID =
+    1751413357;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkSupportsColorspaceMetadata: _BMDDeckLinkAttributeID =
+    1668113780;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkSupportsHDMITimecode: _BMDDeckLinkAttributeID =
+    1752459629;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkSupportsHighFrameRateTimecode:
+    _BMDDeckLinkAttributeID = 1212568148;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkSupportsSynchronizeToCaptureGroup:
+    _BMDDeckLinkAttributeID = 1937007463;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkSupportsSynchronizeToPlaybackGroup:
+    _BMDDeckLinkAttributeID = 1937010791;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkMaximumAudioChannels: _BMDDeckLinkAttributeID =
+    1835098984;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkMaximumAnalogAudioInputChannels:
+    _BMDDeckLinkAttributeID = 1767990120;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkMaximumAnalogAudioOutputChannels:
+    _BMDDeckLinkAttributeID = 1633772392;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkNumberOfSubDevices: _BMDDeckLinkAttributeID =
+    1853055588;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkSubDeviceIndex: _BMDDeckLinkAttributeID = 1937072745;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkPersistentID: _BMDDeckLinkAttributeID = 1885694308;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkDeviceGroupID: _BMDDeckLinkAttributeID = 1684498788;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkTopologicalID: _BMDDeckLinkAttributeID = 1953458532;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkVideoOutputConnections: _BMDDeckLinkAttributeID =
+    1987011438;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkVideoInputConnections: _BMDDeckLinkAttributeID =
+    1986618222;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkAudioOutputConnections: _BMDDeckLinkAttributeID =
+    1634689902;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkAudioInputConnections: _BMDDeckLinkAttributeID =
+    1634296686;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkVideoIOSupport: _BMDDeckLinkAttributeID = 1986621299;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkDeckControlConnections: _BMDDeckLinkAttributeID =
+    1684235118;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkDeviceInterface: _BMDDeckLinkAttributeID = 1684174195;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkAudioInputRCAChannelCount: _BMDDeckLinkAttributeID =
+    1634300515;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkAudioInputXLRChannelCount: _BMDDeckLinkAttributeID =
+    1634302051;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkAudioOutputRCAChannelCount: _BMDDeckLinkAttributeID =
+    1634693731;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkAudioOutputXLRChannelCount: _BMDDeckLinkAttributeID =
+    1634695267;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkPairedDevicePersistentID: _BMDDeckLinkAttributeID =
+    1886415204;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkVideoInputGainMinimum: _BMDDeckLinkAttributeID =
+    1986619245;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkVideoInputGainMaximum: _BMDDeckLinkAttributeID =
+    1986619256;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkVideoOutputGainMinimum: _BMDDeckLinkAttributeID =
+    1987012461;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkVideoOutputGainMaximum: _BMDDeckLinkAttributeID =
+    1987012472;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkMicrophoneInputGainMinimum: _BMDDeckLinkAttributeID =
+    1835624301;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkMicrophoneInputGainMaximum: _BMDDeckLinkAttributeID =
+    1835624312;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkSerialPortDeviceName: _BMDDeckLinkAttributeID =
+    1936486510;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkVendorName: _BMDDeckLinkAttributeID = 1986946162;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkDisplayName: _BMDDeckLinkAttributeID = 1685287022;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkModelName: _BMDDeckLinkAttributeID = 1835297902;
+pub const _BMDDeckLinkAttributeID_BMDDeckLinkDeviceHandle: _BMDDeckLinkAttributeID = 1684371048;
+pub type _BMDDeckLinkAttributeID = ::std::os::raw::c_uint;
+pub const _BMDDeckLinkAPIInformationID_BMDDeckLinkAPIVersion: _BMDDeckLinkAPIInformationID =
+    1986359923;
+pub type _BMDDeckLinkAPIInformationID = ::std::os::raw::c_uint;
+pub const _BMDDeckLinkStatusID_bmdDeckLinkStatusDetectedVideoInputMode: _BMDDeckLinkStatusID =
+    1685481837;
+pub const _BMDDeckLinkStatusID_bmdDeckLinkStatusDetectedVideoInputFlags: _BMDDeckLinkStatusID =
+    1685481830;
+pub const _BMDDeckLinkStatusID_bmdDeckLinkStatusCurrentVideoInputMode: _BMDDeckLinkStatusID =
+    1668704621;
+pub const _BMDDeckLinkStatusID_bmdDeckLinkStatusCurrentVideoInputPixelFormat: _BMDDeckLinkStatusID =
+    1668704624;
+pub const _BMDDeckLinkStatusID_bmdDeckLinkStatusCurrentVideoInputFlags: _BMDDeckLinkStatusID =
+    1668704614;
+pub const _BMDDeckLinkStatusID_bmdDeckLinkStatusCurrentVideoOutputMode: _BMDDeckLinkStatusID =
+    1668706157;
+pub const _BMDDeckLinkStatusID_bmdDeckLinkStatusCurrentVideoOutputFlags: _BMDDeckLinkStatusID =
+    1668706150;
+pub const _BMDDeckLinkStatusID_bmdDeckLinkStatusPCIExpressLinkWidth: _BMDDeckLinkStatusID =
+    1886873956;
+pub const _BMDDeckLinkStatusID_bmdDeckLinkStatusPCIExpressLinkSpeed: _BMDDeckLinkStatusID =
+    1886154347;
+pub const _BMDDeckLinkStatusID_bmdDeckLinkStatusLastVideoOutputPixelFormat: _BMDDeckLinkStatusID =
+    1869638008;
+pub const _BMDDeckLinkStatusID_bmdDeckLinkStatusReferenceSignalMode: _BMDDeckLinkStatusID =
+    1919247981;
+pub const _BMDDeckLinkStatusID_bmdDeckLinkStatusReferenceSignalFlags: _BMDDeckLinkStatusID =
+    1919247974;
+pub const _BMDDeckLinkStatusID_bmdDeckLinkStatusDuplexMode: _BMDDeckLinkStatusID = 1685418104;
+pub const _BMDDeckLinkStatusID_bmdDeckLinkStatusBusy: _BMDDeckLinkStatusID = 1651864441;
+pub const _BMDDeckLinkStatusID_bmdDeckLinkStatusInterchangeablePanelType: _BMDDeckLinkStatusID =
+    1768124532;
+pub const _BMDDeckLinkStatusID_bmdDeckLinkStatusDeviceTemperature: _BMDDeckLinkStatusID =
+    1685351792;
+pub const _BMDDeckLinkStatusID_bmdDeckLinkStatusVideoInputSignalLocked: _BMDDeckLinkStatusID =
+    1986622316;
+pub const _BMDDeckLinkStatusID_bmdDeckLinkStatusReferenceSignalLocked: _BMDDeckLinkStatusID =
+    1919247980;
+pub const _BMDDeckLinkStatusID_bmdDeckLinkStatusReceivedEDID: _BMDDeckLinkStatusID = 1701079396;
+pub type _BMDDeckLinkStatusID = ::std::os::raw::c_uint;
+pub const _BMDDeckLinkVideoStatusFlags_bmdDeckLinkVideoStatusPsF: _BMDDeckLinkVideoStatusFlags = 1;
+pub const _BMDDeckLinkVideoStatusFlags_bmdDeckLinkVideoStatusDualStream3D:
+    _BMDDeckLinkVideoStatusFlags = 2;
+pub type _BMDDeckLinkVideoStatusFlags = ::std::os::raw::c_uint;
+pub const _BMDDuplexStatus_bmdDuplexStatusFullDuplex: _BMDDuplexStatus = 1717859696;
+pub const _BMDDuplexStatus_bmdDuplexStatusHalfDuplex: _BMDDuplexStatus = 1751414128;
+pub const _BMDDuplexStatus_bmdDuplexStatusSimplex: _BMDDuplexStatus = 1936747640;
+pub const _BMDDuplexStatus_bmdDuplexStatusInactive: _BMDDuplexStatus = 1768841571;
+pub type _BMDDuplexStatus = ::std::os::raw::c_uint;
+pub const _BMDPanelType_bmdPanelNotDetected: _BMDPanelType = 1852862060;
+pub const _BMDPanelType_bmdPanelTeranexMiniSmartPanel: _BMDPanelType = 1953330029;
+pub type _BMDPanelType = ::std::os::raw::c_uint;
+pub const _BMDDeviceBusyState_bmdDeviceCaptureBusy: _BMDDeviceBusyState = 1;
+pub const _BMDDeviceBusyState_bmdDevicePlaybackBusy: _BMDDeviceBusyState = 2;
+pub const _BMDDeviceBusyState_bmdDeviceSerialPortBusy: _BMDDeviceBusyState = 4;
+pub type _BMDDeviceBusyState = ::std::os::raw::c_uint;
+pub const _BMDVideoIOSupport_bmdDeviceSupportsCapture: _BMDVideoIOSupport = 1;
+pub const _BMDVideoIOSupport_bmdDeviceSupportsPlayback: _BMDVideoIOSupport = 2;
+pub type _BMDVideoIOSupport = ::std::os::raw::c_uint;
+pub const _BMD3DPreviewFormat_bmd3DPreviewFormatDefault: _BMD3DPreviewFormat = 1684366945;
+pub const _BMD3DPreviewFormat_bmd3DPreviewFormatLeftOnly: _BMD3DPreviewFormat = 1818584692;
+pub const _BMD3DPreviewFormat_bmd3DPreviewFormatRightOnly: _BMD3DPreviewFormat = 1919510376;
+pub const _BMD3DPreviewFormat_bmd3DPreviewFormatSideBySide: _BMD3DPreviewFormat = 1936286821;
+pub const _BMD3DPreviewFormat_bmd3DPreviewFormatTopBottom: _BMD3DPreviewFormat = 1953460322;
+pub type _BMD3DPreviewFormat = ::std::os::raw::c_uint;
+pub const _BMDNotifications_bmdPreferencesChanged: _BMDNotifications = 1886545254;
+pub const _BMDNotifications_bmdStatusChanged: _BMDNotifications = 1937006964;
+pub type _BMDNotifications = ::std::os::raw::c_uint;
+pub const _BMDStreamingDeviceMode_bmdStreamingDeviceIdle: _BMDStreamingDeviceMode = 1768189029;
+pub const _BMDStreamingDeviceMode_bmdStreamingDeviceEncoding: _BMDStreamingDeviceMode = 1701733231;
+pub const _BMDStreamingDeviceMode_bmdStreamingDeviceStopping: _BMDStreamingDeviceMode = 1937010544;
+pub const _BMDStreamingDeviceMode_bmdStreamingDeviceUnknown: _BMDStreamingDeviceMode = 1836412523;
+pub type _BMDStreamingDeviceMode = ::std::os::raw::c_uint;
+pub const _BMDStreamingEncodingFrameRate_bmdStreamingEncodedFrameRate50i:
+    _BMDStreamingEncodingFrameRate = 1697984617;
+pub const _BMDStreamingEncodingFrameRate_bmdStreamingEncodedFrameRate5994i:
+    _BMDStreamingEncodingFrameRate = 1697986921;
+pub const _BMDStreamingEncodingFrameRate_bmdStreamingEncodedFrameRate60i:
+    _BMDStreamingEncodingFrameRate = 1698050153;
+pub const _BMDStreamingEncodingFrameRate_bmdStreamingEncodedFrameRate2398p:
+    _BMDStreamingEncodingFrameRate = 1697788784;
+pub const _BMDStreamingEncodingFrameRate_bmdStreamingEncodedFrameRate24p:
+    _BMDStreamingEncodingFrameRate = 1697789040;
+pub const _BMDStreamingEncodingFrameRate_bmdStreamingEncodedFrameRate25p:
+    _BMDStreamingEncodingFrameRate = 1697789296;
+pub const _BMDStreamingEncodingFrameRate_bmdStreamingEncodedFrameRate2997p:
+    _BMDStreamingEncodingFrameRate = 1697790320;
+pub const _BMDStreamingEncodingFrameRate_bmdStreamingEncodedFrameRate30p:
+    _BMDStreamingEncodingFrameRate = 1697853552;
+pub const _BMDStreamingEncodingFrameRate_bmdStreamingEncodedFrameRate50p:
+    _BMDStreamingEncodingFrameRate = 1697984624;
+pub const _BMDStreamingEncodingFrameRate_bmdStreamingEncodedFrameRate5994p:
+    _BMDStreamingEncodingFrameRate = 1697986928;
+pub const _BMDStreamingEncodingFrameRate_bmdStreamingEncodedFrameRate60p:
+    _BMDStreamingEncodingFrameRate = 1698050160;
+pub type _BMDStreamingEncodingFrameRate = ::std::os::raw::c_uint;
+pub const _BMDStreamingEncodingSupport_bmdStreamingEncodingModeNotSupported:
+    _BMDStreamingEncodingSupport = 0;
+pub const _BMDStreamingEncodingSupport_bmdStreamingEncodingModeSupported:
+    _BMDStreamingEncodingSupport = 1;
+pub const _BMDStreamingEncodingSupport_bmdStreamingEncodingModeSupportedWithChanges:
+    _BMDStreamingEncodingSupport = 2;
+pub type _BMDStreamingEncodingSupport = ::std::os::raw::c_uint;
+pub const _BMDStreamingVideoCodec_bmdStreamingVideoCodecH264: _BMDStreamingVideoCodec = 1211250228;
+pub type _BMDStreamingVideoCodec = ::std::os::raw::c_uint;
+pub const _BMDStreamingH264Profile_bmdStreamingH264ProfileHigh: _BMDStreamingH264Profile =
+    1751738216;
+pub const _BMDStreamingH264Profile_bmdStreamingH264ProfileMain: _BMDStreamingH264Profile =
+    1835100526;
+pub const _BMDStreamingH264Profile_bmdStreamingH264ProfileBaseline: _BMDStreamingH264Profile =
+    1650553701;
+pub type _BMDStreamingH264Profile = ::std::os::raw::c_uint;
+pub const _BMDStreamingH264Level_bmdStreamingH264Level12: _BMDStreamingH264Level = 1819685170;
+pub const _BMDStreamingH264Level_bmdStreamingH264Level13: _BMDStreamingH264Level = 1819685171;
+pub const _BMDStreamingH264Level_bmdStreamingH264Level2: _BMDStreamingH264Level = 1819685408;
+pub const _BMDStreamingH264Level_bmdStreamingH264Level21: _BMDStreamingH264Level = 1819685425;
+pub const _BMDStreamingH264Level_bmdStreamingH264Level22: _BMDStreamingH264Level = 1819685426;
+pub const _BMDStreamingH264Level_bmdStreamingH264Level3: _BMDStreamingH264Level = 1819685664;
+pub const _BMDStreamingH264Level_bmdStreamingH264Level31: _BMDStreamingH264Level = 1819685681;
+pub const _BMDStreamingH264Level_bmdStreamingH264Level32: _BMDStreamingH264Level = 1819685682;
+pub const _BMDStreamingH264Level_bmdStreamingH264Level4: _BMDStreamingH264Level = 1819685920;
+pub const _BMDStreamingH264Level_bmdStreamingH264Level41: _BMDStreamingH264Level = 1819685937;
+pub const _BMDStreamingH264Level_bmdStreamingH264Level42: _BMDStreamingH264Level = 1819685938;
+pub type _BMDStreamingH264Level = ::std::os::raw::c_uint;
+pub const _BMDStreamingH264EntropyCoding_bmdStreamingH264EntropyCodingCAVLC:
+    _BMDStreamingH264EntropyCoding = 1163283523;
+pub const _BMDStreamingH264EntropyCoding_bmdStreamingH264EntropyCodingCABAC:
+    _BMDStreamingH264EntropyCoding = 1161969987;
+pub type _BMDStreamingH264EntropyCoding = ::std::os::raw::c_uint;
+pub const _BMDStreamingAudioCodec_bmdStreamingAudioCodecAAC: _BMDStreamingAudioCodec = 1094796064;
+pub type _BMDStreamingAudioCodec = ::std::os::raw::c_uint;
+pub const _BMDStreamingEncodingModePropertyID_bmdStreamingEncodingPropertyVideoFrameRate:
+    _BMDStreamingEncodingModePropertyID = 1986425460;
+pub const _BMDStreamingEncodingModePropertyID_bmdStreamingEncodingPropertyVideoBitRateKbps:
+    _BMDStreamingEncodingModePropertyID = 1986163316;
+pub const _BMDStreamingEncodingModePropertyID_bmdStreamingEncodingPropertyH264Profile:
+    _BMDStreamingEncodingModePropertyID = 1752199782;
+pub const _BMDStreamingEncodingModePropertyID_bmdStreamingEncodingPropertyH264Level:
+    _BMDStreamingEncodingModePropertyID = 1751938668;
+pub const _BMDStreamingEncodingModePropertyID_bmdStreamingEncodingPropertyH264EntropyCoding:
+    _BMDStreamingEncodingModePropertyID = 1751477876;
+pub const _BMDStreamingEncodingModePropertyID_bmdStreamingEncodingPropertyH264HasBFrames:
+    _BMDStreamingEncodingModePropertyID = 1749182066;
+pub const _BMDStreamingEncodingModePropertyID_bmdStreamingEncodingPropertyAudioCodec:
+    _BMDStreamingEncodingModePropertyID = 1633903715;
+pub const _BMDStreamingEncodingModePropertyID_bmdStreamingEncodingPropertyAudioSampleRate:
+    _BMDStreamingEncodingModePropertyID = 1634955892;
+pub const _BMDStreamingEncodingModePropertyID_bmdStreamingEncodingPropertyAudioChannelCount:
+    _BMDStreamingEncodingModePropertyID = 1633904739;
+pub const _BMDStreamingEncodingModePropertyID_bmdStreamingEncodingPropertyAudioBitRateKbps:
+    _BMDStreamingEncodingModePropertyID = 1633841780;
+pub type _BMDStreamingEncodingModePropertyID = ::std::os::raw::c_uint;
+pub type BMDTimeValue = i64;
+pub type BMDTimeScale = i64;
+pub type BMDTimecodeBCD = u32;
+pub type BMDTimecodeUserBits = u32;
+pub type BMDTimecodeFlags = u32;
+pub type BMDVideoConnection = u32;
+pub type BMDAudioConnection = u32;
+pub type BMDDeckControlConnection = u32;
+pub type BMDDisplayMode = u32;
+pub type BMDFieldDominance = u32;
+pub type BMDPixelFormat = u32;
+pub type BMDDisplayModeFlags = u32;
+pub type BMDDeckLinkConfigurationID = u32;
+pub type BMDDeckLinkEncoderConfigurationID = u32;
+pub type BMDDeckControlMode = u32;
+pub type BMDDeckControlEvent = u32;
+pub type BMDDeckControlVTRControlState = u32;
+pub type BMDDeckControlStatusFlags = u32;
+pub type BMDDeckControlExportModeOpsFlags = u32;
+pub type BMDDeckControlError = u32;
+pub type BMDVideoOutputFlags = u32;
+pub type BMDPacketType = u32;
+pub type BMDFrameFlags = u32;
+pub type BMDVideoInputFlags = u32;
+pub type BMDVideoInputFormatChangedEvents = u32;
+pub type BMDDetectedVideoInputFormatFlags = u32;
+pub type BMDDeckLinkCapturePassthroughMode = u32;
+pub type BMDOutputFrameCompletionResult = u32;
+pub type BMDReferenceStatus = u32;
+pub type BMDAudioFormat = u32;
+pub type BMDAudioSampleRate = u32;
+pub type BMDAudioSampleType = u32;
+pub type BMDAudioOutputStreamType = u32;
+pub type BMDDisplayModeSupport = u32;
+pub type BMDAncillaryPacketFormat = u32;
+pub type BMDTimecodeFormat = u32;
+pub type BMDAnalogVideoFlags = u32;
+pub type BMDAudioOutputAnalogAESSwitch = u32;
+pub type BMDVideoOutputConversionMode = u32;
+pub type BMDVideoInputConversionMode = u32;
+pub type BMDVideo3DPackingFormat = u32;
+pub type BMDIdleVideoOutputOperation = u32;
+pub type BMDVideoEncoderFrameCodingMode = u32;
+pub type BMDDNxHRLevel = u32;
+pub type BMDLinkConfiguration = u32;
+pub type BMDDeviceInterface = u32;
+pub type BMDColorspace = u32;
+pub type BMDDynamicRange = u32;
+pub type BMDDeckLinkHDMIInputEDIDID = u32;
+pub type BMDDeckLinkFrameMetadataID = u32;
+pub type BMDDuplexMode = u32;
+pub type BMDDeckLinkAttributeID = u32;
+pub type BMDDeckLinkAPIInformationID = u32;
+pub type BMDDeckLinkStatusID = u32;
+pub type BMDDeckLinkVideoStatusFlags = u32;
+pub type BMDDuplexStatus = u32;
+pub type BMDPanelType = u32;
+pub type BMDDeviceBusyState = u32;
+pub type BMDVideoIOSupport = u32;
+pub type BMD3DPreviewFormat = u32;
+pub type BMDNotifications = u32;
+pub type BMDStreamingDeviceMode = u32;
+pub type BMDStreamingEncodingFrameRate = u32;
+pub type BMDStreamingEncodingSupport = u32;
+pub type BMDStreamingVideoCodec = u32;
+pub type BMDStreamingH264Profile = u32;
+pub type BMDStreamingH264Level = u32;
+pub type BMDStreamingH264EntropyCoding = u32;
+pub type BMDStreamingAudioCodec = u32;
+pub type BMDStreamingEncodingModePropertyID = u32;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IDeckLinkTimecode {
+    pub _base: IUnknown,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IDeckLinkDisplayModeIterator {
+    pub _base: IUnknown,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IDeckLinkDisplayMode {
+    pub _base: IUnknown,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IDeckLink {
+    pub _base: IUnknown,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IDeckLinkVideoOutputCallback {
+    pub _base: IUnknown,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IDeckLinkInputCallback {
+    pub _base: IUnknown,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IDeckLinkIterator {
+    pub _base: IUnknown,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IDeckLinkAPIInformation {
+    pub _base: IUnknown,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IDeckLinkOutput {
+    pub _base: IUnknown,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IDeckLinkInput {
+    pub _base: IUnknown,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IDeckLinkVideoFrame {
+    pub _base: IUnknown,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IDeckLinkMutableVideoFrame {
+    pub _base: IDeckLinkVideoFrame,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IDeckLinkVideoInputFrame {
+    pub _base: IDeckLinkVideoFrame,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IDeckLinkAncillaryPacket {
+    pub _base: IUnknown,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IDeckLinkAncillaryPacketIterator {
+    pub _base: IUnknown,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IDeckLinkVideoFrameAncillaryPackets {
+    pub _base: IUnknown,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IDeckLinkAudioInputPacket {
+    pub _base: IUnknown,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IDeckLinkAttributes {
+    pub _base: IUnknown,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IDeckLinkStatus {
+    pub _base: IUnknown,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct IDeckLinkVideoConversion {
+    pub _base: IUnknown,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Buffer {
+    _unused: [u8; 0],
+}
+extern "C" {
+    pub fn decklink_get_e_fail() -> HRESULT;
+}
+extern "C" {
+    pub fn unknown_add_ref(obj: *mut IUnknown) -> ULONG;
+}
+extern "C" {
+    pub fn unknown_release(obj: *mut IUnknown) -> ULONG;
+}
+extern "C" {
+    pub fn unknown_query_interface(obj: *mut IUnknown, iid: REFIID, iface: *mut LPVOID) -> HRESULT;
+}
+extern "C" {
+    pub fn create_decklink_iterator_instance() -> *mut IDeckLinkIterator;
+}
+extern "C" {
+    pub fn decklink_iterator_next(
+        iterator: *mut IDeckLinkIterator,
+        deckLinkInstance: *mut *mut IDeckLink,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_get_model_name(decklink: *mut IDeckLink, str: *mut *mut Buffer) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_query_input(
+        decklink: *mut IDeckLink,
+        input: *mut *mut IDeckLinkInput,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_query_output(
+        decklink: *mut IDeckLink,
+        output: *mut *mut IDeckLinkOutput,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_attributes_get_flag(
+        attr: *mut IDeckLinkAttributes,
+        cfgID: BMDDeckLinkAttributeID,
+        value: *mut bool,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_attributes_get_int(
+        attr: *mut IDeckLinkAttributes,
+        cfgID: BMDDeckLinkAttributeID,
+        value: *mut i64,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_attributes_get_float(
+        attr: *mut IDeckLinkAttributes,
+        cfgID: BMDDeckLinkAttributeID,
+        value: *mut f64,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_attributes_get_string(
+        attr: *mut IDeckLinkAttributes,
+        cfgID: BMDDeckLinkAttributeID,
+        value: *mut *mut Buffer,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_status_get_flag(
+        status: *mut IDeckLinkStatus,
+        statusID: BMDDeckLinkStatusID,
+        value: *mut bool,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_status_get_int(
+        status: *mut IDeckLinkStatus,
+        statusID: BMDDeckLinkStatusID,
+        value: *mut i64,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_input_get_display_mode_iterator(
+        input: *mut IDeckLinkInput,
+        iterator: *mut *mut IDeckLinkDisplayModeIterator,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_input_start_streams(input: *mut IDeckLinkInput) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_input_stop_streams(input: *mut IDeckLinkInput) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_input_pause_streams(input: *mut IDeckLinkInput) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_input_flush_streams(input: *mut IDeckLinkInput) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_input_enable_audio_input(
+        input: *mut IDeckLinkInput,
+        sampleRate: BMDAudioSampleRate,
+        sampleType: BMDAudioSampleType,
+        channelCount: u32,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_input_enable_video_input(
+        input: *mut IDeckLinkInput,
+        displayMode: BMDDisplayMode,
+        pixelFormat: BMDPixelFormat,
+        flags: BMDVideoInputFlags,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_input_disable_video_input(input: *mut IDeckLinkInput) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_input_disable_audio_input(input: *mut IDeckLinkInput) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_input_set_callback(
+        input: *mut IDeckLinkInput,
+        callback: *mut IDeckLinkInputCallback,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_input_get_hardware_reference_clock(
+        input: *mut IDeckLinkInput,
+        timeScale: BMDTimeScale,
+        hardwareTime: *mut BMDTimeValue,
+        timeInFrame: *mut BMDTimeValue,
+        ticksPerFrame: *mut BMDTimeValue,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_output_get_display_mode_iterator(
+        output: *mut IDeckLinkOutput,
+        iterator: *mut *mut IDeckLinkDisplayModeIterator,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_output_create_video_frame(
+        output: *mut IDeckLinkOutput,
+        width: i32,
+        height: i32,
+        rowBytes: i32,
+        pixelFormat: BMDPixelFormat,
+        flags: BMDFrameFlags,
+        outFrame: *mut *mut IDeckLinkMutableVideoFrame,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_output_disable_video_output(output: *mut IDeckLinkOutput) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_output_enable_video_output(
+        output: *mut IDeckLinkOutput,
+        displayMode: BMDDisplayMode,
+        flags: BMDVideoOutputFlags,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_output_set_scheduled_frame_completion_callback(
+        output: *mut IDeckLinkOutput,
+        callback: *mut IDeckLinkVideoOutputCallback,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_output_start_scheduled_playback(
+        output: *mut IDeckLinkOutput,
+        playbackStartTime: BMDTimeValue,
+        timeScale: BMDTimeScale,
+        playbackSpeed: f64,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_output_get_scheduled_stream_time(
+        output: *mut IDeckLinkOutput,
+        desiredTimeScale: BMDTimeScale,
+        streamTime: *mut BMDTimeValue,
+        playbackSpeed: *mut f64,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_output_schedule_video_frame(
+        output: *mut IDeckLinkOutput,
+        theFrame: *mut IDeckLinkVideoFrame,
+        displayTime: BMDTimeValue,
+        displayDuration: BMDTimeValue,
+        timeScale: BMDTimeScale,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_output_enable_audio_output(
+        output: *mut IDeckLinkOutput,
+        sampleRate: BMDAudioSampleRate,
+        sampleType: BMDAudioSampleType,
+        channelCount: u32,
+        streamType: BMDAudioOutputStreamType,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_output_disable_audio_output(output: *mut IDeckLinkOutput) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_output_write_audio_samples_sync(
+        output: *mut IDeckLinkOutput,
+        buffer: *mut ::std::os::raw::c_void,
+        sampleFrameCount: u32,
+        sampleFramesWritten: *mut u32,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_output_begin_audio_preroll(output: *mut IDeckLinkOutput) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_output_end_audio_preroll(output: *mut IDeckLinkOutput) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_output_schedule_audio_samples(
+        output: *mut IDeckLinkOutput,
+        buffer: *mut ::std::os::raw::c_void,
+        sampleFrameCount: u32,
+        streamTime: BMDTimeValue,
+        timeScale: BMDTimeScale,
+        sampleFramesWritten: *mut u32,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_output_get_buffered_audio_sample_frame_count(
+        output: *mut IDeckLinkOutput,
+        bufferedSampleFrameCount: *mut u32,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_output_flush_buffered_audio_samples(output: *mut IDeckLinkOutput) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_display_mode_iterator_next(
+        iterator: *mut IDeckLinkDisplayModeIterator,
+        deckLinkDisplayMode: *mut *mut IDeckLinkDisplayMode,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_display_mode_get_display_mode(
+        mode: *mut IDeckLinkDisplayMode,
+    ) -> BMDDisplayMode;
+}
+extern "C" {
+    pub fn decklink_display_mode_get_name(
+        mode: *mut IDeckLinkDisplayMode,
+        value: *mut *mut Buffer,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_display_mode_get_frame_rate(
+        mode: *mut IDeckLinkDisplayMode,
+        frameDuration: *mut BMDTimeValue,
+        timeScale: *mut BMDTimeScale,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_display_mode_get_width(
+        mode: *mut IDeckLinkDisplayMode,
+    ) -> ::std::os::raw::c_long;
+}
+extern "C" {
+    pub fn decklink_display_mode_get_height(
+        mode: *mut IDeckLinkDisplayMode,
+    ) -> ::std::os::raw::c_long;
+}
+extern "C" {
+    pub fn decklink_display_mode_get_field_dominance(
+        mode: *mut IDeckLinkDisplayMode,
+    ) -> BMDFieldDominance;
+}
+extern "C" {
+    pub fn create_decklink_input_callback(
+        implementation: *mut ::std::os::raw::c_void,
+    ) -> *mut IDeckLinkInputCallback;
+}
+extern "C" {
+    pub fn create_decklink_video_output_callback(
+        implementation: *mut ::std::os::raw::c_void,
+    ) -> *mut IDeckLinkVideoOutputCallback;
+}
+extern "C" {
+    pub fn decklink_audio_input_packet_get_sample_frame_count(
+        packet: *mut IDeckLinkAudioInputPacket,
+    ) -> ::std::os::raw::c_long;
+}
+extern "C" {
+    pub fn decklink_audio_input_packet_get_bytes(
+        packet: *mut IDeckLinkAudioInputPacket,
+        bytes: *mut *mut ::std::os::raw::c_void,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_audio_input_packet_get_packet_time(
+        packet: *mut IDeckLinkAudioInputPacket,
+        packetTime: *mut BMDTimeValue,
+        timeScale: BMDTimeScale,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_video_frame_get_width(
+        frame: *mut IDeckLinkVideoFrame,
+    ) -> ::std::os::raw::c_long;
+}
+extern "C" {
+    pub fn decklink_video_frame_get_height(
+        frame: *mut IDeckLinkVideoFrame,
+    ) -> ::std::os::raw::c_long;
+}
+extern "C" {
+    pub fn decklink_video_frame_get_row_bytes(
+        frame: *mut IDeckLinkVideoFrame,
+    ) -> ::std::os::raw::c_long;
+}
+extern "C" {
+    pub fn decklink_video_frame_get_pixel_format(frame: *mut IDeckLinkVideoFrame)
+        -> BMDPixelFormat;
+}
+extern "C" {
+    pub fn decklink_video_frame_get_flags(frame: *mut IDeckLinkVideoFrame) -> BMDFrameFlags;
+}
+extern "C" {
+    pub fn decklink_video_frame_get_bytes(
+        frame: *mut IDeckLinkVideoFrame,
+        bytes: *mut *mut ::std::os::raw::c_void,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_video_frame_get_timecode(
+        frame: *mut IDeckLinkVideoFrame,
+        format: BMDTimecodeFormat,
+        timecode: *mut *mut IDeckLinkTimecode,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_mutable_video_frame_set_timecode_from_components(
+        frame: *mut IDeckLinkMutableVideoFrame,
+        format: BMDTimecodeFormat,
+        hours: u8,
+        minutes: u8,
+        seconds: u8,
+        frames: u8,
+        flags: BMDTimecodeFlags,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_mutable_video_frame_set_timecode_user_bits(
+        frame: *mut IDeckLinkMutableVideoFrame,
+        format: BMDTimecodeFormat,
+        userBits: BMDTimecodeUserBits,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_video_input_frame_get_stream_time(
+        frame: *mut IDeckLinkVideoInputFrame,
+        frameTime: *mut BMDTimeValue,
+        frameDuration: *mut BMDTimeValue,
+        timeScale: BMDTimeScale,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_video_input_frame_get_hardware_reference_timestamp(
+        frame: *mut IDeckLinkVideoInputFrame,
+        timeScale: BMDTimeScale,
+        frameTime: *mut BMDTimeValue,
+        frameDuration: *mut BMDTimeValue,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn create_decklink_video_conversion_instance() -> *mut IDeckLinkVideoConversion;
+}
+extern "C" {
+    pub fn decklink_video_conversion_convert_frame(
+        conversion: *mut IDeckLinkVideoConversion,
+        srcFrame: *mut IDeckLinkVideoFrame,
+        dstFrame: *mut IDeckLinkVideoFrame,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_timecode_get_components(
+        timecode: *mut IDeckLinkTimecode,
+        hours: *mut u8,
+        minutes: *mut u8,
+        seconds: *mut u8,
+        frames: *mut u8,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_timecode_get_string(
+        timecode: *mut IDeckLinkTimecode,
+        value: *mut *mut Buffer,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_timecode_get_flags(timecode: *mut IDeckLinkTimecode) -> BMDTimecodeFlags;
+}
+extern "C" {
+    pub fn decklink_timecode_get_user_bits(
+        timecode: *mut IDeckLinkTimecode,
+        userBits: *mut BMDTimecodeUserBits,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn create_decklink_api_information_instance() -> *mut IDeckLinkAPIInformation;
+}
+extern "C" {
+    pub fn decklink_api_information_get_version_int(
+        apiInfo: *mut IDeckLinkAPIInformation,
+        cfgID: BMDDeckLinkAPIInformationID,
+        value: *mut i64,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_api_information_get_version_string(
+        apiInfo: *mut IDeckLinkAPIInformation,
+        cfgID: BMDDeckLinkAPIInformationID,
+        value: *mut *mut Buffer,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_runtime_is_available() -> bool;
+}
+extern "C" {
+    pub fn decklink_runtime_load_from(
+        path: *const ::std::os::raw::c_char,
+        error: *mut *mut Buffer,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_video_frame_ancillary_packets_get_packet_iterator(
+        packets: *mut IDeckLinkVideoFrameAncillaryPackets,
+        iterator: *mut *mut IDeckLinkAncillaryPacketIterator,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_video_frame_ancillary_packets_get_first_packet_by_id(
+        packets: *mut IDeckLinkVideoFrameAncillaryPackets,
+        DID: u8,
+        SDID: u8,
+        packet: *mut *mut IDeckLinkAncillaryPacket,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_ancillary_packet_iterator_next(
+        iterator: *mut IDeckLinkAncillaryPacketIterator,
+        packet: *mut *mut IDeckLinkAncillaryPacket,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_ancillary_packet_get_bytes(
+        packet: *mut IDeckLinkAncillaryPacket,
+        format: BMDAncillaryPacketFormat,
+        data: *mut *const ::std::os::raw::c_void,
+        size: *mut u32,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn decklink_ancillary_packet_get_did(packet: *mut IDeckLinkAncillaryPacket) -> u8;
+}
+extern "C" {
+    pub fn decklink_ancillary_packet_get_sdid(packet: *mut IDeckLinkAncillaryPacket) -> u8;
+}
+extern "C" {
+    pub fn decklink_ancillary_packet_get_line_number(packet: *mut IDeckLinkAncillaryPacket) -> u32;
+}
+extern "C" {
+    pub fn decklink_ancillary_packet_get_data_stream_index(
+        packet: *mut IDeckLinkAncillaryPacket,
+    ) -> u8;
+}
+extern "C" {
+    pub fn create_decklink_timecode(
+        hours: u8,
+        minutes: u8,
+        seconds: u8,
+        frames: u8,
+        flags: BMDTimecodeFlags,
+        userBits: BMDTimecodeUserBits,
+    ) -> *mut IDeckLinkTimecode;
+}
+extern "C" {
+    pub fn create_decklink_ancillary_packet(
+        did: u8,
+        sdid: u8,
+        lineNumber: u32,
+        dataStreamIndex: u8,
+        data: *const u8,
+        size: u32,
+    ) -> *mut IDeckLinkAncillaryPacket;
+}
+extern "C" {
+    pub fn create_decklink_video_frame_ancillary_packets(
+    ) -> *mut IDeckLinkVideoFrameAncillaryPackets;
+}
+extern "C" {
+    pub fn decklink_video_frame_ancillary_packets_attach_packet(
+        packets: *mut IDeckLinkVideoFrameAncillaryPackets,
+        packet: *mut IDeckLinkAncillaryPacket,
+    ) -> HRESULT;
+}
+extern "C" {
+    pub fn create_decklink_video_frame(
+        width: ::std::os::raw::c_long,
+        height: ::std::os::raw::c_long,
+        rowBytes: ::std::os::raw::c_long,
+        pixelFormat: BMDPixelFormat,
+        flags: BMDFrameFlags,
+        bytes: *mut ::std::os::raw::c_void,
+        owner: *mut ::std::os::raw::c_void,
+    ) -> *mut IDeckLinkVideoFrame;
+}
+extern "C" {
+    pub fn decklink_custom_video_frame_set_timecode(
+        frame: *mut IDeckLinkVideoFrame,
+        format: BMDTimecodeFormat,
+        timecode: *mut IDeckLinkTimecode,
+    );
+}
+extern "C" {
+    pub fn decklink_custom_video_frame_attach_ancillary_packet(
+        frame: *mut IDeckLinkVideoFrame,
+        packet: *mut IDeckLinkAncillaryPacket,
+    );
+}
+extern "C" {
+    pub fn decklink_custom_video_frame_get_owner(
+        frame: *mut IDeckLinkVideoFrame,
+    ) -> *mut ::std::os::raw::c_void;
+}
+extern "C" {
+    pub fn buffer_data(str: *mut Buffer) -> *const ::std::os::raw::c_void;
+}
+extern "C" {
+    pub fn buffer_release(str: *mut Buffer);
+}
@@ -0,0 +1,400 @@
+use pixel;
+use {Error, PixelFormat, VideoFrame};
+
+/// The luma coefficients used to convert between Y'CbCr and R'G'B'.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ColorMatrix {
+    Rec601,
+    Rec709,
+    Rec2020,
+}
+
+impl ColorMatrix {
+    /// The matrix conventionally used at a frame height: Rec.601 for standard definition and
+    /// Rec.709 for everything larger.
+    pub fn for_height(height: i32) -> ColorMatrix {
+        if height <= 576 {
+            ColorMatrix::Rec601
+        } else {
+            ColorMatrix::Rec709
+        }
+    }
+
+    // (Kr, Kb)
+    fn coefficients(self) -> (f32, f32) {
+        match self {
+            ColorMatrix::Rec601 => (0.299, 0.114),
+            ColorMatrix::Rec709 => (0.2126, 0.0722),
+            ColorMatrix::Rec2020 => (0.2627, 0.0593),
+        }
+    }
+}
+
+/// Whether samples use the legal (video) range, such as 16-235 at 8 bits, or the full range of
+/// their bit depth.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ColorRange {
+    Legal,
+    Full,
+}
+
+impl ColorRange {
+    /// The range the SDK documents for a format: legal for the YUV and 10-bit RGB formats and full
+    /// for the 8 and 12-bit RGB formats. Returns `None` for compressed and raw formats.
+    pub fn for_format(format: PixelFormat) -> Option<ColorRange> {
+        match format {
+            PixelFormat::FORMAT_8BIT_YUV
+            | PixelFormat::FORMAT_10BIT_YUV
+            | PixelFormat::FORMAT_10BIT_RGB
+            | PixelFormat::FORMAT_10BIT_RGBX
+            | PixelFormat::FORMAT_10BIT_RGBXLE => Some(ColorRange::Legal),
+            PixelFormat::FORMAT_8BIT_ARGB
+            | PixelFormat::FORMAT_8BIT_BGRA
+            | PixelFormat::FORMAT_12BIT_RGB
+            | PixelFormat::FORMAT_12BIT_RGBLE => Some(ColorRange::Full),
+            _ => None,
+        }
+    }
+
+    // Maps a code value to 0..1, or -0.5..0.5 for chroma.
+    fn normalize(self, code: u16, bits: u32, chroma: bool) -> f32 {
+        let code = code as f32;
+        match (self, chroma) {
+            (ColorRange::Legal, false) => {
+                (code - (16 << (bits - 8)) as f32) / (219 << (bits - 8)) as f32
+            }
+            (ColorRange::Legal, true) => {
+                (code - (128 << (bits - 8)) as f32) / (224 << (bits - 8)) as f32
+            }
+            (ColorRange::Full, false) => code / ((1 << bits) - 1) as f32,
+            (ColorRange::Full, true) => {
+                (code - (1 << (bits - 1)) as f32) / ((1 << bits) - 1) as f32
+            }
+        }
+    }
+
+    // The inverse of `normalize`, rounded and clamped. Legal range keeps clear of the codes SDI
+    // reserves for timing references.
+    fn quantize(self, value: f32, bits: u32, chroma: bool) -> u16 {
+        let (code, min, max) = match (self, chroma) {
+            (ColorRange::Legal, false) => (
+                value * (219 << (bits - 8)) as f32 + (16 << (bits - 8)) as f32,
+                1 << (bits - 8),
+                (1 << bits) - (1 << (bits - 8)) - 1,
+            ),
+            (ColorRange::Legal, true) => (
+                value * (224 << (bits - 8)) as f32 + (128 << (bits - 8)) as f32,
+                1 << (bits - 8),
+                (1 << bits) - (1 << (bits - 8)) - 1,
+            ),
+            (ColorRange::Full, false) => (value * ((1 << bits) - 1) as f32, 0, (1 << bits) - 1),
+            (ColorRange::Full, true) => (
+                value * ((1 << bits) - 1) as f32 + (1 << (bits - 1)) as f32,
+                0,
+                (1 << bits) - 1,
+            ),
+        };
+        (code.round() as i32).max(min).min(max) as u16
+    }
+}
+
+// A row of pixels in the source or destination color model: Y' Cb Cr A or R' G' B' A, with 16-bit
+// MSB-aligned code values or normalized floats.
+pub(crate) type Pixel16 = [u16; 4];
+pub(crate) type PixelF = [f32; 4];
+
+struct Layout {
+    format: PixelFormat,
+    yuv: bool,
+    bits: u32,
+    range: ColorRange,
+}
+
+impl Layout {
+    fn new(format: PixelFormat, range: Option<ColorRange>) -> Result<Layout, Error> {
+        let default_range = ColorRange::for_format(format).ok_or_else(Error::new)?;
+        Ok(Layout {
+            format,
+            yuv: format.is_yuv(),
+            bits: format.bits_per_component().ok_or_else(Error::new)?,
+            range: range.unwrap_or(default_range),
+        })
+    }
+
+    fn decode_row(&self, src: &[u8], dst: &mut [Pixel16]) -> Result<(), Error> {
+        let width = dst.len();
+        match self.format {
+            PixelFormat::FORMAT_8BIT_YUV => {
+                for (src, dst) in src.chunks_exact(4).zip(dst.chunks_exact_mut(2)) {
+                    let (cb, cr) = ((src[0] as u16) << 8, (src[2] as u16) << 8);
+                    dst[0] = [(src[1] as u16) << 8, cb, cr, 0xffff];
+                    dst[1] = [(src[3] as u16) << 8, cb, cr, 0xffff];
+                }
+            }
+            PixelFormat::FORMAT_10BIT_YUV => {
+                let (mut y, mut uv) = (vec![0; width], vec![0; width]);
+                pixel::v210_to_p210(src, src.len(), width, 1, &mut y, &mut uv)
+                    .map_err(|_| Error::new())?;
+                for (i, dst) in dst.iter_mut().enumerate() {
+                    let c = i / 2 * 2;
+                    *dst = [y[i], uv[c], uv[c + 1], 0xffff];
+                }
+            }
+            PixelFormat::FORMAT_8BIT_ARGB | PixelFormat::FORMAT_8BIT_BGRA => {
+                let argb = self.format == PixelFormat::FORMAT_8BIT_ARGB;
+                for (src, dst) in src.chunks_exact(4).zip(dst.iter_mut()) {
+                    let [a, r, g, b] = if argb {
+                        [src[0], src[1], src[2], src[3]]
+                    } else {
+                        [src[3], src[2], src[1], src[0]]
+                    };
+                    *dst = [
+                        (r as u16) << 8,
+                        (g as u16) << 8,
+                        (b as u16) << 8,
+                        (a as u16) << 8 | a as u16,
+                    ];
+                }
+            }
+            _ => {
+                let mut rgba = vec![0; width * 4];
+                pixel::rgb_to_rgba16(self.format, src, src.len(), width, 1, &mut rgba)
+                    .map_err(|_| Error::new())?;
+                for (src, dst) in rgba.chunks_exact(4).zip(dst.iter_mut()) {
+                    *dst = [src[0], src[1], src[2], src[3]];
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // YUV destinations take the chroma of the first pixel of each pair, so it must already be
+    // averaged.
+    fn encode_row(&self, src: &[Pixel16], dst: &mut [u8]) -> Result<(), Error> {
+        let width = src.len();
+        match self.format {
+            PixelFormat::FORMAT_8BIT_YUV => {
+                for (src, dst) in src.chunks_exact(2).zip(dst.chunks_exact_mut(4)) {
+                    dst[0] = (src[0][1] >> 8) as u8;
+                    dst[1] = (src[0][0] >> 8) as u8;
+                    dst[2] = (src[0][2] >> 8) as u8;
+                    dst[3] = (src[1][0] >> 8) as u8;
+                }
+                for b in &mut dst[width * 2..] {
+                    *b = 0;
+                }
+            }
+            PixelFormat::FORMAT_10BIT_YUV => {
+                let y: Vec<u16> = src.iter().map(|px| px[0]).collect();
+                let uv: Vec<u16> = src
+                    .chunks_exact(2)
+                    .flat_map(|pair| vec![pair[0][1], pair[0][2]])
+                    .collect();
+                let row_bytes = dst.len();
+                pixel::p210_to_v210(&y, &uv, width, 1, dst, row_bytes).map_err(|_| Error::new())?;
+            }
+            PixelFormat::FORMAT_8BIT_ARGB | PixelFormat::FORMAT_8BIT_BGRA => {
+                let argb = self.format == PixelFormat::FORMAT_8BIT_ARGB;
+                for (src, dst) in src.iter().zip(dst.chunks_exact_mut(4)) {
+                    let [r, g, b, a] = [src[0] >> 8, src[1] >> 8, src[2] >> 8, src[3] >> 8];
+                    let bytes = if argb { [a, r, g, b] } else { [b, g, r, a] };
+                    for (dst, c) in dst.iter_mut().zip(bytes.iter()) {
+                        *dst = *c as u8;
+                    }
+                }
+                for b in &mut dst[width * 4..] {
+                    *b = 0;
+                }
+            }
+            _ => {
+                let rgba: Vec<u16> = src.iter().flat_map(|px| px.to_vec()).collect();
+                let row_bytes = dst.len();
+                pixel::rgba16_to_rgb(&rgba, width, 1, self.format, dst, row_bytes)
+                    .map_err(|_| Error::new())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn normalize(&self, px: &Pixel16) -> PixelF {
+        let shift = 16 - self.bits;
+        let mut out = [0.0; 4];
+        for c in 0..3 {
+            out[c] = self
+                .range
+                .normalize(px[c] >> shift, self.bits, self.yuv && c > 0);
+        }
+        out[3] = px[3] as f32 / 65535.0;
+        out
+    }
+
+    fn quantize(&self, px: &PixelF) -> Pixel16 {
+        let shift = 16 - self.bits;
+        let mut out = [0; 4];
+        for c in 0..3 {
+            out[c] = self.range.quantize(px[c], self.bits, self.yuv && c > 0) << shift;
+        }
+        out[3] = (px[3].clamp(0.0, 1.0) * 65535.0).round() as u16;
+        out
+    }
+}
+
+/// Decodes and encodes whole rows of a pixel format in its documented range, for pictures
+/// generated or modified on the CPU. Normalized values outside 0..1 are clipped to what the range
+/// can carry.
+pub(crate) struct RowCodec {
+    layout: Layout,
+    matrix: (f32, f32),
+    row16: Vec<Pixel16>,
+}
+
+impl RowCodec {
+    pub(crate) fn new(format: PixelFormat, width: i32, height: i32) -> Result<RowCodec, Error> {
+        Ok(RowCodec {
+            layout: Layout::new(format, None)?,
+            matrix: ColorMatrix::for_height(height).coefficients(),
+            row16: vec![[0; 4]; width.max(0) as usize],
+        })
+    }
+
+    /// The code values of a normalized R'G'B'A pixel, in the format's color model.
+    pub(crate) fn quantize(&self, mut px: PixelF) -> Pixel16 {
+        if self.layout.yuv {
+            rgb_to_yuv(self.matrix, &mut px);
+        }
+        self.layout.quantize(&px)
+    }
+
+    /// Decodes a row, repeating 4:2:2 chroma over each pixel pair.
+    pub(crate) fn decode(&self, src: &[u8], dst: &mut [Pixel16]) -> Result<(), Error> {
+        self.layout.decode_row(src, dst)
+    }
+
+    /// Encodes a row of code values. 4:2:2 chroma is averaged over each pixel pair.
+    pub(crate) fn encode(&self, src: &mut [Pixel16], dst: &mut [u8]) -> Result<(), Error> {
+        if self.layout.yuv {
+            let average = |a: u16, b: u16| (a as u32 + b as u32).div_ceil(2) as u16;
+            for pair in src.chunks_exact_mut(2) {
+                pair[0][1] = average(pair[0][1], pair[1][1]);
+                pair[0][2] = average(pair[0][2], pair[1][2]);
+            }
+        }
+        self.layout.encode_row(src, dst)
+    }
+
+    /// Encodes a row of normalized R'G'B'A pixels.
+    pub(crate) fn encode_rgb(&mut self, row: &mut [PixelF], dst: &mut [u8]) -> Result<(), Error> {
+        if self.layout.yuv {
+            for px in row.iter_mut() {
+                rgb_to_yuv(self.matrix, px);
+            }
+            for pair in row.chunks_exact_mut(2) {
+                pair[0][1] = (pair[0][1] + pair[1][1]) / 2.0;
+                pair[0][2] = (pair[0][2] + pair[1][2]) / 2.0;
+            }
+        }
+        for (px16, px) in self.row16.iter_mut().zip(row.iter()) {
+            *px16 = self.layout.quantize(px);
+        }
+        self.layout.encode_row(&self.row16, dst)
+    }
+}
+
+fn rgb_to_yuv((kr, kb): (f32, f32), px: &mut PixelF) {
+    let [r, g, b, _] = *px;
+    let y = kr * r + (1.0 - kr - kb) * g + kb * b;
+    px[0] = y;
+    px[1] = (b - y) / (2.0 * (1.0 - kb));
+    px[2] = (r - y) / (2.0 * (1.0 - kr));
+}
+
+fn yuv_to_rgb((kr, kb): (f32, f32), px: &mut PixelF) {
+    let [y, cb, cr, _] = *px;
+    let r = y + 2.0 * (1.0 - kr) * cr;
+    let b = y + 2.0 * (1.0 - kb) * cb;
+    px[0] = r;
+    px[1] = (y - kr * r - kb * b) / (1.0 - kr - kb);
+    px[2] = b;
+}
+
+/// Converts between uncompressed pixel formats on the CPU.
+///
+/// It takes any `VideoFrame`, including `VideoBuffer`, and covers every pair of the 8-bit YUV and
+/// RGB, v210, r210, R10b, R10l, R12B and R12L formats. 4:2:2 chroma is repeated when unpacking and
+/// averaged over each pixel pair when packing. `VideoConversion` falls back to it when the driver's
+/// converter is unavailable.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SoftwareConversion {
+    /// The matrix used between YUV and RGB. `None` picks one from the frame height.
+    pub matrix: Option<ColorMatrix>,
+    /// The range of the source samples. `None` uses the range documented for the source format.
+    pub src_range: Option<ColorRange>,
+    /// The range of the destination samples. `None` uses the range documented for the destination
+    /// format.
+    pub dst_range: Option<ColorRange>,
+}
+
+impl SoftwareConversion {
+    pub fn new() -> SoftwareConversion {
+        Default::default()
+    }
+
+    /// Converts `src_frame` into `dst_frame`, which must be the same size. Fails with `E_FAIL` if
+    /// either format isn't an uncompressed format listed above.
+    pub fn convert_frame<S: VideoFrame, D: VideoFrame>(
+        &mut self,
+        mut src_frame: S,
+        dst_frame: &mut D,
+    ) -> Result<(), Error> {
+        let (width, height) = (src_frame.get_width(), src_frame.get_height());
+        if dst_frame.get_width() != width || dst_frame.get_height() != height {
+            return Err(Error::new());
+        }
+        let src = Layout::new(src_frame.get_pixel_format(), self.src_range)?;
+        let dst = Layout::new(dst_frame.get_pixel_format(), self.dst_range)?;
+        let matrix = self
+            .matrix
+            .unwrap_or_else(|| ColorMatrix::for_height(height))
+            .coefficients();
+        let (src_row_bytes, dst_row_bytes) = (src_frame.get_row_bytes(), dst_frame.get_row_bytes());
+        for (layout, row_bytes) in [(&src, src_row_bytes), (&dst, dst_row_bytes)].iter() {
+            layout
+                .format
+                .validate(width, height, *row_bytes)
+                .map_err(|_| Error::new())?;
+        }
+        let (src_row_bytes, dst_row_bytes) = (src_row_bytes as usize, dst_row_bytes as usize);
+        let src_bytes = src_frame.get_bytes()?;
+        let dst_bytes = dst_frame.get_bytes_mut()?;
+
+        let width = width as usize;
+        let mut row16 = vec![[0u16; 4]; width];
+        let mut row = vec![[0f32; 4]; width];
+        for (src_row, dst_row) in src_bytes
+            .chunks(src_row_bytes)
+            .zip(dst_bytes.chunks_mut(dst_row_bytes))
+            .take(height as usize)
+        {
+            src.decode_row(src_row, &mut row16)?;
+            for (px, px16) in row.iter_mut().zip(row16.iter()) {
+                *px = src.normalize(px16);
+                match (src.yuv, dst.yuv) {
+                    (false, true) => rgb_to_yuv(matrix, px),
+                    (true, false) => yuv_to_rgb(matrix, px),
+                    _ => {}
+                }
+            }
+            if dst.yuv {
+                for pair in row.chunks_exact_mut(2) {
+                    pair[0][1] = (pair[0][1] + pair[1][1]) / 2.0;
+                    pair[0][2] = (pair[0][2] + pair[1][2]) / 2.0;
+                }
+            }
+            for (px16, px) in row16.iter_mut().zip(row.iter()) {
+                *px16 = dst.quantize(px);
+            }
+            dst.encode_row(&row16, dst_row)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,255 @@
+use super::{DisplayMode, FieldDominance};
+
+use simple_error::SimpleError;
+use std::{fmt, str::FromStr};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ScanType {
+    Interlaced,
+    Progressive,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DisplayModeFamily {
+    /// NTSC and PAL.
+    Sd,
+    /// 720 and 1080 line modes.
+    Hd,
+    /// 2048x1556 full aperture.
+    TwoK,
+    /// 3840x2160.
+    FourK,
+    /// 7680x4320.
+    EightK,
+    /// The 2K, 4K and 8K DCI modes.
+    Dci,
+}
+
+/// Static properties of a display mode, available without a device.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DisplayModeSpec {
+    pub mode: DisplayMode,
+    pub family: DisplayModeFamily,
+    pub width: i32,
+    pub height: i32,
+    /// Together with `time_scale`, the frame duration as reported by `DisplayModeInfo::get_frame_rate`.
+    pub frame_duration: i64,
+    pub time_scale: i64,
+    pub field_dominance: FieldDominance,
+}
+
+impl DisplayModeSpec {
+    pub fn scan_type(&self) -> ScanType {
+        match self.field_dominance {
+            FieldDominance::LowerFieldFirst | FieldDominance::UpperFieldFirst => {
+                ScanType::Interlaced
+            }
+            _ => ScanType::Progressive,
+        }
+    }
+
+    /// The frame rate as a (numerator, denominator) pair, e.g. (30000, 1001).
+    pub fn frame_rate(&self) -> (i64, i64) {
+        (self.time_scale, self.frame_duration)
+    }
+
+    fn has_frame_rate(&self, frame_duration: i64, time_scale: i64) -> bool {
+        self.time_scale * frame_duration == time_scale * self.frame_duration
+    }
+}
+
+const fn spec(
+    mode: DisplayMode,
+    family: DisplayModeFamily,
+    width: i32,
+    height: i32,
+    frame_duration: i64,
+    time_scale: i64,
+    field_dominance: FieldDominance,
+) -> DisplayModeSpec {
+    DisplayModeSpec {
+        mode,
+        family,
+        width,
+        height,
+        frame_duration,
+        time_scale,
+        field_dominance,
+    }
+}
+
+use self::DisplayModeFamily::*;
+use FieldDominance::{LowerFieldFirst, ProgressiveFrame, UpperFieldFirst};
+
+#[rustfmt::skip]
+static CATALOG: &[DisplayModeSpec] = &[
+    spec(DisplayMode::MODE_NTSC, Sd, 720, 486, 1001, 30000, LowerFieldFirst),
+    spec(DisplayMode::MODE_NTSC2398, Sd, 720, 486, 1001, 24000, LowerFieldFirst),
+    spec(DisplayMode::MODE_PAL, Sd, 720, 576, 1000, 25000, UpperFieldFirst),
+    spec(DisplayMode::MODE_NTSCP, Sd, 720, 486, 1001, 60000, ProgressiveFrame),
+    spec(DisplayMode::MODE_PALP, Sd, 720, 576, 1000, 50000, ProgressiveFrame),
+
+    spec(DisplayMode::MODE_HD1080P2398, Hd, 1920, 1080, 1001, 24000, ProgressiveFrame),
+    spec(DisplayMode::MODE_HD1080P24, Hd, 1920, 1080, 1000, 24000, ProgressiveFrame),
+    spec(DisplayMode::MODE_HD1080P25, Hd, 1920, 1080, 1000, 25000, ProgressiveFrame),
+    spec(DisplayMode::MODE_HD1080P2997, Hd, 1920, 1080, 1001, 30000, ProgressiveFrame),
+    spec(DisplayMode::MODE_HD1080P30, Hd, 1920, 1080, 1000, 30000, ProgressiveFrame),
+    spec(DisplayMode::MODE_HD1080P50, Hd, 1920, 1080, 1000, 50000, ProgressiveFrame),
+    spec(DisplayMode::MODE_HD1080P5994, Hd, 1920, 1080, 1001, 60000, ProgressiveFrame),
+    spec(DisplayMode::MODE_HD1080P6000, Hd, 1920, 1080, 1000, 60000, ProgressiveFrame),
+    spec(DisplayMode::MODE_HD1080I50, Hd, 1920, 1080, 1000, 25000, UpperFieldFirst),
+    spec(DisplayMode::MODE_HD1080I5994, Hd, 1920, 1080, 1001, 30000, UpperFieldFirst),
+    spec(DisplayMode::MODE_HD1080I6000, Hd, 1920, 1080, 1000, 30000, UpperFieldFirst),
+    spec(DisplayMode::MODE_HD720P50, Hd, 1280, 720, 1000, 50000, ProgressiveFrame),
+    spec(DisplayMode::MODE_HD720P5994, Hd, 1280, 720, 1001, 60000, ProgressiveFrame),
+    spec(DisplayMode::MODE_HD720P60, Hd, 1280, 720, 1000, 60000, ProgressiveFrame),
+
+    spec(DisplayMode::MODE_2K2398, TwoK, 2048, 1556, 1001, 24000, ProgressiveFrame),
+    spec(DisplayMode::MODE_2K24, TwoK, 2048, 1556, 1000, 24000, ProgressiveFrame),
+    spec(DisplayMode::MODE_2K25, TwoK, 2048, 1556, 1000, 25000, ProgressiveFrame),
+
+    spec(DisplayMode::MODE_2KDCI2398, Dci, 2048, 1080, 1001, 24000, ProgressiveFrame),
+    spec(DisplayMode::MODE_2KDCI24, Dci, 2048, 1080, 1000, 24000, ProgressiveFrame),
+    spec(DisplayMode::MODE_2KDCI25, Dci, 2048, 1080, 1000, 25000, ProgressiveFrame),
+    spec(DisplayMode::MODE_2KDCI2997, Dci, 2048, 1080, 1001, 30000, ProgressiveFrame),
+    spec(DisplayMode::MODE_2KDCI30, Dci, 2048, 1080, 1000, 30000, ProgressiveFrame),
+    spec(DisplayMode::MODE_2KDCI50, Dci, 2048, 1080, 1000, 50000, ProgressiveFrame),
+    spec(DisplayMode::MODE_2KDCI5994, Dci, 2048, 1080, 1001, 60000, ProgressiveFrame),
+    spec(DisplayMode::MODE_2KDCI60, Dci, 2048, 1080, 1000, 60000, ProgressiveFrame),
+
+    spec(DisplayMode::MODE_4K2160P2398, FourK, 3840, 2160, 1001, 24000, ProgressiveFrame),
+    spec(DisplayMode::MODE_4K2160P24, FourK, 3840, 2160, 1000, 24000, ProgressiveFrame),
+    spec(DisplayMode::MODE_4K2160P25, FourK, 3840, 2160, 1000, 25000, ProgressiveFrame),
+    spec(DisplayMode::MODE_4K2160P2997, FourK, 3840, 2160, 1001, 30000, ProgressiveFrame),
+    spec(DisplayMode::MODE_4K2160P30, FourK, 3840, 2160, 1000, 30000, ProgressiveFrame),
+    spec(DisplayMode::MODE_4K2160P50, FourK, 3840, 2160, 1000, 50000, ProgressiveFrame),
+    spec(DisplayMode::MODE_4K2160P5994, FourK, 3840, 2160, 1001, 60000, ProgressiveFrame),
+    spec(DisplayMode::MODE_4K2160P60, FourK, 3840, 2160, 1000, 60000, ProgressiveFrame),
+
+    spec(DisplayMode::MODE_4KDCI2398, Dci, 4096, 2160, 1001, 24000, ProgressiveFrame),
+    spec(DisplayMode::MODE_4KDCI24, Dci, 4096, 2160, 1000, 24000, ProgressiveFrame),
+    spec(DisplayMode::MODE_4KDCI25, Dci, 4096, 2160, 1000, 25000, ProgressiveFrame),
+    spec(DisplayMode::MODE_4KDCI2997, Dci, 4096, 2160, 1001, 30000, ProgressiveFrame),
+    spec(DisplayMode::MODE_4KDCI30, Dci, 4096, 2160, 1000, 30000, ProgressiveFrame),
+    spec(DisplayMode::MODE_4KDCI50, Dci, 4096, 2160, 1000, 50000, ProgressiveFrame),
+    spec(DisplayMode::MODE_4KDCI5994, Dci, 4096, 2160, 1001, 60000, ProgressiveFrame),
+    spec(DisplayMode::MODE_4KDCI60, Dci, 4096, 2160, 1000, 60000, ProgressiveFrame),
+
+    spec(DisplayMode::MODE_8K4320P2398, EightK, 7680, 4320, 1001, 24000, ProgressiveFrame),
+    spec(DisplayMode::MODE_8K4320P24, EightK, 7680, 4320, 1000, 24000, ProgressiveFrame),
+    spec(DisplayMode::MODE_8K4320P25, EightK, 7680, 4320, 1000, 25000, ProgressiveFrame),
+    spec(DisplayMode::MODE_8K4320P2997, EightK, 7680, 4320, 1001, 30000, ProgressiveFrame),
+    spec(DisplayMode::MODE_8K4320P30, EightK, 7680, 4320, 1000, 30000, ProgressiveFrame),
+    spec(DisplayMode::MODE_8K4320P50, EightK, 7680, 4320, 1000, 50000, ProgressiveFrame),
+    spec(DisplayMode::MODE_8K4320P5994, EightK, 7680, 4320, 1001, 60000, ProgressiveFrame),
+    spec(DisplayMode::MODE_8K4320P60, EightK, 7680, 4320, 1000, 60000, ProgressiveFrame),
+
+    spec(DisplayMode::MODE_8KDCI2398, Dci, 8192, 4320, 1001, 24000, ProgressiveFrame),
+    spec(DisplayMode::MODE_8KDCI24, Dci, 8192, 4320, 1000, 24000, ProgressiveFrame),
+    spec(DisplayMode::MODE_8KDCI25, Dci, 8192, 4320, 1000, 25000, ProgressiveFrame),
+    spec(DisplayMode::MODE_8KDCI2997, Dci, 8192, 4320, 1001, 30000, ProgressiveFrame),
+    spec(DisplayMode::MODE_8KDCI30, Dci, 8192, 4320, 1000, 30000, ProgressiveFrame),
+    spec(DisplayMode::MODE_8KDCI50, Dci, 8192, 4320, 1000, 50000, ProgressiveFrame),
+    spec(DisplayMode::MODE_8KDCI5994, Dci, 8192, 4320, 1001, 60000, ProgressiveFrame),
+    spec(DisplayMode::MODE_8KDCI60, Dci, 8192, 4320, 1000, 60000, ProgressiveFrame),
+];
+
+impl DisplayMode {
+    /// Returns the static properties of this mode, or `None` for modes without a fixed raster
+    /// such as `MODE_CINTEL_RAW` and `MODE_UNKNOWN`.
+    pub fn info(self) -> Option<&'static DisplayModeSpec> {
+        CATALOG.iter().find(|spec| spec.mode == self)
+    }
+
+    /// Every mode with a fixed raster, in the order the SDK lists them.
+    pub fn catalog() -> &'static [DisplayModeSpec] {
+        CATALOG
+    }
+
+    /// Finds the mode with the given raster and frame rate. The rate is per frame, so 1080i59.94
+    /// is found with a frame duration of 1001 and a time scale of 30000. Rates are compared as
+    /// ratios, so 1/25 and 1000/25000 are equivalent.
+    pub fn find(
+        width: i32,
+        height: i32,
+        frame_duration: i64,
+        time_scale: i64,
+        interlaced: bool,
+    ) -> Option<DisplayMode> {
+        let scan_type = if interlaced {
+            ScanType::Interlaced
+        } else {
+            ScanType::Progressive
+        };
+        CATALOG
+            .iter()
+            .find(|spec| {
+                spec.width == width
+                    && spec.height == height
+                    && spec.scan_type() == scan_type
+                    && spec.has_frame_rate(frame_duration, time_scale)
+            })
+            .map(|spec| spec.mode)
+    }
+}
+
+fn fmt_rate(f: &mut fmt::Formatter, spec: &DisplayModeSpec) -> fmt::Result {
+    // Interlaced modes are conventionally named by their field rate.
+    let time_scale = match spec.scan_type() {
+        ScanType::Interlaced => spec.time_scale * 2,
+        ScanType::Progressive => spec.time_scale,
+    };
+    if time_scale % spec.frame_duration == 0 {
+        write!(f, "{}", time_scale / spec.frame_duration)
+    } else {
+        write!(f, "{:.2}", time_scale as f64 / spec.frame_duration as f64)
+    }
+}
+
+/// Formats the mode as e.g. `1080i59.94`, `2160p50`, `2kdci23.98` or `ntsc`. Modes without a
+/// catalog entry are formatted as their four character code.
+impl fmt::Display for DisplayMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let spec = match self.info() {
+            Some(spec) => spec,
+            None => {
+                let bytes = self.0.to_be_bytes();
+                return match std::str::from_utf8(&bytes) {
+                    Ok(s) if bytes.iter().all(|b| b.is_ascii_graphic()) => write!(f, "{}", s),
+                    _ => write!(f, "{:#010x}", self.0),
+                };
+            }
+        };
+        match *self {
+            DisplayMode::MODE_NTSC => write!(f, "ntsc"),
+            DisplayMode::MODE_NTSC2398 => write!(f, "ntsc23.98"),
+            DisplayMode::MODE_PAL => write!(f, "pal"),
+            DisplayMode::MODE_NTSCP => write!(f, "ntscp"),
+            DisplayMode::MODE_PALP => write!(f, "palp"),
+            _ => {
+                match spec.family {
+                    TwoK => write!(f, "2k")?,
+                    Dci => write!(f, "{}kdci", spec.width / 2048 * 2)?,
+                    _ => match spec.scan_type() {
+                        ScanType::Interlaced => write!(f, "{}i", spec.height)?,
+                        ScanType::Progressive => write!(f, "{}p", spec.height)?,
+                    },
+                }
+                fmt_rate(f, spec)
+            }
+        }
+    }
+}
+
+/// Parses the names produced by `Display`, ignoring case.
+impl FromStr for DisplayMode {
+    type Err = SimpleError;
+
+    fn from_str(s: &str) -> Result<DisplayMode, SimpleError> {
+        CATALOG
+            .iter()
+            .map(|spec| spec.mode)
+            .find(|mode| mode.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| SimpleError::new(format!("unknown display mode: {}", s)))
+    }
+}
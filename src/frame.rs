@@ -0,0 +1,617 @@
+use std::{any::Any, ffi::c_void, sync::Arc};
+
+use simple_error::SimpleError;
+
+use {
+    create_decklink_ancillary_packet, create_decklink_timecode, create_decklink_video_frame,
+    create_decklink_video_frame_ancillary_packets,
+    decklink_custom_video_frame_attach_ancillary_packet, decklink_custom_video_frame_get_owner,
+    decklink_custom_video_frame_set_timecode, decklink_video_frame_ancillary_packets_attach_packet,
+    unknown_release, Error, FrameFlags, IDeckLinkAncillaryPacket, IDeckLinkTimecode,
+    IDeckLinkVideoFrame, MutableVideoFrame, PixelFormat, TimeValue, Timecode, TimecodeFlags,
+    TimecodeFormat, VideoFrame, VideoFrameAncillaryPackets, VideoInputFrame,
+};
+
+/// A video frame held in an ordinary Rust buffer rather than an SDK object.
+///
+/// It can be read and written through `VideoFrame` like any other frame, and converted with
+/// `SoftwareConversion`, but it can't be handed to the SDK: `implementation` returns null.
+#[derive(Clone, Debug)]
+pub struct VideoBuffer {
+    width: i32,
+    height: i32,
+    row_bytes: i32,
+    pixel_format: PixelFormat,
+    flags: FrameFlags,
+    bytes: Vec<u8>,
+}
+
+impl VideoBuffer {
+    /// Allocates a zeroed frame with the minimum row bytes for the pixel format.
+    pub fn new(
+        width: i32,
+        height: i32,
+        pixel_format: PixelFormat,
+    ) -> Result<VideoBuffer, SimpleError> {
+        let row_bytes = pixel_format
+            .row_bytes(width)
+            .ok_or_else(|| SimpleError::new(format!("{:?} has no fixed row size", pixel_format)))?;
+        VideoBuffer::from_bytes(
+            width,
+            height,
+            row_bytes,
+            pixel_format,
+            vec![0; row_bytes.max(0) as usize * height.max(0) as usize],
+        )
+    }
+
+    /// Wraps existing bytes, which must hold at least `row_bytes * height` bytes.
+    pub fn from_bytes(
+        width: i32,
+        height: i32,
+        row_bytes: i32,
+        pixel_format: PixelFormat,
+        bytes: Vec<u8>,
+    ) -> Result<VideoBuffer, SimpleError> {
+        pixel_format.validate(width, height, row_bytes)?;
+        let required = row_bytes as usize * height as usize;
+        if bytes.len() < required {
+            return Err(SimpleError::new(format!(
+                "buffer holds {} bytes, {} are required",
+                bytes.len(),
+                required
+            )));
+        }
+        Ok(VideoBuffer {
+            width,
+            height,
+            row_bytes,
+            pixel_format,
+            flags: FrameFlags::DEFAULT,
+            bytes,
+        })
+    }
+
+    pub fn set_flags(&mut self, flags: FrameFlags) {
+        self.flags = flags;
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+impl VideoFrame for VideoBuffer {
+    unsafe fn implementation(&mut self) -> *mut IDeckLinkVideoFrame {
+        std::ptr::null_mut()
+    }
+
+    fn get_width(&mut self) -> i32 {
+        self.width
+    }
+
+    fn get_height(&mut self) -> i32 {
+        self.height
+    }
+
+    fn get_row_bytes(&mut self) -> i32 {
+        self.row_bytes
+    }
+
+    fn get_pixel_format(&mut self) -> PixelFormat {
+        self.pixel_format
+    }
+
+    fn get_flags(&mut self) -> FrameFlags {
+        self.flags
+    }
+
+    fn get_bytes(&mut self) -> Result<&[u8], Error> {
+        Ok(&self.bytes[..self.row_bytes as usize * self.height as usize])
+    }
+
+    fn get_bytes_mut(&mut self) -> Result<&mut [u8], Error> {
+        Ok(&mut self.bytes[..self.row_bytes as usize * self.height as usize])
+    }
+
+    fn get_timecode(&mut self, _format: TimecodeFormat) -> Result<Option<Timecode>, Error> {
+        Ok(None)
+    }
+
+    fn query_ancillary_packets(&mut self) -> Result<VideoFrameAncillaryPackets, Error> {
+        Err(Error::new())
+    }
+}
+
+/// A timecode carried by a `Frame`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrameTimecode {
+    pub hours: u8,
+    pub minutes: u8,
+    pub seconds: u8,
+    pub frames: u8,
+    pub flags: TimecodeFlags,
+}
+
+/// An ancillary data packet carried by a `Frame`, as 8-bit user data words.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrameAncillaryPacket {
+    pub did: u8,
+    pub sdid: u8,
+    /// The line the packet was captured on. Zero lets the driver place it on output.
+    pub line_number: u32,
+    pub data_stream_index: u8,
+    pub data: Vec<u8>,
+}
+
+/// A frame's stream time and duration, in units of `time_scale`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrameTime {
+    pub time: i64,
+    pub duration: i64,
+    pub time_scale: i64,
+}
+
+impl FrameTime {
+    pub fn time_value(&self) -> TimeValue {
+        TimeValue::new(self.time, self.time_scale)
+    }
+
+    pub fn duration_value(&self) -> TimeValue {
+        TimeValue::new(self.duration, self.time_scale)
+    }
+}
+
+// The formats a captured frame's timecodes are copied from.
+const CAPTURED_TIMECODE_FORMATS: [TimecodeFormat; 6] = [
+    TimecodeFormat::FORMAT_RP188_VITC1,
+    TimecodeFormat::FORMAT_RP188_VITC2,
+    TimecodeFormat::FORMAT_RP188_LTC,
+    TimecodeFormat::FORMAT_RP188_HIGH_FRAME_RATE,
+    TimecodeFormat::FORMAT_VITC,
+    TimecodeFormat::FORMAT_VITC_FIELD2,
+];
+
+fn is_rp188(format: TimecodeFormat) -> bool {
+    CAPTURED_TIMECODE_FORMATS[..4].contains(&format)
+}
+
+/// An owned frame with its metadata, independent of the SDK.
+///
+/// Frames can be built from files or test data, copied from captured frames with `capture`, and
+/// scheduled with `Output::schedule_frame`.
+#[derive(Clone, Debug)]
+pub struct Frame {
+    buffer: VideoBuffer,
+    timecodes: Vec<(TimecodeFormat, FrameTimecode)>,
+    pub stream_time: Option<FrameTime>,
+    pub ancillary_packets: Vec<FrameAncillaryPacket>,
+}
+
+impl Frame {
+    /// Allocates a zeroed frame with the minimum row bytes for the pixel format.
+    pub fn new(width: i32, height: i32, pixel_format: PixelFormat) -> Result<Frame, SimpleError> {
+        Ok(Frame::from_buffer(VideoBuffer::new(
+            width,
+            height,
+            pixel_format,
+        )?))
+    }
+
+    pub fn from_buffer(buffer: VideoBuffer) -> Frame {
+        Frame {
+            buffer,
+            timecodes: Vec::new(),
+            stream_time: None,
+            ancillary_packets: Vec::new(),
+        }
+    }
+
+    /// Copies a captured frame: its pixels, flags, RP188 and VITC timecodes, ancillary packets and
+    /// stream time in `time_scale` units. Metadata the frame doesn't carry is left empty.
+    pub fn capture(frame: &mut VideoInputFrame, time_scale: i64) -> Result<Frame, Error> {
+        let bytes = frame.get_bytes()?.to_vec();
+        let mut buffer = VideoBuffer::from_bytes(
+            frame.get_width(),
+            frame.get_height(),
+            frame.get_row_bytes(),
+            frame.get_pixel_format(),
+            bytes,
+        )
+        .map_err(|_| Error::new())?;
+        buffer.set_flags(frame.get_flags());
+
+        let mut timecodes = Vec::new();
+        for format in CAPTURED_TIMECODE_FORMATS.iter().cloned() {
+            if let Ok(Some(timecode)) = frame.get_timecode(format) {
+                let (hours, minutes, seconds, frames) = timecode.get_components()?;
+                let flags = timecode.get_flags();
+                timecodes.push((
+                    format,
+                    FrameTimecode {
+                        hours,
+                        minutes,
+                        seconds,
+                        frames,
+                        flags,
+                    },
+                ));
+            }
+        }
+
+        let mut ancillary_packets = Vec::new();
+        if let Ok(mut packets) = frame.query_ancillary_packets() {
+            for mut packet in packets.get_packet_iterator()? {
+                ancillary_packets.push(FrameAncillaryPacket {
+                    did: packet.get_did(),
+                    sdid: packet.get_sdid(),
+                    line_number: packet.get_line_number(),
+                    data_stream_index: packet.get_data_stream_index(),
+                    data: packet.get_bytes()?.to_vec(),
+                });
+            }
+        }
+
+        let stream_time = frame
+            .get_stream_time(time_scale)
+            .ok()
+            .map(|(time, duration)| FrameTime {
+                time,
+                duration,
+                time_scale,
+            });
+
+        Ok(Frame {
+            buffer,
+            timecodes,
+            stream_time,
+            ancillary_packets,
+        })
+    }
+
+    pub fn buffer(&self) -> &VideoBuffer {
+        &self.buffer
+    }
+
+    pub fn buffer_mut(&mut self) -> &mut VideoBuffer {
+        &mut self.buffer
+    }
+
+    pub fn into_buffer(self) -> VideoBuffer {
+        self.buffer
+    }
+
+    /// Returns the timecode in the given format. `FORMAT_RP188_ANY` returns the first RP188
+    /// timecode.
+    pub fn timecode(&self, format: TimecodeFormat) -> Option<FrameTimecode> {
+        self.timecodes
+            .iter()
+            .find(|(f, _)| {
+                *f == format || (format == TimecodeFormat::FORMAT_RP188_ANY && is_rp188(*f))
+            })
+            .map(|(_, timecode)| *timecode)
+    }
+
+    pub fn timecodes(&self) -> &[(TimecodeFormat, FrameTimecode)] {
+        &self.timecodes
+    }
+
+    /// Sets the timecode in the given format, replacing any existing one.
+    pub fn set_timecode(&mut self, format: TimecodeFormat, timecode: FrameTimecode) {
+        self.remove_timecode(format);
+        self.timecodes.push((format, timecode));
+    }
+
+    pub fn remove_timecode(&mut self, format: TimecodeFormat) {
+        self.timecodes.retain(|(f, _)| *f != format);
+    }
+
+    /// Moves the frame into an SDK frame object. The frame is dropped when the SDK releases its
+    /// last reference.
+    pub(crate) fn into_implementation(mut self) -> *mut IDeckLinkVideoFrame {
+        unsafe {
+            let bytes = self.buffer.bytes.as_mut_ptr();
+            let buffer = &self.buffer;
+            let geometry = (
+                buffer.width,
+                buffer.height,
+                buffer.row_bytes,
+                buffer.pixel_format,
+                buffer.flags,
+            );
+            let timecodes: Vec<_> = self
+                .timecodes
+                .iter()
+                .map(|(format, timecode)| (*format, create_timecode(timecode, 0)))
+                .collect();
+            let packets: Vec<_> = self
+                .ancillary_packets
+                .iter()
+                .map(|packet| create_ancillary_packet(packet))
+                .collect();
+
+            let implementation = create_implementation(geometry, bytes, Box::new(self));
+            for (format, timecode) in timecodes {
+                decklink_custom_video_frame_set_timecode(implementation, format.0, timecode);
+                unknown_release(timecode as _);
+            }
+            for packet in packets {
+                decklink_custom_video_frame_attach_ancillary_packet(implementation, packet);
+                unknown_release(packet as _);
+            }
+            implementation
+        }
+    }
+}
+
+/// Moves the frame into an SDK frame object that can be scheduled or pooled like one from
+/// `Output::create_video_frame`.
+impl From<Frame> for MutableVideoFrame {
+    fn from(frame: Frame) -> MutableVideoFrame {
+        MutableVideoFrame {
+            implementation: frame.into_implementation() as _,
+        }
+    }
+}
+
+type Geometry = (i32, i32, i32, PixelFormat, FrameFlags);
+
+// Moving the owner into the box doesn't move the pixels `bytes` points to.
+unsafe fn create_implementation(
+    (width, height, row_bytes, pixel_format, flags): Geometry,
+    bytes: *mut u8,
+    owner: Box<dyn Any + Send>,
+) -> *mut IDeckLinkVideoFrame {
+    create_decklink_video_frame(
+        width as _,
+        height as _,
+        row_bytes as _,
+        pixel_format.0,
+        flags.bits(),
+        bytes as _,
+        Box::into_raw(Box::new(owner)) as _,
+    )
+}
+
+/// Returns the owner of a frame created by `create_implementation`, if it is one.
+///
+/// # Safety
+///
+/// The reference is only valid for as long as the frame is.
+pub(crate) unsafe fn owner<'a>(frame: *mut IDeckLinkVideoFrame) -> Option<&'a (dyn Any + Send)> {
+    let owner = decklink_custom_video_frame_get_owner(frame) as *const Box<dyn Any + Send>;
+    if owner.is_null() {
+        None
+    } else {
+        Some(&**owner)
+    }
+}
+
+/// Sets a timecode on a frame created by `create_implementation`, replacing any existing one in
+/// the same format.
+///
+/// # Safety
+///
+/// `frame` must be a frame from `create_implementation`.
+pub(crate) unsafe fn set_timecode(
+    frame: *mut IDeckLinkVideoFrame,
+    format: TimecodeFormat,
+    timecode: &FrameTimecode,
+    user_bits: u32,
+) {
+    let timecode = create_timecode(timecode, user_bits);
+    decklink_custom_video_frame_set_timecode(frame, format.0, timecode);
+    unknown_release(timecode as _);
+}
+
+unsafe fn create_timecode(timecode: &FrameTimecode, user_bits: u32) -> *mut IDeckLinkTimecode {
+    create_decklink_timecode(
+        timecode.hours,
+        timecode.minutes,
+        timecode.seconds,
+        timecode.frames,
+        timecode.flags.bits(),
+        user_bits,
+    )
+}
+
+unsafe fn create_ancillary_packet(packet: &FrameAncillaryPacket) -> *mut IDeckLinkAncillaryPacket {
+    create_decklink_ancillary_packet(
+        packet.did,
+        packet.sdid,
+        packet.line_number,
+        packet.data_stream_index,
+        packet.data.as_ptr(),
+        packet.data.len() as _,
+    )
+}
+
+#[no_mangle]
+unsafe extern "C" fn custom_video_frame_release(owner: *mut c_void) {
+    drop(Box::from_raw(owner as *mut Box<dyn Any + Send>));
+}
+
+impl VideoFrame for Frame {
+    unsafe fn implementation(&mut self) -> *mut IDeckLinkVideoFrame {
+        std::ptr::null_mut()
+    }
+
+    fn get_width(&mut self) -> i32 {
+        self.buffer.get_width()
+    }
+
+    fn get_height(&mut self) -> i32 {
+        self.buffer.get_height()
+    }
+
+    fn get_row_bytes(&mut self) -> i32 {
+        self.buffer.get_row_bytes()
+    }
+
+    fn get_pixel_format(&mut self) -> PixelFormat {
+        self.buffer.get_pixel_format()
+    }
+
+    fn get_flags(&mut self) -> FrameFlags {
+        self.buffer.get_flags()
+    }
+
+    fn get_bytes(&mut self) -> Result<&[u8], Error> {
+        self.buffer.get_bytes()
+    }
+
+    fn get_bytes_mut(&mut self) -> Result<&mut [u8], Error> {
+        self.buffer.get_bytes_mut()
+    }
+
+    fn get_timecode(&mut self, format: TimecodeFormat) -> Result<Option<Timecode>, Error> {
+        Ok(self.timecode(format).map(|timecode| Timecode {
+            implementation: unsafe { create_timecode(&timecode, 0) },
+        }))
+    }
+
+    fn query_ancillary_packets(&mut self) -> Result<VideoFrameAncillaryPackets, Error> {
+        unsafe {
+            let packets = create_decklink_video_frame_ancillary_packets();
+            for packet in &self.ancillary_packets {
+                let packet = create_ancillary_packet(packet);
+                decklink_video_frame_ancillary_packets_attach_packet(packets, packet);
+                unknown_release(packet as _);
+            }
+            Ok(VideoFrameAncillaryPackets {
+                implementation: packets,
+            })
+        }
+    }
+}
+
+type ReleaseHook = Box<dyn FnOnce(Arc<[u8]>) + Send>;
+
+/// A frame over pixels shared with the driver rather than copied, so frames can be scheduled
+/// straight from a renderer's memory with `Output::schedule_shared_frame`.
+///
+/// The driver holds on to the frame until it has been played out. The completed frame passed to
+/// `VideoOutputCallback::scheduled_frame_completed` gives the buffer back through
+/// `MutableVideoFrame::shared_bytes`, and the release hook receives it once the driver has let go
+/// of the frame entirely.
+pub struct SharedFrame {
+    width: i32,
+    height: i32,
+    row_bytes: i32,
+    pixel_format: PixelFormat,
+    flags: FrameFlags,
+    // Only taken when the frame is dropped.
+    bytes: Option<Arc<[u8]>>,
+    release_hook: Option<ReleaseHook>,
+}
+
+impl SharedFrame {
+    /// Wraps a buffer, which must hold at least `row_bytes * height` bytes.
+    pub fn new(
+        width: i32,
+        height: i32,
+        row_bytes: i32,
+        pixel_format: PixelFormat,
+        bytes: Arc<[u8]>,
+    ) -> Result<SharedFrame, SimpleError> {
+        pixel_format.validate(width, height, row_bytes)?;
+        let required = row_bytes as usize * height as usize;
+        if bytes.len() < required {
+            return Err(SimpleError::new(format!(
+                "buffer holds {} bytes, {} are required",
+                bytes.len(),
+                required
+            )));
+        }
+        Ok(SharedFrame {
+            width,
+            height,
+            row_bytes,
+            pixel_format,
+            flags: FrameFlags::DEFAULT,
+            bytes: Some(bytes),
+            release_hook: None,
+        })
+    }
+
+    pub fn set_flags(&mut self, flags: FrameFlags) {
+        self.flags = flags;
+    }
+
+    /// Sets a function to call with the buffer when the frame is dropped. For a scheduled frame,
+    /// that's when the driver releases it, after `scheduled_frame_completed` has returned, so the
+    /// buffer can be reused once the hook holds the last reference.
+    pub fn set_release_hook<F: FnOnce(Arc<[u8]>) + Send + 'static>(&mut self, hook: F) {
+        self.release_hook = Some(Box::new(hook));
+    }
+
+    pub fn bytes(&self) -> &Arc<[u8]> {
+        self.bytes.as_ref().unwrap()
+    }
+
+    pub(crate) fn into_implementation(self) -> *mut IDeckLinkVideoFrame {
+        let geometry = (
+            self.width,
+            self.height,
+            self.row_bytes,
+            self.pixel_format,
+            self.flags,
+        );
+        // The SDK's frame interface takes a mutable pointer, but nothing in the crate writes
+        // through it: `MutableVideoFrame::get_bytes_mut` refuses frames owned by a `SharedFrame`.
+        let bytes = self.bytes().as_ptr() as *mut u8;
+        unsafe { create_implementation(geometry, bytes, Box::new(self)) }
+    }
+}
+
+impl Drop for SharedFrame {
+    fn drop(&mut self) {
+        if let (Some(hook), Some(bytes)) = (self.release_hook.take(), self.bytes.take()) {
+            hook(bytes);
+        }
+    }
+}
+
+impl VideoFrame for SharedFrame {
+    unsafe fn implementation(&mut self) -> *mut IDeckLinkVideoFrame {
+        std::ptr::null_mut()
+    }
+
+    fn get_width(&mut self) -> i32 {
+        self.width
+    }
+
+    fn get_height(&mut self) -> i32 {
+        self.height
+    }
+
+    fn get_row_bytes(&mut self) -> i32 {
+        self.row_bytes
+    }
+
+    fn get_pixel_format(&mut self) -> PixelFormat {
+        self.pixel_format
+    }
+
+    fn get_flags(&mut self) -> FrameFlags {
+        self.flags
+    }
+
+    fn get_bytes(&mut self) -> Result<&[u8], Error> {
+        let len = self.row_bytes as usize * self.height as usize;
+        Ok(&self.bytes()[..len])
+    }
+
+    /// Shared buffers are read-only.
+    fn get_bytes_mut(&mut self) -> Result<&mut [u8], Error> {
+        Err(Error::new())
+    }
+
+    fn get_timecode(&mut self, _format: TimecodeFormat) -> Result<Option<Timecode>, Error> {
+        Ok(None)
+    }
+
+    fn query_ancillary_packets(&mut self) -> Result<VideoFrameAncillaryPackets, Error> {
+        Err(Error::new())
+    }
+}
@@ -0,0 +1,238 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use {DisplayMode, Error, FrameFlags, MutableVideoFrame, Output, PixelFormat};
+
+type Key = (i32, i32, PixelFormat);
+
+/// Creates the frames an `OutputFramePool` hands out.
+///
+/// `Output` allocates them with `create_video_frame`. Other allocators can build frames from
+/// `Frame`s, to use a pool without a device.
+pub trait FrameAllocator {
+    /// Creates a frame with the minimum row bytes for the pixel format.
+    fn allocate_frame(
+        &mut self,
+        width: i32,
+        height: i32,
+        pixel_format: PixelFormat,
+    ) -> Result<MutableVideoFrame, Error>;
+}
+
+impl FrameAllocator for Output {
+    fn allocate_frame(
+        &mut self,
+        width: i32,
+        height: i32,
+        pixel_format: PixelFormat,
+    ) -> Result<MutableVideoFrame, Error> {
+        let row_bytes = pixel_format.row_bytes(width).ok_or_else(Error::new)?;
+        self.create_video_frame(width, height, row_bytes, pixel_format, FrameFlags::DEFAULT)
+    }
+}
+
+/// Counters describing how an `OutputFramePool` is being used.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FramePoolStats {
+    /// Frames created through the pool's allocator and owned by the pool.
+    pub allocated: usize,
+    /// Frames waiting in the pool to be handed out.
+    pub available: usize,
+    /// Frames handed out and not yet reclaimed.
+    pub in_flight: usize,
+    /// The most frames that have been in flight at once. A pool sized to the preroll depth should
+    /// see this settle at the number of frames prerolled plus the ones being rendered.
+    pub peak_in_flight: usize,
+    /// Total frames handed out by `acquire`.
+    pub acquired: u64,
+    /// Total frames returned through `reclaim`.
+    pub reclaimed: u64,
+    /// Calls to `acquire` that found no free frame and the pool at capacity.
+    pub exhausted: u64,
+}
+
+struct Bucket {
+    allocated: usize,
+    free: Vec<MutableVideoFrame>,
+}
+
+struct Inner {
+    capacity: usize,
+    buckets: HashMap<Key, Bucket>,
+    // Frames currently handed out, by implementation address. The completion callback wraps the
+    // same SDK object in a new `MutableVideoFrame`, so identity is all that survives the trip.
+    in_flight: HashMap<usize, Key>,
+    stats: FramePoolStats,
+}
+
+/// A pool of output frames that are recycled once the SDK has finished with them.
+///
+/// Frames are kept per width, height and pixel format, with at most `capacity` of each. Clones
+/// share the same pool, so one can be moved into a `VideoOutputCallback` and frames handed back
+/// with `reclaim` from `scheduled_frame_completed`. Frames that are acquired but never scheduled
+/// should be handed back the same way; `schedule` does so for frames the output rejects.
+#[derive(Clone)]
+pub struct OutputFramePool {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl OutputFramePool {
+    /// Creates an empty pool that allocates up to `capacity` frames of each size and format.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                capacity,
+                buckets: HashMap::new(),
+                in_flight: HashMap::new(),
+                stats: FramePoolStats::default(),
+            })),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.lock().capacity
+    }
+
+    /// Allocates frames up front until `count` frames of the given size and format exist, so that
+    /// allocation doesn't happen during playout. `count` is limited to the pool's capacity.
+    pub fn preallocate<A: FrameAllocator + ?Sized>(
+        &self,
+        allocator: &mut A,
+        width: i32,
+        height: i32,
+        pixel_format: PixelFormat,
+        count: usize,
+    ) -> Result<(), Error> {
+        let mut inner = self.lock();
+        let count = count.min(inner.capacity);
+        let key = (width, height, pixel_format);
+        while inner.bucket(key).allocated < count {
+            let frame = allocator.allocate_frame(width, height, pixel_format)?;
+            inner.allocated(key);
+            let bucket = inner.bucket(key);
+            bucket.free.push(frame);
+            inner.stats.available += 1;
+        }
+        Ok(())
+    }
+
+    /// Hands out a frame of the given size and format, allocating one if none are free and the
+    /// pool is below capacity. Returns `Ok(None)` when the pool is exhausted.
+    ///
+    /// The frame's contents are whatever was last rendered into it.
+    pub fn acquire<A: FrameAllocator + ?Sized>(
+        &self,
+        allocator: &mut A,
+        width: i32,
+        height: i32,
+        pixel_format: PixelFormat,
+    ) -> Result<Option<MutableVideoFrame>, Error> {
+        let mut inner = self.lock();
+        let key = (width, height, pixel_format);
+        let capacity = inner.capacity;
+        let frame = match inner.bucket(key).free.pop() {
+            Some(frame) => {
+                inner.stats.available -= 1;
+                frame
+            }
+            None if inner.bucket(key).allocated < capacity => {
+                let frame = allocator.allocate_frame(width, height, pixel_format)?;
+                inner.allocated(key);
+                frame
+            }
+            None => {
+                inner.stats.exhausted += 1;
+                return Ok(None);
+            }
+        };
+        inner.in_flight.insert(frame.implementation as usize, key);
+        let stats = &mut inner.stats;
+        stats.acquired += 1;
+        stats.in_flight += 1;
+        stats.peak_in_flight = stats.peak_in_flight.max(stats.in_flight);
+        Ok(Some(frame))
+    }
+
+    /// Like `acquire`, using the dimensions of a display mode.
+    pub fn acquire_for_mode<A: FrameAllocator + ?Sized>(
+        &self,
+        allocator: &mut A,
+        display_mode: DisplayMode,
+        pixel_format: PixelFormat,
+    ) -> Result<Option<MutableVideoFrame>, Error> {
+        let spec = display_mode.info().ok_or_else(Error::new)?;
+        self.acquire(allocator, spec.width, spec.height, pixel_format)
+    }
+
+    /// Returns a frame to the pool. Returns false, dropping the frame, if it wasn't handed out by
+    /// this pool.
+    pub fn reclaim(&self, frame: MutableVideoFrame) -> bool {
+        let mut inner = self.lock();
+        let key = match inner.in_flight.remove(&(frame.implementation as usize)) {
+            Some(key) => key,
+            None => return false,
+        };
+        inner.bucket(key).free.push(frame);
+        let stats = &mut inner.stats;
+        stats.reclaimed += 1;
+        stats.in_flight -= 1;
+        stats.available += 1;
+        true
+    }
+
+    /// Schedules a frame handed out by the pool with `Output::schedule_video_frame`. If the output
+    /// rejects it, the frame is reclaimed before the error is returned.
+    pub fn schedule(
+        &self,
+        output: &mut Output,
+        frame: MutableVideoFrame,
+        display_time: i64,
+        display_duration: i64,
+        time_scale: i64,
+    ) -> Result<(), Error> {
+        let result =
+            output.schedule_video_frame_ref(&frame, display_time, display_duration, time_scale);
+        if result.is_err() {
+            self.reclaim(frame);
+        }
+        result
+    }
+
+    pub fn stats(&self) -> FramePoolStats {
+        self.lock().stats
+    }
+
+    /// Drops all free frames, keeping the counters. Frames in flight are still accepted by
+    /// `reclaim`.
+    pub fn clear(&self) {
+        let mut inner = self.lock();
+        let mut released = 0;
+        for bucket in inner.buckets.values_mut() {
+            bucket.allocated -= bucket.free.len();
+            released += bucket.free.len();
+            bucket.free.clear();
+        }
+        inner.stats.allocated -= released;
+        inner.stats.available = 0;
+    }
+}
+
+impl Inner {
+    fn bucket(&mut self, key: Key) -> &mut Bucket {
+        self.buckets.entry(key).or_insert_with(|| Bucket {
+            allocated: 0,
+            free: Vec::new(),
+        })
+    }
+
+    fn allocated(&mut self, key: Key) {
+        self.bucket(key).allocated += 1;
+        self.stats.allocated += 1;
+    }
+}
@@ -2,7 +2,21 @@
 
 #include <atomic>
 #include <cstdlib>
+#include <cstdio>
 #include <cstring>
+#include <utility>
+#include <vector>
+
+#ifndef __APPLE__
+#include <dlfcn.h>
+#endif
+
+// Defined by the dispatch source compiled alongside this file.
+bool IsDeckLinkAPIPresent(void);
+
+// Set once anything has gone through the dispatcher, which loads the library on first use and
+// never again, so decklink_runtime_load_from can tell it would come too late.
+static std::atomic<bool> gDispatcherUsed(false);
 
 HRESULT decklink_get_e_fail() {
     return E_FAIL;
@@ -68,6 +82,7 @@ HRESULT unknown_query_interface(IUnknown* obj, REFIID iid, LPVOID* iface) {
 }
 
 IDeckLinkIterator* create_decklink_iterator_instance() {
+    gDispatcherUsed = true;
     return CreateDeckLinkIteratorInstance();
 }
 
@@ -79,6 +94,288 @@ HRESULT decklink_get_model_name(IDeckLink* decklink, Buffer** str) {
     return decklink->GetModelName(StringArg(str));
 }
 
+// Presents an IDeckLinkInput_v9_2 from an older driver as the current IDeckLinkInput.
+struct LegacyInputV9_2: IDeckLinkInput {
+    explicit LegacyInputV9_2(IDeckLinkInput_v9_2* inner) : _ref_count(1), _inner(inner) {}
+    virtual ~LegacyInputV9_2() {
+        _inner->Release();
+    }
+
+    virtual HRESULT DoesSupportVideoMode(BMDDisplayMode displayMode, BMDPixelFormat pixelFormat, BMDVideoInputFlags flags, BMDDisplayModeSupport *result, IDeckLinkDisplayMode **resultDisplayMode) {
+        return _inner->DoesSupportVideoMode(displayMode, pixelFormat, flags, result, resultDisplayMode);
+    }
+
+    virtual HRESULT GetDisplayModeIterator(IDeckLinkDisplayModeIterator **iterator) {
+        return _inner->GetDisplayModeIterator(iterator);
+    }
+
+    virtual HRESULT SetScreenPreviewCallback(IDeckLinkScreenPreviewCallback *previewCallback) {
+        return _inner->SetScreenPreviewCallback(previewCallback);
+    }
+
+    virtual HRESULT EnableVideoInput(BMDDisplayMode displayMode, BMDPixelFormat pixelFormat, BMDVideoInputFlags flags) {
+        return _inner->EnableVideoInput(displayMode, pixelFormat, flags);
+    }
+
+    virtual HRESULT DisableVideoInput() {
+        return _inner->DisableVideoInput();
+    }
+
+    virtual HRESULT GetAvailableVideoFrameCount(uint32_t *availableFrameCount) {
+        return _inner->GetAvailableVideoFrameCount(availableFrameCount);
+    }
+
+    virtual HRESULT SetVideoInputFrameMemoryAllocator(IDeckLinkMemoryAllocator*) {
+        return E_NOTIMPL;
+    }
+
+    virtual HRESULT EnableAudioInput(BMDAudioSampleRate sampleRate, BMDAudioSampleType sampleType, uint32_t channelCount) {
+        return _inner->EnableAudioInput(sampleRate, sampleType, channelCount);
+    }
+
+    virtual HRESULT DisableAudioInput() {
+        return _inner->DisableAudioInput();
+    }
+
+    virtual HRESULT GetAvailableAudioSampleFrameCount(uint32_t *availableSampleFrameCount) {
+        return _inner->GetAvailableAudioSampleFrameCount(availableSampleFrameCount);
+    }
+
+    virtual HRESULT StartStreams() {
+        return _inner->StartStreams();
+    }
+
+    virtual HRESULT StopStreams() {
+        return _inner->StopStreams();
+    }
+
+    virtual HRESULT PauseStreams() {
+        return _inner->PauseStreams();
+    }
+
+    virtual HRESULT FlushStreams() {
+        return _inner->FlushStreams();
+    }
+
+    virtual HRESULT SetCallback(IDeckLinkInputCallback *theCallback) {
+        return _inner->SetCallback(theCallback);
+    }
+
+    virtual HRESULT GetHardwareReferenceClock(BMDTimeScale desiredTimeScale, BMDTimeValue *hardwareTime, BMDTimeValue *timeInFrame, BMDTimeValue *ticksPerFrame) {
+        return _inner->GetHardwareReferenceClock(desiredTimeScale, hardwareTime, timeInFrame, ticksPerFrame);
+    }
+
+    virtual HRESULT QueryInterface(REFIID iid, LPVOID *ppv) {
+        if (ppv == NULL) {
+            return E_INVALIDARG;
+        }
+
+        CFUUIDBytes iunknown = CFUUIDGetUUIDBytes(IUnknownUUID);
+        if (memcmp(&iid, &iunknown, sizeof(REFIID)) == 0 || memcmp(&iid, &IID_IDeckLinkInput, sizeof(REFIID)) == 0) {
+            *ppv = (IDeckLinkInput*)this;
+            AddRef();
+            return S_OK;
+        }
+
+        return _inner->QueryInterface(iid, ppv);
+    }
+
+    virtual ULONG AddRef() {
+        return _ref_count.fetch_add(1);
+    }
+
+    virtual ULONG Release() {
+        int refs = _ref_count.fetch_sub(1);
+        if (refs == 1) {
+            delete this;
+        }
+        return refs;
+    }
+
+    std::atomic<uint32_t> _ref_count;
+    IDeckLinkInput_v9_2* _inner;
+};
+
+// Presents an IDeckLinkOutput_v9_9 from an older driver as the current IDeckLinkOutput.
+struct LegacyOutputV9_9: IDeckLinkOutput {
+    explicit LegacyOutputV9_9(IDeckLinkOutput_v9_9* inner) : _ref_count(1), _inner(inner) {}
+    virtual ~LegacyOutputV9_9() {
+        _inner->Release();
+    }
+
+    virtual HRESULT DoesSupportVideoMode(BMDDisplayMode displayMode, BMDPixelFormat pixelFormat, BMDVideoOutputFlags flags, BMDDisplayModeSupport *result, IDeckLinkDisplayMode **resultDisplayMode) {
+        return _inner->DoesSupportVideoMode(displayMode, pixelFormat, flags, result, resultDisplayMode);
+    }
+
+    virtual HRESULT GetDisplayModeIterator(IDeckLinkDisplayModeIterator **iterator) {
+        return _inner->GetDisplayModeIterator(iterator);
+    }
+
+    virtual HRESULT SetScreenPreviewCallback(IDeckLinkScreenPreviewCallback *previewCallback) {
+        return _inner->SetScreenPreviewCallback(previewCallback);
+    }
+
+    virtual HRESULT EnableVideoOutput(BMDDisplayMode displayMode, BMDVideoOutputFlags flags) {
+        return _inner->EnableVideoOutput(displayMode, flags);
+    }
+
+    virtual HRESULT DisableVideoOutput() {
+        return _inner->DisableVideoOutput();
+    }
+
+    virtual HRESULT SetVideoOutputFrameMemoryAllocator(IDeckLinkMemoryAllocator *theAllocator) {
+        return _inner->SetVideoOutputFrameMemoryAllocator(theAllocator);
+    }
+
+    virtual HRESULT CreateVideoFrame(int32_t width, int32_t height, int32_t rowBytes, BMDPixelFormat pixelFormat, BMDFrameFlags flags, IDeckLinkMutableVideoFrame **outFrame) {
+        return _inner->CreateVideoFrame(width, height, rowBytes, pixelFormat, flags, outFrame);
+    }
+
+    virtual HRESULT CreateAncillaryData(BMDPixelFormat pixelFormat, IDeckLinkVideoFrameAncillary **outBuffer) {
+        return _inner->CreateAncillaryData(pixelFormat, outBuffer);
+    }
+
+    virtual HRESULT DisplayVideoFrameSync(IDeckLinkVideoFrame *theFrame) {
+        return _inner->DisplayVideoFrameSync(theFrame);
+    }
+
+    virtual HRESULT ScheduleVideoFrame(IDeckLinkVideoFrame *theFrame, BMDTimeValue displayTime, BMDTimeValue displayDuration, BMDTimeScale timeScale) {
+        return _inner->ScheduleVideoFrame(theFrame, displayTime, displayDuration, timeScale);
+    }
+
+    virtual HRESULT SetScheduledFrameCompletionCallback(IDeckLinkVideoOutputCallback *theCallback) {
+        return _inner->SetScheduledFrameCompletionCallback(theCallback);
+    }
+
+    virtual HRESULT GetBufferedVideoFrameCount(uint32_t *bufferedFrameCount) {
+        return _inner->GetBufferedVideoFrameCount(bufferedFrameCount);
+    }
+
+    virtual HRESULT EnableAudioOutput(BMDAudioSampleRate sampleRate, BMDAudioSampleType sampleType, uint32_t channelCount, BMDAudioOutputStreamType streamType) {
+        return _inner->EnableAudioOutput(sampleRate, sampleType, channelCount, streamType);
+    }
+
+    virtual HRESULT DisableAudioOutput() {
+        return _inner->DisableAudioOutput();
+    }
+
+    virtual HRESULT WriteAudioSamplesSync(void *buffer, uint32_t sampleFrameCount, uint32_t *sampleFramesWritten) {
+        return _inner->WriteAudioSamplesSync(buffer, sampleFrameCount, sampleFramesWritten);
+    }
+
+    virtual HRESULT BeginAudioPreroll() {
+        return _inner->BeginAudioPreroll();
+    }
+
+    virtual HRESULT EndAudioPreroll() {
+        return _inner->EndAudioPreroll();
+    }
+
+    virtual HRESULT ScheduleAudioSamples(void *buffer, uint32_t sampleFrameCount, BMDTimeValue streamTime, BMDTimeScale timeScale, uint32_t *sampleFramesWritten) {
+        return _inner->ScheduleAudioSamples(buffer, sampleFrameCount, streamTime, timeScale, sampleFramesWritten);
+    }
+
+    virtual HRESULT GetBufferedAudioSampleFrameCount(uint32_t *bufferedSampleFrameCount) {
+        return _inner->GetBufferedAudioSampleFrameCount(bufferedSampleFrameCount);
+    }
+
+    virtual HRESULT FlushBufferedAudioSamples() {
+        return _inner->FlushBufferedAudioSamples();
+    }
+
+    virtual HRESULT SetAudioCallback(IDeckLinkAudioOutputCallback *theCallback) {
+        return _inner->SetAudioCallback(theCallback);
+    }
+
+    virtual HRESULT StartScheduledPlayback(BMDTimeValue playbackStartTime, BMDTimeScale timeScale, double playbackSpeed) {
+        return _inner->StartScheduledPlayback(playbackStartTime, timeScale, playbackSpeed);
+    }
+
+    virtual HRESULT StopScheduledPlayback(BMDTimeValue stopPlaybackAtTime, BMDTimeValue *actualStopTime, BMDTimeScale timeScale) {
+        return _inner->StopScheduledPlayback(stopPlaybackAtTime, actualStopTime, timeScale);
+    }
+
+    virtual HRESULT IsScheduledPlaybackRunning(bool *active) {
+        return _inner->IsScheduledPlaybackRunning(active);
+    }
+
+    virtual HRESULT GetScheduledStreamTime(BMDTimeScale desiredTimeScale, BMDTimeValue *streamTime, double *playbackSpeed) {
+        return _inner->GetScheduledStreamTime(desiredTimeScale, streamTime, playbackSpeed);
+    }
+
+    virtual HRESULT GetReferenceStatus(BMDReferenceStatus *referenceStatus) {
+        return _inner->GetReferenceStatus(referenceStatus);
+    }
+
+    virtual HRESULT GetHardwareReferenceClock(BMDTimeScale desiredTimeScale, BMDTimeValue *hardwareTime, BMDTimeValue *timeInFrame, BMDTimeValue *ticksPerFrame) {
+        return _inner->GetHardwareReferenceClock(desiredTimeScale, hardwareTime, timeInFrame, ticksPerFrame);
+    }
+
+    virtual HRESULT GetFrameCompletionReferenceTimestamp(IDeckLinkVideoFrame*, BMDTimeScale, BMDTimeValue*) {
+        return E_NOTIMPL;
+    }
+
+    virtual HRESULT QueryInterface(REFIID iid, LPVOID *ppv) {
+        if (ppv == NULL) {
+            return E_INVALIDARG;
+        }
+
+        CFUUIDBytes iunknown = CFUUIDGetUUIDBytes(IUnknownUUID);
+        if (memcmp(&iid, &iunknown, sizeof(REFIID)) == 0 || memcmp(&iid, &IID_IDeckLinkOutput, sizeof(REFIID)) == 0) {
+            *ppv = (IDeckLinkOutput*)this;
+            AddRef();
+            return S_OK;
+        }
+
+        return _inner->QueryInterface(iid, ppv);
+    }
+
+    virtual ULONG AddRef() {
+        return _ref_count.fetch_add(1);
+    }
+
+    virtual ULONG Release() {
+        int refs = _ref_count.fetch_sub(1);
+        if (refs == 1) {
+            delete this;
+        }
+        return refs;
+    }
+
+    std::atomic<uint32_t> _ref_count;
+    IDeckLinkOutput_v9_9* _inner;
+};
+
+HRESULT decklink_query_input(IDeckLink* decklink, IDeckLinkInput** input) {
+    HRESULT result = decklink->QueryInterface(IID_IDeckLinkInput, (void**)input);
+    if (result == S_OK) {
+        return result;
+    }
+
+    IDeckLinkInput_v9_2* legacy = nullptr;
+    if (decklink->QueryInterface(IID_IDeckLinkInput_v9_2, (void**)&legacy) == S_OK) {
+        *input = new LegacyInputV9_2(legacy);
+        return S_OK;
+    }
+
+    return result;
+}
+
+HRESULT decklink_query_output(IDeckLink* decklink, IDeckLinkOutput** output) {
+    HRESULT result = decklink->QueryInterface(IID_IDeckLinkOutput, (void**)output);
+    if (result == S_OK) {
+        return result;
+    }
+
+    IDeckLinkOutput_v9_9* legacy = nullptr;
+    if (decklink->QueryInterface(IID_IDeckLinkOutput_v9_9, (void**)&legacy) == S_OK) {
+        *output = new LegacyOutputV9_9(legacy);
+        return S_OK;
+    }
+
+    return result;
+}
+
 HRESULT decklink_attributes_get_flag(IDeckLinkAttributes* attr, BMDDeckLinkAttributeID cfgID, bool* value) {
     return attr->GetFlag(cfgID, value);
 }
@@ -179,6 +476,38 @@ HRESULT decklink_output_get_scheduled_stream_time(IDeckLinkOutput* output, BMDTi
     return output->GetScheduledStreamTime(desiredTimeScale, streamTime, playbackSpeed);
 }
 
+HRESULT decklink_output_enable_audio_output(IDeckLinkOutput* output, BMDAudioSampleRate sampleRate, BMDAudioSampleType sampleType, uint32_t channelCount, BMDAudioOutputStreamType streamType) {
+    return output->EnableAudioOutput(sampleRate, sampleType, channelCount, streamType);
+}
+
+HRESULT decklink_output_disable_audio_output(IDeckLinkOutput* output) {
+    return output->DisableAudioOutput();
+}
+
+HRESULT decklink_output_write_audio_samples_sync(IDeckLinkOutput* output, void* buffer, uint32_t sampleFrameCount, uint32_t* sampleFramesWritten) {
+    return output->WriteAudioSamplesSync(buffer, sampleFrameCount, sampleFramesWritten);
+}
+
+HRESULT decklink_output_begin_audio_preroll(IDeckLinkOutput* output) {
+    return output->BeginAudioPreroll();
+}
+
+HRESULT decklink_output_end_audio_preroll(IDeckLinkOutput* output) {
+    return output->EndAudioPreroll();
+}
+
+HRESULT decklink_output_schedule_audio_samples(IDeckLinkOutput* output, void* buffer, uint32_t sampleFrameCount, BMDTimeValue streamTime, BMDTimeScale timeScale, uint32_t* sampleFramesWritten) {
+    return output->ScheduleAudioSamples(buffer, sampleFrameCount, streamTime, timeScale, sampleFramesWritten);
+}
+
+HRESULT decklink_output_get_buffered_audio_sample_frame_count(IDeckLinkOutput* output, uint32_t* bufferedSampleFrameCount) {
+    return output->GetBufferedAudioSampleFrameCount(bufferedSampleFrameCount);
+}
+
+HRESULT decklink_output_flush_buffered_audio_samples(IDeckLinkOutput* output) {
+    return output->FlushBufferedAudioSamples();
+}
+
 HRESULT decklink_display_mode_iterator_next(IDeckLinkDisplayModeIterator* iterator, IDeckLinkDisplayMode** deckLinkDisplayMode) {
 	return iterator->Next(deckLinkDisplayMode);
 }
@@ -362,6 +691,14 @@ HRESULT decklink_video_frame_get_timecode(IDeckLinkVideoFrame* frame, BMDTimecod
     return frame->GetTimecode(format, timecode);
 }
 
+HRESULT decklink_mutable_video_frame_set_timecode_from_components(IDeckLinkMutableVideoFrame* frame, BMDTimecodeFormat format, uint8_t hours, uint8_t minutes, uint8_t seconds, uint8_t frames, BMDTimecodeFlags flags) {
+    return frame->SetTimecodeFromComponents(format, hours, minutes, seconds, frames, flags);
+}
+
+HRESULT decklink_mutable_video_frame_set_timecode_user_bits(IDeckLinkMutableVideoFrame* frame, BMDTimecodeFormat format, BMDTimecodeUserBits userBits) {
+    return frame->SetTimecodeUserBits(format, userBits);
+}
+
 HRESULT decklink_video_input_frame_get_stream_time(IDeckLinkVideoInputFrame* frame, BMDTimeValue* frameTime, BMDTimeValue* frameDuration, BMDTimeScale timeScale) {
     return frame->GetStreamTime(frameTime, frameDuration, timeScale);
 }
@@ -371,6 +708,7 @@ HRESULT decklink_video_input_frame_get_hardware_reference_timestamp(IDeckLinkVid
 }
 
 IDeckLinkVideoConversion* create_decklink_video_conversion_instance() {
+	gDispatcherUsed = true;
 	return CreateVideoConversionInstance();
 }
 
@@ -386,11 +724,16 @@ BMDTimecodeFlags decklink_timecode_get_flags(IDeckLinkTimecode* timecode) {
     return timecode->GetFlags();
 }
 
+HRESULT decklink_timecode_get_user_bits(IDeckLinkTimecode* timecode, BMDTimecodeUserBits* userBits) {
+    return timecode->GetTimecodeUserBits(userBits);
+}
+
 HRESULT decklink_timecode_get_string(IDeckLinkTimecode* timecode, Buffer** value) {
     return timecode->GetString(StringArg(value));
 }
 
 IDeckLinkAPIInformation* create_decklink_api_information_instance() {
+	gDispatcherUsed = true;
 	return CreateDeckLinkAPIInformationInstance();
 }
 
@@ -402,6 +745,41 @@ HRESULT decklink_api_information_get_version_string(IDeckLinkAPIInformation* api
     return apiInfo->GetString(cfgID, StringArg(value));
 }
 
+bool decklink_runtime_is_available() {
+    // The dispatcher only loads the library on first use, so make sure that has happened.
+    gDispatcherUsed = true;
+    IDeckLinkAPIInformation* apiInfo = CreateDeckLinkAPIInformationInstance();
+    if (apiInfo != nullptr) {
+        apiInfo->Release();
+    }
+    return IsDeckLinkAPIPresent();
+}
+
+HRESULT decklink_runtime_load_from(const char* path, Buffer** error) {
+    *error = nullptr;
+    if (gDispatcherUsed) {
+        *error = new Buffer(strdup("the decklink api was already in use; load_from must be called before anything else"));
+        return E_FAIL;
+    }
+#ifdef __APPLE__
+    (void)path;
+    *error = new Buffer(strdup("loading the decklink api from a custom path is not supported on this platform"));
+    return E_NOTIMPL;
+#else
+    // Once the library is loaded, the dispatcher's own dlopen of libDeckLinkAPI.so resolves to it by soname.
+    if (dlopen(path, RTLD_NOW | RTLD_GLOBAL) == nullptr) {
+        const char* message = dlerror();
+        *error = new Buffer(strdup(message != nullptr ? message : "unable to load library"));
+        return E_FAIL;
+    }
+    if (!decklink_runtime_is_available()) {
+        *error = new Buffer(strdup("the library loaded, but the decklink api could not be initialized from it"));
+        return E_FAIL;
+    }
+    return S_OK;
+#endif
+}
+
 HRESULT decklink_video_frame_ancillary_packets_get_packet_iterator(IDeckLinkVideoFrameAncillaryPackets* packets, IDeckLinkAncillaryPacketIterator** iterator) {
     return packets->GetPacketIterator(iterator);
 }
@@ -426,6 +804,364 @@ uint8_t decklink_ancillary_packet_get_sdid(IDeckLinkAncillaryPacket* packet) {
     return packet->GetSDID();
 }
 
+uint32_t decklink_ancillary_packet_get_line_number(IDeckLinkAncillaryPacket* packet) {
+    return packet->GetLineNumber();
+}
+
+uint8_t decklink_ancillary_packet_get_data_stream_index(IDeckLinkAncillaryPacket* packet) {
+    return packet->GetDataStreamIndex();
+}
+
+// Implementations of the SDK's interfaces for data owned by Rust, so it can be handed to the SDK.
+
+extern "C++" {
+
+template <typename Interface>
+struct Unknown: Interface {
+    explicit Unknown(REFIID iid) : _ref_count(1), _iid(iid) {}
+    virtual ~Unknown() {}
+
+    virtual HRESULT QueryInterface(REFIID iid, LPVOID *ppv) {
+        if (ppv == NULL) {
+            return E_INVALIDARG;
+        }
+
+        *ppv = NULL;
+
+        CFUUIDBytes iunknown = CFUUIDGetUUIDBytes(IUnknownUUID);
+        if (memcmp(&iid, &iunknown, sizeof(REFIID)) == 0 || memcmp(&iid, &_iid, sizeof(REFIID)) == 0) {
+            *ppv = (Interface*)this;
+            this->AddRef();
+            return S_OK;
+        }
+        return E_NOINTERFACE;
+    }
+
+    virtual ULONG AddRef() {
+        return _ref_count.fetch_add(1) + 1;
+    }
+
+    virtual ULONG Release() {
+        ULONG refs = _ref_count.fetch_sub(1) - 1;
+        if (refs == 0) {
+            delete this;
+        }
+        return refs;
+    }
+
+    std::atomic<uint32_t> _ref_count;
+    REFIID _iid;
+};
+
+}
+
+struct Timecode: Unknown<IDeckLinkTimecode> {
+    Timecode(uint8_t hours, uint8_t minutes, uint8_t seconds, uint8_t frames, BMDTimecodeFlags flags, BMDTimecodeUserBits userBits)
+        : Unknown(IID_IDeckLinkTimecode), _hours(hours), _minutes(minutes), _seconds(seconds), _frames(frames), _flags(flags), _user_bits(userBits) {}
+
+    static BMDTimecodeBCD bcd(uint8_t value) {
+        return ((value / 10) << 4) | (value % 10);
+    }
+
+    virtual BMDTimecodeBCD GetBCD() {
+        return bcd(_hours) << 24 | bcd(_minutes) << 16 | bcd(_seconds) << 8 | bcd(_frames);
+    }
+
+    virtual HRESULT GetComponents(uint8_t* hours, uint8_t* minutes, uint8_t* seconds, uint8_t* frames) {
+        *hours = _hours;
+        *minutes = _minutes;
+        *seconds = _seconds;
+        *frames = _frames;
+        return S_OK;
+    }
+
+#ifdef __APPLE__
+    virtual HRESULT GetString(CFStringRef* timecode) {
+#else
+    virtual HRESULT GetString(const char** timecode) {
+#endif
+        char str[16];
+        snprintf(str, sizeof(str), "%02u:%02u:%02u%c%02u", _hours, _minutes, _seconds, (_flags & bmdTimecodeIsDropFrame) ? ';' : ':', _frames);
+#ifdef __APPLE__
+        *timecode = CFStringCreateWithCString(NULL, str, kCFStringEncodingUTF8);
+#else
+        *timecode = strdup(str);
+#endif
+        return S_OK;
+    }
+
+    virtual BMDTimecodeFlags GetFlags() {
+        return _flags;
+    }
+
+    virtual HRESULT GetTimecodeUserBits(BMDTimecodeUserBits* userBits) {
+        *userBits = _user_bits;
+        return S_OK;
+    }
+
+    uint8_t _hours, _minutes, _seconds, _frames;
+    BMDTimecodeFlags _flags;
+    BMDTimecodeUserBits _user_bits;
+};
+
+IDeckLinkTimecode* create_decklink_timecode(uint8_t hours, uint8_t minutes, uint8_t seconds, uint8_t frames, BMDTimecodeFlags flags, BMDTimecodeUserBits userBits) {
+    return new Timecode(hours, minutes, seconds, frames, flags, userBits);
+}
+
+struct AncillaryPacket: Unknown<IDeckLinkAncillaryPacket> {
+    AncillaryPacket(uint8_t did, uint8_t sdid, uint32_t lineNumber, uint8_t dataStreamIndex, const uint8_t* data, uint32_t size)
+        : Unknown(IID_IDeckLinkAncillaryPacket), _did(did), _sdid(sdid), _line_number(lineNumber), _data_stream_index(dataStreamIndex), _data(data, data + size) {}
+
+    virtual HRESULT GetBytes(BMDAncillaryPacketFormat format, const void** data, uint32_t* size) {
+        if (format != bmdAncillaryPacketFormatUInt8) {
+            return E_NOTIMPL;
+        }
+        if (data != NULL) {
+            *data = _data.data();
+        }
+        if (size != NULL) {
+            *size = (uint32_t)_data.size();
+        }
+        return S_OK;
+    }
+
+    virtual uint8_t GetDID() {
+        return _did;
+    }
+
+    virtual uint8_t GetSDID() {
+        return _sdid;
+    }
+
+    virtual uint32_t GetLineNumber() {
+        return _line_number;
+    }
+
+    virtual uint8_t GetDataStreamIndex() {
+        return _data_stream_index;
+    }
+
+    uint8_t _did, _sdid;
+    uint32_t _line_number;
+    uint8_t _data_stream_index;
+    std::vector<uint8_t> _data;
+};
+
+IDeckLinkAncillaryPacket* create_decklink_ancillary_packet(uint8_t did, uint8_t sdid, uint32_t lineNumber, uint8_t dataStreamIndex, const uint8_t* data, uint32_t size) {
+    return new AncillaryPacket(did, sdid, lineNumber, dataStreamIndex, data, size);
+}
+
+struct AncillaryPacketIterator: Unknown<IDeckLinkAncillaryPacketIterator> {
+    explicit AncillaryPacketIterator(const std::vector<IDeckLinkAncillaryPacket*>& packets)
+        : Unknown(IID_IDeckLinkAncillaryPacketIterator), _packets(packets), _next(0) {
+        for (auto packet : _packets) {
+            packet->AddRef();
+        }
+    }
+
+    virtual ~AncillaryPacketIterator() {
+        for (auto packet : _packets) {
+            packet->Release();
+        }
+    }
+
+    virtual HRESULT Next(IDeckLinkAncillaryPacket** packet) {
+        if (_next == _packets.size()) {
+            *packet = NULL;
+            return S_FALSE;
+        }
+        *packet = _packets[_next++];
+        (*packet)->AddRef();
+        return S_OK;
+    }
+
+    std::vector<IDeckLinkAncillaryPacket*> _packets;
+    size_t _next;
+};
+
+struct VideoFrameAncillaryPackets: Unknown<IDeckLinkVideoFrameAncillaryPackets> {
+    VideoFrameAncillaryPackets() : Unknown(IID_IDeckLinkVideoFrameAncillaryPackets) {}
+
+    virtual ~VideoFrameAncillaryPackets() {
+        DetachAllPackets();
+    }
+
+    virtual HRESULT GetPacketIterator(IDeckLinkAncillaryPacketIterator** iterator) {
+        *iterator = new AncillaryPacketIterator(_packets);
+        return S_OK;
+    }
+
+    virtual HRESULT GetFirstPacketByID(uint8_t DID, uint8_t SDID, IDeckLinkAncillaryPacket** packet) {
+        for (auto candidate : _packets) {
+            if (candidate->GetDID() == DID && candidate->GetSDID() == SDID) {
+                *packet = candidate;
+                candidate->AddRef();
+                return S_OK;
+            }
+        }
+        *packet = NULL;
+        return S_FALSE;
+    }
+
+    virtual HRESULT AttachPacket(IDeckLinkAncillaryPacket* packet) {
+        packet->AddRef();
+        _packets.push_back(packet);
+        return S_OK;
+    }
+
+    virtual HRESULT DetachPacket(IDeckLinkAncillaryPacket* packet) {
+        for (auto it = _packets.begin(); it != _packets.end(); ++it) {
+            if (*it == packet) {
+                _packets.erase(it);
+                packet->Release();
+                return S_OK;
+            }
+        }
+        return E_INVALIDARG;
+    }
+
+    virtual HRESULT DetachAllPackets() {
+        for (auto packet : _packets) {
+            packet->Release();
+        }
+        _packets.clear();
+        return S_OK;
+    }
+
+    std::vector<IDeckLinkAncillaryPacket*> _packets;
+};
+
+IDeckLinkVideoFrameAncillaryPackets* create_decklink_video_frame_ancillary_packets() {
+    return new VideoFrameAncillaryPackets();
+}
+
+HRESULT decklink_video_frame_ancillary_packets_attach_packet(IDeckLinkVideoFrameAncillaryPackets* packets, IDeckLinkAncillaryPacket* packet) {
+    return packets->AttachPacket(packet);
+}
+
+extern void custom_video_frame_release(void*);
+
+// Lets frames created here be recognized when the SDK hands them back.
+static const REFIID IID_CustomVideoFrame = /* 8E1D52A4-3C77-4F0B-9A61-2B5D7C0E4F93 */ {0x8E,0x1D,0x52,0xA4,0x3C,0x77,0x4F,0x0B,0x9A,0x61,0x2B,0x5D,0x7C,0x0E,0x4F,0x93};
+
+// A frame over memory owned by Rust. The owner is handed back to Rust once the last reference,
+// including any the SDK holds while the frame is scheduled, is released.
+struct VideoFrame: Unknown<IDeckLinkVideoFrame> {
+    VideoFrame(long width, long height, long rowBytes, BMDPixelFormat pixelFormat, BMDFrameFlags flags, void* bytes, void* owner)
+        : Unknown(IID_IDeckLinkVideoFrame), _width(width), _height(height), _row_bytes(rowBytes), _pixel_format(pixelFormat), _flags(flags), _bytes(bytes), _owner(owner), _packets(new VideoFrameAncillaryPackets()) {}
+
+    virtual ~VideoFrame() {
+        for (auto& timecode : _timecodes) {
+            timecode.second->Release();
+        }
+        _packets->Release();
+        if (_owner != NULL) {
+            custom_video_frame_release(_owner);
+        }
+    }
+
+    virtual HRESULT QueryInterface(REFIID iid, LPVOID *ppv) {
+        if (ppv != NULL && memcmp(&iid, &IID_IDeckLinkVideoFrameAncillaryPackets, sizeof(REFIID)) == 0) {
+            *ppv = (IDeckLinkVideoFrameAncillaryPackets*)_packets;
+            _packets->AddRef();
+            return S_OK;
+        }
+        if (ppv != NULL && memcmp(&iid, &IID_CustomVideoFrame, sizeof(REFIID)) == 0) {
+            *ppv = this;
+            AddRef();
+            return S_OK;
+        }
+        return Unknown::QueryInterface(iid, ppv);
+    }
+
+    virtual long GetWidth() {
+        return _width;
+    }
+
+    virtual long GetHeight() {
+        return _height;
+    }
+
+    virtual long GetRowBytes() {
+        return _row_bytes;
+    }
+
+    virtual BMDPixelFormat GetPixelFormat() {
+        return _pixel_format;
+    }
+
+    virtual BMDFrameFlags GetFlags() {
+        return _flags;
+    }
+
+    virtual HRESULT GetBytes(void** buffer) {
+        *buffer = _bytes;
+        return S_OK;
+    }
+
+    virtual HRESULT GetTimecode(BMDTimecodeFormat format, IDeckLinkTimecode** timecode) {
+        for (auto& candidate : _timecodes) {
+            bool rp188 = candidate.first == bmdTimecodeRP188VITC1 || candidate.first == bmdTimecodeRP188VITC2 || candidate.first == bmdTimecodeRP188LTC || candidate.first == bmdTimecodeRP188HighFrameRate;
+            if (candidate.first == format || (format == bmdTimecodeRP188Any && rp188)) {
+                *timecode = candidate.second;
+                candidate.second->AddRef();
+                return S_OK;
+            }
+        }
+        *timecode = NULL;
+        return S_FALSE;
+    }
+
+    virtual HRESULT GetAncillaryData(IDeckLinkVideoFrameAncillary** ancillary) {
+        *ancillary = NULL;
+        return E_NOTIMPL;
+    }
+
+    void SetTimecode(BMDTimecodeFormat format, IDeckLinkTimecode* timecode) {
+        timecode->AddRef();
+        for (auto& existing : _timecodes) {
+            if (existing.first == format) {
+                existing.second->Release();
+                existing.second = timecode;
+                return;
+            }
+        }
+        _timecodes.push_back(std::make_pair(format, timecode));
+    }
+
+    long _width, _height, _row_bytes;
+    BMDPixelFormat _pixel_format;
+    BMDFrameFlags _flags;
+    void* _bytes;
+    void* _owner;
+    VideoFrameAncillaryPackets* _packets;
+    std::vector<std::pair<BMDTimecodeFormat, IDeckLinkTimecode*>> _timecodes;
+};
+
+IDeckLinkVideoFrame* create_decklink_video_frame(long width, long height, long rowBytes, BMDPixelFormat pixelFormat, BMDFrameFlags flags, void* bytes, void* owner) {
+    return new VideoFrame(width, height, rowBytes, pixelFormat, flags, bytes, owner);
+}
+
+// These are only valid for frames from create_decklink_video_frame.
+void decklink_custom_video_frame_set_timecode(IDeckLinkVideoFrame* frame, BMDTimecodeFormat format, IDeckLinkTimecode* timecode) {
+    static_cast<VideoFrame*>(frame)->SetTimecode(format, timecode);
+}
+
+void decklink_custom_video_frame_attach_ancillary_packet(IDeckLinkVideoFrame* frame, IDeckLinkAncillaryPacket* packet) {
+    static_cast<VideoFrame*>(frame)->_packets->AttachPacket(packet);
+}
+
+// Returns the owner of a frame from create_decklink_video_frame, or null for any other frame.
+void* decklink_custom_video_frame_get_owner(IDeckLinkVideoFrame* frame) {
+    VideoFrame* custom = NULL;
+    if (frame->QueryInterface(IID_CustomVideoFrame, (void**)&custom) != S_OK) {
+        return NULL;
+    }
+    void* owner = custom->_owner;
+    custom->Release();
+    return owner;
+}
+
 const void* buffer_data(Buffer* buf) {
     return buf->_data;
 }
@@ -1,4 +1,7 @@
 #include <DeckLinkAPI.h>
+#include <DeckLinkAPIVersion.h>
+#include <DeckLinkAPI_v9_2.h>
+#include <DeckLinkAPI_v9_9.h>
 
 struct Buffer;
 
@@ -14,6 +17,8 @@ IDeckLinkIterator* create_decklink_iterator_instance();
 HRESULT decklink_iterator_next(IDeckLinkIterator* iterator, IDeckLink** deckLinkInstance);
 
 HRESULT decklink_get_model_name(IDeckLink* decklink, Buffer** str);
+HRESULT decklink_query_input(IDeckLink* decklink, IDeckLinkInput** input);
+HRESULT decklink_query_output(IDeckLink* decklink, IDeckLinkOutput** output);
 
 HRESULT decklink_attributes_get_flag(IDeckLinkAttributes* attr, BMDDeckLinkAttributeID cfgID, bool* value);
 HRESULT decklink_attributes_get_int(IDeckLinkAttributes* attr, BMDDeckLinkAttributeID cfgID, int64_t* value);
@@ -43,6 +48,14 @@ HRESULT decklink_output_set_scheduled_frame_completion_callback(IDeckLinkOutput*
 HRESULT decklink_output_start_scheduled_playback(IDeckLinkOutput* output, BMDTimeValue playbackStartTime, BMDTimeScale timeScale, double playbackSpeed);
 HRESULT decklink_output_get_scheduled_stream_time(IDeckLinkOutput* output, BMDTimeScale desiredTimeScale, BMDTimeValue* streamTime, double* playbackSpeed);
 HRESULT decklink_output_schedule_video_frame(IDeckLinkOutput* output, IDeckLinkVideoFrame* theFrame, BMDTimeValue displayTime, BMDTimeValue displayDuration, BMDTimeScale timeScale);
+HRESULT decklink_output_enable_audio_output(IDeckLinkOutput* output, BMDAudioSampleRate sampleRate, BMDAudioSampleType sampleType, uint32_t channelCount, BMDAudioOutputStreamType streamType);
+HRESULT decklink_output_disable_audio_output(IDeckLinkOutput* output);
+HRESULT decklink_output_write_audio_samples_sync(IDeckLinkOutput* output, void* buffer, uint32_t sampleFrameCount, uint32_t* sampleFramesWritten);
+HRESULT decklink_output_begin_audio_preroll(IDeckLinkOutput* output);
+HRESULT decklink_output_end_audio_preroll(IDeckLinkOutput* output);
+HRESULT decklink_output_schedule_audio_samples(IDeckLinkOutput* output, void* buffer, uint32_t sampleFrameCount, BMDTimeValue streamTime, BMDTimeScale timeScale, uint32_t* sampleFramesWritten);
+HRESULT decklink_output_get_buffered_audio_sample_frame_count(IDeckLinkOutput* output, uint32_t* bufferedSampleFrameCount);
+HRESULT decklink_output_flush_buffered_audio_samples(IDeckLinkOutput* output);
 
 HRESULT decklink_display_mode_iterator_next(IDeckLinkDisplayModeIterator* iterator, IDeckLinkDisplayMode** deckLinkDisplayMode);
 
@@ -68,6 +81,9 @@ BMDFrameFlags decklink_video_frame_get_flags(IDeckLinkVideoFrame* frame);
 HRESULT decklink_video_frame_get_bytes(IDeckLinkVideoFrame* frame, void** bytes);
 HRESULT decklink_video_frame_get_timecode(IDeckLinkVideoFrame* frame, BMDTimecodeFormat format, IDeckLinkTimecode** timecode);
 
+HRESULT decklink_mutable_video_frame_set_timecode_from_components(IDeckLinkMutableVideoFrame* frame, BMDTimecodeFormat format, uint8_t hours, uint8_t minutes, uint8_t seconds, uint8_t frames, BMDTimecodeFlags flags);
+HRESULT decklink_mutable_video_frame_set_timecode_user_bits(IDeckLinkMutableVideoFrame* frame, BMDTimecodeFormat format, BMDTimecodeUserBits userBits);
+
 HRESULT decklink_video_input_frame_get_stream_time(IDeckLinkVideoInputFrame* frame, BMDTimeValue* frameTime, BMDTimeValue* frameDuration, BMDTimeScale timeScale);
 HRESULT decklink_video_input_frame_get_hardware_reference_timestamp(IDeckLinkVideoInputFrame* frame, BMDTimeScale timeScale, BMDTimeValue* frameTime, BMDTimeValue* frameDuration);
 
@@ -77,11 +93,15 @@ HRESULT decklink_video_conversion_convert_frame(IDeckLinkVideoConversion* conver
 HRESULT decklink_timecode_get_components(IDeckLinkTimecode* timecode, uint8_t* hours, uint8_t* minutes, uint8_t* seconds, uint8_t* frames);
 HRESULT decklink_timecode_get_string(IDeckLinkTimecode* timecode, Buffer** value);
 BMDTimecodeFlags decklink_timecode_get_flags(IDeckLinkTimecode* timecode);
+HRESULT decklink_timecode_get_user_bits(IDeckLinkTimecode* timecode, BMDTimecodeUserBits* userBits);
 
 IDeckLinkAPIInformation* create_decklink_api_information_instance();
 HRESULT decklink_api_information_get_version_int(IDeckLinkAPIInformation* apiInfo, BMDDeckLinkAPIInformationID cfgID, int64_t* value);
 HRESULT decklink_api_information_get_version_string(IDeckLinkAPIInformation* apiInfo, BMDDeckLinkAPIInformationID cfgID, Buffer** value);
 
+bool decklink_runtime_is_available();
+HRESULT decklink_runtime_load_from(const char* path, Buffer** error);
+
 HRESULT decklink_video_frame_ancillary_packets_get_packet_iterator(IDeckLinkVideoFrameAncillaryPackets* packets, IDeckLinkAncillaryPacketIterator** iterator);
 HRESULT decklink_video_frame_ancillary_packets_get_first_packet_by_id(IDeckLinkVideoFrameAncillaryPackets* packets, uint8_t DID, uint8_t SDID, IDeckLinkAncillaryPacket **packet);
 
@@ -90,6 +110,17 @@ HRESULT decklink_ancillary_packet_iterator_next(IDeckLinkAncillaryPacketIterator
 HRESULT decklink_ancillary_packet_get_bytes(IDeckLinkAncillaryPacket* packet, BMDAncillaryPacketFormat format, const void** data, uint32_t* size);
 uint8_t decklink_ancillary_packet_get_did(IDeckLinkAncillaryPacket* packet);
 uint8_t decklink_ancillary_packet_get_sdid(IDeckLinkAncillaryPacket* packet);
+uint32_t decklink_ancillary_packet_get_line_number(IDeckLinkAncillaryPacket* packet);
+uint8_t decklink_ancillary_packet_get_data_stream_index(IDeckLinkAncillaryPacket* packet);
+
+IDeckLinkTimecode* create_decklink_timecode(uint8_t hours, uint8_t minutes, uint8_t seconds, uint8_t frames, BMDTimecodeFlags flags, BMDTimecodeUserBits userBits);
+IDeckLinkAncillaryPacket* create_decklink_ancillary_packet(uint8_t did, uint8_t sdid, uint32_t lineNumber, uint8_t dataStreamIndex, const uint8_t* data, uint32_t size);
+IDeckLinkVideoFrameAncillaryPackets* create_decklink_video_frame_ancillary_packets();
+HRESULT decklink_video_frame_ancillary_packets_attach_packet(IDeckLinkVideoFrameAncillaryPackets* packets, IDeckLinkAncillaryPacket* packet);
+IDeckLinkVideoFrame* create_decklink_video_frame(long width, long height, long rowBytes, BMDPixelFormat pixelFormat, BMDFrameFlags flags, void* bytes, void* owner);
+void decklink_custom_video_frame_set_timecode(IDeckLinkVideoFrame* frame, BMDTimecodeFormat format, IDeckLinkTimecode* timecode);
+void decklink_custom_video_frame_attach_ancillary_packet(IDeckLinkVideoFrame* frame, IDeckLinkAncillaryPacket* packet);
+void* decklink_custom_video_frame_get_owner(IDeckLinkVideoFrame* frame);
 
 const void* buffer_data(Buffer* str);
 void buffer_release(Buffer* str);
@@ -3,6 +3,8 @@ include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 
 #[macro_use]
 extern crate bitflags;
+#[cfg(feature = "serde")]
+extern crate serde;
 extern crate simple_error;
 
 use std::{
@@ -10,15 +12,43 @@ use std::{
     fmt,
     ops::{Deref, DerefMut},
     os::raw::c_char,
+    sync::{Arc, Mutex, MutexGuard},
 };
 
 use simple_error::SimpleError;
 
+pub mod audio;
+mod conversion;
+mod display_mode;
+mod frame;
+mod frame_pool;
+mod lineup;
+mod time;
+pub mod timecode;
+pub use audio::{AudioFormat, AudioPacket, PcmSample};
+pub use conversion::{ColorMatrix, ColorRange, SoftwareConversion};
+pub use display_mode::{DisplayModeFamily, DisplayModeSpec, ScanType};
+pub use frame::{Frame, FrameAncillaryPacket, FrameTime, FrameTimecode, SharedFrame, VideoBuffer};
+pub use frame_pool::{FrameAllocator, FramePoolStats, OutputFramePool};
+pub use lineup::Lineup;
+pub use time::{Rational, Rounding, TimeValue};
+pub use timecode::{SmpteTimecode, TimecodeRate};
+
+pub mod overlay;
+pub mod pattern;
+pub mod pixel;
+
 #[derive(Debug)]
 pub struct Error {
     pub result: HRESULT,
 }
 
+impl Default for Error {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Error {
     pub fn new() -> Self {
         Error {
@@ -38,7 +68,7 @@ impl std::error::Error for Error {}
 fn void_result(result: HRESULT) -> Result<(), Error> {
     match result {
         0 => Ok(()),
-        result => Err(Error { result: result }),
+        result => Err(Error { result }),
     }
 }
 
@@ -46,7 +76,7 @@ fn void_option_result(result: HRESULT) -> Result<Option<()>, Error> {
     match result {
         0 => Ok(Some(())),
         1 => Ok(None),
-        result => Err(Error { result: result }),
+        result => Err(Error { result }),
     }
 }
 
@@ -95,7 +125,7 @@ unsafe fn query_interface<T>(obj: *mut IUnknown, iid: REFIID) -> Result<*mut T,
         std::mem::transmute::<&mut *mut T, &mut *mut c_void>(&mut iface),
     ) {
         0 => Ok(iface),
-        result => Err(Error { result: result }),
+        result => Err(Error { result }),
     }
 }
 
@@ -112,7 +142,7 @@ impl Device {
                     buffer_release(buf);
                     Ok(ret)
                 }
-                result => Err(Error { result: result }),
+                result => Err(Error { result }),
             }
         }
     }
@@ -151,37 +181,31 @@ impl Device {
         }
     }
 
+    /// Queries the device's input interface. Drivers that predate the vendored headers' interface
+    /// are adapted transparently; methods they don't implement return `E_NOTIMPL`.
     pub fn query_input(&self) -> Result<Input, Error> {
-        match unsafe {
-            query_interface(
-                self.implementation as _,
-                REFIID::new([
-                    0xAF, 0x22, 0x76, 0x2B, 0xDF, 0xAC, 0x48, 0x46, 0xAA, 0x79, 0xFA, 0x88, 0x83,
-                    0x56, 0x09, 0x95,
-                ]),
-            )
-        } {
-            Ok(iface) => Ok(Input {
+        let mut iface: *mut IDeckLinkInput = std::ptr::null_mut();
+        match unsafe { decklink_query_input(self.implementation, &mut iface) } {
+            0 => Ok(Input {
                 implementation: iface,
+                audio_format: Arc::new(Mutex::new(None)),
+                callback_context: None,
             }),
-            Err(e) => Err(e),
+            result => Err(Error { result }),
         }
     }
 
+    /// Queries the device's output interface. Drivers that predate the vendored headers' interface
+    /// are adapted transparently; methods they don't implement return `E_NOTIMPL`.
     pub fn query_output(&self) -> Result<Output, Error> {
-        match unsafe {
-            query_interface(
-                self.implementation as _,
-                REFIID::new([
-                    0xCC, 0x5C, 0x8A, 0x6E, 0x3F, 0x2F, 0x4B, 0x3A, 0x87, 0xEA, 0xFD, 0x78, 0xAF,
-                    0x30, 0x05, 0x64,
-                ]),
-            )
-        } {
-            Ok(iface) => Ok(Output {
+        let mut iface: *mut IDeckLinkOutput = std::ptr::null_mut();
+        match unsafe { decklink_query_output(self.implementation, &mut iface) } {
+            0 => Ok(Output {
                 implementation: iface,
+                audio_format: None,
+                audio_stream_type: None,
             }),
-            Err(e) => Err(e),
+            result => Err(Error { result }),
         }
     }
 }
@@ -205,7 +229,7 @@ impl Attributes {
             let mut v = false;
             match decklink_attributes_get_flag(self.implementation, id, &mut v) {
                 0 => Ok(v),
-                result => Err(Error { result: result }),
+                result => Err(Error { result }),
             }
         }
     }
@@ -293,7 +317,7 @@ impl Attributes {
             let mut v = 0i64;
             match decklink_attributes_get_int(self.implementation, id, &mut v) {
                 0 => Ok(v),
-                result => Err(Error { result: result }),
+                result => Err(Error { result }),
             }
         }
     }
@@ -365,7 +389,7 @@ impl Attributes {
             let mut v = 0f64;
             match decklink_attributes_get_float(self.implementation, id, &mut v) {
                 0 => Ok(v),
-                result => Err(Error { result: result }),
+                result => Err(Error { result }),
             }
         }
     }
@@ -401,7 +425,7 @@ impl Attributes {
                     buffer_release(v);
                     ret
                 }
-                result => Err(Error { result: result }),
+                result => Err(Error { result }),
             }
         }
     }
@@ -462,7 +486,7 @@ bitflags! {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct PixelFormat(pub u32);
 
 impl PixelFormat {
@@ -483,6 +507,112 @@ impl PixelFormat {
         PixelFormat(_BMDPixelFormat_bmdFormat12BitRAWJPEG);
 }
 
+impl PixelFormat {
+    /// The number of bytes in one row of the given width, including the padding the SDK requires.
+    /// Returns `None` for compressed and raw formats, which have no fixed row size.
+    pub fn row_bytes(self, width: i32) -> Option<i32> {
+        match self {
+            PixelFormat::FORMAT_8BIT_YUV => Some(width * 2),
+            PixelFormat::FORMAT_10BIT_YUV => Some((width + 47) / 48 * 128),
+            PixelFormat::FORMAT_8BIT_ARGB | PixelFormat::FORMAT_8BIT_BGRA => Some(width * 4),
+            PixelFormat::FORMAT_10BIT_RGB
+            | PixelFormat::FORMAT_10BIT_RGBX
+            | PixelFormat::FORMAT_10BIT_RGBXLE => Some((width + 63) / 64 * 256),
+            PixelFormat::FORMAT_12BIT_RGB | PixelFormat::FORMAT_12BIT_RGBLE => {
+                Some((width + 7) / 8 * 36)
+            }
+            _ => None,
+        }
+    }
+
+    /// The number of bytes in a frame of the given size. Returns `None` where `row_bytes` does.
+    pub fn frame_size(self, width: i32, height: i32) -> Option<usize> {
+        self.row_bytes(width)
+            .map(|row_bytes| row_bytes as usize * height as usize)
+    }
+
+    pub fn bits_per_component(self) -> Option<u32> {
+        match self {
+            PixelFormat::FORMAT_8BIT_YUV
+            | PixelFormat::FORMAT_8BIT_ARGB
+            | PixelFormat::FORMAT_8BIT_BGRA => Some(8),
+            PixelFormat::FORMAT_10BIT_YUV
+            | PixelFormat::FORMAT_10BIT_RGB
+            | PixelFormat::FORMAT_10BIT_RGBX
+            | PixelFormat::FORMAT_10BIT_RGBXLE => Some(10),
+            PixelFormat::FORMAT_12BIT_RGB
+            | PixelFormat::FORMAT_12BIT_RGBLE
+            | PixelFormat::FORMAT_12BIT_RAW_GRBG
+            | PixelFormat::FORMAT_12BIT_RAW_JPEG => Some(12),
+            _ => None,
+        }
+    }
+
+    pub fn is_rgb(self) -> bool {
+        matches!(
+            self,
+            PixelFormat::FORMAT_8BIT_ARGB
+                | PixelFormat::FORMAT_8BIT_BGRA
+                | PixelFormat::FORMAT_10BIT_RGB
+                | PixelFormat::FORMAT_10BIT_RGBX
+                | PixelFormat::FORMAT_10BIT_RGBXLE
+                | PixelFormat::FORMAT_12BIT_RGB
+                | PixelFormat::FORMAT_12BIT_RGBLE
+        )
+    }
+
+    pub fn is_yuv(self) -> bool {
+        matches!(
+            self,
+            PixelFormat::FORMAT_8BIT_YUV | PixelFormat::FORMAT_10BIT_YUV
+        )
+    }
+
+    /// The four character code the SDK uses for this format, e.g. `b"v210"`. Returns `None` for
+    /// formats whose identifier isn't printable, such as `FORMAT_8BIT_ARGB`.
+    pub fn fourcc(self) -> Option<[u8; 4]> {
+        let bytes = self.0.to_be_bytes();
+        if bytes.iter().all(|b| b.is_ascii_graphic()) {
+            Some(bytes)
+        } else {
+            None
+        }
+    }
+
+    /// Checks that a frame of the given geometry can be stored in this format: the dimensions must
+    /// be positive, even in width for 4:2:2 formats, and `row_bytes` must be at least `row_bytes(width)`.
+    pub fn validate(self, width: i32, height: i32, row_bytes: i32) -> Result<(), SimpleError> {
+        let min_row_bytes = match self.row_bytes(width) {
+            Some(n) => n,
+            None => {
+                return Err(SimpleError::new(format!(
+                    "pixel format {:#x} has no fixed geometry",
+                    self.0
+                )))
+            }
+        };
+        if width <= 0 || height <= 0 {
+            return Err(SimpleError::new(format!(
+                "invalid frame size {}x{}",
+                width, height
+            )));
+        }
+        if self.is_yuv() && width % 2 != 0 {
+            return Err(SimpleError::new(format!(
+                "4:2:2 frames must have an even width, got {}",
+                width
+            )));
+        }
+        if row_bytes < min_row_bytes {
+            return Err(SimpleError::new(format!(
+                "row bytes {} is less than the {} required for a width of {}",
+                row_bytes, min_row_bytes, width
+            )));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct OutputFrameCompletionResult(pub u32);
 
@@ -609,7 +739,7 @@ impl DisplayModeInfo {
                     buffer_release(buf);
                     Ok(ret)
                 }
-                result => Err(Error { result: result }),
+                result => Err(Error { result }),
             }
         }
     }
@@ -673,9 +803,9 @@ impl std::iter::Iterator for DisplayModeIterator {
             {
                 return None;
             }
-            return Some(DisplayModeInfo {
+            Some(DisplayModeInfo {
                 implementation: mode,
-            });
+            })
         }
     }
 }
@@ -692,7 +822,7 @@ impl Status {
             let mut v = false;
             match decklink_status_get_flag(self.implementation, id, &mut v) {
                 0 => Ok(v),
-                result => Err(Error { result: result }),
+                result => Err(Error { result }),
             }
         }
     }
@@ -712,7 +842,7 @@ impl Status {
             let mut v = 0i64;
             match decklink_status_get_int(self.implementation, id, &mut v) {
                 0 => Ok(v),
-                result => Err(Error { result: result }),
+                result => Err(Error { result }),
             }
         }
     }
@@ -802,9 +932,9 @@ impl std::iter::Iterator for Iterator {
             if decklink_iterator_next(self.implementation, &mut device) != 0 || device.is_null() {
                 return None;
             }
-            return Some(Device {
+            Some(Device {
                 implementation: device,
-            });
+            })
         }
     }
 }
@@ -816,9 +946,9 @@ impl Iterator {
             if iterator.is_null() {
                 return Err(SimpleError::new("unable to create decklink iterator. the latest decklink drivers may need to be installed"));
             }
-            return Ok(Iterator {
+            Ok(Iterator {
                 implementation: iterator,
-            });
+            })
         }
     }
 }
@@ -858,14 +988,21 @@ pub trait InputCallback {
     }
 }
 
+// What the SDK's callback object points at: the user's callback and the input's audio format, so
+// that audio packets can be typed.
+struct InputCallbackContext {
+    callback: *mut Box<dyn InputCallback>,
+    audio_format: Arc<Mutex<Option<AudioFormat>>>,
+}
+
 #[no_mangle]
 unsafe extern "C" fn input_callback_video_input_format_changed(
-    implementation: *mut Box<dyn InputCallback>,
+    context: *mut InputCallbackContext,
     notification_events: u32,
     new_display_mode: *mut IDeckLinkDisplayMode,
     detected_signal_flags: u32,
 ) -> HRESULT {
-    let implementation = &mut *implementation;
+    let implementation = &mut *(*context).callback;
     match implementation.video_input_format_changed(
         VideoInputFormatChangedEvents::from_bits_truncate(notification_events),
         {
@@ -883,11 +1020,12 @@ unsafe extern "C" fn input_callback_video_input_format_changed(
 
 #[no_mangle]
 unsafe extern "C" fn input_callback_video_input_frame_arrived(
-    implementation: *mut Box<dyn InputCallback>,
+    context: *mut InputCallbackContext,
     video_frame: *mut IDeckLinkVideoInputFrame,
     audio_packet: *mut IDeckLinkAudioInputPacket,
 ) -> HRESULT {
-    let implementation = &mut *implementation;
+    let context = &mut *context;
+    let implementation = &mut *context.callback;
     match implementation.video_input_frame_arrived(
         match video_frame.is_null() {
             true => None,
@@ -904,6 +1042,10 @@ unsafe extern "C" fn input_callback_video_input_frame_arrived(
                 unknown_add_ref(audio_packet as *mut IUnknown);
                 Some(AudioInputPacket {
                     implementation: audio_packet,
+                    format: *context
+                        .audio_format
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner()),
                 })
             }
         },
@@ -915,6 +1057,8 @@ unsafe extern "C" fn input_callback_video_input_frame_arrived(
 
 pub struct Input {
     implementation: *mut IDeckLinkInput,
+    audio_format: Arc<Mutex<Option<AudioFormat>>>,
+    callback_context: Option<Box<InputCallbackContext>>,
 }
 
 unsafe impl Send for Input {}
@@ -971,12 +1115,22 @@ impl<'a> Drop for InputWithCallback<'a> {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum AudioSampleType {
     I16,
     I32,
 }
 
+impl AudioSampleType {
+    /// The size of one sample in bytes.
+    pub fn bytes(self) -> usize {
+        match self {
+            AudioSampleType::I16 => 2,
+            AudioSampleType::I32 => 4,
+        }
+    }
+}
+
 impl From<AudioSampleType> for BMDAudioSampleType {
     fn from(t: AudioSampleType) -> Self {
         match t {
@@ -986,6 +1140,32 @@ impl From<AudioSampleType> for BMDAudioSampleType {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AudioOutputStreamType {
+    /// Samples are played as they are written, resampled by the driver to track the video clock.
+    Continuous,
+    /// Samples are played as they are written, without resampling.
+    ContinuousDontResample,
+    /// Samples are scheduled at stream times alongside video frames.
+    Timestamped,
+}
+
+impl From<AudioOutputStreamType> for BMDAudioOutputStreamType {
+    fn from(t: AudioOutputStreamType) -> Self {
+        match t {
+            AudioOutputStreamType::Continuous => {
+                _BMDAudioOutputStreamType_bmdAudioOutputStreamContinuous
+            }
+            AudioOutputStreamType::ContinuousDontResample => {
+                _BMDAudioOutputStreamType_bmdAudioOutputStreamContinuousDontResample
+            }
+            AudioOutputStreamType::Timestamped => {
+                _BMDAudioOutputStreamType_bmdAudioOutputStreamTimestamped
+            }
+        }
+    }
+}
+
 impl Input {
     pub fn get_display_mode_iterator(&mut self) -> Result<DisplayModeIterator, Error> {
         unsafe {
@@ -994,7 +1174,7 @@ impl Input {
                 0 => Ok(DisplayModeIterator {
                     implementation: iterator,
                 }),
-                result => Err(Error { result: result }),
+                result => Err(Error { result }),
             }
         }
     }
@@ -1014,6 +1194,8 @@ impl Input {
         }
     }
 
+    /// # Safety
+    ///
     /// The caller must ensure that the given callback lives until the callback is unset. Use with_callback for a safer alternative.
     pub unsafe fn set_callback<'a>(
         &mut self,
@@ -1021,18 +1203,31 @@ impl Input {
     ) -> Result<(), Error> {
         match callback {
             Some(callback) => {
+                let mut context = Box::new(InputCallbackContext {
+                    callback: callback as *mut Box<dyn InputCallback + Send + 'a> as *mut _,
+                    audio_format: self.audio_format.clone(),
+                });
                 let callback = create_decklink_input_callback(
-                    callback as *mut Box<dyn InputCallback + Send + 'a> as *mut c_void,
+                    &mut *context as *mut InputCallbackContext as *mut c_void,
                 );
                 let result =
                     void_result(decklink_input_set_callback(self.implementation, callback));
                 unknown_release(callback as *mut IUnknown);
+                if result.is_ok() {
+                    self.callback_context = Some(context);
+                }
+                result
+            }
+            None => {
+                let result = void_result(decklink_input_set_callback(
+                    self.implementation,
+                    std::ptr::null_mut(),
+                ));
+                if result.is_ok() {
+                    self.callback_context = None;
+                }
                 result
             }
-            None => void_result(decklink_input_set_callback(
-                self.implementation,
-                std::ptr::null_mut(),
-            )),
         }
     }
 
@@ -1064,8 +1259,20 @@ impl Input {
                 sample_rate,
                 sample_type.into(),
                 channel_count,
-            ))
+            ))?;
         }
+        *self.lock_audio_format() = Some(AudioFormat::new(sample_rate, sample_type, channel_count));
+        Ok(())
+    }
+
+    /// The format passed to the last successful `enable_audio_input`, or `None` if audio input is
+    /// disabled.
+    pub fn audio_format(&self) -> Option<AudioFormat> {
+        *self.lock_audio_format()
+    }
+
+    fn lock_audio_format(&self) -> MutexGuard<'_, Option<AudioFormat>> {
+        self.audio_format.lock().unwrap_or_else(|e| e.into_inner())
     }
 
     pub fn enable_video_input(
@@ -1089,7 +1296,9 @@ impl Input {
     }
 
     pub fn disable_audio_input(&mut self) -> Result<(), Error> {
-        unsafe { void_result(decklink_input_disable_audio_input(self.implementation)) }
+        unsafe { void_result(decklink_input_disable_audio_input(self.implementation))? };
+        *self.lock_audio_format() = None;
+        Ok(())
     }
 
     pub fn get_hardware_reference_clock(
@@ -1110,10 +1319,27 @@ impl Input {
         }
         Ok((hardware_time, time_in_frame, ticks_per_frame))
     }
+
+    /// Like `get_hardware_reference_clock`: the hardware time, the time into the current frame
+    /// and the frame duration.
+    pub fn hardware_reference_clock(
+        &mut self,
+        time_scale: i64,
+    ) -> Result<(TimeValue, TimeValue, TimeValue), Error> {
+        let (hardware_time, time_in_frame, ticks_per_frame) =
+            self.get_hardware_reference_clock(time_scale)?;
+        Ok((
+            TimeValue::new(hardware_time, time_scale),
+            TimeValue::new(time_in_frame, time_scale),
+            TimeValue::new(ticks_per_frame, time_scale),
+        ))
+    }
 }
 
 pub struct Output {
     implementation: *mut IDeckLinkOutput,
+    audio_format: Option<AudioFormat>,
+    audio_stream_type: Option<AudioOutputStreamType>,
 }
 
 unsafe impl Send for Output {}
@@ -1225,7 +1451,7 @@ impl Output {
                 0 => Ok(DisplayModeIterator {
                     implementation: iterator,
                 }),
-                result => Err(Error { result: result }),
+                result => Err(Error { result }),
             }
         }
     }
@@ -1252,11 +1478,29 @@ impl Output {
                 0 => Ok(MutableVideoFrame {
                     implementation: frame,
                 }),
-                result => Err(Error { result: result }),
+                result => Err(Error { result }),
             }
         }
     }
 
+    /// Creates a frame sized for the given mode, computing the row bytes from the pixel format.
+    /// Fails with `E_FAIL` if the mode isn't in the catalog or the format has no fixed geometry.
+    pub fn create_video_frame_for_mode(
+        &mut self,
+        display_mode: DisplayMode,
+        pixel_format: PixelFormat,
+    ) -> Result<MutableVideoFrame, Error> {
+        let spec = display_mode.info().ok_or_else(Error::new)?;
+        let row_bytes = pixel_format.row_bytes(spec.width).ok_or_else(Error::new)?;
+        self.create_video_frame(
+            spec.width,
+            spec.height,
+            row_bytes,
+            pixel_format,
+            FrameFlags::DEFAULT,
+        )
+    }
+
     pub fn enable_video_output(
         &mut self,
         display_mode: DisplayMode,
@@ -1275,6 +1519,8 @@ impl Output {
         unsafe { void_result(decklink_output_disable_video_output(self.implementation)) }
     }
 
+    /// # Safety
+    ///
     /// The caller must ensure that the given callback lives until the callback is unset. Use with_callback for a safer alternative.
     pub unsafe fn set_scheduled_frame_completion_callback<'a>(
         &mut self,
@@ -1331,6 +1577,19 @@ impl Output {
         }
     }
 
+    /// Like `start_scheduled_playback`, with the start time in any scale.
+    pub fn start_scheduled_playback_at(
+        &mut self,
+        playback_start_time: TimeValue,
+        playback_speed: f64,
+    ) -> Result<(), Error> {
+        self.start_scheduled_playback(
+            playback_start_time.value,
+            playback_start_time.scale,
+            playback_speed,
+        )
+    }
+
     // TODO: support other types of frames?
     pub fn schedule_video_frame(
         &mut self,
@@ -1338,6 +1597,18 @@ impl Output {
         display_time: i64,
         display_duration: i64,
         time_scale: i64,
+    ) -> Result<(), Error> {
+        self.schedule_video_frame_ref(&frame, display_time, display_duration, time_scale)
+    }
+
+    // The driver takes its own reference, so the caller keeps the frame whether or not this
+    // succeeds.
+    pub(crate) fn schedule_video_frame_ref(
+        &mut self,
+        frame: &MutableVideoFrame,
+        display_time: i64,
+        display_duration: i64,
+        time_scale: i64,
     ) -> Result<(), Error> {
         unsafe {
             void_result(decklink_output_schedule_video_frame(
@@ -1350,6 +1621,212 @@ impl Output {
         }
     }
 
+    /// Like `schedule_video_frame`, with the time and duration in any scales. Fails with `E_FAIL`
+    /// if they have no common scale that fits in 64 bits.
+    pub fn schedule_video_frame_at(
+        &mut self,
+        frame: MutableVideoFrame,
+        display_time: TimeValue,
+        display_duration: TimeValue,
+    ) -> Result<(), Error> {
+        let (time, duration) = display_time
+            .to_common_scale(display_duration)
+            .ok_or_else(Error::new)?;
+        self.schedule_video_frame(frame, time.value, duration.value, time.scale)
+    }
+
+    /// Schedules an owned frame. The frame is kept alive until the driver releases it after
+    /// playback.
+    pub fn schedule_frame(
+        &mut self,
+        frame: Frame,
+        display_time: i64,
+        display_duration: i64,
+        time_scale: i64,
+    ) -> Result<(), Error> {
+        unsafe {
+            let frame = frame.into_implementation();
+            let result = decklink_output_schedule_video_frame(
+                self.implementation,
+                frame,
+                display_time as _,
+                display_duration as _,
+                time_scale as _,
+            );
+            unknown_release(frame as _);
+            void_result(result)
+        }
+    }
+
+    /// Like `schedule_frame`, with the time and duration in any scales.
+    pub fn schedule_frame_at(
+        &mut self,
+        frame: Frame,
+        display_time: TimeValue,
+        display_duration: TimeValue,
+    ) -> Result<(), Error> {
+        let (time, duration) = display_time
+            .to_common_scale(display_duration)
+            .ok_or_else(Error::new)?;
+        self.schedule_frame(frame, time.value, duration.value, time.scale)
+    }
+
+    /// Schedules a frame over a shared buffer without copying it. See `SharedFrame` for how the
+    /// buffer is returned.
+    pub fn schedule_shared_frame(
+        &mut self,
+        frame: SharedFrame,
+        display_time: i64,
+        display_duration: i64,
+        time_scale: i64,
+    ) -> Result<(), Error> {
+        unsafe {
+            let frame = frame.into_implementation();
+            let result = decklink_output_schedule_video_frame(
+                self.implementation,
+                frame,
+                display_time as _,
+                display_duration as _,
+                time_scale as _,
+            );
+            unknown_release(frame as _);
+            void_result(result)
+        }
+    }
+
+    /// Like `schedule_shared_frame`, with the time and duration in any scales.
+    pub fn schedule_shared_frame_at(
+        &mut self,
+        frame: SharedFrame,
+        display_time: TimeValue,
+        display_duration: TimeValue,
+    ) -> Result<(), Error> {
+        let (time, duration) = display_time
+            .to_common_scale(display_duration)
+            .ok_or_else(Error::new)?;
+        self.schedule_shared_frame(frame, time.value, duration.value, time.scale)
+    }
+
+    pub fn enable_audio_output(
+        &mut self,
+        sample_rate: u32,
+        sample_type: AudioSampleType,
+        channel_count: u32,
+        stream_type: AudioOutputStreamType,
+    ) -> Result<(), Error> {
+        unsafe {
+            void_result(decklink_output_enable_audio_output(
+                self.implementation,
+                sample_rate,
+                sample_type.into(),
+                channel_count,
+                stream_type.into(),
+            ))?;
+        }
+        self.audio_format = Some(AudioFormat::new(sample_rate, sample_type, channel_count));
+        self.audio_stream_type = Some(stream_type);
+        Ok(())
+    }
+
+    /// The format passed to the last successful `enable_audio_output`, or `None` if audio output
+    /// is disabled.
+    pub fn audio_format(&self) -> Option<AudioFormat> {
+        self.audio_format
+    }
+
+    /// The stream type passed to the last successful `enable_audio_output`, or `None` if audio
+    /// output is disabled.
+    pub fn audio_stream_type(&self) -> Option<AudioOutputStreamType> {
+        self.audio_stream_type
+    }
+
+    pub fn disable_audio_output(&mut self) -> Result<(), Error> {
+        unsafe { void_result(decklink_output_disable_audio_output(self.implementation))? };
+        self.audio_format = None;
+        self.audio_stream_type = None;
+        Ok(())
+    }
+
+    // Checks interleaved samples against the enabled audio format, returning the sample frame
+    // count.
+    fn audio_sample_frames<S: PcmSample>(&self, samples: &[S]) -> Result<u32, Error> {
+        let format = self.audio_format.ok_or_else(Error::new)?;
+        let channels = format.channel_count as usize;
+        if format.sample_type != S::SAMPLE_TYPE || !samples.len().is_multiple_of(channels) {
+            return Err(Error::new());
+        }
+        Ok((samples.len() / channels) as u32)
+    }
+
+    /// Writes interleaved samples for continuous playback, returning the number of sample frames
+    /// the driver accepted.
+    pub fn write_audio_samples_sync<S: PcmSample>(
+        &mut self,
+        samples: &[S],
+    ) -> Result<usize, Error> {
+        let frames = self.audio_sample_frames(samples)?;
+        let mut written = 0;
+        unsafe {
+            void_result(decklink_output_write_audio_samples_sync(
+                self.implementation,
+                samples.as_ptr() as *mut c_void,
+                frames,
+                &mut written,
+            ))?;
+        }
+        Ok(written as usize)
+    }
+
+    pub fn begin_audio_preroll(&mut self) -> Result<(), Error> {
+        unsafe { void_result(decklink_output_begin_audio_preroll(self.implementation)) }
+    }
+
+    pub fn end_audio_preroll(&mut self) -> Result<(), Error> {
+        unsafe { void_result(decklink_output_end_audio_preroll(self.implementation)) }
+    }
+
+    /// Schedules interleaved samples to play at a stream time, returning the number of sample
+    /// frames the driver accepted.
+    pub fn schedule_audio_samples<S: PcmSample>(
+        &mut self,
+        samples: &[S],
+        stream_time: i64,
+        time_scale: i64,
+    ) -> Result<usize, Error> {
+        let frames = self.audio_sample_frames(samples)?;
+        let mut written = 0;
+        unsafe {
+            void_result(decklink_output_schedule_audio_samples(
+                self.implementation,
+                samples.as_ptr() as *mut c_void,
+                frames,
+                stream_time,
+                time_scale,
+                &mut written,
+            ))?;
+        }
+        Ok(written as usize)
+    }
+
+    pub fn get_buffered_audio_sample_frame_count(&mut self) -> Result<u32, Error> {
+        let mut count = 0;
+        unsafe {
+            void_result(decklink_output_get_buffered_audio_sample_frame_count(
+                self.implementation,
+                &mut count,
+            ))?;
+        }
+        Ok(count)
+    }
+
+    pub fn flush_buffered_audio_samples(&mut self) -> Result<(), Error> {
+        unsafe {
+            void_result(decklink_output_flush_buffered_audio_samples(
+                self.implementation,
+            ))
+        }
+    }
+
     pub fn get_scheduled_stream_time(
         &mut self,
         desired_time_scale: i64,
@@ -1407,6 +1884,15 @@ impl VideoInputFrame {
         Ok((frame_time, frame_duration))
     }
 
+    /// Like `get_stream_time`: the frame's time and duration.
+    pub fn stream_time(&mut self, time_scale: i64) -> Result<(TimeValue, TimeValue), Error> {
+        let (frame_time, frame_duration) = self.get_stream_time(time_scale)?;
+        Ok((
+            TimeValue::new(frame_time, time_scale),
+            TimeValue::new(frame_duration, time_scale),
+        ))
+    }
+
     pub fn get_hardware_reference_timestamp(
         &mut self,
         time_scale: i64,
@@ -1439,17 +1925,30 @@ impl VideoFrame for VideoInputFrame {
     }
 }
 
-impl VideoFrame for &mut VideoInputFrame {
-    unsafe fn implementation(&mut self) -> *mut IDeckLinkVideoFrame {
-        self.implementation as _
-    }
-}
-
 pub struct AudioInputPacket {
     implementation: *mut IDeckLinkAudioInputPacket,
+    format: Option<AudioFormat>,
 }
 
 impl AudioInputPacket {
+    /// The format audio input was enabled with, or `None` if it was enabled without going through
+    /// this crate's `Input`.
+    pub fn format(&self) -> Option<AudioFormat> {
+        self.format
+    }
+
+    /// Returns a typed view of the packet's samples, timed in units of the sample rate.
+    pub fn packet(&mut self) -> Result<AudioPacket<'_>, Error> {
+        let format = self.format.ok_or_else(Error::new)?;
+        let len = self.get_sample_frame_count() * format.bytes_per_frame();
+        let time = self.get_packet_time(format.sample_rate as i64)?;
+        let bytes = match len {
+            0 => &[][..],
+            _ => unsafe { std::slice::from_raw_parts(self.get_bytes_raw()?, len) },
+        };
+        AudioPacket::from_sdk_samples(format, bytes, time).ok_or_else(Error::new)
+    }
+
     pub fn get_sample_frame_count(&mut self) -> usize {
         unsafe { decklink_audio_input_packet_get_sample_frame_count(self.implementation) as _ }
     }
@@ -1476,6 +1975,14 @@ impl AudioInputPacket {
         }
         Ok(ret)
     }
+
+    /// Like `get_packet_time`.
+    pub fn packet_time(&mut self, time_scale: i64) -> Result<TimeValue, Error> {
+        Ok(TimeValue::new(
+            self.get_packet_time(time_scale)?,
+            time_scale,
+        ))
+    }
 }
 
 unsafe impl Send for AudioInputPacket {}
@@ -1488,49 +1995,72 @@ impl Drop for AudioInputPacket {
     }
 }
 
+/// Converts frames with the driver's converter, falling back to `SoftwareConversion` when the
+/// converter is unavailable, rejects the conversion, or either frame isn't an SDK frame.
 pub struct VideoConversion {
     implementation: *mut IDeckLinkVideoConversion,
+    software: SoftwareConversion,
 }
 
 unsafe impl Send for VideoConversion {}
 
 impl Drop for VideoConversion {
     fn drop(&mut self) {
-        unsafe {
-            unknown_release(self.implementation as *mut IUnknown);
+        if !self.implementation.is_null() {
+            unsafe {
+                unknown_release(self.implementation as *mut IUnknown);
+            }
         }
     }
 }
 
 impl VideoConversion {
+    /// Creates the driver's converter if it's available. Always returns `Ok`: when the drivers are
+    /// missing, conversions run in software instead.
     pub fn new() -> Result<VideoConversion, SimpleError> {
-        unsafe {
-            let conversion = create_decklink_video_conversion_instance();
-            if conversion.is_null() {
-                return Err(SimpleError::new("unable to create decklink video conversion. the latest decklink drivers may need to be installed"));
-            }
-            return Ok(VideoConversion {
-                implementation: conversion,
-            });
-        }
+        Ok(VideoConversion {
+            implementation: unsafe { create_decklink_video_conversion_instance() },
+            software: SoftwareConversion::new(),
+        })
+    }
+
+    /// Returns whether conversions run in software because the driver's converter is unavailable.
+    /// With the converter, frames that aren't SDK frames are still converted in software.
+    pub fn is_software(&self) -> bool {
+        self.implementation.is_null()
     }
 
+    /// The conversion used when the driver's converter can't be.
+    pub fn software(&mut self) -> &mut SoftwareConversion {
+        &mut self.software
+    }
+
+    /// Converts with the driver's converter when it's available and both frames are SDK frames,
+    /// returning its error if it fails. Otherwise converts in software.
     pub fn convert_frame<S: VideoFrame, D: VideoFrame>(
         &mut self,
         mut src_frame: S,
         dst_frame: &mut D,
     ) -> Result<(), Error> {
         unsafe {
-            void_result(decklink_video_conversion_convert_frame(
-                self.implementation,
-                src_frame.implementation(),
-                dst_frame.implementation(),
-            ))
+            let (src, dst) = (src_frame.implementation(), dst_frame.implementation());
+            if !self.implementation.is_null() && !src.is_null() && !dst.is_null() {
+                return void_result(decklink_video_conversion_convert_frame(
+                    self.implementation,
+                    src,
+                    dst,
+                ));
+            }
         }
+        self.software.convert_frame(src_frame, dst_frame)
     }
 }
 
 pub trait VideoFrame {
+    /// # Safety
+    ///
+    /// The returned pointer is only valid for as long as the frame is. Frames that aren't backed by
+    /// an SDK object, such as `VideoBuffer`, return null and override the other methods.
     unsafe fn implementation(&mut self) -> *mut IDeckLinkVideoFrame;
 
     fn get_width(&mut self) -> i32 {
@@ -1569,6 +2099,12 @@ pub trait VideoFrame {
         }
     }
 
+    /// Returns the frame's memory for writing. Only frames this crate owns the memory of, such as
+    /// `MutableVideoFrame`, `VideoBuffer` and `Frame`, allow it; others return an error.
+    fn get_bytes_mut(&mut self) -> Result<&mut [u8], Error> {
+        Err(Error::new())
+    }
+
     fn get_timecode(&mut self, format: TimecodeFormat) -> Result<Option<Timecode>, Error> {
         unsafe {
             let mut timecode: *mut IDeckLinkTimecode = std::ptr::null_mut();
@@ -1601,6 +2137,48 @@ pub trait VideoFrame {
     }
 }
 
+impl<T: VideoFrame + ?Sized> VideoFrame for &mut T {
+    unsafe fn implementation(&mut self) -> *mut IDeckLinkVideoFrame {
+        (**self).implementation()
+    }
+
+    fn get_width(&mut self) -> i32 {
+        (**self).get_width()
+    }
+
+    fn get_height(&mut self) -> i32 {
+        (**self).get_height()
+    }
+
+    fn get_row_bytes(&mut self) -> i32 {
+        (**self).get_row_bytes()
+    }
+
+    fn get_pixel_format(&mut self) -> PixelFormat {
+        (**self).get_pixel_format()
+    }
+
+    fn get_flags(&mut self) -> FrameFlags {
+        (**self).get_flags()
+    }
+
+    fn get_bytes(&mut self) -> Result<&[u8], Error> {
+        (**self).get_bytes()
+    }
+
+    fn get_bytes_mut(&mut self) -> Result<&mut [u8], Error> {
+        (**self).get_bytes_mut()
+    }
+
+    fn get_timecode(&mut self, format: TimecodeFormat) -> Result<Option<Timecode>, Error> {
+        (**self).get_timecode(format)
+    }
+
+    fn query_ancillary_packets(&mut self) -> Result<VideoFrameAncillaryPackets, Error> {
+        (**self).query_ancillary_packets()
+    }
+}
+
 pub struct VideoFrameAncillaryPackets {
     implementation: *mut IDeckLinkVideoFrameAncillaryPackets,
 }
@@ -1676,9 +2254,9 @@ impl std::iter::Iterator for AncillaryPacketIterator {
             {
                 return None;
             }
-            return Some(AncillaryPacket {
+            Some(AncillaryPacket {
                 implementation: packet,
-            });
+            })
         }
     }
 }
@@ -1706,6 +2284,14 @@ impl AncillaryPacket {
         unsafe { decklink_ancillary_packet_get_sdid(self.implementation) }
     }
 
+    pub fn get_line_number(&mut self) -> u32 {
+        unsafe { decklink_ancillary_packet_get_line_number(self.implementation) }
+    }
+
+    pub fn get_data_stream_index(&mut self) -> u8 {
+        unsafe { decklink_ancillary_packet_get_data_stream_index(self.implementation) }
+    }
+
     pub fn get_bytes(&mut self) -> Result<&[u8], Error> {
         unsafe {
             let mut buf: *const c_void = std::ptr::null_mut();
@@ -1736,16 +2322,67 @@ impl Drop for MutableVideoFrame {
 }
 
 impl MutableVideoFrame {
+    /// Same as `VideoFrame::get_bytes_mut`, so callers don't need the trait in scope.
     pub fn get_bytes_mut(&mut self) -> Result<&mut [u8], Error> {
+        VideoFrame::get_bytes_mut(self)
+    }
+
+    /// Returns the buffer of a frame scheduled with `Output::schedule_shared_frame`, such as the
+    /// completed frame passed to `scheduled_frame_completed`. Returns `None` for other frames.
+    pub fn shared_bytes(&mut self) -> Option<Arc<[u8]>> {
+        unsafe { frame::owner(self.implementation as _) }
+            .and_then(|owner| owner.downcast_ref::<SharedFrame>())
+            .map(|frame| frame.bytes().clone())
+    }
+
+    /// Sets the timecode in the given format, replacing any existing one.
+    pub fn set_timecode(
+        &mut self,
+        format: TimecodeFormat,
+        timecode: &FrameTimecode,
+    ) -> Result<(), Error> {
         unsafe {
-            let mut buf: *mut c_void = std::ptr::null_mut();
-            void_result(decklink_video_frame_get_bytes(
-                self.implementation(),
-                &mut buf,
-            ))?;
-            Ok(std::slice::from_raw_parts_mut(
-                buf as *mut u8,
-                (self.get_row_bytes() * self.get_height()) as usize,
+            // Frames created by this crate don't implement the SDK's mutable frame interface.
+            if frame::owner(self.implementation as _).is_some() {
+                frame::set_timecode(self.implementation as _, format, timecode, 0);
+                return Ok(());
+            }
+            void_result(decklink_mutable_video_frame_set_timecode_from_components(
+                self.implementation,
+                format.0,
+                timecode.hours,
+                timecode.minutes,
+                timecode.seconds,
+                timecode.frames,
+                timecode.flags.bits(),
+            ))
+        }
+    }
+
+    /// Sets the user bits of the timecode in the given format, which must already be set.
+    pub fn set_timecode_user_bits(
+        &mut self,
+        format: TimecodeFormat,
+        user_bits: u32,
+    ) -> Result<(), Error> {
+        unsafe {
+            if frame::owner(self.implementation as _).is_some() {
+                let timecode = self.get_timecode(format)?.ok_or_else(Error::new)?;
+                let (hours, minutes, seconds, frames) = timecode.get_components()?;
+                let timecode = FrameTimecode {
+                    hours,
+                    minutes,
+                    seconds,
+                    frames,
+                    flags: timecode.get_flags(),
+                };
+                frame::set_timecode(self.implementation as _, format, &timecode, user_bits);
+                return Ok(());
+            }
+            void_result(decklink_mutable_video_frame_set_timecode_user_bits(
+                self.implementation,
+                format.0,
+                user_bits,
             ))
         }
     }
@@ -1755,11 +2392,25 @@ impl VideoFrame for MutableVideoFrame {
     unsafe fn implementation(&mut self) -> *mut IDeckLinkVideoFrame {
         self.implementation as _
     }
-}
 
-impl VideoFrame for &mut MutableVideoFrame {
-    unsafe fn implementation(&mut self) -> *mut IDeckLinkVideoFrame {
-        self.implementation as _
+    /// Fails for frames scheduled with `Output::schedule_shared_frame`, whose buffer other
+    /// `SharedFrame`s may hold at the same time.
+    fn get_bytes_mut(&mut self) -> Result<&mut [u8], Error> {
+        unsafe {
+            if frame::owner(self.implementation as _).is_some_and(|owner| owner.is::<SharedFrame>())
+            {
+                return Err(Error::new());
+            }
+            let mut buf: *mut c_void = std::ptr::null_mut();
+            void_result(decklink_video_frame_get_bytes(
+                self.implementation(),
+                &mut buf,
+            ))?;
+            Ok(std::slice::from_raw_parts_mut(
+                buf as *mut u8,
+                (self.get_row_bytes() * self.get_height()) as usize,
+            ))
+        }
     }
 }
 
@@ -1785,10 +2436,10 @@ impl TimecodeFormat {
 
 bitflags! {
     pub struct TimecodeFlags: u32 {
-        const DEFAULT = _BMDTimecodeFlags_bmdTimecodeFlagDefault as u32;
-        const DROP_FRAME = _BMDTimecodeFlags_bmdTimecodeIsDropFrame as u32;
-        const FIELD_MARK = _BMDTimecodeFlags_bmdTimecodeFieldMark as u32;
-        const COLOR_FRAME = _BMDTimecodeFlags_bmdTimecodeColorFrame as u32;
+        const DEFAULT = _BMDTimecodeFlags_bmdTimecodeFlagDefault;
+        const DROP_FRAME = _BMDTimecodeFlags_bmdTimecodeIsDropFrame;
+        const FIELD_MARK = _BMDTimecodeFlags_bmdTimecodeFieldMark;
+        const COLOR_FRAME = _BMDTimecodeFlags_bmdTimecodeColorFrame;
     }
 }
 
@@ -1839,6 +2490,17 @@ impl Timecode {
             TimecodeFlags::from_bits_truncate(decklink_timecode_get_flags(self.implementation))
         }
     }
+
+    pub fn get_user_bits(&self) -> Result<u32, Error> {
+        unsafe {
+            let mut user_bits = 0;
+            void_result(decklink_timecode_get_user_bits(
+                self.implementation,
+                &mut user_bits,
+            ))?;
+            Ok(user_bits)
+        }
+    }
 }
 
 pub struct APIInformation {
@@ -1904,3 +2566,142 @@ impl Drop for APIInformation {
         }
     }
 }
+
+/// A DeckLink API version, as reported by the driver or defined by the headers this crate was
+/// built against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct APIVersion {
+    pub major: u8,
+    pub minor: u8,
+    pub sub: u8,
+}
+
+impl APIVersion {
+    /// The version of the SDK headers this crate was compiled against.
+    pub const HEADERS: APIVersion = APIVersion::from_int(BLACKMAGIC_DECKLINK_API_VERSION as i64);
+
+    /// Decodes the packed 0xMMmmss00 representation used by the SDK.
+    pub const fn from_int(v: i64) -> APIVersion {
+        APIVersion {
+            major: (v >> 24) as u8,
+            minor: (v >> 16) as u8,
+            sub: (v >> 8) as u8,
+        }
+    }
+}
+
+impl fmt::Display for APIVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.sub)
+    }
+}
+
+#[derive(Debug)]
+pub enum RuntimeError {
+    /// The driver library could not be loaded.
+    NotAvailable,
+    /// The library at the given path could not be loaded.
+    LoadFailed(String),
+    /// The driver was loaded, but querying it failed.
+    Query(Error),
+    /// The installed driver is older than the headers this crate was built against.
+    DriverOlderThanHeaders {
+        driver: APIVersion,
+        headers: APIVersion,
+    },
+}
+
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuntimeError::NotAvailable => write!(
+                f,
+                "the decklink api is not available. the latest decklink drivers may need to be installed"
+            ),
+            RuntimeError::LoadFailed(message) => {
+                write!(f, "unable to load the decklink api: {}", message)
+            }
+            RuntimeError::Query(e) => write!(f, "unable to query the decklink api: {}", e),
+            RuntimeError::DriverOlderThanHeaders { driver, headers } => write!(
+                f,
+                "the installed decklink driver ({}) is older than the sdk headers ({})",
+                driver, headers
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RuntimeError {}
+
+impl From<Error> for RuntimeError {
+    fn from(e: Error) -> Self {
+        RuntimeError::Query(e)
+    }
+}
+
+/// Loading and version checks for the DeckLink driver library.
+///
+/// The library is loaded lazily the first time anything in this crate needs it, so
+/// `load_from` must be called before any other use of the API, and fails otherwise.
+pub struct Runtime;
+
+impl Runtime {
+    /// Returns true if the driver library is installed and could be loaded.
+    pub fn is_available() -> bool {
+        unsafe { decklink_runtime_is_available() }
+    }
+
+    /// Loads the driver library from a non-standard location. This is only supported on Linux, and
+    /// the library must carry the usual libDeckLinkAPI.so soname. Fails if the API has already been
+    /// used, including through `is_available`, since the library it loaded would still be the one
+    /// in use.
+    pub fn load_from<P: AsRef<std::path::Path>>(path: P) -> Result<(), RuntimeError> {
+        use std::os::unix::ffi::OsStrExt;
+        let path = std::ffi::CString::new(path.as_ref().as_os_str().as_bytes())
+            .map_err(|_| RuntimeError::LoadFailed("path contains a nul byte".to_string()))?;
+        unsafe {
+            let mut error: *mut Buffer = std::ptr::null_mut();
+            match decklink_runtime_load_from(path.as_ptr(), &mut error) {
+                0 => Ok(()),
+                _ => {
+                    let message = match error.is_null() {
+                        true => "unknown error".to_string(),
+                        false => {
+                            let message =
+                                std::ffi::CStr::from_ptr(buffer_data(error) as *const c_char)
+                                    .to_string_lossy()
+                                    .into_owned();
+                            buffer_release(error);
+                            message
+                        }
+                    };
+                    Err(RuntimeError::LoadFailed(message))
+                }
+            }
+        }
+    }
+
+    /// Returns the version of the installed driver.
+    pub fn driver_version() -> Result<APIVersion, RuntimeError> {
+        let info = APIInformation::new().ok_or(RuntimeError::NotAvailable)?;
+        Ok(APIVersion::from_int(info.get_version_int()?))
+    }
+
+    /// Returns the version of the SDK headers this crate was built against.
+    pub fn header_version() -> APIVersion {
+        APIVersion::HEADERS
+    }
+
+    /// Compares the installed driver against the headers, returning the driver version if it is at
+    /// least as new.
+    pub fn check_version() -> Result<APIVersion, RuntimeError> {
+        let driver = Self::driver_version()?;
+        if driver < APIVersion::HEADERS {
+            return Err(RuntimeError::DriverOlderThanHeaders {
+                driver,
+                headers: APIVersion::HEADERS,
+            });
+        }
+        Ok(driver)
+    }
+}
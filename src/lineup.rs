@@ -0,0 +1,179 @@
+use std::sync::Arc;
+
+use simple_error::SimpleError;
+
+use audio::tone::ToneGenerator;
+use pattern::Pattern;
+use {
+    AudioOutputStreamType, AudioSampleType, DisplayMode, Error, Output, PixelFormat, SharedFrame,
+    VideoBuffer, VideoFrame,
+};
+
+/// A still picture and a test tone played out together, for lineup and fault-finding.
+///
+/// Each call to `schedule_next` schedules the picture for one frame of the display mode and, if
+/// audio output is enabled, the tone for the same span of stream time, so the two stay aligned
+/// however far ahead playout is scheduled. The picture is shared between frames rather than copied.
+/// Audio output must be enabled in timestamped mode, in the tone generator's sample rate and
+/// channel count; `schedule_next` fails otherwise.
+pub struct Lineup {
+    width: i32,
+    height: i32,
+    row_bytes: i32,
+    pixel_format: PixelFormat,
+    bytes: Arc<[u8]>,
+    tone: ToneGenerator,
+    frame_duration: i64,
+    time_scale: i64,
+    next_frame: i64,
+    dropped_audio_frames: u64,
+}
+
+impl Lineup {
+    /// Creates a lineup showing `video`, which must match the display mode's dimensions.
+    pub fn new(
+        video: VideoBuffer,
+        tone: ToneGenerator,
+        display_mode: DisplayMode,
+    ) -> Result<Lineup, SimpleError> {
+        let spec = display_mode
+            .info()
+            .ok_or_else(|| SimpleError::new("unknown display mode"))?;
+        let mut lineup = Lineup {
+            width: spec.width,
+            height: spec.height,
+            row_bytes: 0,
+            pixel_format: PixelFormat::FORMAT_8BIT_YUV,
+            bytes: Arc::new([]),
+            tone,
+            frame_duration: spec.frame_duration,
+            time_scale: spec.time_scale,
+            next_frame: 0,
+            dropped_audio_frames: 0,
+        };
+        lineup.set_video(video)?;
+        Ok(lineup)
+    }
+
+    /// Creates a lineup showing SMPTE colour bars in `pixel_format`.
+    pub fn smpte_bars(
+        tone: ToneGenerator,
+        display_mode: DisplayMode,
+        pixel_format: PixelFormat,
+    ) -> Result<Lineup, SimpleError> {
+        let video = Pattern::SmpteBars
+            .buffer_for_mode(display_mode, pixel_format, 0)
+            .map_err(|_| SimpleError::new("can't render bars in this mode and pixel format"))?;
+        Lineup::new(video, tone, display_mode)
+    }
+
+    /// Replaces the picture from the next scheduled frame on.
+    pub fn set_video(&mut self, mut video: VideoBuffer) -> Result<(), SimpleError> {
+        if video.get_width() != self.width || video.get_height() != self.height {
+            return Err(SimpleError::new(
+                "picture doesn't match the display mode's dimensions",
+            ));
+        }
+        self.row_bytes = video.get_row_bytes();
+        self.pixel_format = video.get_pixel_format();
+        self.bytes = video.into_bytes().into();
+        Ok(())
+    }
+
+    pub fn tone(&self) -> &ToneGenerator {
+        &self.tone
+    }
+
+    /// The tone generator, e.g. to switch signals on demand.
+    pub fn tone_mut(&mut self) -> &mut ToneGenerator {
+        &mut self.tone
+    }
+
+    /// The index of the next frame `schedule_next` schedules. Frame n is displayed at
+    /// `n * frame_duration` in the display mode's time scale.
+    pub fn next_frame(&self) -> i64 {
+        self.next_frame
+    }
+
+    /// Sets the index of the next frame to schedule.
+    pub fn set_next_frame(&mut self, frame: i64) {
+        self.next_frame = frame;
+    }
+
+    /// Sample frames of tone the driver refused, because its buffer was full. The tone for later
+    /// frames is still scheduled at their own times, so a refusal leaves a gap rather than
+    /// shifting the audio.
+    pub fn dropped_audio_frames(&self) -> u64 {
+        self.dropped_audio_frames
+    }
+
+    /// The stream time, in sample frames, at which frame `n` starts, rounded to the nearest.
+    fn audio_time(&self, n: i64) -> i64 {
+        let sample_rate = self.tone.sample_rate() as i128;
+        let time_scale = self.time_scale as i128;
+        (2 * n as i128 * self.frame_duration as i128 * sample_rate + time_scale)
+            .div_euclid(2 * time_scale) as i64
+    }
+
+    /// The number of sample frames of audio that accompany frame `n`. At fractional frame rates
+    /// this follows the usual cadence, e.g. 1602, 1601, 1602, 1601, 1602 at 29.97 fps and 48 kHz.
+    pub fn audio_frames_for(&self, n: i64) -> usize {
+        (self.audio_time(n + 1) - self.audio_time(n)) as usize
+    }
+
+    /// Schedules the picture and tone for the next frame.
+    ///
+    /// Nothing changes if the picture can't be scheduled. If the tone can't be scheduled after it,
+    /// the frame still counts as scheduled, so calling again moves on to the next frame rather
+    /// than scheduling the picture twice. Tone the driver doesn't accept is counted in
+    /// `dropped_audio_frames`.
+    pub fn schedule_next(&mut self, output: &mut Output) -> Result<(), Error> {
+        let n = self.next_frame;
+        let format = output.audio_format();
+        if let Some(format) = format {
+            if output.audio_stream_type() != Some(AudioOutputStreamType::Timestamped)
+                || format.sample_rate != self.tone.sample_rate()
+                || format.channel_count as usize != self.tone.channels()
+            {
+                return Err(Error::new());
+            }
+        }
+
+        let frame = SharedFrame::new(
+            self.width,
+            self.height,
+            self.row_bytes,
+            self.pixel_format,
+            self.bytes.clone(),
+        )
+        .map_err(|_| Error::new())?;
+        output.schedule_shared_frame(
+            frame,
+            n * self.frame_duration,
+            self.frame_duration,
+            self.time_scale,
+        )?;
+        self.next_frame += 1;
+
+        if let Some(format) = format {
+            let frames = self.audio_frames_for(n);
+            let len = frames * self.tone.channels();
+            let time = self.audio_time(n);
+            let sample_rate = format.sample_rate as i64;
+            let accepted = match format.sample_type {
+                AudioSampleType::I16 => {
+                    let mut samples = vec![0i16; len];
+                    self.tone.fill(&mut samples).map_err(|_| Error::new())?;
+                    output.schedule_audio_samples(&samples, time, sample_rate)?
+                }
+                AudioSampleType::I32 => {
+                    let mut samples = vec![0i32; len];
+                    self.tone.fill(&mut samples).map_err(|_| Error::new())?;
+                    output.schedule_audio_samples(&samples, time, sample_rate)?
+                }
+            };
+            self.dropped_audio_frames += (frames - accepted.min(frames)) as u64;
+        }
+        Ok(())
+    }
+}
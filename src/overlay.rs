@@ -0,0 +1,264 @@
+//! Text drawn into frames in place, for timecode burn-in and labels.
+
+use conversion::{Pixel16, RowCodec};
+use pattern::Color;
+use {Error, TimecodeFormat, VideoFrame};
+
+// Glyphs are 5 dots wide and 9 tall, 7 above the baseline and 2 below for descenders. Characters
+// advance by 6 dots and lines by 10.
+const GLYPH_WIDTH: usize = 5;
+const GLYPH_HEIGHT: usize = 9;
+const ADVANCE: usize = 6;
+const LINE_HEIGHT: usize = 10;
+
+// Printable ASCII from space to `~`. The low five bits of each row are its dots, leftmost in the
+// most significant bit.
+#[rustfmt::skip]
+const FONT: [[u8; GLYPH_HEIGHT]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // space
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04, 0x00, 0x00], // `!`
+    [0x0a, 0x0a, 0x0a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // `"`
+    [0x0a, 0x0a, 0x1f, 0x0a, 0x1f, 0x0a, 0x0a, 0x00, 0x00], // `#`
+    [0x04, 0x0f, 0x14, 0x0e, 0x05, 0x1e, 0x04, 0x00, 0x00], // `$`
+    [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03, 0x00, 0x00], // `%`
+    [0x0c, 0x12, 0x14, 0x08, 0x15, 0x12, 0x0d, 0x00, 0x00], // `&`
+    [0x04, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // `'`
+    [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02, 0x00, 0x00], // `(`
+    [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08, 0x00, 0x00], // `)`
+    [0x00, 0x04, 0x15, 0x0e, 0x15, 0x04, 0x00, 0x00, 0x00], // `*`
+    [0x00, 0x04, 0x04, 0x1f, 0x04, 0x04, 0x00, 0x00, 0x00], // `+`
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x0c, 0x04, 0x08], // `,`
+    [0x00, 0x00, 0x00, 0x1f, 0x00, 0x00, 0x00, 0x00, 0x00], // `-`
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x0c, 0x00, 0x00], // `.`
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00, 0x00, 0x00], // `/`
+    [0x0e, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0e, 0x00, 0x00], // `0`
+    [0x04, 0x0c, 0x04, 0x04, 0x04, 0x04, 0x0e, 0x00, 0x00], // `1`
+    [0x0e, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1f, 0x00, 0x00], // `2`
+    [0x1f, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0e, 0x00, 0x00], // `3`
+    [0x02, 0x06, 0x0a, 0x12, 0x1f, 0x02, 0x02, 0x00, 0x00], // `4`
+    [0x1f, 0x10, 0x1e, 0x01, 0x01, 0x11, 0x0e, 0x00, 0x00], // `5`
+    [0x06, 0x08, 0x10, 0x1e, 0x11, 0x11, 0x0e, 0x00, 0x00], // `6`
+    [0x1f, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08, 0x00, 0x00], // `7`
+    [0x0e, 0x11, 0x11, 0x0e, 0x11, 0x11, 0x0e, 0x00, 0x00], // `8`
+    [0x0e, 0x11, 0x11, 0x0f, 0x01, 0x02, 0x0c, 0x00, 0x00], // `9`
+    [0x00, 0x0c, 0x0c, 0x00, 0x0c, 0x0c, 0x00, 0x00, 0x00], // `:`
+    [0x00, 0x0c, 0x0c, 0x00, 0x0c, 0x0c, 0x04, 0x08, 0x00], // `;`
+    [0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02, 0x00, 0x00], // `<`
+    [0x00, 0x00, 0x1f, 0x00, 0x1f, 0x00, 0x00, 0x00, 0x00], // `=`
+    [0x08, 0x04, 0x02, 0x01, 0x02, 0x04, 0x08, 0x00, 0x00], // `>`
+    [0x0e, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04, 0x00, 0x00], // `?`
+    [0x0e, 0x11, 0x01, 0x0d, 0x15, 0x15, 0x0e, 0x00, 0x00], // `@`
+    [0x0e, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x11, 0x00, 0x00], // `A`
+    [0x1e, 0x11, 0x11, 0x1e, 0x11, 0x11, 0x1e, 0x00, 0x00], // `B`
+    [0x0e, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0e, 0x00, 0x00], // `C`
+    [0x1c, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1c, 0x00, 0x00], // `D`
+    [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x1f, 0x00, 0x00], // `E`
+    [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x10, 0x00, 0x00], // `F`
+    [0x0e, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0f, 0x00, 0x00], // `G`
+    [0x11, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x11, 0x00, 0x00], // `H`
+    [0x0e, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0e, 0x00, 0x00], // `I`
+    [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0c, 0x00, 0x00], // `J`
+    [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11, 0x00, 0x00], // `K`
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1f, 0x00, 0x00], // `L`
+    [0x11, 0x1b, 0x15, 0x15, 0x11, 0x11, 0x11, 0x00, 0x00], // `M`
+    [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11, 0x00, 0x00], // `N`
+    [0x0e, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e, 0x00, 0x00], // `O`
+    [0x1e, 0x11, 0x11, 0x1e, 0x10, 0x10, 0x10, 0x00, 0x00], // `P`
+    [0x0e, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0d, 0x00, 0x00], // `Q`
+    [0x1e, 0x11, 0x11, 0x1e, 0x14, 0x12, 0x11, 0x00, 0x00], // `R`
+    [0x0f, 0x10, 0x10, 0x0e, 0x01, 0x01, 0x1e, 0x00, 0x00], // `S`
+    [0x1f, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x00], // `T`
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e, 0x00, 0x00], // `U`
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x0a, 0x04, 0x00, 0x00], // `V`
+    [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0a, 0x00, 0x00], // `W`
+    [0x11, 0x11, 0x0a, 0x04, 0x0a, 0x11, 0x11, 0x00, 0x00], // `X`
+    [0x11, 0x11, 0x0a, 0x04, 0x04, 0x04, 0x04, 0x00, 0x00], // `Y`
+    [0x1f, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1f, 0x00, 0x00], // `Z`
+    [0x0e, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0e, 0x00, 0x00], // `[`
+    [0x00, 0x10, 0x08, 0x04, 0x02, 0x01, 0x00, 0x00, 0x00], // `\`
+    [0x0e, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0e, 0x00, 0x00], // `]`
+    [0x04, 0x0a, 0x11, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // `^`
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1f, 0x00], // `_`
+    [0x08, 0x04, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ```
+    [0x00, 0x00, 0x0e, 0x01, 0x0f, 0x11, 0x0f, 0x00, 0x00], // `a`
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x1e, 0x00, 0x00], // `b`
+    [0x00, 0x00, 0x0e, 0x10, 0x10, 0x11, 0x0e, 0x00, 0x00], // `c`
+    [0x01, 0x01, 0x0d, 0x13, 0x11, 0x11, 0x0f, 0x00, 0x00], // `d`
+    [0x00, 0x00, 0x0e, 0x11, 0x1f, 0x10, 0x0e, 0x00, 0x00], // `e`
+    [0x06, 0x09, 0x08, 0x1c, 0x08, 0x08, 0x08, 0x00, 0x00], // `f`
+    [0x00, 0x00, 0x0f, 0x11, 0x11, 0x11, 0x0f, 0x01, 0x0e], // `g`
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x11, 0x00, 0x00], // `h`
+    [0x04, 0x00, 0x0c, 0x04, 0x04, 0x04, 0x0e, 0x00, 0x00], // `i`
+    [0x02, 0x00, 0x06, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0c], // `j`
+    [0x10, 0x10, 0x12, 0x14, 0x18, 0x14, 0x12, 0x00, 0x00], // `k`
+    [0x0c, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0e, 0x00, 0x00], // `l`
+    [0x00, 0x00, 0x1a, 0x15, 0x15, 0x11, 0x11, 0x00, 0x00], // `m`
+    [0x00, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11, 0x00, 0x00], // `n`
+    [0x00, 0x00, 0x0e, 0x11, 0x11, 0x11, 0x0e, 0x00, 0x00], // `o`
+    [0x00, 0x00, 0x1e, 0x11, 0x11, 0x11, 0x1e, 0x10, 0x10], // `p`
+    [0x00, 0x00, 0x0f, 0x11, 0x11, 0x11, 0x0f, 0x01, 0x01], // `q`
+    [0x00, 0x00, 0x16, 0x19, 0x10, 0x10, 0x10, 0x00, 0x00], // `r`
+    [0x00, 0x00, 0x0f, 0x10, 0x0e, 0x01, 0x1e, 0x00, 0x00], // `s`
+    [0x08, 0x08, 0x1c, 0x08, 0x08, 0x09, 0x06, 0x00, 0x00], // `t`
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0d, 0x00, 0x00], // `u`
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x0a, 0x04, 0x00, 0x00], // `v`
+    [0x00, 0x00, 0x11, 0x11, 0x15, 0x15, 0x0a, 0x00, 0x00], // `w`
+    [0x00, 0x00, 0x11, 0x0a, 0x04, 0x0a, 0x11, 0x00, 0x00], // `x`
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x11, 0x0f, 0x01, 0x0e], // `y`
+    [0x00, 0x00, 0x1f, 0x02, 0x04, 0x08, 0x1f, 0x00, 0x00], // `z`
+    [0x02, 0x04, 0x04, 0x08, 0x04, 0x04, 0x02, 0x00, 0x00], // `{`
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x00], // `|`
+    [0x08, 0x04, 0x04, 0x02, 0x04, 0x04, 0x08, 0x00, 0x00], // `}`
+    [0x00, 0x00, 0x08, 0x15, 0x02, 0x00, 0x00, 0x00, 0x00], // `~`
+];
+
+fn glyph(c: char) -> &'static [u8; GLYPH_HEIGHT] {
+    match c {
+        ' '..='~' => &FONT[c as usize - 0x20],
+        _ => &FONT['?' as usize - 0x20],
+    }
+}
+
+fn is_lit(c: char, x: usize, y: usize) -> bool {
+    (glyph(c)[y] >> (GLYPH_WIDTH - 1 - x)) & 1 != 0
+}
+
+fn blend(dst: &mut Pixel16, src: &Pixel16, alpha: f32) {
+    for (dst, src) in dst.iter_mut().zip(src.iter()) {
+        let value = *dst as f32 + (*src as f32 - *dst as f32) * alpha;
+        *dst = value.round() as u16;
+    }
+}
+
+/// Draws text into frames in place, in a built-in bitmap font.
+///
+/// Text is drawn over a box that's blended with the picture underneath. It works in every format
+/// `SoftwareConversion` supports, including UYVY, v210 and BGRA, with the same ranges and
+/// matrices. Characters outside printable ASCII are drawn as `?`, and `\n` starts a new line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextOverlay {
+    /// The size of each dot of the font, in pixels.
+    pub scale: u32,
+    pub color: Color,
+    pub background: Color,
+    /// The opacity of the box: 0 draws no box and 1 hides the picture under it.
+    pub background_opacity: f32,
+    /// The space between the text and the edges of the box, in dots.
+    pub padding: u32,
+}
+
+impl TextOverlay {
+    /// White text on a 60% black box.
+    pub fn new(scale: u32) -> TextOverlay {
+        TextOverlay {
+            scale: scale.max(1),
+            color: Color::WHITE,
+            background: Color::BLACK,
+            background_opacity: 0.6,
+            padding: 1,
+        }
+    }
+
+    /// An overlay whose lines are about a twentieth of the frame height.
+    pub fn for_height(height: i32) -> TextOverlay {
+        TextOverlay::new((height / 200).max(1) as u32)
+    }
+
+    /// The width and height of the box drawn around `text`, in pixels.
+    pub fn size(&self, text: &str) -> (i32, i32) {
+        let columns = text.split('\n').map(|line| line.chars().count()).max();
+        let (columns, lines) = (columns.unwrap_or(0), text.split('\n').count());
+        let padding = 2 * self.padding as usize;
+        // The spacing after the last character and below the last line isn't part of the box.
+        let width = (columns * ADVANCE).saturating_sub(1) + padding;
+        let height = lines * LINE_HEIGHT - 1 + padding;
+        let scale = self.scale.max(1) as usize;
+        ((width * scale) as i32, (height * scale) as i32)
+    }
+
+    /// Draws `text` in a box whose top left corner is at (`x`, `y`). The box is clipped to the
+    /// frame. Fails with `E_FAIL` if the frame's format isn't supported.
+    pub fn draw<F: VideoFrame>(
+        &self,
+        frame: &mut F,
+        x: i32,
+        y: i32,
+        text: &str,
+    ) -> Result<(), Error> {
+        let (width, height) = (frame.get_width(), frame.get_height());
+        let row_bytes = frame.get_row_bytes();
+        let format = frame.get_pixel_format();
+        format
+            .validate(width, height, row_bytes)
+            .map_err(|_| Error::new())?;
+        let codec = RowCodec::new(format, width, height)?;
+        let foreground = codec.quantize(self.color.pixel());
+        let background = codec.quantize(self.background.pixel());
+        let alpha = self.background_opacity.clamp(0.0, 1.0);
+
+        let lines: Vec<Vec<char>> = text
+            .split('\n')
+            .map(|line| line.chars().collect())
+            .collect();
+        let (box_width, box_height) = self.size(text);
+        let columns = x.max(0)..(x + box_width).min(width).max(0);
+        let rows = y.max(0)..(y + box_height).min(height).max(0);
+        let (scale, padding) = (self.scale.max(1) as i32, self.padding as i32);
+        // The dot of the text at a pixel, or `None` if it falls outside the text.
+        let dot = |position: i32, origin: i32| {
+            let dot = (position - origin) / scale - padding;
+            if dot >= 0 {
+                Some(dot as usize)
+            } else {
+                None
+            }
+        };
+
+        let row_bytes = row_bytes as usize;
+        let bytes = frame.get_bytes_mut()?;
+        let mut row = vec![[0u16; 4]; width as usize];
+        for py in rows {
+            let dst = &mut bytes[py as usize * row_bytes..(py as usize + 1) * row_bytes];
+            codec.decode(dst, &mut row)?;
+            let glyph_row = dot(py, y).and_then(|dy| {
+                let line = lines.get(dy / LINE_HEIGHT)?;
+                Some((line, dy % LINE_HEIGHT)).filter(|(_, gy)| *gy < GLYPH_HEIGHT)
+            });
+            for px in columns.clone() {
+                let lit = match (glyph_row, dot(px, x)) {
+                    (Some((line, gy)), Some(dx)) => {
+                        let gx = dx % ADVANCE;
+                        gx < GLYPH_WIDTH
+                            && line.get(dx / ADVANCE).is_some_and(|c| is_lit(*c, gx, gy))
+                    }
+                    _ => false,
+                };
+                let pixel = &mut row[px as usize];
+                if lit {
+                    *pixel = foreground;
+                } else {
+                    blend(pixel, &background, alpha);
+                }
+            }
+            codec.encode(&mut row, dst)?;
+        }
+        Ok(())
+    }
+
+    /// Burns the frame's RP188 timecode into it, centered near the bottom of the picture, and
+    /// returns the timecode drawn. Returns `None` and leaves the frame alone if it has no RP188
+    /// timecode.
+    pub fn burn_timecode<F: VideoFrame>(&self, frame: &mut F) -> Result<Option<String>, Error> {
+        let timecode = match frame.get_timecode(TimecodeFormat::FORMAT_RP188_ANY)? {
+            Some(timecode) => timecode.get_string()?,
+            None => return Ok(None),
+        };
+        let (width, height) = (frame.get_width(), frame.get_height());
+        let (box_width, box_height) = self.size(&timecode);
+        // Keep the box inside the title-safe area, on an even pixel so 4:2:2 chroma lines up.
+        let x = (width - box_width) / 4 * 2;
+        let y = height - height / 10 - box_height;
+        self.draw(frame, x, y, &timecode)?;
+        Ok(Some(timecode))
+    }
+}
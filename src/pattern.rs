@@ -0,0 +1,286 @@
+//! Test patterns rendered straight into a frame's pixel format.
+//!
+//! Patterns are described in normalized R'G'B' and encoded with the same ranges and matrices as
+//! `SoftwareConversion`: legal range for the YUV and 10-bit RGB formats, full range for the 8 and
+//! 12-bit RGB formats, and Rec.601 or Rec.709 by frame height. Vertical edges fall on even pixels
+//! so they don't smear 4:2:2 chroma.
+
+use conversion::{PixelF, RowCodec};
+use {DisplayMode, Error, PixelFormat, VideoBuffer, VideoFrame};
+
+/// A nonlinear R'G'B' color with components nominally in 0..1. Components outside that range,
+/// such as the -2% step of a PLUGE, are clipped to what the destination range can carry.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::gray(0.0);
+    pub const WHITE: Color = Color::gray(1.0);
+
+    pub const fn new(r: f32, g: f32, b: f32) -> Color {
+        Color { r, g, b }
+    }
+
+    pub const fn gray(level: f32) -> Color {
+        Color::new(level, level, level)
+    }
+
+    pub(crate) fn pixel(self) -> PixelF {
+        [self.r, self.g, self.b, 1.0]
+    }
+}
+
+/// A test pattern.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Pattern {
+    /// SMPTE RP 219 color bars: 75% bars over 40% gray side panels, a 100% cyan, 75% white and
+    /// 100% blue row, a luma ramp between 100% yellow and red, and a bottom row with 100% white
+    /// and -2%, +2% and +4% PLUGE steps.
+    SmpteBars,
+    /// EBU 100/0/75/0 bars: 100% white followed by 75% yellow, cyan, green, magenta, red, blue
+    /// and black.
+    EbuBars75,
+    /// EBU 100/0/100/0 bars.
+    EbuBars100,
+    /// A picture line-up signal after ITU-R BT.814: black with -2%, +2% and +4% stripes and a 100%
+    /// white patch across the middle half of the picture.
+    Pluge,
+    /// A horizontal ramp from black to white.
+    LumaRamp,
+    /// A circular zone plate whose frequency rises from zero at the center to the Nyquist limit
+    /// at the left and right edges.
+    ZonePlate,
+    /// A white square on black that moves to the right by a fixed step each frame and wraps
+    /// around, for checking motion and measuring latency.
+    MovingBox,
+    /// A single color.
+    Solid(Color),
+}
+
+// A horizontal span of a row, ending at a fraction of the width.
+enum Fill {
+    Flat(Color),
+    Ramp(f32, f32),
+}
+
+// The bars in order: white, yellow, cyan, green, magenta, red, blue and black.
+const BAR_COLORS: [(f32, f32, f32); 8] = [
+    (1.0, 1.0, 1.0),
+    (1.0, 1.0, 0.0),
+    (0.0, 1.0, 1.0),
+    (0.0, 1.0, 0.0),
+    (1.0, 0.0, 1.0),
+    (1.0, 0.0, 0.0),
+    (0.0, 0.0, 1.0),
+    (0.0, 0.0, 0.0),
+];
+
+fn bar(index: usize, level: f32) -> Color {
+    let (r, g, b) = BAR_COLORS[index];
+    Color::new(r * level, g * level, b * level)
+}
+
+// The pixel where a fraction of the width starts, rounded to an even pixel.
+fn edge(width: usize, fraction: f64) -> usize {
+    (((fraction * width as f64 / 2.0).round() as usize) * 2).min(width)
+}
+
+// The SMPTE RP 219 row at `y`, as widths in units of the picture width. Side panels are 1/8 of
+// the width and the seven bars share the rest.
+fn smpte_row(y: usize, height: usize) -> Vec<(f64, Fill)> {
+    let (d, c) = (1.0 / 8.0, 3.0 / 28.0);
+    let y = y as f64 / height as f64;
+    if y < 7.0 / 12.0 {
+        let mut row = vec![(d, Fill::Flat(Color::gray(0.4)))];
+        row.extend((0..7).map(|i| (c, Fill::Flat(bar(i, 0.75)))));
+        row.push((d, Fill::Flat(Color::gray(0.4))));
+        row
+    } else if y < 8.0 / 12.0 {
+        vec![
+            (d, Fill::Flat(Color::new(0.0, 1.0, 1.0))),
+            (7.0 * c, Fill::Flat(Color::gray(0.75))),
+            (d, Fill::Flat(Color::new(0.0, 0.0, 1.0))),
+        ]
+    } else if y < 9.0 / 12.0 {
+        vec![
+            (d, Fill::Flat(Color::new(1.0, 1.0, 0.0))),
+            (c, Fill::Flat(Color::BLACK)),
+            (5.0 * c, Fill::Ramp(0.0, 1.0)),
+            (c, Fill::Flat(Color::WHITE)),
+            (d, Fill::Flat(Color::new(1.0, 0.0, 0.0))),
+        ]
+    } else {
+        vec![
+            (d, Fill::Flat(Color::gray(0.15))),
+            (1.5 * c, Fill::Flat(Color::BLACK)),
+            (2.0 * c, Fill::Flat(Color::WHITE)),
+            (5.0 / 6.0 * c, Fill::Flat(Color::BLACK)),
+            (c / 3.0, Fill::Flat(Color::gray(-0.02))),
+            (c / 3.0, Fill::Flat(Color::BLACK)),
+            (c / 3.0, Fill::Flat(Color::gray(0.02))),
+            (c / 3.0, Fill::Flat(Color::BLACK)),
+            (c / 3.0, Fill::Flat(Color::gray(0.04))),
+            (c, Fill::Flat(Color::BLACK)),
+            (d, Fill::Flat(Color::gray(0.15))),
+        ]
+    }
+}
+
+fn ebu_row(level: f32) -> Vec<(f64, Fill)> {
+    (0..8)
+        .map(|i| {
+            let color = if i == 0 { Color::WHITE } else { bar(i, level) };
+            (1.0 / 8.0, Fill::Flat(color))
+        })
+        .collect()
+}
+
+fn pluge_row(y: usize, height: usize) -> Vec<(f64, Fill)> {
+    if y < height / 4 || y >= height * 3 / 4 {
+        return vec![(1.0, Fill::Flat(Color::BLACK))];
+    }
+    let step = 1.0 / 12.0;
+    vec![
+        (3.0 * step, Fill::Flat(Color::BLACK)),
+        (step, Fill::Flat(Color::gray(-0.02))),
+        (step, Fill::Flat(Color::BLACK)),
+        (step, Fill::Flat(Color::gray(0.02))),
+        (step, Fill::Flat(Color::BLACK)),
+        (step, Fill::Flat(Color::gray(0.04))),
+        (step, Fill::Flat(Color::BLACK)),
+        (step, Fill::Flat(Color::WHITE)),
+        (2.0 * step, Fill::Flat(Color::BLACK)),
+    ]
+}
+
+fn fill_spans(spans: &[(f64, Fill)], row: &mut [PixelF]) {
+    let width = row.len();
+    let (mut start, mut end_fraction) = (0, 0.0);
+    for (span, fill) in spans {
+        end_fraction += span;
+        let end = edge(width, end_fraction);
+        let len = end.saturating_sub(start);
+        for (i, px) in row[start..end.max(start)].iter_mut().enumerate() {
+            *px = match *fill {
+                Fill::Flat(color) => color.pixel(),
+                Fill::Ramp(from, to) => {
+                    let t = if len > 1 {
+                        i as f32 / (len - 1) as f32
+                    } else {
+                        0.0
+                    };
+                    Color::gray(from + (to - from) * t).pixel()
+                }
+            };
+        }
+        start = end.max(start);
+    }
+    for px in &mut row[start..] {
+        *px = Color::BLACK.pixel();
+    }
+}
+
+impl Pattern {
+    /// Where the `MovingBox` square is in a frame of this size, as the (left, top) of the square
+    /// and its side, in pixels. Captures can be matched back to the frame they were rendered from
+    /// to measure latency.
+    pub fn moving_box(width: i32, height: i32, frame_index: u64) -> (usize, usize, usize) {
+        let (width, height) = (width.max(0) as usize, height.max(0) as usize);
+        let side = edge(height, 1.0 / 8.0).max(2).min(width).min(height);
+        let step = edge(width, 1.0 / 96.0).max(2) as u64;
+        let positions = (width - side) as u64 + 1;
+        let left = (frame_index % positions * step % positions) as usize / 2 * 2;
+        (left, (height - side) / 2, side)
+    }
+
+    fn fill_row(&self, frame_index: u64, height: usize, y: usize, row: &mut [PixelF]) {
+        let width = row.len();
+        match *self {
+            Pattern::SmpteBars => fill_spans(&smpte_row(y, height), row),
+            Pattern::EbuBars75 => fill_spans(&ebu_row(0.75), row),
+            Pattern::EbuBars100 => fill_spans(&ebu_row(1.0), row),
+            Pattern::Pluge => fill_spans(&pluge_row(y, height), row),
+            Pattern::LumaRamp => fill_spans(&[(1.0, Fill::Ramp(0.0, 1.0))], row),
+            Pattern::ZonePlate => {
+                let scale = std::f64::consts::PI / width as f64;
+                let dy = y as f64 + 0.5 - height as f64 / 2.0;
+                for (x, px) in row.iter_mut().enumerate() {
+                    let dx = x as f64 + 0.5 - width as f64 / 2.0;
+                    let level = 0.5 + 0.5 * (scale * (dx * dx + dy * dy)).cos();
+                    *px = Color::gray(level as f32).pixel();
+                }
+            }
+            Pattern::MovingBox => {
+                let (left, top, side) =
+                    Pattern::moving_box(width as i32, height as i32, frame_index);
+                for (x, px) in row.iter_mut().enumerate() {
+                    let inside = y >= top && y < top + side && x >= left && x < left + side;
+                    *px = if inside { Color::WHITE } else { Color::BLACK }.pixel();
+                }
+            }
+            Pattern::Solid(color) => {
+                for px in row.iter_mut() {
+                    *px = color.pixel();
+                }
+            }
+        }
+    }
+
+    /// Renders frame `frame_index` of the pattern into `frame`, in its pixel format. Only
+    /// `MovingBox` depends on the frame index. Fails with `E_FAIL` if the format isn't one
+    /// `SoftwareConversion` supports.
+    pub fn render<F: VideoFrame>(&self, frame_index: u64, frame: &mut F) -> Result<(), Error> {
+        let (width, height) = (frame.get_width(), frame.get_height());
+        let row_bytes = frame.get_row_bytes();
+        let format = frame.get_pixel_format();
+        format
+            .validate(width, height, row_bytes)
+            .map_err(|_| Error::new())?;
+        let mut codec = RowCodec::new(format, width, height)?;
+        let (width, height, row_bytes) = (width as usize, height as usize, row_bytes as usize);
+        let bytes = frame.get_bytes_mut()?;
+
+        // Most patterns repeat rows, so a row that matches the one above is copied rather than
+        // encoded again.
+        let (mut row, mut previous) = (vec![[0f32; 4]; width], vec![[0f32; 4]; width]);
+        for y in 0..height {
+            let start = y * row_bytes;
+            self.fill_row(frame_index, height, y, &mut row);
+            if y > 0 && row == previous {
+                bytes.copy_within(start - row_bytes..start, start);
+                continue;
+            }
+            previous.copy_from_slice(&row);
+            codec.encode_rgb(&mut row, &mut bytes[start..start + row_bytes])?;
+        }
+        Ok(())
+    }
+
+    /// Renders the pattern into a new buffer.
+    pub fn buffer(
+        &self,
+        width: i32,
+        height: i32,
+        pixel_format: PixelFormat,
+        frame_index: u64,
+    ) -> Result<VideoBuffer, Error> {
+        let mut buffer = VideoBuffer::new(width, height, pixel_format).map_err(|_| Error::new())?;
+        self.render(frame_index, &mut buffer)?;
+        Ok(buffer)
+    }
+
+    /// Renders the pattern into a new buffer the size of a display mode.
+    pub fn buffer_for_mode(
+        &self,
+        display_mode: DisplayMode,
+        pixel_format: PixelFormat,
+        frame_index: u64,
+    ) -> Result<VideoBuffer, Error> {
+        let spec = display_mode.info().ok_or_else(Error::new)?;
+        self.buffer(spec.width, spec.height, pixel_format, frame_index)
+    }
+}
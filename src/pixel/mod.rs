@@ -0,0 +1,519 @@
+//! Conversions between the SDK's packed pixel formats and the planar layouts most processing code
+//! expects.
+//!
+//! Packed buffers are described by their row bytes, so the padding `VideoFrame::get_bytes` exposes
+//! at the end of each row is skipped on read and zeroed on write. Planar buffers are tightly packed:
+//! a 4:2:2 luma plane holds `width * height` samples and each chroma plane `width / 2 * height`.
+//! I420 and NV12 are single buffers with the chroma planes following the luma plane. RGB16 and
+//! RGBA16 are interleaved. 10 and 12-bit samples are stored in 16-bit containers MSB-aligned, as in
+//! P210 and yuv422p16, and truncated back when packing.
+//!
+//! The free functions use the fastest kernels the CPU supports. `Kernels` selects a specific
+//! instruction set; every instruction set produces bit-identical output. The NEON kernels are only
+//! built with the `neon` feature.
+
+use simple_error::SimpleError;
+
+use super::PixelFormat;
+
+#[cfg(all(feature = "neon", target_arch = "aarch64", target_endian = "little"))]
+mod neon;
+mod rgb;
+mod scalar;
+mod v210;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86;
+
+pub use self::rgb::{rgb16_to_rgb, rgb_to_rgb16, rgb_to_rgba16, rgba16_to_rgb};
+pub use self::v210::{v210_to_yuv422p16, yuv422p16_to_v210};
+
+fn check_packed(
+    format: PixelFormat,
+    len: usize,
+    width: usize,
+    height: usize,
+    row_bytes: usize,
+) -> Result<(), SimpleError> {
+    format.validate(width as i32, height as i32, row_bytes as i32)?;
+    if len < row_bytes * height {
+        return Err(SimpleError::new(format!(
+            "packed buffer holds {} bytes, {} are required",
+            len,
+            row_bytes * height
+        )));
+    }
+    Ok(())
+}
+
+fn check_plane(name: &str, len: usize, required: usize) -> Result<(), SimpleError> {
+    if len < required {
+        return Err(SimpleError::new(format!(
+            "{} plane holds {} samples, {} are required",
+            name, len, required
+        )));
+    }
+    Ok(())
+}
+
+fn check_420(height: usize) -> Result<(), SimpleError> {
+    if !height.is_multiple_of(2) {
+        return Err(SimpleError::new(format!(
+            "4:2:0 frames must have an even height, got {}",
+            height
+        )));
+    }
+    Ok(())
+}
+
+/// An instruction set the conversion kernels are implemented in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Isa {
+    Scalar,
+    Sse41,
+    Avx2,
+    Neon,
+}
+
+impl Isa {
+    /// Every instruction set, from slowest to fastest.
+    pub const ALL: [Isa; 4] = [Isa::Scalar, Isa::Sse41, Isa::Avx2, Isa::Neon];
+
+    /// Returns whether this build has kernels for the instruction set and the CPU supports it.
+    pub fn is_supported(self) -> bool {
+        match self {
+            Isa::Scalar => true,
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Isa::Sse41 => is_x86_feature_detected!("sse4.1"),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Isa::Avx2 => is_x86_feature_detected!("avx2"),
+            #[cfg(all(feature = "neon", target_arch = "aarch64", target_endian = "little"))]
+            Isa::Neon => std::arch::is_aarch64_feature_detected!("neon"),
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+
+    /// The fastest supported instruction set.
+    pub fn detect() -> Isa {
+        Isa::ALL
+            .iter()
+            .rev()
+            .cloned()
+            .find(|isa| isa.is_supported())
+            .unwrap_or(Isa::Scalar)
+    }
+}
+
+type Rows420 = fn(&[u8], &[u8], &mut [u8], &mut [u8], &mut [u8], &mut [u8]);
+type RowsNv12 = fn(&[u8], &[u8], &mut [u8], &mut [u8], &mut [u8]);
+type RowFrom420 = fn(&[u8], &[u8], &[u8], &mut [u8]);
+type RowFromNv12 = fn(&[u8], &[u8], &mut [u8]);
+type RowFromV210 = fn(&[u8], &mut [u16], &mut [u16]);
+type RowToV210 = fn(&[u16], &[u16], &mut [u8]);
+
+struct RowKernels {
+    uyvy_to_i420: Rows420,
+    uyvy_to_nv12: RowsNv12,
+    i420_to_uyvy: RowFrom420,
+    nv12_to_uyvy: RowFromNv12,
+    bgra_to_uyvy: fn(&[u8], &mut [u8]),
+    v210_to_p210: RowFromV210,
+    p210_to_v210: RowToV210,
+}
+
+static SCALAR: RowKernels = RowKernels {
+    uyvy_to_i420: scalar::uyvy_to_i420_rows,
+    uyvy_to_nv12: scalar::uyvy_to_nv12_rows,
+    i420_to_uyvy: scalar::i420_to_uyvy_row,
+    nv12_to_uyvy: scalar::nv12_to_uyvy_row,
+    bgra_to_uyvy: scalar::bgra_to_uyvy_row,
+    v210_to_p210: scalar::v210_to_p210_row,
+    p210_to_v210: scalar::p210_to_v210_row,
+};
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+static SSE41: RowKernels = RowKernels {
+    uyvy_to_i420: x86::sse41::uyvy_to_i420_rows,
+    uyvy_to_nv12: x86::sse41::uyvy_to_nv12_rows,
+    i420_to_uyvy: x86::sse41::i420_to_uyvy_row,
+    nv12_to_uyvy: x86::sse41::nv12_to_uyvy_row,
+    bgra_to_uyvy: x86::sse41::bgra_to_uyvy_row,
+    v210_to_p210: x86::sse41::v210_to_p210_row,
+    p210_to_v210: x86::sse41::p210_to_v210_row,
+};
+
+// The v210 shuffles don't gain anything from 256-bit registers, so AVX2 reuses the SSE4.1 kernels.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+static AVX2: RowKernels = RowKernels {
+    uyvy_to_i420: x86::avx2::uyvy_to_i420_rows,
+    uyvy_to_nv12: x86::avx2::uyvy_to_nv12_rows,
+    i420_to_uyvy: x86::avx2::i420_to_uyvy_row,
+    nv12_to_uyvy: x86::avx2::nv12_to_uyvy_row,
+    bgra_to_uyvy: x86::avx2::bgra_to_uyvy_row,
+    v210_to_p210: x86::sse41::v210_to_p210_row,
+    p210_to_v210: x86::sse41::p210_to_v210_row,
+};
+
+#[cfg(all(feature = "neon", target_arch = "aarch64", target_endian = "little"))]
+static NEON: RowKernels = RowKernels {
+    uyvy_to_i420: neon::uyvy_to_i420_rows,
+    uyvy_to_nv12: neon::uyvy_to_nv12_rows,
+    i420_to_uyvy: neon::i420_to_uyvy_row,
+    nv12_to_uyvy: neon::nv12_to_uyvy_row,
+    bgra_to_uyvy: neon::bgra_to_uyvy_row,
+    v210_to_p210: neon::v210_to_p210_row,
+    p210_to_v210: neon::p210_to_v210_row,
+};
+
+/// The conversion kernels for one instruction set.
+#[derive(Clone, Copy)]
+pub struct Kernels {
+    isa: Isa,
+    rows: &'static RowKernels,
+}
+
+impl Kernels {
+    /// Returns the kernels for the given instruction set, or `None` if it isn't supported.
+    pub fn new(isa: Isa) -> Option<Kernels> {
+        if !isa.is_supported() {
+            return None;
+        }
+        let rows = match isa {
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Isa::Sse41 => &SSE41,
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Isa::Avx2 => &AVX2,
+            #[cfg(all(feature = "neon", target_arch = "aarch64", target_endian = "little"))]
+            Isa::Neon => &NEON,
+            _ => &SCALAR,
+        };
+        Some(Kernels { isa, rows })
+    }
+
+    /// Returns the kernels for the fastest supported instruction set.
+    pub fn detect() -> Kernels {
+        Kernels::new(Isa::detect()).unwrap_or(Kernels {
+            isa: Isa::Scalar,
+            rows: &SCALAR,
+        })
+    }
+
+    pub fn isa(&self) -> Isa {
+        self.isa
+    }
+
+    /// Converts UYVY to I420, averaging the chroma of each pair of rows.
+    pub fn uyvy_to_i420(
+        &self,
+        src: &[u8],
+        row_bytes: usize,
+        width: usize,
+        height: usize,
+        dst: &mut [u8],
+    ) -> Result<(), SimpleError> {
+        check_packed(
+            PixelFormat::FORMAT_8BIT_YUV,
+            src.len(),
+            width,
+            height,
+            row_bytes,
+        )?;
+        check_420(height)?;
+        let chroma = width / 2 * height / 2;
+        check_plane("i420", dst.len(), width * height + chroma * 2)?;
+
+        let (y, rest) = dst.split_at_mut(width * height);
+        let (u, v) = rest.split_at_mut(chroma);
+        for (row, ((y, u), v)) in y
+            .chunks_exact_mut(width * 2)
+            .zip(u.chunks_exact_mut(width / 2))
+            .zip(v.chunks_exact_mut(width / 2))
+            .enumerate()
+        {
+            let (y0, y1) = y.split_at_mut(width);
+            let src0 = &src[row * 2 * row_bytes..][..width * 2];
+            let src1 = &src[(row * 2 + 1) * row_bytes..][..width * 2];
+            (self.rows.uyvy_to_i420)(src0, src1, y0, y1, u, v);
+        }
+        Ok(())
+    }
+
+    /// Converts UYVY to NV12, averaging the chroma of each pair of rows.
+    pub fn uyvy_to_nv12(
+        &self,
+        src: &[u8],
+        row_bytes: usize,
+        width: usize,
+        height: usize,
+        dst: &mut [u8],
+    ) -> Result<(), SimpleError> {
+        check_packed(
+            PixelFormat::FORMAT_8BIT_YUV,
+            src.len(),
+            width,
+            height,
+            row_bytes,
+        )?;
+        check_420(height)?;
+        check_plane("nv12", dst.len(), width * height + width * height / 2)?;
+
+        let (y, uv) = dst.split_at_mut(width * height);
+        for (row, (y, uv)) in y
+            .chunks_exact_mut(width * 2)
+            .zip(uv.chunks_exact_mut(width))
+            .enumerate()
+        {
+            let (y0, y1) = y.split_at_mut(width);
+            let src0 = &src[row * 2 * row_bytes..][..width * 2];
+            let src1 = &src[(row * 2 + 1) * row_bytes..][..width * 2];
+            (self.rows.uyvy_to_nv12)(src0, src1, y0, y1, uv);
+        }
+        Ok(())
+    }
+
+    /// Converts I420 to UYVY, repeating each chroma row.
+    pub fn i420_to_uyvy(
+        &self,
+        src: &[u8],
+        width: usize,
+        height: usize,
+        dst: &mut [u8],
+        row_bytes: usize,
+    ) -> Result<(), SimpleError> {
+        check_packed(
+            PixelFormat::FORMAT_8BIT_YUV,
+            dst.len(),
+            width,
+            height,
+            row_bytes,
+        )?;
+        check_420(height)?;
+        let chroma = width / 2 * height / 2;
+        check_plane("i420", src.len(), width * height + chroma * 2)?;
+
+        let (y, rest) = src.split_at(width * height);
+        let (u, v) = rest.split_at(chroma);
+        for (row, dst) in dst.chunks_mut(row_bytes).take(height).enumerate() {
+            let c = row / 2 * width / 2;
+            let (dst, padding) = dst.split_at_mut(width * 2);
+            (self.rows.i420_to_uyvy)(
+                &y[row * width..][..width],
+                &u[c..][..width / 2],
+                &v[c..][..width / 2],
+                dst,
+            );
+            zero(padding);
+        }
+        Ok(())
+    }
+
+    /// Converts NV12 to UYVY, repeating each chroma row.
+    pub fn nv12_to_uyvy(
+        &self,
+        src: &[u8],
+        width: usize,
+        height: usize,
+        dst: &mut [u8],
+        row_bytes: usize,
+    ) -> Result<(), SimpleError> {
+        check_packed(
+            PixelFormat::FORMAT_8BIT_YUV,
+            dst.len(),
+            width,
+            height,
+            row_bytes,
+        )?;
+        check_420(height)?;
+        check_plane("nv12", src.len(), width * height + width * height / 2)?;
+
+        let (y, uv) = src.split_at(width * height);
+        for (row, dst) in dst.chunks_mut(row_bytes).take(height).enumerate() {
+            let (dst, padding) = dst.split_at_mut(width * 2);
+            (self.rows.nv12_to_uyvy)(
+                &y[row * width..][..width],
+                &uv[row / 2 * width..][..width],
+                dst,
+            );
+            zero(padding);
+        }
+        Ok(())
+    }
+
+    /// Converts full-range BGRA to legal-range UYVY with the Rec.709 matrix. Alpha is ignored and
+    /// chroma is taken from the average of each pixel pair.
+    pub fn bgra_to_uyvy(
+        &self,
+        src: &[u8],
+        src_row_bytes: usize,
+        width: usize,
+        height: usize,
+        dst: &mut [u8],
+        dst_row_bytes: usize,
+    ) -> Result<(), SimpleError> {
+        check_packed(
+            PixelFormat::FORMAT_8BIT_BGRA,
+            src.len(),
+            width,
+            height,
+            src_row_bytes,
+        )?;
+        check_packed(
+            PixelFormat::FORMAT_8BIT_YUV,
+            dst.len(),
+            width,
+            height,
+            dst_row_bytes,
+        )?;
+
+        for (row, dst) in dst.chunks_mut(dst_row_bytes).take(height).enumerate() {
+            let (dst, padding) = dst.split_at_mut(width * 2);
+            (self.rows.bgra_to_uyvy)(&src[row * src_row_bytes..][..width * 4], dst);
+            zero(padding);
+        }
+        Ok(())
+    }
+
+    /// Unpacks a v210 frame into P210: a luma plane followed by an interleaved Cb Cr plane, each of
+    /// `width * height` samples.
+    pub fn v210_to_p210(
+        &self,
+        src: &[u8],
+        row_bytes: usize,
+        width: usize,
+        height: usize,
+        y: &mut [u16],
+        uv: &mut [u16],
+    ) -> Result<(), SimpleError> {
+        check_packed(
+            PixelFormat::FORMAT_10BIT_YUV,
+            src.len(),
+            width,
+            height,
+            row_bytes,
+        )?;
+        check_plane("y", y.len(), width * height)?;
+        check_plane("uv", uv.len(), width * height)?;
+
+        for (row, (y, uv)) in y
+            .chunks_exact_mut(width)
+            .zip(uv.chunks_exact_mut(width))
+            .take(height)
+            .enumerate()
+        {
+            (self.rows.v210_to_p210)(&src[row * row_bytes..][..row_bytes], y, uv);
+        }
+        Ok(())
+    }
+
+    /// Packs P210 into a v210 frame.
+    pub fn p210_to_v210(
+        &self,
+        y: &[u16],
+        uv: &[u16],
+        width: usize,
+        height: usize,
+        dst: &mut [u8],
+        row_bytes: usize,
+    ) -> Result<(), SimpleError> {
+        check_packed(
+            PixelFormat::FORMAT_10BIT_YUV,
+            dst.len(),
+            width,
+            height,
+            row_bytes,
+        )?;
+        check_plane("y", y.len(), width * height)?;
+        check_plane("uv", uv.len(), width * height)?;
+
+        for (row, dst) in dst.chunks_mut(row_bytes).take(height).enumerate() {
+            (self.rows.p210_to_v210)(&y[row * width..][..width], &uv[row * width..][..width], dst);
+        }
+        Ok(())
+    }
+}
+
+fn zero(bytes: &mut [u8]) {
+    for b in bytes {
+        *b = 0;
+    }
+}
+
+/// Converts UYVY to I420. See `Kernels::uyvy_to_i420`.
+pub fn uyvy_to_i420(
+    src: &[u8],
+    row_bytes: usize,
+    width: usize,
+    height: usize,
+    dst: &mut [u8],
+) -> Result<(), SimpleError> {
+    Kernels::detect().uyvy_to_i420(src, row_bytes, width, height, dst)
+}
+
+/// Converts UYVY to NV12. See `Kernels::uyvy_to_nv12`.
+pub fn uyvy_to_nv12(
+    src: &[u8],
+    row_bytes: usize,
+    width: usize,
+    height: usize,
+    dst: &mut [u8],
+) -> Result<(), SimpleError> {
+    Kernels::detect().uyvy_to_nv12(src, row_bytes, width, height, dst)
+}
+
+/// Converts I420 to UYVY. See `Kernels::i420_to_uyvy`.
+pub fn i420_to_uyvy(
+    src: &[u8],
+    width: usize,
+    height: usize,
+    dst: &mut [u8],
+    row_bytes: usize,
+) -> Result<(), SimpleError> {
+    Kernels::detect().i420_to_uyvy(src, width, height, dst, row_bytes)
+}
+
+/// Converts NV12 to UYVY. See `Kernels::nv12_to_uyvy`.
+pub fn nv12_to_uyvy(
+    src: &[u8],
+    width: usize,
+    height: usize,
+    dst: &mut [u8],
+    row_bytes: usize,
+) -> Result<(), SimpleError> {
+    Kernels::detect().nv12_to_uyvy(src, width, height, dst, row_bytes)
+}
+
+/// Converts BGRA to UYVY. See `Kernels::bgra_to_uyvy`.
+pub fn bgra_to_uyvy(
+    src: &[u8],
+    src_row_bytes: usize,
+    width: usize,
+    height: usize,
+    dst: &mut [u8],
+    dst_row_bytes: usize,
+) -> Result<(), SimpleError> {
+    Kernels::detect().bgra_to_uyvy(src, src_row_bytes, width, height, dst, dst_row_bytes)
+}
+
+/// Unpacks v210 into P210. See `Kernels::v210_to_p210`.
+pub fn v210_to_p210(
+    src: &[u8],
+    row_bytes: usize,
+    width: usize,
+    height: usize,
+    y: &mut [u16],
+    uv: &mut [u16],
+) -> Result<(), SimpleError> {
+    Kernels::detect().v210_to_p210(src, row_bytes, width, height, y, uv)
+}
+
+/// Packs P210 into v210. See `Kernels::p210_to_v210`.
+pub fn p210_to_v210(
+    y: &[u16],
+    uv: &[u16],
+    width: usize,
+    height: usize,
+    dst: &mut [u8],
+    row_bytes: usize,
+) -> Result<(), SimpleError> {
+    Kernels::detect().p210_to_v210(y, uv, width, height, dst, row_bytes)
+}
@@ -0,0 +1,318 @@
+//! NEON row kernels for little-endian AArch64. Each handles as many whole vectors as fit in the
+//! row and passes the remainder to the scalar kernel.
+//!
+//! The safe wrappers at the bottom are only reachable through the kernel tables in `mod.rs`,
+//! which are handed out after runtime feature detection.
+
+use std::arch::aarch64::*;
+
+use super::scalar::{self, REC709_CB, REC709_CR, REC709_Y};
+
+#[target_feature(enable = "neon")]
+unsafe fn uyvy_to_i420_rows_neon(
+    src0: &[u8],
+    src1: &[u8],
+    y0: &mut [u8],
+    y1: &mut [u8],
+    u: &mut [u8],
+    v: &mut [u8],
+) {
+    let n = y0.len() / 32 * 32;
+    for x in (0..n).step_by(32) {
+        let a = vld4q_u8(src0.as_ptr().add(x * 2));
+        let b = vld4q_u8(src1.as_ptr().add(x * 2));
+        vst2q_u8(y0.as_mut_ptr().add(x), uint8x16x2_t(a.1, a.3));
+        vst2q_u8(y1.as_mut_ptr().add(x), uint8x16x2_t(b.1, b.3));
+        vst1q_u8(u.as_mut_ptr().add(x / 2), vrhaddq_u8(a.0, b.0));
+        vst1q_u8(v.as_mut_ptr().add(x / 2), vrhaddq_u8(a.2, b.2));
+    }
+    scalar::uyvy_to_i420_rows(
+        &src0[n * 2..],
+        &src1[n * 2..],
+        &mut y0[n..],
+        &mut y1[n..],
+        &mut u[n / 2..],
+        &mut v[n / 2..],
+    );
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn uyvy_to_nv12_rows_neon(
+    src0: &[u8],
+    src1: &[u8],
+    y0: &mut [u8],
+    y1: &mut [u8],
+    uv: &mut [u8],
+) {
+    let n = y0.len() / 32 * 32;
+    for x in (0..n).step_by(32) {
+        let a = vld4q_u8(src0.as_ptr().add(x * 2));
+        let b = vld4q_u8(src1.as_ptr().add(x * 2));
+        vst2q_u8(y0.as_mut_ptr().add(x), uint8x16x2_t(a.1, a.3));
+        vst2q_u8(y1.as_mut_ptr().add(x), uint8x16x2_t(b.1, b.3));
+        vst2q_u8(
+            uv.as_mut_ptr().add(x),
+            uint8x16x2_t(vrhaddq_u8(a.0, b.0), vrhaddq_u8(a.2, b.2)),
+        );
+    }
+    scalar::uyvy_to_nv12_rows(
+        &src0[n * 2..],
+        &src1[n * 2..],
+        &mut y0[n..],
+        &mut y1[n..],
+        &mut uv[n..],
+    );
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn i420_to_uyvy_row_neon(y: &[u8], u: &[u8], v: &[u8], dst: &mut [u8]) {
+    let n = y.len() / 32 * 32;
+    for x in (0..n).step_by(32) {
+        let yy = vld2q_u8(y.as_ptr().add(x));
+        let uu = vld1q_u8(u.as_ptr().add(x / 2));
+        let vv = vld1q_u8(v.as_ptr().add(x / 2));
+        vst4q_u8(
+            dst.as_mut_ptr().add(x * 2),
+            uint8x16x4_t(uu, yy.0, vv, yy.1),
+        );
+    }
+    scalar::i420_to_uyvy_row(&y[n..], &u[n / 2..], &v[n / 2..], &mut dst[n * 2..]);
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn nv12_to_uyvy_row_neon(y: &[u8], uv: &[u8], dst: &mut [u8]) {
+    let n = y.len() / 32 * 32;
+    for x in (0..n).step_by(32) {
+        let yy = vld2q_u8(y.as_ptr().add(x));
+        let uvv = vld2q_u8(uv.as_ptr().add(x));
+        vst4q_u8(
+            dst.as_mut_ptr().add(x * 2),
+            uint8x16x4_t(uvv.0, yy.0, uvv.1, yy.1),
+        );
+    }
+    scalar::nv12_to_uyvy_row(&y[n..], &uv[n..], &mut dst[n * 2..]);
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn matrix_half_neon(k: [i32; 3], r: int16x4_t, g: int16x4_t, b: int16x4_t) -> int32x4_t {
+    let sum = vmull_n_s16(r, k[0] as i16);
+    let sum = vmlal_n_s16(sum, g, k[1] as i16);
+    let sum = vmlal_n_s16(sum, b, k[2] as i16);
+    vshrq_n_s32::<8>(vaddq_s32(sum, vdupq_n_s32(128)))
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn matrix_neon(
+    k: [i32; 3],
+    r: uint8x8_t,
+    g: uint8x8_t,
+    b: uint8x8_t,
+    offset: i32,
+) -> uint8x8_t {
+    let r = vreinterpretq_s16_u16(vmovl_u8(r));
+    let g = vreinterpretq_s16_u16(vmovl_u8(g));
+    let b = vreinterpretq_s16_u16(vmovl_u8(b));
+    let offset = vdupq_n_s32(offset);
+    let lo = vaddq_s32(
+        matrix_half_neon(k, vget_low_s16(r), vget_low_s16(g), vget_low_s16(b)),
+        offset,
+    );
+    let hi = vaddq_s32(
+        matrix_half_neon(k, vget_high_s16(r), vget_high_s16(g), vget_high_s16(b)),
+        offset,
+    );
+    vqmovun_s16(vcombine_s16(vmovn_s32(lo), vmovn_s32(hi)))
+}
+
+// Splits 16 lanes into the even and odd lanes.
+#[target_feature(enable = "neon")]
+unsafe fn even_odd_neon(c: uint8x16_t) -> (uint8x8_t, uint8x8_t) {
+    let uz = vuzpq_u8(c, c);
+    (vget_low_u8(uz.0), vget_low_u8(uz.1))
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn bgra_to_uyvy_row_neon(src: &[u8], dst: &mut [u8]) {
+    let width = dst.len() / 2;
+    let n = width / 16 * 16;
+    for x in (0..n).step_by(16) {
+        let p = vld4q_u8(src.as_ptr().add(x * 4));
+        let (b, g, r) = (p.0, p.1, p.2);
+        let y = vcombine_u8(
+            matrix_neon(REC709_Y, vget_low_u8(r), vget_low_u8(g), vget_low_u8(b), 16),
+            matrix_neon(
+                REC709_Y,
+                vget_high_u8(r),
+                vget_high_u8(g),
+                vget_high_u8(b),
+                16,
+            ),
+        );
+        let (r0, r1) = even_odd_neon(r);
+        let (g0, g1) = even_odd_neon(g);
+        let (b0, b1) = even_odd_neon(b);
+        let (r, g, b) = (vrhadd_u8(r0, r1), vrhadd_u8(g0, g1), vrhadd_u8(b0, b1));
+        let (y0, y1) = even_odd_neon(y);
+        vst4_u8(
+            dst.as_mut_ptr().add(x * 2),
+            uint8x8x4_t(
+                matrix_neon(REC709_CB, r, g, b, 128),
+                y0,
+                matrix_neon(REC709_CR, r, g, b, 128),
+                y1,
+            ),
+        );
+    }
+    scalar::bgra_to_uyvy_row(&src[n * 4..], &mut dst[n * 2..]);
+}
+
+// After the 10-bit components of a v210 group are narrowed into a table of
+// [c0 of words 0-3, c1 of words 0-3, c2 of words 0-3, 0...] 16-bit lanes, these indices gather
+// the group's six luma and six interleaved chroma samples into the low 12 bytes. Out of range
+// indices produce zero.
+const V210_Y: [u8; 16] = [8, 9, 2, 3, 18, 19, 12, 13, 6, 7, 22, 23, 255, 255, 255, 255];
+const V210_UV: [u8; 16] = [
+    0, 1, 16, 17, 10, 11, 4, 5, 20, 21, 14, 15, 255, 255, 255, 255,
+];
+
+// The reverse: from a table of a group's luma followed by its chroma, each starting at the first
+// 16-bit lane, place samples into the 32-bit lanes of the first, second and third component of
+// each word.
+const P210_C0: [u8; 16] = [
+    16, 17, 255, 255, 2, 3, 255, 255, 22, 23, 255, 255, 8, 9, 255, 255,
+];
+const P210_C1: [u8; 16] = [
+    0, 1, 255, 255, 20, 21, 255, 255, 6, 7, 255, 255, 26, 27, 255, 255,
+];
+const P210_C2: [u8; 16] = [
+    18, 19, 255, 255, 4, 5, 255, 255, 24, 25, 255, 255, 10, 11, 255, 255,
+];
+
+#[target_feature(enable = "neon")]
+unsafe fn v210_to_p210_row_neon(src: &[u8], y: &mut [u16], uv: &mut [u16]) {
+    let n = y.len() / 24 * 24;
+    let component = vdupq_n_u32(0x3ff);
+    let zero = vdupq_n_u8(0);
+    let (yi, uvi) = (vld1q_u8(V210_Y.as_ptr()), vld1q_u8(V210_UV.as_ptr()));
+    for x in (0..n).step_by(24) {
+        let mut ys = [zero; 4];
+        let mut uvs = [zero; 4];
+        for g in 0..4 {
+            let w = vreinterpretq_u32_u8(vld1q_u8(src.as_ptr().add(x / 24 * 64 + g * 16)));
+            let c0 = vandq_u32(w, component);
+            let c1 = vandq_u32(vshrq_n_u32::<10>(w), component);
+            let c2 = vandq_u32(vshrq_n_u32::<20>(w), component);
+            let table = uint8x16x2_t(
+                vreinterpretq_u8_u16(vcombine_u16(vmovn_u32(c0), vmovn_u32(c1))),
+                vreinterpretq_u8_u16(vcombine_u16(vmovn_u32(c2), vdup_n_u16(0))),
+            );
+            ys[g] = vreinterpretq_u8_u16(vshlq_n_u16::<6>(vreinterpretq_u16_u8(vqtbl2q_u8(
+                table, yi,
+            ))));
+            uvs[g] = vreinterpretq_u8_u16(vshlq_n_u16::<6>(vreinterpretq_u16_u8(vqtbl2q_u8(
+                table, uvi,
+            ))));
+        }
+        for (plane, groups) in [(y.as_mut_ptr(), ys), (uv.as_mut_ptr(), uvs)] {
+            let p = plane.add(x) as *mut u8;
+            vst1q_u8(p, vorrq_u8(groups[0], vextq_u8::<4>(zero, groups[1])));
+            vst1q_u8(
+                p.add(16),
+                vorrq_u8(
+                    vextq_u8::<4>(groups[1], zero),
+                    vextq_u8::<8>(zero, groups[2]),
+                ),
+            );
+            vst1q_u8(
+                p.add(32),
+                vorrq_u8(
+                    vextq_u8::<8>(groups[2], zero),
+                    vextq_u8::<12>(zero, groups[3]),
+                ),
+            );
+        }
+    }
+    scalar::v210_to_p210_row(&src[n / 24 * 64..], &mut y[n..], &mut uv[n..]);
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn split_groups_neon(plane: &[u16], x: usize) -> [uint8x16_t; 4] {
+    let p = plane.as_ptr().add(x) as *const u8;
+    let load = |offset: usize| {
+        vreinterpretq_u8_u16(vshrq_n_u16::<6>(vreinterpretq_u16_u8(vld1q_u8(
+            p.add(offset),
+        ))))
+    };
+    let (r0, r1, r2) = (load(0), load(16), load(32));
+    [
+        r0,
+        vextq_u8::<12>(r0, r1),
+        vextq_u8::<8>(r1, r2),
+        vextq_u8::<4>(r2, vdupq_n_u8(0)),
+    ]
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn p210_to_v210_row_neon(y: &[u16], uv: &[u16], dst: &mut [u8]) {
+    let n = y.len() / 24 * 24;
+    let c0i = vld1q_u8(P210_C0.as_ptr());
+    let c1i = vld1q_u8(P210_C1.as_ptr());
+    let c2i = vld1q_u8(P210_C2.as_ptr());
+    for x in (0..n).step_by(24) {
+        let ys = split_groups_neon(y, x);
+        let uvs = split_groups_neon(uv, x);
+        for g in 0..4 {
+            let table = uint8x16x2_t(ys[g], uvs[g]);
+            let c0 = vreinterpretq_u32_u8(vqtbl2q_u8(table, c0i));
+            let c1 = vreinterpretq_u32_u8(vqtbl2q_u8(table, c1i));
+            let c2 = vreinterpretq_u32_u8(vqtbl2q_u8(table, c2i));
+            let w = vorrq_u32(c0, vorrq_u32(vshlq_n_u32::<10>(c1), vshlq_n_u32::<20>(c2)));
+            vst1q_u8(
+                dst.as_mut_ptr().add(x / 24 * 64 + g * 16),
+                vreinterpretq_u8_u32(w),
+            );
+        }
+    }
+    scalar::p210_to_v210_row(&y[n..], &uv[n..], &mut dst[n / 24 * 64..]);
+}
+
+pub(super) fn uyvy_to_i420_rows(
+    src0: &[u8],
+    src1: &[u8],
+    y0: &mut [u8],
+    y1: &mut [u8],
+    u: &mut [u8],
+    v: &mut [u8],
+) {
+    unsafe { uyvy_to_i420_rows_neon(src0, src1, y0, y1, u, v) }
+}
+
+pub(super) fn uyvy_to_nv12_rows(
+    src0: &[u8],
+    src1: &[u8],
+    y0: &mut [u8],
+    y1: &mut [u8],
+    uv: &mut [u8],
+) {
+    unsafe { uyvy_to_nv12_rows_neon(src0, src1, y0, y1, uv) }
+}
+
+pub(super) fn i420_to_uyvy_row(y: &[u8], u: &[u8], v: &[u8], dst: &mut [u8]) {
+    unsafe { i420_to_uyvy_row_neon(y, u, v, dst) }
+}
+
+pub(super) fn nv12_to_uyvy_row(y: &[u8], uv: &[u8], dst: &mut [u8]) {
+    unsafe { nv12_to_uyvy_row_neon(y, uv, dst) }
+}
+
+pub(super) fn bgra_to_uyvy_row(src: &[u8], dst: &mut [u8]) {
+    unsafe { bgra_to_uyvy_row_neon(src, dst) }
+}
+
+pub(super) fn v210_to_p210_row(src: &[u8], y: &mut [u16], uv: &mut [u16]) {
+    unsafe { v210_to_p210_row_neon(src, y, uv) }
+}
+
+pub(super) fn p210_to_v210_row(y: &[u16], uv: &[u16], dst: &mut [u8]) {
+    unsafe { p210_to_v210_row_neon(y, uv, dst) }
+}
@@ -0,0 +1,237 @@
+use simple_error::SimpleError;
+
+use super::{check_packed, check_plane, zero};
+use PixelFormat;
+
+// The 10-bit formats hold one pixel per 32-bit word. r210 is big-endian with two unused high bits
+// and the components in the low 30; R10b and R10l put the components in the high 30 bits and leave
+// the low two unused, and differ only in byte order.
+//
+// The 12-bit formats treat eight pixels as a stream of 24 components, least significant bits
+// first, across nine 32-bit words: R0 is bits 0-11 of word 0, G0 bits 12-23, B0 straddles words 0
+// and 1, and so on. R12L stores the words little-endian and R12B big-endian, as in 12-bit DPX.
+// Rows are padded out to whole eight-pixel, 36-byte groups.
+
+#[derive(Clone, Copy)]
+enum Layout {
+    R210,
+    R10b,
+    R10l,
+    R12b,
+    R12l,
+}
+
+impl Layout {
+    fn of(format: PixelFormat) -> Result<Layout, SimpleError> {
+        match format {
+            PixelFormat::FORMAT_10BIT_RGB => Ok(Layout::R210),
+            PixelFormat::FORMAT_10BIT_RGBX => Ok(Layout::R10b),
+            PixelFormat::FORMAT_10BIT_RGBXLE => Ok(Layout::R10l),
+            PixelFormat::FORMAT_12BIT_RGB => Ok(Layout::R12b),
+            PixelFormat::FORMAT_12BIT_RGBLE => Ok(Layout::R12l),
+            _ => Err(SimpleError::new(format!(
+                "{:?} is not a packed 10 or 12-bit RGB format",
+                format
+            ))),
+        }
+    }
+
+    fn big_endian(self) -> bool {
+        match self {
+            Layout::R210 | Layout::R10b | Layout::R12b => true,
+            Layout::R10l | Layout::R12l => false,
+        }
+    }
+
+    fn read_word(self, src: &[u8], n: usize) -> u32 {
+        let bytes = [src[n * 4], src[n * 4 + 1], src[n * 4 + 2], src[n * 4 + 3]];
+        if self.big_endian() {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        }
+    }
+
+    fn write_word(self, dst: &mut [u8], n: usize, word: u32) {
+        let bytes = if self.big_endian() {
+            word.to_be_bytes()
+        } else {
+            word.to_le_bytes()
+        };
+        dst[n * 4..n * 4 + 4].copy_from_slice(&bytes);
+    }
+
+    /// Reads `width` pixels from `src` into `dst`, `channels` samples apart.
+    fn unpack_row(self, src: &[u8], width: usize, dst: &mut [u16], channels: usize) {
+        match self {
+            Layout::R210 | Layout::R10b | Layout::R10l => {
+                let shift = if let Layout::R210 = self { 0 } else { 2 };
+                for (i, px) in dst.chunks_exact_mut(channels).take(width).enumerate() {
+                    let word = self.read_word(src, i) >> shift;
+                    px[0] = (((word >> 20) & 0x3ff) as u16) << 6;
+                    px[1] = (((word >> 10) & 0x3ff) as u16) << 6;
+                    px[2] = ((word & 0x3ff) as u16) << 6;
+                }
+            }
+            Layout::R12b | Layout::R12l => {
+                let mut words = [0u32; 9];
+                for (group, dst) in dst
+                    .chunks_mut(channels * 8)
+                    .take(width.div_ceil(8))
+                    .enumerate()
+                {
+                    for (n, word) in words.iter_mut().enumerate() {
+                        *word = self.read_word(src, group * 9 + n);
+                    }
+                    for (i, px) in dst.chunks_exact_mut(channels).enumerate() {
+                        for (c, sample) in px[..3].iter_mut().enumerate() {
+                            let bit = (i * 3 + c) * 12;
+                            let (n, shift) = (bit / 32, bit % 32);
+                            let mut value = words[n] as u64;
+                            if n + 1 < words.len() {
+                                value |= (words[n + 1] as u64) << 32;
+                            }
+                            *sample = (((value >> shift) & 0xfff) as u16) << 4;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Writes `width` pixels from `src`, `channels` samples apart, and zeroes the rest of `dst`.
+    fn pack_row(self, src: &[u16], width: usize, dst: &mut [u8], channels: usize) {
+        let used = match self {
+            Layout::R210 | Layout::R10b | Layout::R10l => {
+                let shift = if let Layout::R210 = self { 0 } else { 2 };
+                for (i, px) in src.chunks_exact(channels).take(width).enumerate() {
+                    let word = ((px[0] >> 6) as u32) << 20
+                        | ((px[1] >> 6) as u32) << 10
+                        | (px[2] >> 6) as u32;
+                    self.write_word(dst, i, word << shift);
+                }
+                width * 4
+            }
+            Layout::R12b | Layout::R12l => {
+                let groups = width.div_ceil(8);
+                for (group, src) in src[..width * channels].chunks(channels * 8).enumerate() {
+                    let mut words = [0u64; 9];
+                    for (i, px) in src.chunks_exact(channels).enumerate() {
+                        for (c, sample) in px[..3].iter().enumerate() {
+                            let bit = (i * 3 + c) * 12;
+                            let (n, shift) = (bit / 32, bit % 32);
+                            words[n] |= ((sample >> 4) as u64) << shift;
+                        }
+                    }
+                    // Carry the bits that straddle a word boundary into the next word.
+                    for n in 0..words.len() - 1 {
+                        words[n + 1] |= words[n] >> 32;
+                    }
+                    for (n, word) in words.iter().enumerate() {
+                        self.write_word(dst, group * 9 + n, *word as u32);
+                    }
+                }
+                groups * 36
+            }
+        };
+        zero(&mut dst[used..]);
+    }
+}
+
+fn unpack(
+    format: PixelFormat,
+    src: &[u8],
+    row_bytes: usize,
+    width: usize,
+    height: usize,
+    dst: &mut [u16],
+    channels: usize,
+) -> Result<(), SimpleError> {
+    let layout = Layout::of(format)?;
+    check_packed(format, src.len(), width, height, row_bytes)?;
+    check_plane("rgb", dst.len(), width * height * channels)?;
+
+    for (row, dst) in dst
+        .chunks_exact_mut(width * channels)
+        .take(height)
+        .enumerate()
+    {
+        layout.unpack_row(&src[row * row_bytes..][..row_bytes], width, dst, channels);
+        if channels == 4 {
+            for px in dst.chunks_exact_mut(4) {
+                px[3] = 0xffff;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn pack(
+    src: &[u16],
+    channels: usize,
+    width: usize,
+    height: usize,
+    format: PixelFormat,
+    dst: &mut [u8],
+    row_bytes: usize,
+) -> Result<(), SimpleError> {
+    let layout = Layout::of(format)?;
+    check_packed(format, dst.len(), width, height, row_bytes)?;
+    check_plane("rgb", src.len(), width * height * channels)?;
+
+    for (row, dst) in dst.chunks_mut(row_bytes).take(height).enumerate() {
+        layout.pack_row(&src[row * width * channels..], width, dst, channels);
+    }
+    Ok(())
+}
+
+/// Unpacks an r210, R10b, R10l, R12B or R12L frame into interleaved 16-bit RGB.
+///
+/// Samples are MSB-aligned and keep the levels of the source: SMPTE video levels for the 10-bit
+/// formats and full range for the 12-bit formats.
+pub fn rgb_to_rgb16(
+    format: PixelFormat,
+    src: &[u8],
+    row_bytes: usize,
+    width: usize,
+    height: usize,
+    dst: &mut [u16],
+) -> Result<(), SimpleError> {
+    unpack(format, src, row_bytes, width, height, dst, 3)
+}
+
+/// Unpacks an r210, R10b, R10l, R12B or R12L frame into interleaved 16-bit RGBA with opaque alpha.
+pub fn rgb_to_rgba16(
+    format: PixelFormat,
+    src: &[u8],
+    row_bytes: usize,
+    width: usize,
+    height: usize,
+    dst: &mut [u16],
+) -> Result<(), SimpleError> {
+    unpack(format, src, row_bytes, width, height, dst, 4)
+}
+
+/// Packs interleaved 16-bit RGB into an r210, R10b, R10l, R12B or R12L frame.
+pub fn rgb16_to_rgb(
+    src: &[u16],
+    width: usize,
+    height: usize,
+    format: PixelFormat,
+    dst: &mut [u8],
+    row_bytes: usize,
+) -> Result<(), SimpleError> {
+    pack(src, 3, width, height, format, dst, row_bytes)
+}
+
+/// Packs interleaved 16-bit RGBA into an r210, R10b, R10l, R12B or R12L frame, dropping alpha.
+pub fn rgba16_to_rgb(
+    src: &[u16],
+    width: usize,
+    height: usize,
+    format: PixelFormat,
+    dst: &mut [u8],
+    row_bytes: usize,
+) -> Result<(), SimpleError> {
+    pack(src, 4, width, height, format, dst, row_bytes)
+}
@@ -0,0 +1,140 @@
+//! Reference row kernels. The SIMD kernels hand their tails to these, and must produce
+//! bit-identical output.
+
+#[inline]
+fn avg(a: u8, b: u8) -> u8 {
+    ((a as u16 + b as u16 + 1) >> 1) as u8
+}
+
+/// Rec.709 full-range RGB to legal-range Y'CbCr, with 8 fractional bits.
+pub(super) const REC709_Y: [i32; 3] = [47, 157, 16];
+pub(super) const REC709_CB: [i32; 3] = [-26, -86, 112];
+pub(super) const REC709_CR: [i32; 3] = [112, -102, -10];
+
+#[inline]
+fn matrix(k: [i32; 3], r: u8, g: u8, b: u8, offset: i32) -> u8 {
+    (((k[0] * r as i32 + k[1] * g as i32 + k[2] * b as i32 + 128) >> 8) + offset) as u8
+}
+
+pub(super) fn uyvy_to_i420_rows(
+    src0: &[u8],
+    src1: &[u8],
+    y0: &mut [u8],
+    y1: &mut [u8],
+    u: &mut [u8],
+    v: &mut [u8],
+) {
+    for i in 0..y0.len() / 2 {
+        let (a, b) = (&src0[i * 4..i * 4 + 4], &src1[i * 4..i * 4 + 4]);
+        u[i] = avg(a[0], b[0]);
+        v[i] = avg(a[2], b[2]);
+        y0[i * 2] = a[1];
+        y0[i * 2 + 1] = a[3];
+        y1[i * 2] = b[1];
+        y1[i * 2 + 1] = b[3];
+    }
+}
+
+pub(super) fn uyvy_to_nv12_rows(
+    src0: &[u8],
+    src1: &[u8],
+    y0: &mut [u8],
+    y1: &mut [u8],
+    uv: &mut [u8],
+) {
+    for i in 0..y0.len() / 2 {
+        let (a, b) = (&src0[i * 4..i * 4 + 4], &src1[i * 4..i * 4 + 4]);
+        uv[i * 2] = avg(a[0], b[0]);
+        uv[i * 2 + 1] = avg(a[2], b[2]);
+        y0[i * 2] = a[1];
+        y0[i * 2 + 1] = a[3];
+        y1[i * 2] = b[1];
+        y1[i * 2 + 1] = b[3];
+    }
+}
+
+pub(super) fn i420_to_uyvy_row(y: &[u8], u: &[u8], v: &[u8], dst: &mut [u8]) {
+    for i in 0..y.len() / 2 {
+        dst[i * 4] = u[i];
+        dst[i * 4 + 1] = y[i * 2];
+        dst[i * 4 + 2] = v[i];
+        dst[i * 4 + 3] = y[i * 2 + 1];
+    }
+}
+
+pub(super) fn nv12_to_uyvy_row(y: &[u8], uv: &[u8], dst: &mut [u8]) {
+    for i in 0..y.len() / 2 {
+        dst[i * 4] = uv[i * 2];
+        dst[i * 4 + 1] = y[i * 2];
+        dst[i * 4 + 2] = uv[i * 2 + 1];
+        dst[i * 4 + 3] = y[i * 2 + 1];
+    }
+}
+
+/// Converts `dst.len() / 2` pixels. Chroma is taken from the rounded average of each pixel pair.
+pub(super) fn bgra_to_uyvy_row(src: &[u8], dst: &mut [u8]) {
+    for i in 0..dst.len() / 4 {
+        let (p0, p1) = (&src[i * 8..i * 8 + 4], &src[i * 8 + 4..i * 8 + 8]);
+        let (r, g, b) = (avg(p0[2], p1[2]), avg(p0[1], p1[1]), avg(p0[0], p1[0]));
+        dst[i * 4] = matrix(REC709_CB, r, g, b, 128);
+        dst[i * 4 + 1] = matrix(REC709_Y, p0[2], p0[1], p0[0], 16);
+        dst[i * 4 + 2] = matrix(REC709_CR, r, g, b, 128);
+        dst[i * 4 + 3] = matrix(REC709_Y, p1[2], p1[1], p1[0], 16);
+    }
+}
+
+#[inline]
+fn read_word(src: &[u8], n: usize) -> u32 {
+    u32::from_le_bytes([src[n * 4], src[n * 4 + 1], src[n * 4 + 2], src[n * 4 + 3]])
+}
+
+/// Converts `y.len()` pixels, reading whole 16-byte groups of `src`.
+pub(super) fn v210_to_p210_row(src: &[u8], y: &mut [u16], uv: &mut [u16]) {
+    let pairs = y.len() / 2;
+    for group in 0..pairs.div_ceil(3) {
+        let mut c = [0u16; 12];
+        for w in 0..4 {
+            let word = read_word(src, group * 4 + w);
+            for i in 0..3 {
+                c[w * 3 + i] = ((word >> (i * 10)) & 0x3ff) as u16;
+            }
+        }
+        for k in 0..3 {
+            let i = group * 3 + k;
+            if i >= pairs {
+                break;
+            }
+            uv[i * 2] = c[k * 4] << 6;
+            y[i * 2] = c[k * 4 + 1] << 6;
+            uv[i * 2 + 1] = c[k * 4 + 2] << 6;
+            y[i * 2 + 1] = c[k * 4 + 3] << 6;
+        }
+    }
+}
+
+/// Packs `y.len()` pixels and zeroes the rest of `dst`.
+pub(super) fn p210_to_v210_row(y: &[u16], uv: &[u16], dst: &mut [u8]) {
+    let pairs = y.len() / 2;
+    let groups = pairs.div_ceil(3);
+    for group in 0..groups {
+        let mut c = [0u32; 12];
+        for k in 0..3 {
+            let i = group * 3 + k;
+            if i >= pairs {
+                break;
+            }
+            c[k * 4] = (uv[i * 2] >> 6) as u32;
+            c[k * 4 + 1] = (y[i * 2] >> 6) as u32;
+            c[k * 4 + 2] = (uv[i * 2 + 1] >> 6) as u32;
+            c[k * 4 + 3] = (y[i * 2 + 1] >> 6) as u32;
+        }
+        for w in 0..4 {
+            let word = c[w * 3] | c[w * 3 + 1] << 10 | c[w * 3 + 2] << 20;
+            let n = group * 4 + w;
+            dst[n * 4..n * 4 + 4].copy_from_slice(&word.to_le_bytes());
+        }
+    }
+    for b in &mut dst[groups * 16..] {
+        *b = 0;
+    }
+}
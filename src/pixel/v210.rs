@@ -0,0 +1,113 @@
+use simple_error::SimpleError;
+
+use super::{check_packed, check_plane};
+use PixelFormat;
+
+// v210 packs three 10-bit components into each little-endian 32-bit word, in the order
+// Cb Y Cr Y, so every pair of pixels is four consecutive components. Six pixels fill four words,
+// and rows are padded out to 48-pixel, 128-byte blocks.
+
+#[inline]
+fn read_components(row: &[u8], components: &mut [u16]) {
+    for (n, chunk) in components.chunks_mut(3).enumerate() {
+        let word = u32::from_le_bytes([row[n * 4], row[n * 4 + 1], row[n * 4 + 2], row[n * 4 + 3]]);
+        for (i, c) in chunk.iter_mut().enumerate() {
+            *c = ((word >> (i * 10)) & 0x3ff) as u16;
+        }
+    }
+}
+
+#[inline]
+fn write_components(components: &[u16], row: &mut [u8]) {
+    let mut used = 0;
+    for (n, chunk) in components.chunks(3).enumerate() {
+        let mut word = 0u32;
+        for (i, c) in chunk.iter().enumerate() {
+            word |= ((*c >> 6) as u32) << (i * 10);
+        }
+        row[n * 4..n * 4 + 4].copy_from_slice(&word.to_le_bytes());
+        used = n * 4 + 4;
+    }
+    for b in &mut row[used..] {
+        *b = 0;
+    }
+}
+
+/// Unpacks a v210 frame into 16-bit planar 4:2:2.
+pub fn v210_to_yuv422p16(
+    src: &[u8],
+    row_bytes: usize,
+    width: usize,
+    height: usize,
+    y: &mut [u16],
+    u: &mut [u16],
+    v: &mut [u16],
+) -> Result<(), SimpleError> {
+    check_packed(
+        PixelFormat::FORMAT_10BIT_YUV,
+        src.len(),
+        width,
+        height,
+        row_bytes,
+    )?;
+    let chroma_width = width / 2;
+    check_plane("y", y.len(), width * height)?;
+    check_plane("u", u.len(), chroma_width * height)?;
+    check_plane("v", v.len(), chroma_width * height)?;
+
+    let mut components = vec![0u16; width * 2];
+    for row in 0..height {
+        read_components(&src[row * row_bytes..], &mut components);
+        let y = &mut y[row * width..(row + 1) * width];
+        let u = &mut u[row * chroma_width..(row + 1) * chroma_width];
+        let v = &mut v[row * chroma_width..(row + 1) * chroma_width];
+        for (i, pair) in components.chunks_exact(4).enumerate() {
+            u[i] = pair[0] << 6;
+            y[i * 2] = pair[1] << 6;
+            v[i] = pair[2] << 6;
+            y[i * 2 + 1] = pair[3] << 6;
+        }
+    }
+    Ok(())
+}
+
+/// Packs 16-bit planar 4:2:2 into a v210 frame.
+pub fn yuv422p16_to_v210(
+    y: &[u16],
+    u: &[u16],
+    v: &[u16],
+    width: usize,
+    height: usize,
+    dst: &mut [u8],
+    row_bytes: usize,
+) -> Result<(), SimpleError> {
+    check_packed(
+        PixelFormat::FORMAT_10BIT_YUV,
+        dst.len(),
+        width,
+        height,
+        row_bytes,
+    )?;
+    let chroma_width = width / 2;
+    check_plane("y", y.len(), width * height)?;
+    check_plane("u", u.len(), chroma_width * height)?;
+    check_plane("v", v.len(), chroma_width * height)?;
+
+    let mut components = vec![0u16; width * 2];
+    for row in 0..height {
+        let y = &y[row * width..(row + 1) * width];
+        let u = &u[row * chroma_width..(row + 1) * chroma_width];
+        let v = &v[row * chroma_width..(row + 1) * chroma_width];
+        for (i, pair) in components.chunks_exact_mut(4).enumerate() {
+            pair[0] = u[i];
+            pair[1] = y[i * 2];
+            pair[2] = v[i];
+            pair[3] = y[i * 2 + 1];
+        }
+        write_components(
+            &components,
+            &mut dst[row * row_bytes..(row + 1) * row_bytes],
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,554 @@
+//! SSE4.1 and AVX2 row kernels. Each handles as many whole vectors as fit in the row and passes
+//! the remainder to the scalar kernel.
+//!
+//! The safe wrappers at the bottom are only reachable through the kernel tables in `mod.rs`,
+//! which are handed out after runtime feature detection.
+
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+use super::scalar::{self, REC709_CB, REC709_CR, REC709_Y};
+
+#[target_feature(enable = "sse4.1")]
+unsafe fn uyvy_to_420_rows_sse41(
+    src0: &[u8],
+    src1: &[u8],
+    y0: &mut [u8],
+    y1: &mut [u8],
+    mut chroma: impl FnMut(usize, __m128i),
+) -> usize {
+    let n = y0.len() / 16 * 16;
+    let mask = _mm_set1_epi16(0xff);
+    for x in (0..n).step_by(16) {
+        let a0 = _mm_loadu_si128(src0.as_ptr().add(x * 2) as *const __m128i);
+        let a1 = _mm_loadu_si128(src0.as_ptr().add(x * 2 + 16) as *const __m128i);
+        let b0 = _mm_loadu_si128(src1.as_ptr().add(x * 2) as *const __m128i);
+        let b1 = _mm_loadu_si128(src1.as_ptr().add(x * 2 + 16) as *const __m128i);
+        let ya = _mm_packus_epi16(_mm_srli_epi16(a0, 8), _mm_srli_epi16(a1, 8));
+        let yb = _mm_packus_epi16(_mm_srli_epi16(b0, 8), _mm_srli_epi16(b1, 8));
+        _mm_storeu_si128(y0.as_mut_ptr().add(x) as *mut __m128i, ya);
+        _mm_storeu_si128(y1.as_mut_ptr().add(x) as *mut __m128i, yb);
+        let ca = _mm_packus_epi16(_mm_and_si128(a0, mask), _mm_and_si128(a1, mask));
+        let cb = _mm_packus_epi16(_mm_and_si128(b0, mask), _mm_and_si128(b1, mask));
+        chroma(x, _mm_avg_epu8(ca, cb));
+    }
+    n
+}
+
+#[target_feature(enable = "sse4.1")]
+unsafe fn uyvy_to_i420_rows_sse41(
+    src0: &[u8],
+    src1: &[u8],
+    y0: &mut [u8],
+    y1: &mut [u8],
+    u: &mut [u8],
+    v: &mut [u8],
+) {
+    let mask = _mm_set1_epi16(0xff);
+    let zero = _mm_setzero_si128();
+    let (up, vp) = (u.as_mut_ptr(), v.as_mut_ptr());
+    let n = uyvy_to_420_rows_sse41(src0, src1, y0, y1, |x, c| {
+        let uu = _mm_packus_epi16(_mm_and_si128(c, mask), zero);
+        let vv = _mm_packus_epi16(_mm_srli_epi16(c, 8), zero);
+        _mm_storel_epi64(up.add(x / 2) as *mut __m128i, uu);
+        _mm_storel_epi64(vp.add(x / 2) as *mut __m128i, vv);
+    });
+    scalar::uyvy_to_i420_rows(
+        &src0[n * 2..],
+        &src1[n * 2..],
+        &mut y0[n..],
+        &mut y1[n..],
+        &mut u[n / 2..],
+        &mut v[n / 2..],
+    );
+}
+
+#[target_feature(enable = "sse4.1")]
+unsafe fn uyvy_to_nv12_rows_sse41(
+    src0: &[u8],
+    src1: &[u8],
+    y0: &mut [u8],
+    y1: &mut [u8],
+    uv: &mut [u8],
+) {
+    let uvp = uv.as_mut_ptr();
+    let n = uyvy_to_420_rows_sse41(src0, src1, y0, y1, |x, c| {
+        _mm_storeu_si128(uvp.add(x) as *mut __m128i, c);
+    });
+    scalar::uyvy_to_nv12_rows(
+        &src0[n * 2..],
+        &src1[n * 2..],
+        &mut y0[n..],
+        &mut y1[n..],
+        &mut uv[n..],
+    );
+}
+
+#[target_feature(enable = "sse4.1")]
+unsafe fn interleave_uyvy_sse41(dst: &mut [u8], x: usize, y: __m128i, uv: __m128i) {
+    _mm_storeu_si128(
+        dst.as_mut_ptr().add(x * 2) as *mut __m128i,
+        _mm_unpacklo_epi8(uv, y),
+    );
+    _mm_storeu_si128(
+        dst.as_mut_ptr().add(x * 2 + 16) as *mut __m128i,
+        _mm_unpackhi_epi8(uv, y),
+    );
+}
+
+#[target_feature(enable = "sse4.1")]
+unsafe fn i420_to_uyvy_row_sse41(y: &[u8], u: &[u8], v: &[u8], dst: &mut [u8]) {
+    let n = y.len() / 16 * 16;
+    for x in (0..n).step_by(16) {
+        let yy = _mm_loadu_si128(y.as_ptr().add(x) as *const __m128i);
+        let uu = _mm_loadl_epi64(u.as_ptr().add(x / 2) as *const __m128i);
+        let vv = _mm_loadl_epi64(v.as_ptr().add(x / 2) as *const __m128i);
+        interleave_uyvy_sse41(dst, x, yy, _mm_unpacklo_epi8(uu, vv));
+    }
+    scalar::i420_to_uyvy_row(&y[n..], &u[n / 2..], &v[n / 2..], &mut dst[n * 2..]);
+}
+
+#[target_feature(enable = "sse4.1")]
+unsafe fn nv12_to_uyvy_row_sse41(y: &[u8], uv: &[u8], dst: &mut [u8]) {
+    let n = y.len() / 16 * 16;
+    for x in (0..n).step_by(16) {
+        let yy = _mm_loadu_si128(y.as_ptr().add(x) as *const __m128i);
+        let uvv = _mm_loadu_si128(uv.as_ptr().add(x) as *const __m128i);
+        interleave_uyvy_sse41(dst, x, yy, uvv);
+    }
+    scalar::nv12_to_uyvy_row(&y[n..], &uv[n..], &mut dst[n * 2..]);
+}
+
+#[target_feature(enable = "sse4.1")]
+unsafe fn matrix_sse41(k: [i32; 3], r: __m128i, g: __m128i, b: __m128i, offset: i32) -> __m128i {
+    let sum = _mm_add_epi32(
+        _mm_add_epi32(
+            _mm_mullo_epi32(r, _mm_set1_epi32(k[0])),
+            _mm_mullo_epi32(g, _mm_set1_epi32(k[1])),
+        ),
+        _mm_add_epi32(
+            _mm_mullo_epi32(b, _mm_set1_epi32(k[2])),
+            _mm_set1_epi32(128),
+        ),
+    );
+    _mm_add_epi32(_mm_srai_epi32(sum, 8), _mm_set1_epi32(offset))
+}
+
+// Averages each even lane with the following odd lane, rounding up.
+#[target_feature(enable = "sse4.1")]
+unsafe fn pair_avg_sse41(c: __m128i) -> __m128i {
+    _mm_srli_epi32(
+        _mm_add_epi32(_mm_add_epi32(c, _mm_srli_si128(c, 4)), _mm_set1_epi32(1)),
+        1,
+    )
+}
+
+#[target_feature(enable = "sse4.1")]
+unsafe fn bgra_to_uyvy_row_sse41(src: &[u8], dst: &mut [u8]) {
+    let width = dst.len() / 2;
+    let n = width / 4 * 4;
+    let mask = _mm_set1_epi32(0xff);
+    for x in (0..n).step_by(4) {
+        let p = _mm_loadu_si128(src.as_ptr().add(x * 4) as *const __m128i);
+        let b = _mm_and_si128(p, mask);
+        let g = _mm_and_si128(_mm_srli_epi32(p, 8), mask);
+        let r = _mm_and_si128(_mm_srli_epi32(p, 16), mask);
+        let y = matrix_sse41(REC709_Y, r, g, b, 16);
+        let (r, g, b) = (pair_avg_sse41(r), pair_avg_sse41(g), pair_avg_sse41(b));
+        let cb = matrix_sse41(REC709_CB, r, g, b, 128);
+        let cr = matrix_sse41(REC709_CR, r, g, b, 128);
+        // Lanes 0 and 2 now hold U Y V Y for each pixel pair.
+        let out = _mm_or_si128(
+            _mm_or_si128(cb, _mm_slli_epi32(y, 8)),
+            _mm_or_si128(
+                _mm_slli_epi32(cr, 16),
+                _mm_slli_epi32(_mm_srli_si128(y, 4), 24),
+            ),
+        );
+        _mm_storel_epi64(
+            dst.as_mut_ptr().add(x * 2) as *mut __m128i,
+            _mm_shuffle_epi32(out, 0b00_00_10_00),
+        );
+    }
+    scalar::bgra_to_uyvy_row(&src[n * 4..], &mut dst[n * 2..]);
+}
+
+// After the 10-bit components of a v210 group are packed into 16-bit lanes as
+// a = [c0 of words 0-3, c1 of words 0-3] and b = [c2 of words 0-3, 0...], these masks gather the
+// group's six luma and six interleaved chroma samples into the low 12 bytes.
+const V210_Y_A: [i8; 16] = [8, 9, 2, 3, -1, -1, 12, 13, 6, 7, -1, -1, -1, -1, -1, -1];
+const V210_Y_B: [i8; 16] = [-1, -1, -1, -1, 2, 3, -1, -1, -1, -1, 6, 7, -1, -1, -1, -1];
+const V210_UV_A: [i8; 16] = [0, 1, -1, -1, 10, 11, 4, 5, -1, -1, 14, 15, -1, -1, -1, -1];
+const V210_UV_B: [i8; 16] = [-1, -1, 0, 1, -1, -1, -1, -1, 4, 5, -1, -1, -1, -1, -1, -1];
+
+// The reverse: place six luma and six chroma samples into the 32-bit lanes of the first, second
+// and third component of each word.
+const P210_C0_UV: [i8; 16] = [0, 1, -1, -1, -1, -1, -1, -1, 6, 7, -1, -1, -1, -1, -1, -1];
+const P210_C0_Y: [i8; 16] = [-1, -1, -1, -1, 2, 3, -1, -1, -1, -1, -1, -1, 8, 9, -1, -1];
+const P210_C1_Y: [i8; 16] = [0, 1, -1, -1, -1, -1, -1, -1, 6, 7, -1, -1, -1, -1, -1, -1];
+const P210_C1_UV: [i8; 16] = [-1, -1, -1, -1, 4, 5, -1, -1, -1, -1, -1, -1, 10, 11, -1, -1];
+const P210_C2_UV: [i8; 16] = [2, 3, -1, -1, -1, -1, -1, -1, 8, 9, -1, -1, -1, -1, -1, -1];
+const P210_C2_Y: [i8; 16] = [-1, -1, -1, -1, 4, 5, -1, -1, -1, -1, -1, -1, 10, 11, -1, -1];
+
+#[target_feature(enable = "sse4.1")]
+unsafe fn load_mask(mask: &[i8; 16]) -> __m128i {
+    _mm_loadu_si128(mask.as_ptr() as *const __m128i)
+}
+
+#[target_feature(enable = "sse4.1")]
+unsafe fn v210_to_p210_row_sse41(src: &[u8], y: &mut [u16], uv: &mut [u16]) {
+    let n = y.len() / 24 * 24;
+    let component = _mm_set1_epi32(0x3ff);
+    let zero = _mm_setzero_si128();
+    let (ya, yb) = (load_mask(&V210_Y_A), load_mask(&V210_Y_B));
+    let (uva, uvb) = (load_mask(&V210_UV_A), load_mask(&V210_UV_B));
+    for x in (0..n).step_by(24) {
+        let mut ys = [zero; 4];
+        let mut uvs = [zero; 4];
+        for g in 0..4 {
+            let w = _mm_loadu_si128(src.as_ptr().add(x / 24 * 64 + g * 16) as *const __m128i);
+            let c0 = _mm_and_si128(w, component);
+            let c1 = _mm_and_si128(_mm_srli_epi32(w, 10), component);
+            let c2 = _mm_and_si128(_mm_srli_epi32(w, 20), component);
+            let a = _mm_packus_epi32(c0, c1);
+            let b = _mm_packus_epi32(c2, zero);
+            ys[g] = _mm_slli_epi16(
+                _mm_or_si128(_mm_shuffle_epi8(a, ya), _mm_shuffle_epi8(b, yb)),
+                6,
+            );
+            uvs[g] = _mm_slli_epi16(
+                _mm_or_si128(_mm_shuffle_epi8(a, uva), _mm_shuffle_epi8(b, uvb)),
+                6,
+            );
+        }
+        for (plane, groups) in [(y.as_mut_ptr(), ys), (uv.as_mut_ptr(), uvs)] {
+            let p = plane.add(x) as *mut __m128i;
+            _mm_storeu_si128(p, _mm_or_si128(groups[0], _mm_slli_si128(groups[1], 12)));
+            _mm_storeu_si128(
+                p.add(1),
+                _mm_or_si128(_mm_srli_si128(groups[1], 4), _mm_slli_si128(groups[2], 8)),
+            );
+            _mm_storeu_si128(
+                p.add(2),
+                _mm_or_si128(_mm_srli_si128(groups[2], 8), _mm_slli_si128(groups[3], 4)),
+            );
+        }
+    }
+    scalar::v210_to_p210_row(&src[n / 24 * 64..], &mut y[n..], &mut uv[n..]);
+}
+
+#[target_feature(enable = "sse4.1")]
+unsafe fn split_groups_sse41(plane: &[u16], x: usize) -> [__m128i; 4] {
+    let p = plane.as_ptr().add(x) as *const __m128i;
+    let r0 = _mm_srli_epi16(_mm_loadu_si128(p), 6);
+    let r1 = _mm_srli_epi16(_mm_loadu_si128(p.add(1)), 6);
+    let r2 = _mm_srli_epi16(_mm_loadu_si128(p.add(2)), 6);
+    [
+        r0,
+        _mm_alignr_epi8(r1, r0, 12),
+        _mm_alignr_epi8(r2, r1, 8),
+        _mm_srli_si128(r2, 4),
+    ]
+}
+
+#[target_feature(enable = "sse4.1")]
+unsafe fn p210_to_v210_row_sse41(y: &[u16], uv: &[u16], dst: &mut [u8]) {
+    let n = y.len() / 24 * 24;
+    let (c0_uv, c0_y) = (load_mask(&P210_C0_UV), load_mask(&P210_C0_Y));
+    let (c1_y, c1_uv) = (load_mask(&P210_C1_Y), load_mask(&P210_C1_UV));
+    let (c2_uv, c2_y) = (load_mask(&P210_C2_UV), load_mask(&P210_C2_Y));
+    for x in (0..n).step_by(24) {
+        let ys = split_groups_sse41(y, x);
+        let uvs = split_groups_sse41(uv, x);
+        for g in 0..4 {
+            let c0 = _mm_or_si128(
+                _mm_shuffle_epi8(uvs[g], c0_uv),
+                _mm_shuffle_epi8(ys[g], c0_y),
+            );
+            let c1 = _mm_or_si128(
+                _mm_shuffle_epi8(ys[g], c1_y),
+                _mm_shuffle_epi8(uvs[g], c1_uv),
+            );
+            let c2 = _mm_or_si128(
+                _mm_shuffle_epi8(uvs[g], c2_uv),
+                _mm_shuffle_epi8(ys[g], c2_y),
+            );
+            let w = _mm_or_si128(
+                c0,
+                _mm_or_si128(_mm_slli_epi32(c1, 10), _mm_slli_epi32(c2, 20)),
+            );
+            _mm_storeu_si128(
+                dst.as_mut_ptr().add(x / 24 * 64 + g * 16) as *mut __m128i,
+                w,
+            );
+        }
+    }
+    scalar::p210_to_v210_row(&y[n..], &uv[n..], &mut dst[n / 24 * 64..]);
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn uyvy_to_420_rows_avx2(
+    src0: &[u8],
+    src1: &[u8],
+    y0: &mut [u8],
+    y1: &mut [u8],
+    mut chroma: impl FnMut(usize, __m256i),
+) -> usize {
+    let n = y0.len() / 32 * 32;
+    let mask = _mm256_set1_epi16(0xff);
+    for x in (0..n).step_by(32) {
+        let a0 = _mm256_loadu_si256(src0.as_ptr().add(x * 2) as *const __m256i);
+        let a1 = _mm256_loadu_si256(src0.as_ptr().add(x * 2 + 32) as *const __m256i);
+        let b0 = _mm256_loadu_si256(src1.as_ptr().add(x * 2) as *const __m256i);
+        let b1 = _mm256_loadu_si256(src1.as_ptr().add(x * 2 + 32) as *const __m256i);
+        // Packing works within 128-bit lanes, so each result needs its middle quadwords swapped.
+        let ya = _mm256_packus_epi16(_mm256_srli_epi16(a0, 8), _mm256_srli_epi16(a1, 8));
+        let yb = _mm256_packus_epi16(_mm256_srli_epi16(b0, 8), _mm256_srli_epi16(b1, 8));
+        _mm256_storeu_si256(
+            y0.as_mut_ptr().add(x) as *mut __m256i,
+            _mm256_permute4x64_epi64(ya, 0b11_01_10_00),
+        );
+        _mm256_storeu_si256(
+            y1.as_mut_ptr().add(x) as *mut __m256i,
+            _mm256_permute4x64_epi64(yb, 0b11_01_10_00),
+        );
+        let ca = _mm256_packus_epi16(_mm256_and_si256(a0, mask), _mm256_and_si256(a1, mask));
+        let cb = _mm256_packus_epi16(_mm256_and_si256(b0, mask), _mm256_and_si256(b1, mask));
+        chroma(
+            x,
+            _mm256_permute4x64_epi64(_mm256_avg_epu8(ca, cb), 0b11_01_10_00),
+        );
+    }
+    n
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn uyvy_to_i420_rows_avx2(
+    src0: &[u8],
+    src1: &[u8],
+    y0: &mut [u8],
+    y1: &mut [u8],
+    u: &mut [u8],
+    v: &mut [u8],
+) {
+    let mask = _mm256_set1_epi16(0xff);
+    let zero = _mm256_setzero_si256();
+    let (up, vp) = (u.as_mut_ptr(), v.as_mut_ptr());
+    let n = uyvy_to_420_rows_avx2(src0, src1, y0, y1, |x, c| {
+        let uu = _mm256_packus_epi16(_mm256_and_si256(c, mask), zero);
+        let vv = _mm256_packus_epi16(_mm256_srli_epi16(c, 8), zero);
+        _mm_storeu_si128(
+            up.add(x / 2) as *mut __m128i,
+            _mm256_castsi256_si128(_mm256_permute4x64_epi64(uu, 0b11_01_10_00)),
+        );
+        _mm_storeu_si128(
+            vp.add(x / 2) as *mut __m128i,
+            _mm256_castsi256_si128(_mm256_permute4x64_epi64(vv, 0b11_01_10_00)),
+        );
+    });
+    uyvy_to_i420_rows_sse41(
+        &src0[n * 2..],
+        &src1[n * 2..],
+        &mut y0[n..],
+        &mut y1[n..],
+        &mut u[n / 2..],
+        &mut v[n / 2..],
+    );
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn uyvy_to_nv12_rows_avx2(
+    src0: &[u8],
+    src1: &[u8],
+    y0: &mut [u8],
+    y1: &mut [u8],
+    uv: &mut [u8],
+) {
+    let uvp = uv.as_mut_ptr();
+    let n = uyvy_to_420_rows_avx2(src0, src1, y0, y1, |x, c| {
+        _mm256_storeu_si256(uvp.add(x) as *mut __m256i, c);
+    });
+    uyvy_to_nv12_rows_sse41(
+        &src0[n * 2..],
+        &src1[n * 2..],
+        &mut y0[n..],
+        &mut y1[n..],
+        &mut uv[n..],
+    );
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn interleave_uyvy_avx2(dst: &mut [u8], x: usize, y: __m256i, uv: __m256i) {
+    // Unpacking works within 128-bit lanes: lo holds pixels 0-7 and 16-23, hi 8-15 and 24-31.
+    let lo = _mm256_unpacklo_epi8(uv, y);
+    let hi = _mm256_unpackhi_epi8(uv, y);
+    _mm256_storeu_si256(
+        dst.as_mut_ptr().add(x * 2) as *mut __m256i,
+        _mm256_permute2x128_si256(lo, hi, 0x20),
+    );
+    _mm256_storeu_si256(
+        dst.as_mut_ptr().add(x * 2 + 32) as *mut __m256i,
+        _mm256_permute2x128_si256(lo, hi, 0x31),
+    );
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn i420_to_uyvy_row_avx2(y: &[u8], u: &[u8], v: &[u8], dst: &mut [u8]) {
+    let n = y.len() / 32 * 32;
+    for x in (0..n).step_by(32) {
+        let yy = _mm256_loadu_si256(y.as_ptr().add(x) as *const __m256i);
+        let uu = _mm_loadu_si128(u.as_ptr().add(x / 2) as *const __m128i);
+        let vv = _mm_loadu_si128(v.as_ptr().add(x / 2) as *const __m128i);
+        let uv = _mm256_set_m128i(_mm_unpackhi_epi8(uu, vv), _mm_unpacklo_epi8(uu, vv));
+        interleave_uyvy_avx2(dst, x, yy, uv);
+    }
+    i420_to_uyvy_row_sse41(&y[n..], &u[n / 2..], &v[n / 2..], &mut dst[n * 2..]);
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn nv12_to_uyvy_row_avx2(y: &[u8], uv: &[u8], dst: &mut [u8]) {
+    let n = y.len() / 32 * 32;
+    for x in (0..n).step_by(32) {
+        let yy = _mm256_loadu_si256(y.as_ptr().add(x) as *const __m256i);
+        let uvv = _mm256_loadu_si256(uv.as_ptr().add(x) as *const __m256i);
+        interleave_uyvy_avx2(dst, x, yy, uvv);
+    }
+    nv12_to_uyvy_row_sse41(&y[n..], &uv[n..], &mut dst[n * 2..]);
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn matrix_avx2(k: [i32; 3], r: __m256i, g: __m256i, b: __m256i, offset: i32) -> __m256i {
+    let sum = _mm256_add_epi32(
+        _mm256_add_epi32(
+            _mm256_mullo_epi32(r, _mm256_set1_epi32(k[0])),
+            _mm256_mullo_epi32(g, _mm256_set1_epi32(k[1])),
+        ),
+        _mm256_add_epi32(
+            _mm256_mullo_epi32(b, _mm256_set1_epi32(k[2])),
+            _mm256_set1_epi32(128),
+        ),
+    );
+    _mm256_add_epi32(_mm256_srai_epi32(sum, 8), _mm256_set1_epi32(offset))
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn pair_avg_avx2(c: __m256i) -> __m256i {
+    _mm256_srli_epi32(
+        _mm256_add_epi32(
+            _mm256_add_epi32(c, _mm256_srli_si256(c, 4)),
+            _mm256_set1_epi32(1),
+        ),
+        1,
+    )
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn bgra_to_uyvy_row_avx2(src: &[u8], dst: &mut [u8]) {
+    let width = dst.len() / 2;
+    let n = width / 8 * 8;
+    let mask = _mm256_set1_epi32(0xff);
+    for x in (0..n).step_by(8) {
+        let p = _mm256_loadu_si256(src.as_ptr().add(x * 4) as *const __m256i);
+        let b = _mm256_and_si256(p, mask);
+        let g = _mm256_and_si256(_mm256_srli_epi32(p, 8), mask);
+        let r = _mm256_and_si256(_mm256_srli_epi32(p, 16), mask);
+        let y = matrix_avx2(REC709_Y, r, g, b, 16);
+        let (r, g, b) = (pair_avg_avx2(r), pair_avg_avx2(g), pair_avg_avx2(b));
+        let cb = matrix_avx2(REC709_CB, r, g, b, 128);
+        let cr = matrix_avx2(REC709_CR, r, g, b, 128);
+        let out = _mm256_or_si256(
+            _mm256_or_si256(cb, _mm256_slli_epi32(y, 8)),
+            _mm256_or_si256(
+                _mm256_slli_epi32(cr, 16),
+                _mm256_slli_epi32(_mm256_srli_si256(y, 4), 24),
+            ),
+        );
+        // Even lanes hold U Y V Y for each pixel pair; gather them into the low 128 bits.
+        let out = _mm256_permute4x64_epi64(_mm256_shuffle_epi32(out, 0b00_00_10_00), 0b00_00_10_00);
+        _mm_storeu_si128(
+            dst.as_mut_ptr().add(x * 2) as *mut __m128i,
+            _mm256_castsi256_si128(out),
+        );
+    }
+    bgra_to_uyvy_row_sse41(&src[n * 4..], &mut dst[n * 2..]);
+}
+
+pub(super) mod sse41 {
+    pub fn uyvy_to_i420_rows(
+        src0: &[u8],
+        src1: &[u8],
+        y0: &mut [u8],
+        y1: &mut [u8],
+        u: &mut [u8],
+        v: &mut [u8],
+    ) {
+        unsafe { super::uyvy_to_i420_rows_sse41(src0, src1, y0, y1, u, v) }
+    }
+
+    pub fn uyvy_to_nv12_rows(
+        src0: &[u8],
+        src1: &[u8],
+        y0: &mut [u8],
+        y1: &mut [u8],
+        uv: &mut [u8],
+    ) {
+        unsafe { super::uyvy_to_nv12_rows_sse41(src0, src1, y0, y1, uv) }
+    }
+
+    pub fn i420_to_uyvy_row(y: &[u8], u: &[u8], v: &[u8], dst: &mut [u8]) {
+        unsafe { super::i420_to_uyvy_row_sse41(y, u, v, dst) }
+    }
+
+    pub fn nv12_to_uyvy_row(y: &[u8], uv: &[u8], dst: &mut [u8]) {
+        unsafe { super::nv12_to_uyvy_row_sse41(y, uv, dst) }
+    }
+
+    pub fn bgra_to_uyvy_row(src: &[u8], dst: &mut [u8]) {
+        unsafe { super::bgra_to_uyvy_row_sse41(src, dst) }
+    }
+
+    pub fn v210_to_p210_row(src: &[u8], y: &mut [u16], uv: &mut [u16]) {
+        unsafe { super::v210_to_p210_row_sse41(src, y, uv) }
+    }
+
+    pub fn p210_to_v210_row(y: &[u16], uv: &[u16], dst: &mut [u8]) {
+        unsafe { super::p210_to_v210_row_sse41(y, uv, dst) }
+    }
+}
+
+pub(super) mod avx2 {
+    pub fn uyvy_to_i420_rows(
+        src0: &[u8],
+        src1: &[u8],
+        y0: &mut [u8],
+        y1: &mut [u8],
+        u: &mut [u8],
+        v: &mut [u8],
+    ) {
+        unsafe { super::uyvy_to_i420_rows_avx2(src0, src1, y0, y1, u, v) }
+    }
+
+    pub fn uyvy_to_nv12_rows(
+        src0: &[u8],
+        src1: &[u8],
+        y0: &mut [u8],
+        y1: &mut [u8],
+        uv: &mut [u8],
+    ) {
+        unsafe { super::uyvy_to_nv12_rows_avx2(src0, src1, y0, y1, uv) }
+    }
+
+    pub fn i420_to_uyvy_row(y: &[u8], u: &[u8], v: &[u8], dst: &mut [u8]) {
+        unsafe { super::i420_to_uyvy_row_avx2(y, u, v, dst) }
+    }
+
+    pub fn nv12_to_uyvy_row(y: &[u8], uv: &[u8], dst: &mut [u8]) {
+        unsafe { super::nv12_to_uyvy_row_avx2(y, uv, dst) }
+    }
+
+    pub fn bgra_to_uyvy_row(src: &[u8], dst: &mut [u8]) {
+        unsafe { super::bgra_to_uyvy_row_avx2(src, dst) }
+    }
+}
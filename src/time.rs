@@ -0,0 +1,463 @@
+//! Exact times and rates.
+//!
+//! The SDK passes times as an integer count of units of `1 / time_scale` seconds, with the scale
+//! chosen by the caller. `TimeValue` keeps a time together with its scale so it can be moved to
+//! another scale with explicit rounding, and `Rational` holds frame and sample rates exactly, so
+//! 29.97 is 30000/1001 rather than a float. Arithmetic goes through 128 bits and reports overflow
+//! instead of wrapping.
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Add, Mul, Neg, Sub},
+    str::FromStr,
+    time::Duration,
+};
+
+use simple_error::SimpleError;
+
+use DisplayMode;
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a.abs()
+}
+
+/// How a result that falls between two integers is rounded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Towards negative infinity.
+    Down,
+    /// Towards positive infinity.
+    Up,
+    /// To the nearest integer, with halves away from zero.
+    Nearest,
+    /// Fails unless the result is an integer.
+    Exact,
+}
+
+// `n / d` for a positive `d`.
+fn divide(n: i128, d: i128, rounding: Rounding) -> Option<i128> {
+    let (q, r) = (n.div_euclid(d), n.rem_euclid(d));
+    match rounding {
+        Rounding::Down => Some(q),
+        Rounding::Up => Some(if r == 0 { q } else { q + 1 }),
+        Rounding::Nearest => Some(if 2 * r > d || (2 * r == d && n > 0) {
+            q + 1
+        } else {
+            q
+        }),
+        Rounding::Exact => Some(q).filter(|_| r == 0),
+    }
+}
+
+fn to_i64(value: i128) -> Option<i64> {
+    if value < i64::MIN as i128 || value > i64::MAX as i128 {
+        None
+    } else {
+        Some(value as i64)
+    }
+}
+
+/// An exact ratio of integers, such as a frame rate of 30000/1001 frames per second.
+///
+/// Ratios are kept in lowest terms with a positive denominator, so equal ratios compare and hash
+/// equal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i64,
+    denominator: i64,
+}
+
+impl Rational {
+    pub const fn from_integer(value: i64) -> Rational {
+        Rational {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+
+    /// Fails if `denominator` is zero.
+    pub fn new(numerator: i64, denominator: i64) -> Result<Rational, SimpleError> {
+        Rational::reduce(numerator as i128, denominator as i128)
+            .ok_or_else(|| SimpleError::new(format!("{}/{} isn't a ratio", numerator, denominator)))
+    }
+
+    fn reduce(numerator: i128, denominator: i128) -> Option<Rational> {
+        if denominator == 0 {
+            return None;
+        }
+        let g = gcd(numerator, denominator) * denominator.signum();
+        Some(Rational {
+            numerator: to_i64(numerator / g)?,
+            denominator: to_i64(denominator / g)?,
+        })
+    }
+
+    /// The frame rate for a frame duration and time scale as returned by
+    /// `DisplayModeInfo::get_frame_rate`, or `None` if the duration is zero.
+    pub fn from_frame_rate(frame_duration: i64, time_scale: i64) -> Option<Rational> {
+        Rational::new(time_scale, frame_duration).ok()
+    }
+
+    /// The frame rate of a display mode.
+    pub fn for_mode(display_mode: DisplayMode) -> Option<Rational> {
+        let spec = display_mode.info()?;
+        Rational::from_frame_rate(spec.frame_duration, spec.time_scale)
+    }
+
+    pub fn numerator(self) -> i64 {
+        self.numerator
+    }
+
+    pub fn denominator(self) -> i64 {
+        self.denominator
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+
+    /// `1 / self`, or `None` for zero.
+    pub fn recip(self) -> Option<Rational> {
+        Rational::reduce(self.denominator as i128, self.numerator as i128)
+    }
+
+    /// Rounds to an integer.
+    pub fn round(self, rounding: Rounding) -> Option<i64> {
+        to_i64(divide(
+            self.numerator as i128,
+            self.denominator as i128,
+            rounding,
+        )?)
+    }
+
+    pub fn checked_add(self, other: Rational) -> Option<Rational> {
+        Rational::reduce(
+            self.numerator as i128 * other.denominator as i128
+                + other.numerator as i128 * self.denominator as i128,
+            self.denominator as i128 * other.denominator as i128,
+        )
+    }
+
+    pub fn checked_sub(self, other: Rational) -> Option<Rational> {
+        Rational::reduce(
+            self.numerator as i128 * other.denominator as i128
+                - other.numerator as i128 * self.denominator as i128,
+            self.denominator as i128 * other.denominator as i128,
+        )
+    }
+
+    pub fn checked_mul(self, other: Rational) -> Option<Rational> {
+        Rational::reduce(
+            self.numerator as i128 * other.numerator as i128,
+            self.denominator as i128 * other.denominator as i128,
+        )
+    }
+
+    /// `None` if `other` is zero or the result overflows.
+    pub fn checked_div(self, other: Rational) -> Option<Rational> {
+        self.checked_mul(other.recip()?)
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        (self.numerator as i128 * other.denominator as i128)
+            .cmp(&(other.numerator as i128 * self.denominator as i128))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Panics if the numerator is `i64::MIN`.
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            numerator: self.numerator.checked_neg().expect("rational overflow"),
+            denominator: self.denominator,
+        }
+    }
+}
+
+/// Panics on overflow, like integer arithmetic in a debug build; see `checked_add`.
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, other: Rational) -> Rational {
+        self.checked_add(other).expect("rational overflow")
+    }
+}
+
+/// Panics on overflow; see `checked_sub`.
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Rational) -> Rational {
+        self.checked_sub(other).expect("rational overflow")
+    }
+}
+
+/// Panics on overflow; see `checked_mul`.
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Rational) -> Rational {
+        self.checked_mul(other).expect("rational overflow")
+    }
+}
+
+/// Formats as `numerator/denominator`, or just the numerator for an integer.
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+/// Parses `numerator/denominator` or an integer.
+impl FromStr for Rational {
+    type Err = SimpleError;
+
+    fn from_str(s: &str) -> Result<Rational, SimpleError> {
+        let invalid = || SimpleError::new(format!("invalid ratio: {}", s));
+        let mut parts = s.trim().splitn(2, '/');
+        let numerator = parts.next().unwrap_or("").trim();
+        let numerator = numerator.parse().map_err(|_| invalid())?;
+        let denominator = match parts.next() {
+            Some(denominator) => denominator.trim().parse().map_err(|_| invalid())?,
+            None => 1,
+        };
+        Rational::new(numerator, denominator)
+    }
+}
+
+/// A time or duration of `value` units of `1 / scale` seconds, as the SDK passes them.
+///
+/// `scale` must be positive. Values compare by the time they stand for, so 1/25 equals 40/1000,
+/// and a negative scale is taken as the same time with both signs flipped. A zero scale stands
+/// for no time at all: such a value only equals itself, and sorts after every time.
+#[derive(Clone, Copy, Debug)]
+pub struct TimeValue {
+    pub value: i64,
+    pub scale: i64,
+}
+
+impl TimeValue {
+    pub const fn new(value: i64, scale: i64) -> TimeValue {
+        TimeValue { value, scale }
+    }
+
+    /// The time of a frame at a frame rate, counting from frame 0 at time 0, in units of the
+    /// rate's numerator so that it's exact. Fails if the rate isn't positive or on overflow.
+    pub fn from_frames(index: i64, frame_rate: Rational) -> Option<TimeValue> {
+        if frame_rate.numerator <= 0 {
+            return None;
+        }
+        Some(TimeValue::new(
+            index.checked_mul(frame_rate.denominator)?,
+            frame_rate.numerator,
+        ))
+    }
+
+    /// The frame that is showing at this time, or another rounding of its frame index. Fails if
+    /// the rate isn't positive, on overflow or, with `Rounding::Exact`, if the time doesn't fall
+    /// on a frame.
+    pub fn to_frames(self, frame_rate: Rational, rounding: Rounding) -> Option<i64> {
+        if frame_rate.numerator <= 0 || self.scale <= 0 {
+            return None;
+        }
+        to_i64(divide(
+            self.value as i128 * frame_rate.numerator as i128,
+            self.scale as i128 * frame_rate.denominator as i128,
+            rounding,
+        )?)
+    }
+
+    /// Rounds a `Duration` to a time in units of `1 / scale` seconds.
+    pub fn from_duration(duration: Duration, scale: i64, rounding: Rounding) -> Option<TimeValue> {
+        if scale <= 0 {
+            return None;
+        }
+        let value = divide(
+            duration.as_nanos() as i128 * scale as i128,
+            1_000_000_000,
+            rounding,
+        )?;
+        Some(TimeValue::new(to_i64(value)?, scale))
+    }
+
+    /// The time as a `Duration`, rounded down to a nanosecond, or `None` if it's negative.
+    pub fn to_duration(self) -> Option<Duration> {
+        if self.value < 0 || self.scale <= 0 {
+            return None;
+        }
+        let nanos = self.value as u128 * 1_000_000_000 / self.scale as u128;
+        Some(Duration::new(
+            (nanos / 1_000_000_000) as u64,
+            (nanos % 1_000_000_000) as u32,
+        ))
+    }
+
+    /// The time in seconds, as an exact ratio.
+    pub fn to_rational(self) -> Option<Rational> {
+        Rational::new(self.value, self.scale).ok()
+    }
+
+    pub fn to_seconds_f64(self) -> f64 {
+        self.value as f64 / self.scale as f64
+    }
+
+    /// The same time in units of `1 / scale` seconds, rounded. Fails if either scale isn't
+    /// positive, on overflow or, with `Rounding::Exact`, if the time can't be held exactly.
+    pub fn rescale(self, scale: i64, rounding: Rounding) -> Option<TimeValue> {
+        if scale <= 0 || self.scale <= 0 {
+            return None;
+        }
+        let value = divide(
+            self.value as i128 * scale as i128,
+            self.scale as i128,
+            rounding,
+        )?;
+        Some(TimeValue::new(to_i64(value)?, scale))
+    }
+
+    /// Both times in the smallest scale that holds them exactly.
+    pub fn to_common_scale(self, other: TimeValue) -> Option<(TimeValue, TimeValue)> {
+        if self.scale <= 0 || other.scale <= 0 {
+            return None;
+        }
+        let scale =
+            self.scale as i128 / gcd(self.scale as i128, other.scale as i128) * other.scale as i128;
+        let scale = to_i64(scale)?;
+        Some((
+            self.rescale(scale, Rounding::Exact)?,
+            other.rescale(scale, Rounding::Exact)?,
+        ))
+    }
+
+    /// The sum, in the smallest scale that holds it exactly.
+    pub fn checked_add(self, other: TimeValue) -> Option<TimeValue> {
+        let (a, b) = self.to_common_scale(other)?;
+        Some(TimeValue::new(a.value.checked_add(b.value)?, a.scale))
+    }
+
+    /// The difference, in the smallest scale that holds it exactly.
+    pub fn checked_sub(self, other: TimeValue) -> Option<TimeValue> {
+        let (a, b) = self.to_common_scale(other)?;
+        Some(TimeValue::new(a.value.checked_sub(b.value)?, a.scale))
+    }
+
+    pub fn checked_mul(self, factor: i64) -> Option<TimeValue> {
+        Some(TimeValue::new(self.value.checked_mul(factor)?, self.scale))
+    }
+
+    // The time as a ratio with a positive scale, or `None` for a zero scale.
+    fn normalized(self) -> Option<(i128, i128)> {
+        match self.scale.signum() {
+            0 => None,
+            sign => Some((
+                self.value as i128 * sign as i128,
+                self.scale as i128 * sign as i128,
+            )),
+        }
+    }
+
+    // As `normalized`, in lowest terms, for hashing.
+    fn reduced(self) -> Option<(i128, i128)> {
+        let (value, scale) = self.normalized()?;
+        let g = gcd(value, scale);
+        Some((value / g, scale / g))
+    }
+}
+
+impl PartialEq for TimeValue {
+    fn eq(&self, other: &TimeValue) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TimeValue {}
+
+impl Hash for TimeValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self.reduced() {
+            Some(reduced) => reduced.hash(state),
+            None => self.value.hash(state),
+        }
+    }
+}
+
+impl Ord for TimeValue {
+    fn cmp(&self, other: &TimeValue) -> Ordering {
+        match (self.normalized(), other.normalized()) {
+            (Some((a, b)), Some((c, d))) => (a * d).cmp(&(c * b)),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => self.value.cmp(&other.value),
+        }
+    }
+}
+
+impl PartialOrd for TimeValue {
+    fn partial_cmp(&self, other: &TimeValue) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Panics if the value is `i64::MIN`.
+impl Neg for TimeValue {
+    type Output = TimeValue;
+
+    fn neg(self) -> TimeValue {
+        TimeValue::new(self.value.checked_neg().expect("time overflow"), self.scale)
+    }
+}
+
+/// Panics on overflow, like integer arithmetic in a debug build; see `checked_add`.
+impl Add for TimeValue {
+    type Output = TimeValue;
+
+    fn add(self, other: TimeValue) -> TimeValue {
+        self.checked_add(other).expect("time overflow")
+    }
+}
+
+/// Panics on overflow; see `checked_sub`.
+impl Sub for TimeValue {
+    type Output = TimeValue;
+
+    fn sub(self, other: TimeValue) -> TimeValue {
+        self.checked_sub(other).expect("time overflow")
+    }
+}
+
+/// Panics on overflow; see `checked_mul`.
+impl Mul<i64> for TimeValue {
+    type Output = TimeValue;
+
+    fn mul(self, factor: i64) -> TimeValue {
+        self.checked_mul(factor).expect("time overflow")
+    }
+}
+
+/// Formats as `value/scale`.
+impl fmt::Display for TimeValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.value, self.scale)
+    }
+}
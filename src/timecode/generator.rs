@@ -0,0 +1,249 @@
+//! Running timecode for playout.
+//!
+//! A `TimecodeGenerator` counts output frames of a display mode and hands out the timecode to
+//! stamp on each one, in every format the mode carries. Above 30 frames per second, LTC and VITC
+//! can only count to 29, so they count pairs of frames, as SMPTE ST 12-1 does, and set the field
+//! mark on the second frame of each pair. High frame rate modes count groups of four, with the
+//! mark on the second half of each group, and also carry the full count in
+//! `FORMAT_RP188_HIGH_FRAME_RATE`.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use simple_error::SimpleError;
+
+use super::{SmpteTimecode, TimecodeRate};
+use {
+    DisplayMode, DisplayModeFamily, Error, Frame, FrameTimecode, MutableVideoFrame, ScanType,
+    Timecode, TimecodeFlags, TimecodeFormat,
+};
+
+/// The timecode of one output frame.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimecodeStamp {
+    /// The frame's label, counting every frame of the mode.
+    pub timecode: SmpteTimecode,
+    /// The components to write in each timecode format the mode carries.
+    pub components: Vec<(TimecodeFormat, FrameTimecode)>,
+    pub user_bits: u32,
+}
+
+impl TimecodeStamp {
+    /// The components for a format, if the mode carries it.
+    pub fn get(&self, format: TimecodeFormat) -> Option<FrameTimecode> {
+        self.components
+            .iter()
+            .find(|(f, _)| *f == format)
+            .map(|(_, timecode)| *timecode)
+    }
+
+    /// Sets the timecode in every format on a frame, with the user bits if they're set.
+    pub fn write(&self, frame: &mut MutableVideoFrame) -> Result<(), Error> {
+        for (format, timecode) in &self.components {
+            frame.set_timecode(*format, timecode)?;
+            if self.user_bits != 0 {
+                frame.set_timecode_user_bits(*format, self.user_bits)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the timecode in every format on an owned frame. `Frame` doesn't carry user bits.
+    pub fn write_to_frame(&self, frame: &mut Frame) {
+        for (format, timecode) in &self.components {
+            frame.set_timecode(*format, *timecode);
+        }
+    }
+}
+
+/// Counts timecode for consecutive output frames of a display mode.
+///
+/// Each call to `next` returns the timecode of the next frame and advances by one frame, wrapping
+/// at midnight. The count can be moved with `seek` or locked to a captured timecode with
+/// `jam_sync`.
+#[derive(Clone, Debug)]
+pub struct TimecodeGenerator {
+    rate: TimecodeRate,
+    // Frames per LTC and VITC label.
+    group: u32,
+    interlaced: bool,
+    standard_definition: bool,
+    frame_duration: i64,
+    time_scale: i64,
+    next_frame: u32,
+    user_bits: u32,
+}
+
+impl TimecodeGenerator {
+    /// Starts counting at `start`, which counts every frame of the mode, so 59.94p labels run to
+    /// 59. The count is drop frame if `start` is, which only 1000/1001 rates allow.
+    pub fn new(
+        display_mode: DisplayMode,
+        start: SmpteTimecode,
+    ) -> Result<TimecodeGenerator, SimpleError> {
+        let mut generator = TimecodeGenerator::for_mode(display_mode)?;
+        let frames_per_second = generator.rate.frames_per_second();
+        let fractional =
+            generator.time_scale != frames_per_second as i64 * generator.frame_duration;
+        if start.drop_frame() && !fractional {
+            return Err(SimpleError::new(format!(
+                "drop frame timecode needs a 1000/1001 rate, not {}",
+                display_mode
+            )));
+        }
+        generator.rate = TimecodeRate::new(frames_per_second, start.drop_frame())?;
+        generator.seek(start)?;
+        Ok(generator)
+    }
+
+    /// Starts counting at the label for a time of day, given as the time since midnight. The
+    /// count runs at the mode's real frame rate, so drop frame labels track the time of day and
+    /// non-drop frame labels at 1000/1001 rates fall behind it.
+    pub fn from_time_of_day(
+        display_mode: DisplayMode,
+        since_midnight: Duration,
+    ) -> Result<TimecodeGenerator, SimpleError> {
+        let mut generator = TimecodeGenerator::for_mode(display_mode)?;
+        let frames = since_midnight.as_nanos() * generator.time_scale as u128
+            / (generator.frame_duration as u128 * 1_000_000_000);
+        let frames = (frames % generator.rate.frames_per_day() as u128) as u32;
+        generator.next_frame = frames;
+        Ok(generator)
+    }
+
+    /// Starts counting at the current time of day in UTC, from the system clock.
+    pub fn from_system_clock(display_mode: DisplayMode) -> Result<TimecodeGenerator, SimpleError> {
+        let since_epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| SimpleError::new("system clock is before 1970"))?;
+        let day = 24 * 60 * 60;
+        let since_midnight = Duration::new(since_epoch.as_secs() % day, since_epoch.subsec_nanos());
+        TimecodeGenerator::from_time_of_day(display_mode, since_midnight)
+    }
+
+    fn for_mode(display_mode: DisplayMode) -> Result<TimecodeGenerator, SimpleError> {
+        let spec = display_mode
+            .info()
+            .ok_or_else(|| SimpleError::new("unknown display mode"))?;
+        let rate = TimecodeRate::for_mode(display_mode)
+            .ok_or_else(|| SimpleError::new(format!("{} has no timecode rate", display_mode)))?;
+        Ok(TimecodeGenerator {
+            rate,
+            group: rate.group(),
+            interlaced: spec.scan_type() == ScanType::Interlaced,
+            standard_definition: spec.family == DisplayModeFamily::Sd,
+            frame_duration: spec.frame_duration,
+            time_scale: spec.time_scale,
+            next_frame: 0,
+            user_bits: 0,
+        })
+    }
+
+    pub fn rate(&self) -> TimecodeRate {
+        self.rate
+    }
+
+    /// The label of the frame the next call to `next` returns.
+    pub fn peek(&self) -> SmpteTimecode {
+        SmpteTimecode::from_frames(self.next_frame as i64, self.rate)
+    }
+
+    /// Continues counting from `timecode`, which counts every frame of the mode.
+    pub fn seek(&mut self, timecode: SmpteTimecode) -> Result<(), SimpleError> {
+        self.next_frame = timecode.to_frames(self.rate)?;
+        Ok(())
+    }
+
+    pub fn user_bits(&self) -> u32 {
+        self.user_bits
+    }
+
+    /// Sets the user bits stamped on every following frame.
+    pub fn set_user_bits(&mut self, user_bits: u32) {
+        self.user_bits = user_bits;
+    }
+
+    /// Locks the count to a timecode captured in the same display mode, as LTC or RP188 carries
+    /// it, so frame pairs above 30 frames per second are told apart by the field mark. The next
+    /// frame is stamped `offset` frames after the captured one, typically 1 plus the frames
+    /// buffered between capture and playout.
+    pub fn jam_sync(&mut self, captured: &FrameTimecode, offset: i64) -> Result<(), SimpleError> {
+        let timecode = SmpteTimecode::from_legacy(captured, self.group)?;
+        self.seek(timecode.add_frames(offset, self.rate)?)
+    }
+
+    /// Like `jam_sync`, for an SDK timecode such as one from `VideoInputFrame::get_timecode`. Also
+    /// takes on its user bits.
+    pub fn jam_sync_timecode(&mut self, captured: &Timecode, offset: i64) -> Result<(), Error> {
+        let (hours, minutes, seconds, frames) = captured.get_components()?;
+        let captured_components = FrameTimecode {
+            hours,
+            minutes,
+            seconds,
+            frames,
+            flags: captured.get_flags(),
+        };
+        self.jam_sync(&captured_components, offset)
+            .map_err(|_| Error::new())?;
+        self.user_bits = captured.get_user_bits()?;
+        Ok(())
+    }
+
+    fn stamp(&self, frame: u32) -> TimecodeStamp {
+        let timecode = SmpteTimecode::from_frames(frame as i64, self.rate);
+        let drop_frame = if timecode.drop_frame() {
+            TimecodeFlags::DROP_FRAME
+        } else {
+            TimecodeFlags::DEFAULT
+        };
+        let mark = if timecode.frames() as u32 % self.group >= self.group.div_ceil(2) {
+            TimecodeFlags::FIELD_MARK
+        } else {
+            TimecodeFlags::DEFAULT
+        };
+        let legacy = FrameTimecode {
+            frames: (timecode.frames() as u32 / self.group) as u8,
+            flags: drop_frame | mark,
+            ..FrameTimecode::from(timecode)
+        };
+        let field2 = FrameTimecode {
+            flags: legacy.flags | TimecodeFlags::FIELD_MARK,
+            ..legacy
+        };
+
+        let mut components = vec![
+            (TimecodeFormat::FORMAT_RP188_VITC1, legacy),
+            (TimecodeFormat::FORMAT_RP188_LTC, legacy),
+        ];
+        if self.interlaced {
+            components.push((TimecodeFormat::FORMAT_RP188_VITC2, field2));
+        }
+        if self.rate.frames_per_second() > 60 {
+            components.push((
+                TimecodeFormat::FORMAT_RP188_HIGH_FRAME_RATE,
+                FrameTimecode::from(timecode),
+            ));
+        }
+        if self.standard_definition {
+            components.push((TimecodeFormat::FORMAT_VITC, legacy));
+            if self.interlaced {
+                components.push((TimecodeFormat::FORMAT_VITC_FIELD2, field2));
+            }
+        }
+        TimecodeStamp {
+            timecode,
+            components,
+            user_bits: self.user_bits,
+        }
+    }
+}
+
+impl Iterator for TimecodeGenerator {
+    type Item = TimecodeStamp;
+
+    /// Returns the timecode of the next frame. Never returns `None`.
+    fn next(&mut self) -> Option<TimecodeStamp> {
+        let stamp = self.stamp(self.next_frame);
+        self.next_frame = (self.next_frame + 1) % self.rate.frames_per_day();
+        Some(stamp)
+    }
+}
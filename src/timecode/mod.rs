@@ -0,0 +1,363 @@
+//! SMPTE timecode arithmetic without the driver.
+//!
+//! A `SmpteTimecode` is a time of day label, hh:mm:ss:ff, and whether it counts in drop frame. It
+//! takes a `TimecodeRate` to turn labels into frame counts and back. Drop frame skips frame
+//! labels 00 and 01 at the start of each minute except every tenth, scaled up for multiples of
+//! 30 frames per second, so that the label keeps within a few frames of real time at 29.97, 59.94
+//! and 119.88.
+
+use std::{fmt, str::FromStr};
+
+use simple_error::SimpleError;
+
+use {DisplayMode, Error, FrameTimecode, Timecode, TimecodeFlags};
+
+pub mod generator;
+pub mod monitor;
+
+/// How timecode counts: the number of frame labels per second and whether it drops frames.
+///
+/// Fractional rates count like the integer rate above them, so 23.98 counts as 24 and 29.97 as 30,
+/// in drop or non-drop frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TimecodeRate {
+    frames_per_second: u32,
+    drop_frame: bool,
+}
+
+impl TimecodeRate {
+    pub const RATE_23_98: TimecodeRate = TimecodeRate::nominal(24, false);
+    pub const RATE_24: TimecodeRate = TimecodeRate::nominal(24, false);
+    pub const RATE_25: TimecodeRate = TimecodeRate::nominal(25, false);
+    pub const RATE_29_97_DF: TimecodeRate = TimecodeRate::nominal(30, true);
+    pub const RATE_29_97_NDF: TimecodeRate = TimecodeRate::nominal(30, false);
+    pub const RATE_30: TimecodeRate = TimecodeRate::nominal(30, false);
+    pub const RATE_48: TimecodeRate = TimecodeRate::nominal(48, false);
+    pub const RATE_50: TimecodeRate = TimecodeRate::nominal(50, false);
+    pub const RATE_59_94_DF: TimecodeRate = TimecodeRate::nominal(60, true);
+    pub const RATE_59_94_NDF: TimecodeRate = TimecodeRate::nominal(60, false);
+    pub const RATE_60: TimecodeRate = TimecodeRate::nominal(60, false);
+    pub const RATE_96: TimecodeRate = TimecodeRate::nominal(96, false);
+    pub const RATE_100: TimecodeRate = TimecodeRate::nominal(100, false);
+    pub const RATE_119_88_DF: TimecodeRate = TimecodeRate::nominal(120, true);
+    pub const RATE_119_88_NDF: TimecodeRate = TimecodeRate::nominal(120, false);
+    pub const RATE_120: TimecodeRate = TimecodeRate::nominal(120, false);
+
+    const fn nominal(frames_per_second: u32, drop_frame: bool) -> TimecodeRate {
+        TimecodeRate {
+            frames_per_second,
+            drop_frame,
+        }
+    }
+
+    /// Fails unless `frames_per_second` is between 1 and 120, and for drop frame, a multiple of 30.
+    pub fn new(frames_per_second: u32, drop_frame: bool) -> Result<TimecodeRate, SimpleError> {
+        if frames_per_second == 0 || frames_per_second > 120 {
+            return Err(SimpleError::new(format!(
+                "{} frames per second is outside 1-120",
+                frames_per_second
+            )));
+        }
+        if drop_frame && !frames_per_second.is_multiple_of(30) {
+            return Err(SimpleError::new(format!(
+                "{} frames per second can't drop frames",
+                frames_per_second
+            )));
+        }
+        Ok(TimecodeRate::nominal(frames_per_second, drop_frame))
+    }
+
+    /// The rate for a frame rate as returned by `DisplayModeInfo::get_frame_rate`. 1000/1001 rates
+    /// use drop frame where they can. Returns `None` for rates that aren't an integer or 1000/1001
+    /// times one.
+    pub fn from_frame_rate(frame_duration: i64, time_scale: i64) -> Option<TimecodeRate> {
+        if frame_duration <= 0 || time_scale <= 0 {
+            return None;
+        }
+        let frames_per_second = (time_scale as f64 / frame_duration as f64).round() as i64;
+        let fractional = time_scale * 1001 == frames_per_second * frame_duration * 1000;
+        if !fractional && time_scale != frames_per_second * frame_duration {
+            return None;
+        }
+        let drop_frame = fractional && frames_per_second % 30 == 0;
+        TimecodeRate::new(frames_per_second as u32, drop_frame).ok()
+    }
+
+    /// The rate for a display mode from the catalog. Interlaced modes count frames, not fields.
+    pub fn for_mode(display_mode: DisplayMode) -> Option<TimecodeRate> {
+        let spec = display_mode.info()?;
+        TimecodeRate::from_frame_rate(spec.frame_duration, spec.time_scale)
+    }
+
+    pub fn frames_per_second(self) -> u32 {
+        self.frames_per_second
+    }
+
+    pub fn drop_frame(self) -> bool {
+        self.drop_frame
+    }
+
+    /// The number of frames from 00:00:00:00 to the next midnight.
+    pub fn frames_per_day(self) -> u32 {
+        24 * 6 * self.frames_per_ten_minutes()
+    }
+
+    // The frames counted by each LTC and VITC label, which can only count to 29: 1 up to 30 frames
+    // per second, 2 up to 60 and 4 above that.
+    pub(crate) fn group(self) -> u32 {
+        let fps = self.frames_per_second;
+        (1..=fps)
+            .find(|group| fps.is_multiple_of(*group) && fps / group <= 30)
+            .unwrap_or(1)
+    }
+
+    // Labels skipped at the start of each minute that isn't a multiple of ten.
+    fn dropped_per_minute(self) -> u32 {
+        if self.drop_frame {
+            self.frames_per_second / 15
+        } else {
+            0
+        }
+    }
+
+    fn frames_per_ten_minutes(self) -> u32 {
+        600 * self.frames_per_second - 9 * self.dropped_per_minute()
+    }
+}
+
+impl fmt::Display for TimecodeRate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            self.frames_per_second,
+            if self.drop_frame { "DF" } else { "NDF" }
+        )
+    }
+}
+
+/// An SMPTE timecode label, independent of the SDK.
+///
+/// Labels order by time of day. They're checked against a rate when converted to a frame count,
+/// so a label can hold a frame number or a dropped label that the rate doesn't allow until then.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SmpteTimecode {
+    hours: u8,
+    minutes: u8,
+    seconds: u8,
+    frames: u8,
+    drop_frame: bool,
+}
+
+impl SmpteTimecode {
+    /// Fails if the hours, minutes or seconds are out of range.
+    pub fn new(
+        hours: u8,
+        minutes: u8,
+        seconds: u8,
+        frames: u8,
+        drop_frame: bool,
+    ) -> Result<SmpteTimecode, SimpleError> {
+        if hours > 23 || minutes > 59 || seconds > 59 {
+            return Err(SimpleError::new(format!(
+                "{:02}:{:02}:{:02} isn't a time of day",
+                hours, minutes, seconds
+            )));
+        }
+        Ok(SmpteTimecode {
+            hours,
+            minutes,
+            seconds,
+            frames,
+            drop_frame,
+        })
+    }
+
+    /// The label `frames` frames after midnight. Counts outside a day wrap around it, so -1 is the
+    /// last frame of the day.
+    pub fn from_frames(frames: i64, rate: TimecodeRate) -> SmpteTimecode {
+        let fps = rate.frames_per_second as u64;
+        let dropped = rate.dropped_per_minute() as u64;
+        let mut frames = frames.rem_euclid(rate.frames_per_day() as i64) as u64;
+        if dropped > 0 {
+            let per_ten_minutes = rate.frames_per_ten_minutes() as u64;
+            let per_minute = 60 * fps - dropped;
+            let (tens, rest) = (frames / per_ten_minutes, frames % per_ten_minutes);
+            frames += 9 * dropped * tens + dropped * (rest.saturating_sub(dropped) / per_minute);
+        }
+        let seconds = frames / fps;
+        SmpteTimecode {
+            hours: (seconds / 3600) as u8,
+            minutes: (seconds / 60 % 60) as u8,
+            seconds: (seconds % 60) as u8,
+            frames: (frames % fps) as u8,
+            drop_frame: rate.drop_frame,
+        }
+    }
+
+    /// The number of frames since midnight. Fails if the label doesn't exist at `rate`: the frame
+    /// number is too high, it's a dropped label, or it disagrees with the rate about drop frame.
+    pub fn to_frames(&self, rate: TimecodeRate) -> Result<u32, SimpleError> {
+        if self.drop_frame != rate.drop_frame {
+            return Err(SimpleError::new(format!(
+                "{} can't be counted at {}",
+                self, rate
+            )));
+        }
+        let dropped = rate.dropped_per_minute();
+        let is_dropped =
+            self.seconds == 0 && !self.minutes.is_multiple_of(10) && (self.frames as u32) < dropped;
+        if self.frames as u32 >= rate.frames_per_second || is_dropped {
+            return Err(SimpleError::new(format!(
+                "{} doesn't exist at {}",
+                self, rate
+            )));
+        }
+        let minutes = 60 * self.hours as u32 + self.minutes as u32;
+        let seconds = 60 * minutes + self.seconds as u32;
+        Ok(seconds * rate.frames_per_second + self.frames as u32
+            - dropped * (minutes - minutes / 10))
+    }
+
+    /// The label `frames` frames later, or earlier if negative, wrapping around midnight.
+    pub fn add_frames(
+        &self,
+        frames: i64,
+        rate: TimecodeRate,
+    ) -> Result<SmpteTimecode, SimpleError> {
+        Ok(SmpteTimecode::from_frames(
+            self.to_frames(rate)? as i64 + frames,
+            rate,
+        ))
+    }
+
+    /// The label `frames` frames earlier, wrapping around midnight.
+    pub fn sub_frames(
+        &self,
+        frames: i64,
+        rate: TimecodeRate,
+    ) -> Result<SmpteTimecode, SimpleError> {
+        self.add_frames(-frames, rate)
+    }
+
+    /// The number of frames from `earlier` to this label, which is negative if `earlier` is
+    /// later in the day.
+    pub fn frames_since(
+        &self,
+        earlier: &SmpteTimecode,
+        rate: TimecodeRate,
+    ) -> Result<i64, SimpleError> {
+        Ok(self.to_frames(rate)? as i64 - earlier.to_frames(rate)? as i64)
+    }
+
+    /// Copies the components and drop frame flag of an SDK timecode.
+    pub fn from_timecode(timecode: &Timecode) -> Result<SmpteTimecode, Error> {
+        let (hours, minutes, seconds, frames) = timecode.get_components()?;
+        let drop_frame = timecode.get_flags().contains(TimecodeFlags::DROP_FRAME);
+        SmpteTimecode::new(hours, minutes, seconds, frames, drop_frame).map_err(|_| Error::new())
+    }
+
+    pub fn from_frame_timecode(timecode: &FrameTimecode) -> Result<SmpteTimecode, SimpleError> {
+        SmpteTimecode::new(
+            timecode.hours,
+            timecode.minutes,
+            timecode.seconds,
+            timecode.frames,
+            timecode.flags.contains(TimecodeFlags::DROP_FRAME),
+        )
+    }
+
+    // The label of the first frame of a group counted by an LTC or VITC label, or of the second
+    // half of the group if the field mark is set.
+    pub(crate) fn from_legacy(
+        timecode: &FrameTimecode,
+        group: u32,
+    ) -> Result<SmpteTimecode, SimpleError> {
+        let second_half = timecode.flags.contains(TimecodeFlags::FIELD_MARK) && group > 1;
+        let frames = timecode.frames as u32 * group + if second_half { group / 2 } else { 0 };
+        if frames > u8::MAX as u32 {
+            return Err(SimpleError::new("frame number is out of range"));
+        }
+        SmpteTimecode::from_frame_timecode(&FrameTimecode {
+            frames: frames as u8,
+            ..*timecode
+        })
+    }
+
+    pub fn hours(&self) -> u8 {
+        self.hours
+    }
+
+    pub fn minutes(&self) -> u8 {
+        self.minutes
+    }
+
+    pub fn seconds(&self) -> u8 {
+        self.seconds
+    }
+
+    pub fn frames(&self) -> u8 {
+        self.frames
+    }
+
+    pub fn drop_frame(&self) -> bool {
+        self.drop_frame
+    }
+}
+
+impl From<SmpteTimecode> for FrameTimecode {
+    fn from(timecode: SmpteTimecode) -> FrameTimecode {
+        FrameTimecode {
+            hours: timecode.hours,
+            minutes: timecode.minutes,
+            seconds: timecode.seconds,
+            frames: timecode.frames,
+            flags: if timecode.drop_frame {
+                TimecodeFlags::DROP_FRAME
+            } else {
+                TimecodeFlags::DEFAULT
+            },
+        }
+    }
+}
+
+/// Formats as hh:mm:ss:ff, or hh:mm:ss;ff in drop frame.
+impl fmt::Display for SmpteTimecode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{}{:02}",
+            self.hours,
+            self.minutes,
+            self.seconds,
+            if self.drop_frame { ';' } else { ':' },
+            self.frames
+        )
+    }
+}
+
+/// Parses hh:mm:ss:ff, or drop frame hh:mm:ss;ff. A `.` before the frames also means drop frame,
+/// as some equipment writes it.
+impl FromStr for SmpteTimecode {
+    type Err = SimpleError;
+
+    fn from_str(s: &str) -> Result<SmpteTimecode, SimpleError> {
+        let invalid = || SimpleError::new(format!("invalid timecode {:?}", s));
+        let split = s.rfind([':', ';', '.']).ok_or_else(invalid)?;
+        let drop_frame = !s[split..].starts_with(':');
+        let mut fields = s[..split].split(':').chain(Some(&s[split + 1..]));
+        let mut components = [0u8; 4];
+        for component in components.iter_mut() {
+            let field = fields.next().ok_or_else(invalid)?;
+            if field.is_empty() || field.len() > 3 || !field.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            *component = field.parse().map_err(|_| invalid())?;
+        }
+        if fields.next().is_some() {
+            return Err(invalid());
+        }
+        let [hours, minutes, seconds, frames] = components;
+        SmpteTimecode::new(hours, minutes, seconds, frames, drop_frame)
+    }
+}
@@ -0,0 +1,340 @@
+//! Timecode continuity on capture.
+//!
+//! A `TimecodeMonitor` checks the timecode of each captured frame against the frame's stream
+//! time, which keeps counting frames whatever the timecode does. It reports when the timecode
+//! jumps ahead, goes back, freezes, switches between drop and non-drop frame or goes missing, and
+//! keeps counts of each for logging.
+
+use std::fmt;
+
+use simple_error::SimpleError;
+
+use super::{SmpteTimecode, TimecodeRate};
+use {
+    DisplayMode, Error, FrameTimecode, TimecodeFlags, TimecodeFormat, VideoFrame, VideoInputFrame,
+};
+
+/// A change in the captured timecode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimecodeEvent {
+    /// Timecode was found on the first frame, or on the first frame after a run without any.
+    Acquired(SmpteTimecode),
+    /// A frame had none of the formats the monitor reads. Sent on the first frame of each run.
+    Missing,
+    /// The timecode is `frames` frames ahead of where the stream time says it should be.
+    Discontinuity {
+        expected: SmpteTimecode,
+        actual: SmpteTimecode,
+        frames: i64,
+    },
+    /// The timecode is `frames` frames behind where the stream time says it should be.
+    Backwards {
+        expected: SmpteTimecode,
+        actual: SmpteTimecode,
+        frames: i64,
+    },
+    /// The timecode repeated the last frame's label. Sent on the first repeat of each freeze.
+    Freeze(SmpteTimecode),
+    /// The timecode switched between drop and non-drop frame, to the setting of this label.
+    DropFrameChanged(SmpteTimecode),
+    /// A label that the mode's rate doesn't count, such as frame 25 at 25 frames per second or a
+    /// label that drop frame skips.
+    Invalid(FrameTimecode),
+}
+
+impl fmt::Display for TimecodeEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TimecodeEvent::Acquired(timecode) => write!(f, "timecode acquired at {}", timecode),
+            TimecodeEvent::Missing => write!(f, "timecode missing"),
+            TimecodeEvent::Discontinuity {
+                expected,
+                actual,
+                frames,
+            } => write!(
+                f,
+                "timecode jumped {} frames ahead, to {} instead of {}",
+                frames, actual, expected
+            ),
+            TimecodeEvent::Backwards {
+                expected,
+                actual,
+                frames,
+            } => write!(
+                f,
+                "timecode went {} frames back, to {} instead of {}",
+                frames, actual, expected
+            ),
+            TimecodeEvent::Freeze(timecode) => write!(f, "timecode frozen at {}", timecode),
+            TimecodeEvent::DropFrameChanged(timecode) => write!(
+                f,
+                "timecode changed to {} at {}",
+                if timecode.drop_frame() {
+                    "drop frame"
+                } else {
+                    "non-drop frame"
+                },
+                timecode
+            ),
+            TimecodeEvent::Invalid(timecode) => write!(
+                f,
+                "invalid timecode {:02}:{:02}:{:02}:{:02}",
+                timecode.hours, timecode.minutes, timecode.seconds, timecode.frames
+            ),
+        }
+    }
+}
+
+/// Counts of what a `TimecodeMonitor` has seen.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TimecodeStats {
+    /// Frames passed to the monitor.
+    pub frames: u64,
+    /// Frames the stream time shows were lost before they reached the monitor.
+    pub dropped: u64,
+    /// Frames without timecode.
+    pub missing: u64,
+    pub discontinuities: u64,
+    pub backward_jumps: u64,
+    /// Freezes, and the repeated frames in them.
+    pub freezes: u64,
+    pub frozen_frames: u64,
+    pub drop_frame_changes: u64,
+    pub invalid: u64,
+    /// The last valid timecode.
+    pub last: Option<SmpteTimecode>,
+}
+
+/// Formats as one line for a log.
+impl fmt::Display for TimecodeStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} frames, {} dropped, {} without timecode, {} discontinuities, {} backward jumps, \
+             {} freezes over {} frames, {} drop frame changes, {} invalid, last ",
+            self.frames,
+            self.dropped,
+            self.missing,
+            self.discontinuities,
+            self.backward_jumps,
+            self.freezes,
+            self.frozen_frames,
+            self.drop_frame_changes,
+            self.invalid
+        )?;
+        match self.last {
+            Some(timecode) => write!(f, "{}", timecode),
+            None => write!(f, "none"),
+        }
+    }
+}
+
+/// Follows the timecode of captured frames.
+///
+/// Each frame's label is compared with the last valid label advanced by the frames the stream
+/// time has moved on, so frames dropped on capture don't show up as discontinuities. Legacy RP188
+/// and VITC labels that count pairs of frames above 30 frames per second are counted per frame
+/// again using the field mark, as `TimecodeGenerator::jam_sync` does.
+#[derive(Clone, Debug)]
+pub struct TimecodeMonitor {
+    frames_per_second: u32,
+    // Frames per LTC and VITC label.
+    group: u32,
+    frame_duration: i64,
+    time_scale: i64,
+    formats: Vec<TimecodeFormat>,
+    // The last valid label, its frame count and the stream time of its frame.
+    last: Option<(SmpteTimecode, u32, i64)>,
+    last_stream_time: Option<i64>,
+    missing: bool,
+    frozen: bool,
+    stats: TimecodeStats,
+}
+
+impl TimecodeMonitor {
+    /// Monitors capture in a display mode. Each frame's timecode is read from the first of
+    /// `FORMAT_RP188_VITC1`, `FORMAT_RP188_LTC` and `FORMAT_VITC` that it carries, preceded by
+    /// `FORMAT_RP188_HIGH_FRAME_RATE` in modes above 60 frames per second.
+    pub fn new(display_mode: DisplayMode) -> Result<TimecodeMonitor, SimpleError> {
+        let spec = display_mode
+            .info()
+            .ok_or_else(|| SimpleError::new("unknown display mode"))?;
+        let rate = TimecodeRate::for_mode(display_mode)
+            .ok_or_else(|| SimpleError::new(format!("{} has no timecode rate", display_mode)))?;
+        let mut formats = Vec::new();
+        if rate.frames_per_second() > 60 {
+            formats.push(TimecodeFormat::FORMAT_RP188_HIGH_FRAME_RATE);
+        }
+        formats.extend_from_slice(&[
+            TimecodeFormat::FORMAT_RP188_VITC1,
+            TimecodeFormat::FORMAT_RP188_LTC,
+            TimecodeFormat::FORMAT_VITC,
+        ]);
+        Ok(TimecodeMonitor {
+            frames_per_second: rate.frames_per_second(),
+            group: rate.group(),
+            frame_duration: spec.frame_duration,
+            time_scale: spec.time_scale,
+            formats,
+            last: None,
+            last_stream_time: None,
+            missing: false,
+            frozen: false,
+            stats: TimecodeStats::default(),
+        })
+    }
+
+    pub fn formats(&self) -> &[TimecodeFormat] {
+        &self.formats
+    }
+
+    /// Sets the formats to read timecode from, in order of preference.
+    pub fn set_formats(&mut self, formats: &[TimecodeFormat]) {
+        self.formats = formats.to_vec();
+    }
+
+    pub fn stats(&self) -> TimecodeStats {
+        self.stats
+    }
+
+    /// Forgets the timecode seen so far and clears the counts.
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.last_stream_time = None;
+        self.missing = false;
+        self.frozen = false;
+        self.stats = TimecodeStats::default();
+    }
+
+    /// Checks a captured frame's timecode, returning what changed. A format the frame fails to
+    /// return is treated as missing, and the next one is tried.
+    pub fn process_frame(
+        &mut self,
+        frame: &mut VideoInputFrame,
+    ) -> Result<Vec<TimecodeEvent>, Error> {
+        let (stream_time, _) = frame.get_stream_time(self.time_scale)?;
+        let mut found = None;
+        for &format in &self.formats {
+            if let Ok(Some(timecode)) = frame.get_timecode(format) {
+                let (hours, minutes, seconds, frames) = match timecode.get_components() {
+                    Ok(components) => components,
+                    Err(_) => continue,
+                };
+                let components = FrameTimecode {
+                    hours,
+                    minutes,
+                    seconds,
+                    frames,
+                    flags: timecode.get_flags(),
+                };
+                found = Some((format, components));
+                break;
+            }
+        }
+        Ok(self.observe(found, stream_time))
+    }
+
+    /// Checks a frame's timecode as read from a format, or `None` if it had none, with the
+    /// frame's stream time in the display mode's time scale.
+    pub fn observe(
+        &mut self,
+        timecode: Option<(TimecodeFormat, FrameTimecode)>,
+        stream_time: i64,
+    ) -> Vec<TimecodeEvent> {
+        self.stats.frames += 1;
+        if let Some(previous) = self.last_stream_time.replace(stream_time) {
+            self.stats.dropped += self.elapsed(previous, stream_time) as u64 - 1;
+        }
+
+        let mut events = Vec::new();
+        let (format, components) = match timecode {
+            Some(timecode) => timecode,
+            None => {
+                self.stats.missing += 1;
+                self.frozen = false;
+                if !self.missing {
+                    self.missing = true;
+                    events.push(TimecodeEvent::Missing);
+                }
+                return events;
+            }
+        };
+        let group = if format == TimecodeFormat::FORMAT_RP188_HIGH_FRAME_RATE {
+            1
+        } else {
+            self.group
+        };
+        let drop_frame = components.flags.contains(TimecodeFlags::DROP_FRAME);
+        let counted = TimecodeRate::new(self.frames_per_second, drop_frame).and_then(|rate| {
+            let label = SmpteTimecode::from_legacy(&components, group)?;
+            Ok((rate, label, label.to_frames(rate)?))
+        });
+        let (rate, label, frame) = match counted {
+            Ok(counted) => counted,
+            Err(_) => {
+                self.stats.invalid += 1;
+                events.push(TimecodeEvent::Invalid(components));
+                return events;
+            }
+        };
+
+        let was_missing = std::mem::replace(&mut self.missing, false);
+        self.stats.last = Some(label);
+        let (previous, previous_frame, previous_time) =
+            match self.last.replace((label, frame, stream_time)) {
+                Some(last) if !was_missing => last,
+                _ => {
+                    self.frozen = false;
+                    events.push(TimecodeEvent::Acquired(label));
+                    return events;
+                }
+            };
+        if previous.drop_frame() != drop_frame {
+            self.stats.drop_frame_changes += 1;
+            self.frozen = false;
+            events.push(TimecodeEvent::DropFrameChanged(label));
+            return events;
+        }
+        if label == previous {
+            self.stats.frozen_frames += 1;
+            if !self.frozen {
+                self.frozen = true;
+                self.stats.freezes += 1;
+                events.push(TimecodeEvent::Freeze(label));
+            }
+            return events;
+        }
+        self.frozen = false;
+
+        let expected_frame = previous_frame as i64 + self.elapsed(previous_time, stream_time);
+        let expected = SmpteTimecode::from_frames(expected_frame, rate);
+        // The nearer way round the clock, so midnight isn't a jump.
+        let day = rate.frames_per_day() as i64;
+        let mut offset = (frame as i64 - expected_frame).rem_euclid(day);
+        if offset > day / 2 {
+            offset -= day;
+        }
+        if offset > 0 {
+            self.stats.discontinuities += 1;
+            events.push(TimecodeEvent::Discontinuity {
+                expected,
+                actual: label,
+                frames: offset,
+            });
+        } else if offset < 0 {
+            self.stats.backward_jumps += 1;
+            events.push(TimecodeEvent::Backwards {
+                expected,
+                actual: label,
+                frames: -offset,
+            });
+        }
+        events
+    }
+
+    // Frames between two stream times, at least one.
+    fn elapsed(&self, from: i64, to: i64) -> i64 {
+        let frames = ((to - from) as f64 / self.frame_duration as f64).round() as i64;
+        frames.max(1)
+    }
+}
@@ -0,0 +1,54 @@
+extern crate decklink;
+
+use decklink::{AudioPacket, AudioSampleType};
+
+#[test]
+fn interleaved_and_channel_access() {
+    let samples: Vec<i16> = vec![1, -1, 2, -2, 3, -3];
+    let packet = AudioPacket::from_i16(48000, 2, &samples, 0).unwrap();
+    assert_eq!(packet.format().sample_type, AudioSampleType::I16);
+    assert_eq!(packet.format().bytes_per_frame(), 4);
+    assert_eq!(packet.sample_frame_count(), 3);
+    assert_eq!(packet.samples_i16(), Some(&samples[..]));
+    assert!(packet.samples_i32().is_none());
+
+    let left = packet.channel(0).unwrap();
+    assert_eq!(left.len(), 3);
+    assert_eq!(left.collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(
+        packet.channel(1).unwrap().collect::<Vec<_>>(),
+        vec![-1, -2, -3]
+    );
+    assert!(packet.channel(2).is_none());
+
+    assert!(AudioPacket::from_i16(48000, 4, &samples, 0).is_err());
+    assert!(AudioPacket::from_i16(48000, 0, &samples, 0).is_err());
+}
+
+#[test]
+fn planar_f32() {
+    let samples = [i32::MIN, 0, 1 << 30, i32::MAX];
+    let packet = AudioPacket::from_i32(48000, 2, &samples, 0).unwrap();
+    assert!(packet.samples_i16().is_none());
+    let planar = packet.to_planar_f32();
+    assert_eq!(planar, vec![vec![-1.0, 0.5], vec![0.0, 1.0]]);
+
+    let samples = [-32768i16, 16384];
+    let packet = AudioPacket::from_i16(48000, 1, &samples, 0).unwrap();
+    assert_eq!(packet.to_planar_f32(), vec![vec![-1.0, 0.5]]);
+}
+
+#[test]
+fn packet_time() {
+    let samples = vec![0i32; 1602 * 8];
+    let packet = AudioPacket::from_i32(48000, 8, &samples, 48048).unwrap();
+    assert_eq!(packet.time(48000), 48048);
+    assert_eq!(packet.time(1000), 1001);
+    assert_eq!(packet.time(30000), 30030);
+    assert_eq!(packet.duration(30000), 1001);
+    assert_eq!(packet.duration(1000), 33);
+
+    let samples = [0i16; 2];
+    let packet = AudioPacket::from_i16(48000, 2, &samples, -1).unwrap();
+    assert_eq!(packet.time(1000), -1);
+}
@@ -0,0 +1,258 @@
+extern crate decklink;
+
+use decklink::audio::meter::{to_dbfs, Meter};
+use decklink::AudioPacket;
+
+const RATE: u32 = 48000;
+
+// A 1 kHz sine in every channel, at a per-channel level in dBFS, as the EBU test signals use.
+struct Signal {
+    channels: usize,
+    samples: Vec<i32>,
+}
+
+impl Signal {
+    fn new(channels: usize) -> Self {
+        Signal {
+            channels,
+            samples: Vec::new(),
+        }
+    }
+
+    fn sine(self, levels: &[f64], seconds: f64) -> Self {
+        self.tone(1000.0, 0.0, levels, seconds)
+    }
+
+    fn tone(mut self, freq: f64, phase: f64, levels: &[f64], seconds: f64) -> Self {
+        let start = self.samples.len() / self.channels;
+        let frames = (seconds * RATE as f64).round() as usize;
+        for n in start..start + frames {
+            let x = (2.0 * std::f64::consts::PI * freq * n as f64 / RATE as f64 + phase).sin();
+            for level in levels.iter().cycle().take(self.channels) {
+                let amplitude = 10f64.powf(level / 20.0) * 2_147_483_647.0;
+                self.samples.push((x * amplitude).round() as i32);
+            }
+        }
+        self
+    }
+
+    fn measure(&self, meter: &mut Meter) {
+        // Packets of a 29.97 fps cadence.
+        let mut time = 0;
+        for (i, chunk) in self.samples.chunks(1602 * self.channels).enumerate() {
+            let chunk = &chunk[..chunk.len().min((1601 + i % 2) * self.channels)];
+            let packet = AudioPacket::from_i32(RATE, self.channels as u32, chunk, time).unwrap();
+            meter.process(&packet).unwrap();
+            time += packet.sample_frame_count() as i64;
+        }
+    }
+}
+
+fn assert_near(value: Option<f64>, expected: f64, tolerance: f64) {
+    let value = value.unwrap();
+    assert!(
+        (value - expected).abs() <= tolerance,
+        "{} is not within {} of {}",
+        value,
+        tolerance,
+        expected
+    );
+}
+
+fn integrated(signal: &Signal) -> Option<f64> {
+    let mut meter = Meter::new(RATE, signal.channels).unwrap();
+    signal.measure(&mut meter);
+    meter.integrated()
+}
+
+#[test]
+fn tech_3341_stereo_steady() {
+    // Cases 1 and 2. The level is steady, so a few seconds fill every window.
+    for &level in [-23.0, -33.0].iter() {
+        let mut meter = Meter::new(RATE, 2).unwrap();
+        Signal::new(2).sine(&[level], 5.0).measure(&mut meter);
+        assert_near(meter.momentary(), level, 0.1);
+        assert_near(meter.short_term(), level, 0.1);
+        assert_near(meter.integrated(), level, 0.1);
+    }
+}
+
+// Tech 3341 and 3342 run their signals for a minute or more, which takes a while in a debug
+// build. Their durations are scaled down here, to a length that still resolves the gates and
+// the loudness distribution to within the tolerances; the `full_length` tests run them as
+// specified.
+const SHORT: f64 = 0.25;
+
+fn gating(scale: f64) {
+    // Case 3: the relative gate drops the quiet ends.
+    let signal = Signal::new(2)
+        .sine(&[-36.0], 10.0 * scale)
+        .sine(&[-23.0], 60.0 * scale)
+        .sine(&[-36.0], 10.0 * scale);
+    assert_near(integrated(&signal), -23.0, 0.1);
+
+    // Case 4: the absolute gate drops the silence-level ends.
+    let signal = Signal::new(2)
+        .sine(&[-72.0], 10.0 * scale)
+        .sine(&[-36.0], 10.0 * scale)
+        .sine(&[-23.0], 60.0 * scale)
+        .sine(&[-36.0], 10.0 * scale)
+        .sine(&[-72.0], 10.0 * scale);
+    assert_near(integrated(&signal), -23.0, 0.1);
+
+    // Case 5.
+    let signal = Signal::new(2)
+        .sine(&[-26.0], 20.0 * scale)
+        .sine(&[-20.0], 20.1 * scale)
+        .sine(&[-26.0], 20.0 * scale);
+    assert_near(integrated(&signal), -23.0, 0.1);
+}
+
+const LOUDNESS_RANGE_CASES: [(&[f64], f64); 4] = [
+    (&[-20.0, -30.0], 10.0),
+    (&[-20.0, -15.0], 5.0),
+    (&[-40.0, -20.0], 20.0),
+    (&[-50.0, -35.0, -20.0, -35.0, -50.0], 15.0),
+];
+
+fn loudness_range(cases: &[(&[f64], f64)], scale: f64) {
+    for &(levels, range) in cases.iter() {
+        let mut meter = Meter::new(RATE, 2).unwrap();
+        let mut signal = Signal::new(2);
+        for &level in levels.iter() {
+            signal = signal.sine(&[level], 20.0 * scale);
+        }
+        signal.measure(&mut meter);
+        assert_near(meter.loudness_range(), range, 1.0);
+    }
+}
+
+#[test]
+fn tech_3341_gating() {
+    gating(SHORT);
+}
+
+#[test]
+#[ignore]
+fn tech_3341_gating_full_length() {
+    gating(1.0);
+}
+
+#[test]
+fn tech_3341_surround() {
+    // Case 6: 5.0 with the surrounds weighted.
+    let signal = Signal::new(5).sine(&[-28.0, -28.0, -24.0, -30.0, -30.0], 5.0);
+    let mut meter = Meter::new(RATE, 5).unwrap();
+    meter.set_channel_weight(3, 1.41).unwrap();
+    meter.set_channel_weight(4, 1.41).unwrap();
+    assert!(meter.set_channel_weight(5, 1.0).is_err());
+    signal.measure(&mut meter);
+    assert_near(meter.integrated(), -23.0, 0.1);
+}
+
+#[test]
+fn tech_3341_windows() {
+    // Case 9: the short-term window always holds one 3 s period.
+    let mut signal = Signal::new(2);
+    for _ in 0..5 {
+        signal = signal.sine(&[-20.0], 1.34).sine(&[-30.0], 1.66);
+    }
+    let mut meter = Meter::new(RATE, 2).unwrap();
+    for chunk in signal.samples.chunks(RATE as usize / 10 * 2) {
+        let packet = AudioPacket::from_i32(RATE, 2, chunk, 0).unwrap();
+        meter.process(&packet).unwrap();
+        if let Some(short_term) = meter.short_term() {
+            assert_near(Some(short_term), -23.0, 0.1);
+        }
+    }
+
+    // Case 12: the momentary window always holds one 400 ms period.
+    let mut signal = Signal::new(2);
+    for _ in 0..25 {
+        signal = signal.sine(&[-20.0], 0.18).sine(&[-30.0], 0.22);
+    }
+    let mut meter = Meter::new(RATE, 2).unwrap();
+    for chunk in signal.samples.chunks(RATE as usize / 10 * 2) {
+        let packet = AudioPacket::from_i32(RATE, 2, chunk, 0).unwrap();
+        meter.process(&packet).unwrap();
+        if let Some(momentary) = meter.momentary() {
+            assert_near(Some(momentary), -23.0, 0.1);
+        }
+    }
+}
+
+#[test]
+fn tech_3341_true_peak() {
+    // Cases 15 to 17: a quarter-rate sine sampled at 0, 45 and 60 degrees. At 45 degrees the
+    // samples sit 3 dB below the peak that reconstruction recovers. The tolerance is Tech 3341's.
+    for &(phase, sample_peak) in [(0.0, -6.0), (45.0, -9.01), (60.0, -7.25)].iter() {
+        let signal =
+            Signal::new(1).tone(12000.0, phase * std::f64::consts::PI / 180.0, &[-6.0], 1.0);
+        let mut meter = Meter::new(RATE, 1).unwrap();
+        let (onset, steady) = signal.samples.split_at(480);
+        // The interpolator rings at the abrupt start of the tone, so it is left out.
+        meter
+            .process(&AudioPacket::from_i32(RATE, 1, onset, 0).unwrap())
+            .unwrap();
+        meter.take_levels();
+        meter
+            .process(&AudioPacket::from_i32(RATE, 1, steady, 480).unwrap())
+            .unwrap();
+        let levels = meter.take_levels()[0];
+        assert_near(Some(to_dbfs(levels.sample_peak)), sample_peak, 0.02);
+        let true_peak = to_dbfs(levels.true_peak);
+        assert!(true_peak > -6.4 && true_peak < -5.8, "{}", true_peak);
+    }
+}
+
+#[test]
+fn tech_3342_loudness_range() {
+    // In case 4 the steps between its five levels weigh on the distribution until each level
+    // runs for the full 20 s, so it is only run at full length.
+    loudness_range(&LOUDNESS_RANGE_CASES[..3], SHORT);
+}
+
+#[test]
+#[ignore]
+fn tech_3342_loudness_range_full_length() {
+    loudness_range(&LOUDNESS_RANGE_CASES, 1.0);
+}
+
+#[test]
+fn levels_and_formats() {
+    // A 16-bit square wave at half scale on the left and quarter scale on the right. Its sample
+    // peak and RMS are both at that scale, and the true peak only rings above it.
+    let samples: Vec<i16> = (0..4800)
+        .flat_map(|n| {
+            let x = if n / 24 % 2 == 0 { 16384 } else { -16384 };
+            vec![x, x / 2]
+        })
+        .collect();
+    let mut meter = Meter::new(RATE, 2).unwrap();
+    meter
+        .process(&AudioPacket::from_i16(RATE, 2, &samples, 0).unwrap())
+        .unwrap();
+    let levels = meter.take_levels();
+    assert_eq!(levels[0].sample_peak, 0.5);
+    assert_eq!(levels[0].rms, 0.5);
+    assert!(levels[0].true_peak >= 0.5);
+    assert_eq!(levels[1].sample_peak, 0.25);
+    assert_eq!(meter.take_levels()[0].sample_peak, 0.0);
+
+    assert!(meter
+        .process(&AudioPacket::from_i16(44100, 2, &samples, 0).unwrap())
+        .is_err());
+    assert!(meter
+        .process(&AudioPacket::from_i16(RATE, 1, &samples, 0).unwrap())
+        .is_err());
+
+    // Silence never passes the absolute gate.
+    let silence = vec![0i32; 48000 * 2];
+    let mut meter = Meter::new(RATE, 2).unwrap();
+    meter
+        .process(&AudioPacket::from_i32(RATE, 2, &silence, 0).unwrap())
+        .unwrap();
+    assert_eq!(meter.momentary(), Some(f64::NEG_INFINITY));
+    assert!(meter.integrated().is_none());
+    assert!(meter.loudness_range().is_none());
+}
@@ -0,0 +1,144 @@
+extern crate decklink;
+
+use decklink::audio::playout::{AudioPlayout, PiController, PlayoutMode, Resampler};
+use decklink::{AudioFormat, AudioSampleType};
+
+#[test]
+fn resampler_passes_through_at_unity() {
+    let mut resampler = Resampler::new(2).unwrap();
+    let input: Vec<i32> = (0..200).collect();
+    let mut output = Vec::new();
+    for chunk in input.chunks(38) {
+        resampler.process(chunk, &mut output).unwrap();
+    }
+    // Two frames of delay.
+    assert_eq!(&output[..4], &[0, 0, 0, 0]);
+    assert_eq!(&output[4..], &input[..input.len() - 4]);
+    assert!(resampler.process(&[0i32; 3], &mut output).is_err());
+    assert!(resampler.set_ratio(0.0).is_err());
+}
+
+#[test]
+fn resampler_converts_rate() {
+    // 1 kHz at 44.1 kHz to 48 kHz, in odd-sized chunks.
+    let sine = |n: usize, rate: f64| (2.0 * std::f64::consts::PI * 1000.0 * n as f64 / rate).sin();
+    let input: Vec<f32> = (0..44100).map(|n| sine(n, 44100.0) as f32).collect();
+    let mut resampler = Resampler::new(1).unwrap();
+    resampler.set_ratio(48000.0 / 44100.0).unwrap();
+    let mut output = Vec::new();
+    for chunk in input.chunks(1001) {
+        resampler.process(chunk, &mut output).unwrap();
+    }
+    assert!((output.len() as i32 - 48000).abs() <= 3, "{}", output.len());
+
+    // The output is the same sine at the new rate, two input frames late.
+    let delay = 2.0 * 48000.0 / 44100.0;
+    for (n, y) in output.iter().enumerate().skip(10) {
+        let t = n as f64 - delay;
+        let ideal = (2.0 * std::f64::consts::PI * 1000.0 * t / 48000.0).sin();
+        assert!(
+            (*y as f64 - ideal).abs() < 2e-3,
+            "{}: {} != {}",
+            n,
+            y,
+            ideal
+        );
+    }
+}
+
+#[test]
+fn controller_limits_and_holds_integral() {
+    let mut controller = PiController::new(1.0, 1.0, 0.5);
+    assert_eq!(controller.update(0.25, 0.0), 0.25);
+    // A large error saturates without winding up the integral.
+    for _ in 0..100 {
+        assert_eq!(controller.update(10.0, 1.0), 0.5);
+    }
+    assert_eq!(controller.update(0.0, 0.0), 0.0);
+    assert_eq!(controller.update(0.1, 1.0), 0.2);
+}
+
+// Simulates a source whose clock runs `ppm` fast or slow against the card, written every 20 ms.
+fn simulate(ppm: f64) -> (AudioPlayout, f64) {
+    let format = AudioFormat::new(48000, AudioSampleType::I32, 2);
+    let target = 4800;
+    let mut playout =
+        AudioPlayout::new(format, PlayoutMode::ContinuousDontResample, target).unwrap();
+    let mut lead = target as f64;
+    let mut source_frames = 0.0;
+    let input = vec![0i32; 2 * 1000];
+    let mut output = Vec::new();
+    for _ in 0..180 * 50 {
+        source_frames += 960.0 * (1.0 + ppm * 1e-6);
+        let frames = source_frames as usize;
+        source_frames -= frames as f64;
+
+        playout.update(lead, 0.02);
+        output.clear();
+        playout.process(&input[..frames * 2], &mut output).unwrap();
+        lead += (output.len() / 2) as f64 - 960.0;
+    }
+    (playout, lead)
+}
+
+#[test]
+fn playout_locks_to_card_clock() {
+    for &ppm in [-100.0, 0.0, 30.0, 250.0].iter() {
+        let (playout, lead) = simulate(ppm);
+        assert!((lead - 4800.0).abs() <= 2.0, "{} ppm: lead {}", ppm, lead);
+        let expected = 1.0 / (1.0 + ppm * 1e-6);
+        assert!(
+            (playout.ratio() - expected).abs() < 5e-6,
+            "{} ppm: ratio {}",
+            ppm,
+            playout.ratio()
+        );
+    }
+}
+
+#[test]
+fn playout_modes() {
+    let format = AudioFormat::new(48000, AudioSampleType::I16, 8);
+    let playout = AudioPlayout::new(format, PlayoutMode::Timestamped, 0).unwrap();
+    assert_eq!(playout.format(), format);
+    assert_eq!(
+        PlayoutMode::ContinuousDontResample.stream_type(),
+        decklink::AudioOutputStreamType::ContinuousDontResample
+    );
+    assert_eq!(
+        PlayoutMode::Timestamped.stream_type(),
+        decklink::AudioOutputStreamType::Timestamped
+    );
+    assert_eq!(playout.ratio(), 1.0);
+    assert_eq!(playout.dropped_frames(), 0);
+}
+
+#[test]
+fn partial_accept() {
+    let format = AudioFormat::new(48000, AudioSampleType::I32, 2);
+    let mut playout = AudioPlayout::new(format, PlayoutMode::Timestamped, 2000).unwrap();
+    playout.set_next_time(1001, 1000);
+    assert_eq!(playout.next_time(), 48048);
+    assert_eq!(playout.lead_at(46048), 2000.0);
+    assert_eq!(playout.lead_at(48548), -500.0);
+
+    // On target, a buffer passes through at its own length.
+    let samples = vec![0i32; 480 * 2];
+    let resampled = playout.prepare(2000.0, &samples).unwrap();
+    assert_eq!(playout.ratio(), 1.0);
+    assert_eq!(resampled.len(), samples.len());
+
+    // The driver took only part of it: the next buffer still follows on in time, and the rest
+    // counts as dropped.
+    playout.advance(480, 300);
+    assert_eq!(playout.next_time(), 48528);
+    assert_eq!(playout.dropped_frames(), 180);
+    playout.advance(480, 480);
+    assert_eq!(playout.next_time(), 49008);
+    assert_eq!(playout.dropped_frames(), 180);
+    // A driver reporting more than it was given doesn't wrap the count.
+    playout.advance(480, 500);
+    assert_eq!(playout.dropped_frames(), 180);
+
+    assert!(playout.prepare(2000.0, &[0i32; 3]).is_err());
+}
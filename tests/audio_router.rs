@@ -0,0 +1,120 @@
+extern crate decklink;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+
+use decklink::audio::router::{AudioRouter, Downmix, OutputChannel};
+use decklink::AudioPacket;
+
+#[test]
+fn select_and_shuffle() {
+    // Pick channels 3 and 0 of four, then a copy of channel 3.
+    let router = AudioRouter::select(4, &[3, 0, 3]);
+    let src: Vec<i16> = vec![1, 2, 3, 4, 5, 6, 7, 8];
+    let mut dst = vec![0i16; 6];
+    router.route(&src, &mut dst).unwrap();
+    assert_eq!(dst, vec![4, 1, 4, 8, 5, 8]);
+
+    let router = AudioRouter::mono_to_stereo(2, 1);
+    let mut dst = vec![0i16; 8];
+    router.route(&src, &mut dst).unwrap();
+    assert_eq!(dst, vec![2, 2, 4, 4, 6, 6, 8, 8]);
+
+    let mut dst = vec![0i16; 8];
+    AudioRouter::identity(4).route(&src, &mut dst).unwrap();
+    assert_eq!(dst, src);
+}
+
+#[test]
+fn downmix_presets() {
+    let router = AudioRouter::downmix(6, 0, Downmix::StereoToMono);
+    let mut dst = vec![0i32; 1];
+    router.route(&[1000, 3000, 0, 0, 0, 0], &mut dst).unwrap();
+    assert_eq!(dst, vec![2000]);
+
+    // 5.1 on channels 8 to 13 of 16, as it often is on SDI.
+    let router = AudioRouter::downmix(16, 8, Downmix::Surround51ToStereo);
+    let mut src = vec![0i32; 16];
+    src[8..14].copy_from_slice(&[1000, 2000, 10000, 30000, 100000, 200000]);
+    let mut dst = vec![0i32; 2];
+    router.route(&src, &mut dst).unwrap();
+    assert_eq!(dst, vec![1000 + 7071 + 70711, 2000 + 7071 + 141421]);
+
+    let router = AudioRouter::downmix(6, 0, Downmix::Surround51ToMono);
+    let mut dst = vec![0f32; 1];
+    router
+        .route(&[0.5f32, 0.5, 0.0, 1.0, 0.0, 0.0], &mut dst)
+        .unwrap();
+    assert_eq!(dst, vec![0.5]);
+    assert_eq!(Downmix::Surround51ToMono.input_channels(), 6);
+}
+
+#[test]
+fn gain_mute_and_invert() {
+    let mut router = AudioRouter::identity(3);
+    router.outputs[0].set_gain_db(-6.0);
+    router.outputs[1].invert = true;
+    router.outputs[2].mute = true;
+    let mut dst = vec![0i16; 3];
+    router.route(&[10000i16, -32768, 5], &mut dst).unwrap();
+    assert_eq!(dst, vec![5012, 32767, 0]);
+
+    // Mixing saturates rather than wrapping.
+    let router = AudioRouter {
+        input_channels: 2,
+        outputs: vec![OutputChannel::mix(&[(0, 1.0), (1, 1.0)])],
+    };
+    let mut dst = vec![0i16; 1];
+    router.route(&[30000i16, 30000], &mut dst).unwrap();
+    assert_eq!(dst, vec![32767]);
+}
+
+#[test]
+fn packets() {
+    let samples: Vec<i16> = vec![1, 2, -1, -2];
+    let packet = AudioPacket::from_i16(48000, 2, &samples, 0).unwrap();
+    let router = AudioRouter::select(2, &[1]);
+    assert_eq!(
+        router.route_packet(&packet).unwrap(),
+        vec![2 << 16, -2 << 16]
+    );
+    assert!(AudioRouter::identity(4).route_packet(&packet).is_err());
+}
+
+#[test]
+fn rejects_invalid_routing() {
+    let mut dst = vec![0i32; 2];
+    assert!(AudioRouter::select(2, &[0, 2])
+        .route(&[0, 0], &mut dst)
+        .is_err());
+    assert!(AudioRouter::default().validate().is_err());
+    assert!(AudioRouter::identity(2)
+        .route(&[0, 0, 0], &mut dst)
+        .is_err());
+    assert!(AudioRouter::identity(2)
+        .route(&[0, 0], &mut dst[..1])
+        .is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn loads_from_config() {
+    let config = r#"{
+        "input_channels": 16,
+        "outputs": [
+            { "sources": [{ "channel": 2 }] },
+            { "sources": [{ "channel": 3, "gain": 0.5 }], "invert": true },
+            { "mute": true }
+        ]
+    }"#;
+    let router: AudioRouter = serde_json::from_str(config).unwrap();
+    router.validate().unwrap();
+    assert_eq!(router.output_channels(), 3);
+    assert_eq!(router.outputs[0], OutputChannel::from_input(2));
+    assert_eq!(router.outputs[1].sources[0].gain, 0.5);
+    assert!(router.outputs[1].invert);
+    assert!(router.outputs[2].mute);
+    assert_eq!(router.outputs[2].gain, 1.0);
+
+    let json = serde_json::to_string(&router).unwrap();
+    assert_eq!(serde_json::from_str::<AudioRouter>(&json).unwrap(), router);
+}
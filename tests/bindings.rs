@@ -0,0 +1,41 @@
+use std::collections::BTreeSet;
+use std::fs;
+
+// Every prototype in the extern "C" block of lib.hpp sits on a single line.
+fn header_functions() -> BTreeSet<String> {
+    let header = fs::read_to_string("src/lib.hpp").unwrap();
+    let body = &header[header.find("extern \"C\"").expect("no extern block")..];
+    body.lines()
+        .map(|line| line.trim())
+        .filter(|line| line.ends_with(");"))
+        .map(|line| {
+            let name = &line[..line.find('(').unwrap()];
+            name.rsplit([' ', '*']).next().unwrap().to_string()
+        })
+        .collect()
+}
+
+fn binding_functions(path: &str) -> BTreeSet<String> {
+    let bindings = fs::read_to_string(path).unwrap();
+    bindings
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("pub fn "))
+        .map(|rest| rest[..rest.find('(').unwrap()].to_string())
+        .collect()
+}
+
+#[test]
+fn linux_bindings_match_header() {
+    assert_eq!(
+        binding_functions("src/bindings/linux.rs"),
+        header_functions()
+    );
+}
+
+#[test]
+fn macos_bindings_match_header() {
+    assert_eq!(
+        binding_functions("src/bindings/macos.rs"),
+        header_functions()
+    );
+}
@@ -0,0 +1,139 @@
+extern crate decklink;
+
+use decklink::{
+    ColorMatrix, ColorRange, PixelFormat, SoftwareConversion, VideoBuffer, VideoConversion,
+    VideoFrame,
+};
+
+const FORMATS: [PixelFormat; 9] = [
+    PixelFormat::FORMAT_8BIT_YUV,
+    PixelFormat::FORMAT_10BIT_YUV,
+    PixelFormat::FORMAT_8BIT_ARGB,
+    PixelFormat::FORMAT_8BIT_BGRA,
+    PixelFormat::FORMAT_10BIT_RGB,
+    PixelFormat::FORMAT_10BIT_RGBX,
+    PixelFormat::FORMAT_10BIT_RGBXLE,
+    PixelFormat::FORMAT_12BIT_RGB,
+    PixelFormat::FORMAT_12BIT_RGBLE,
+];
+
+fn bgra(width: i32, height: i32, pixel: [u8; 4]) -> VideoBuffer {
+    let mut frame = VideoBuffer::new(width, height, PixelFormat::FORMAT_8BIT_BGRA).unwrap();
+    for px in frame.get_bytes_mut().unwrap().chunks_mut(4) {
+        px.copy_from_slice(&pixel);
+    }
+    frame
+}
+
+fn convert(
+    conversion: &mut SoftwareConversion,
+    src: &mut VideoBuffer,
+    format: PixelFormat,
+) -> VideoBuffer {
+    let mut dst = VideoBuffer::new(src.get_width(), src.get_height(), format).unwrap();
+    conversion.convert_frame(src, &mut dst).unwrap();
+    dst
+}
+
+#[test]
+fn bgra_to_uyvy_matrices() {
+    // Pure red in each matrix, at 8-bit legal range: Cb Y Cr Y.
+    let cases = [
+        (ColorMatrix::Rec601, [90, 81, 240, 81]),
+        (ColorMatrix::Rec709, [102, 63, 240, 63]),
+        (ColorMatrix::Rec2020, [97, 74, 240, 74]),
+    ];
+    for &(matrix, expected) in cases.iter() {
+        let mut conversion = SoftwareConversion {
+            matrix: Some(matrix),
+            ..Default::default()
+        };
+        let mut red = bgra(4, 2, [0, 0, 255, 255]);
+        let mut uyvy = convert(&mut conversion, &mut red, PixelFormat::FORMAT_8BIT_YUV);
+        for px in uyvy.get_bytes().unwrap().chunks(4) {
+            assert_eq!(px, expected, "{:?}", matrix);
+        }
+    }
+}
+
+#[test]
+fn legal_and_full_range() {
+    let mut conversion = SoftwareConversion::new();
+
+    // Full-range white and black land on the legal extremes of r210.
+    for &(level, code) in [(255u8, 940u32), (0, 64)].iter() {
+        let mut src = bgra(2, 1, [level, level, level, 255]);
+        let mut r210 = convert(&mut conversion, &mut src, PixelFormat::FORMAT_10BIT_RGB);
+        let bytes = r210.get_bytes().unwrap();
+        let word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        assert_eq!(word, code << 20 | code << 10 | code);
+    }
+
+    // Treating BGRA as legal range passes 16-235 through unscaled.
+    conversion.src_range = Some(ColorRange::Legal);
+    conversion.dst_range = Some(ColorRange::Legal);
+    let mut src = bgra(2, 1, [16, 128, 235, 255]);
+    let mut dst = convert(&mut conversion, &mut src, PixelFormat::FORMAT_8BIT_ARGB);
+    assert_eq!(&dst.get_bytes().unwrap()[..4], &[255, 235, 128, 16]);
+}
+
+#[test]
+fn uyvy_to_v210_is_exact() {
+    let mut uyvy = VideoBuffer::new(48, 2, PixelFormat::FORMAT_8BIT_YUV).unwrap();
+    for (i, b) in uyvy.get_bytes_mut().unwrap().iter_mut().enumerate() {
+        *b = 16 + (i % 200) as u8;
+    }
+    let mut conversion = SoftwareConversion::new();
+    let mut v210 = convert(&mut conversion, &mut uyvy, PixelFormat::FORMAT_10BIT_YUV);
+    let mut back = convert(&mut conversion, &mut v210, PixelFormat::FORMAT_8BIT_YUV);
+    assert_eq!(back.get_bytes().unwrap(), uyvy.get_bytes().unwrap());
+}
+
+#[test]
+fn every_pair_round_trips() {
+    // A gradient of grays survives a trip through every pair of formats to within 8-bit rounding.
+    let width = 96;
+    let mut src = bgra(width, 2, [0, 0, 0, 255]);
+    for (i, px) in src.get_bytes_mut().unwrap().chunks_mut(4).enumerate() {
+        let level = (i as i32 % width * 255 / (width - 1)) as u8;
+        px[..3].copy_from_slice(&[level, level, level]);
+    }
+    let expected = src.get_bytes().unwrap().to_vec();
+
+    let mut conversion = SoftwareConversion::new();
+    for &a in FORMATS.iter() {
+        for &b in FORMATS.iter() {
+            let mut first = convert(&mut conversion, &mut src, a);
+            let mut second = convert(&mut conversion, &mut first, b);
+            let mut back = convert(&mut conversion, &mut second, PixelFormat::FORMAT_8BIT_BGRA);
+            for (x, y) in back.get_bytes().unwrap().iter().zip(expected.iter()) {
+                assert!(
+                    (*x as i32 - *y as i32).abs() <= 2,
+                    "{:?} -> {:?}: {} != {}",
+                    a.fourcc(),
+                    b.fourcc(),
+                    x,
+                    y
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn rejects_unsupported_frames() {
+    let mut conversion = SoftwareConversion::new();
+    let mut src = bgra(4, 2, [0, 0, 0, 255]);
+    let mut small = VideoBuffer::new(2, 2, PixelFormat::FORMAT_8BIT_BGRA).unwrap();
+    assert!(conversion.convert_frame(&mut src, &mut small).is_err());
+    assert!(VideoBuffer::new(4, 2, PixelFormat::FORMAT_H265).is_err());
+}
+
+#[test]
+fn video_conversion_falls_back_to_software() {
+    let mut conversion = VideoConversion::new().unwrap();
+    let mut src = bgra(4, 2, [255, 255, 255, 255]);
+    let mut dst = VideoBuffer::new(4, 2, PixelFormat::FORMAT_8BIT_YUV).unwrap();
+    conversion.convert_frame(&mut src, &mut dst).unwrap();
+    assert_eq!(&dst.get_bytes().unwrap()[..4], &[128, 235, 128, 235]);
+}
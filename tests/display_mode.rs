@@ -0,0 +1,57 @@
+extern crate decklink;
+
+use decklink::{DisplayMode, DisplayModeFamily, FieldDominance, ScanType};
+
+#[test]
+fn info() {
+    let spec = DisplayMode::MODE_HD1080I5994.info().unwrap();
+    assert_eq!((spec.width, spec.height), (1920, 1080));
+    assert_eq!(spec.frame_rate(), (30000, 1001));
+    assert_eq!(spec.field_dominance, FieldDominance::UpperFieldFirst);
+    assert_eq!(spec.scan_type(), ScanType::Interlaced);
+    assert_eq!(spec.family, DisplayModeFamily::Hd);
+
+    assert!(DisplayMode::MODE_UNKNOWN.info().is_none());
+}
+
+#[test]
+fn names() {
+    assert_eq!(DisplayMode::MODE_HD1080I5994.to_string(), "1080i59.94");
+    assert_eq!(DisplayMode::MODE_HD1080I50.to_string(), "1080i50");
+    assert_eq!(DisplayMode::MODE_4K2160P50.to_string(), "2160p50");
+    assert_eq!(DisplayMode::MODE_HD1080P2398.to_string(), "1080p23.98");
+    assert_eq!(DisplayMode::MODE_4KDCI2997.to_string(), "4kdci29.97");
+    assert_eq!(DisplayMode::MODE_2K25.to_string(), "2k25");
+    assert_eq!(DisplayMode::MODE_PAL.to_string(), "pal");
+
+    for spec in DisplayMode::catalog() {
+        let name = spec.mode.to_string();
+        assert_eq!(name.parse::<DisplayMode>().unwrap(), spec.mode, "{}", name);
+    }
+    assert_eq!(
+        "1080P59.94".parse::<DisplayMode>().unwrap(),
+        DisplayMode::MODE_HD1080P5994
+    );
+    assert!("1080p59".parse::<DisplayMode>().is_err());
+}
+
+#[test]
+fn find() {
+    assert_eq!(
+        DisplayMode::find(1920, 1080, 1001, 30000, true),
+        Some(DisplayMode::MODE_HD1080I5994)
+    );
+    assert_eq!(
+        DisplayMode::find(1920, 1080, 1, 25, false),
+        Some(DisplayMode::MODE_HD1080P25)
+    );
+    assert_eq!(
+        DisplayMode::find(4096, 2160, 1000, 50000, false),
+        Some(DisplayMode::MODE_4KDCI50)
+    );
+    assert_eq!(
+        DisplayMode::find(1920, 1080, 1, 25, true).map(|m| m.to_string()),
+        Some("1080i50".to_string())
+    );
+    assert_eq!(DisplayMode::find(1920, 1080, 1, 12, false), None);
+}
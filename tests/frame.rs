@@ -0,0 +1,135 @@
+extern crate decklink;
+
+use decklink::{
+    Frame, FrameAncillaryPacket, FrameTimecode, PixelFormat, SoftwareConversion, TimecodeFlags,
+    TimecodeFormat, VideoFrame,
+};
+
+fn timecode(frames: u8, flags: TimecodeFlags) -> FrameTimecode {
+    FrameTimecode {
+        hours: 1,
+        minutes: 2,
+        seconds: 3,
+        frames,
+        flags,
+    }
+}
+
+#[test]
+fn frame_geometry() {
+    let mut frame = Frame::new(1920, 1080, PixelFormat::FORMAT_10BIT_YUV).unwrap();
+    assert_eq!(frame.get_width(), 1920);
+    assert_eq!(frame.get_height(), 1080);
+    assert_eq!(frame.get_row_bytes(), 5120);
+    assert_eq!(frame.get_pixel_format(), PixelFormat::FORMAT_10BIT_YUV);
+    assert_eq!(frame.get_bytes().unwrap().len(), 5120 * 1080);
+    assert!(Frame::new(1919, 1080, PixelFormat::FORMAT_8BIT_YUV).is_err());
+}
+
+#[test]
+fn frame_timecodes() {
+    let mut frame = Frame::new(16, 2, PixelFormat::FORMAT_8BIT_BGRA).unwrap();
+    assert!(frame
+        .get_timecode(TimecodeFormat::FORMAT_RP188_ANY)
+        .unwrap()
+        .is_none());
+
+    frame.set_timecode(
+        TimecodeFormat::FORMAT_VITC,
+        timecode(4, TimecodeFlags::DEFAULT),
+    );
+    frame.set_timecode(
+        TimecodeFormat::FORMAT_RP188_LTC,
+        timecode(5, TimecodeFlags::DEFAULT),
+    );
+    frame.set_timecode(
+        TimecodeFormat::FORMAT_RP188_LTC,
+        timecode(6, TimecodeFlags::DROP_FRAME),
+    );
+    assert_eq!(frame.timecodes().len(), 2);
+    assert_eq!(
+        frame.timecode(TimecodeFormat::FORMAT_RP188_ANY),
+        Some(timecode(6, TimecodeFlags::DROP_FRAME))
+    );
+
+    // Timecodes read through VideoFrame are SDK objects, as they are for captured frames.
+    let vitc = frame
+        .get_timecode(TimecodeFormat::FORMAT_VITC)
+        .unwrap()
+        .unwrap();
+    assert_eq!(vitc.get_components().unwrap(), (1, 2, 3, 4));
+    assert_eq!(vitc.get_string().unwrap(), "01:02:03:04");
+    let ltc = frame
+        .get_timecode(TimecodeFormat::FORMAT_RP188_ANY)
+        .unwrap()
+        .unwrap();
+    assert_eq!(ltc.get_string().unwrap(), "01:02:03;06");
+    assert_eq!(ltc.get_flags(), TimecodeFlags::DROP_FRAME);
+
+    frame.remove_timecode(TimecodeFormat::FORMAT_VITC);
+    assert!(frame
+        .get_timecode(TimecodeFormat::FORMAT_VITC)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn frame_ancillary_packets() {
+    let mut frame = Frame::new(16, 2, PixelFormat::FORMAT_8BIT_YUV).unwrap();
+    frame.ancillary_packets.push(FrameAncillaryPacket {
+        did: 0x61,
+        sdid: 0x01,
+        line_number: 9,
+        data_stream_index: 0,
+        data: vec![1, 2, 3],
+    });
+    frame.ancillary_packets.push(FrameAncillaryPacket {
+        did: 0x41,
+        sdid: 0x05,
+        line_number: 0,
+        data_stream_index: 1,
+        data: vec![4, 5],
+    });
+
+    let mut packets = frame.query_ancillary_packets().unwrap();
+    let mut all: Vec<_> = packets.get_packet_iterator().unwrap().collect();
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[0].get_did(), 0x61);
+    assert_eq!(all[0].get_line_number(), 9);
+    assert_eq!(all[0].get_bytes().unwrap(), &[1, 2, 3]);
+    assert_eq!(all[1].get_data_stream_index(), 1);
+
+    let mut afd = packets.get_first_packet_by_id(0x41, 0x05).unwrap().unwrap();
+    assert_eq!(afd.get_bytes().unwrap(), &[4, 5]);
+    assert!(packets
+        .get_first_packet_by_id(0x60, 0x60)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn frame_converts_in_software() {
+    let mut src = Frame::new(8, 2, PixelFormat::FORMAT_8BIT_BGRA).unwrap();
+    for b in src.get_bytes_mut().unwrap().iter_mut() {
+        *b = 255;
+    }
+    let mut dst = Frame::new(8, 2, PixelFormat::FORMAT_8BIT_YUV).unwrap();
+    SoftwareConversion::new()
+        .convert_frame(&mut src, &mut dst)
+        .unwrap();
+    assert_eq!(&dst.get_bytes().unwrap()[..4], &[128, 235, 128, 235]);
+}
+
+// Without `VideoFrame` in scope.
+mod inherent {
+    use decklink::{Frame, MutableVideoFrame, PixelFormat};
+
+    #[test]
+    fn mutable_frame_bytes() {
+        let frame = Frame::new(8, 2, PixelFormat::FORMAT_8BIT_BGRA).unwrap();
+        let mut frame = MutableVideoFrame::from(frame);
+        let bytes = frame.get_bytes_mut().unwrap();
+        assert_eq!(bytes.len(), 64);
+        bytes[0] = 1;
+    }
+}
@@ -0,0 +1,112 @@
+extern crate decklink;
+
+use decklink::{
+    DisplayMode, Error, Frame, FrameAllocator, FramePoolStats, MutableVideoFrame, OutputFramePool,
+    PixelFormat, VideoFrame,
+};
+
+// Allocates frames from `Frame`s and counts them.
+#[derive(Default)]
+struct Allocator {
+    frames: usize,
+}
+
+impl FrameAllocator for Allocator {
+    fn allocate_frame(
+        &mut self,
+        width: i32,
+        height: i32,
+        pixel_format: PixelFormat,
+    ) -> Result<MutableVideoFrame, Error> {
+        self.frames += 1;
+        let frame = Frame::new(width, height, pixel_format).map_err(|_| Error::new())?;
+        Ok(MutableVideoFrame::from(frame))
+    }
+}
+
+const BGRA: PixelFormat = PixelFormat::FORMAT_8BIT_BGRA;
+
+#[test]
+fn capacity_and_exhaustion() {
+    let pool = OutputFramePool::new(2);
+    let mut allocator = Allocator::default();
+    assert_eq!(pool.capacity(), 2);
+    let a = pool.acquire(&mut allocator, 16, 4, BGRA).unwrap().unwrap();
+    let _b = pool.acquire(&mut allocator, 16, 4, BGRA).unwrap().unwrap();
+    assert!(pool.acquire(&mut allocator, 16, 4, BGRA).unwrap().is_none());
+    // The capacity is per size and format.
+    let c = pool.acquire(&mut allocator, 8, 4, BGRA).unwrap().unwrap();
+    assert_eq!(allocator.frames, 3);
+
+    assert!(pool.reclaim(a));
+    assert!(pool.reclaim(c));
+    assert!(pool.acquire(&mut allocator, 16, 4, BGRA).unwrap().is_some());
+    assert_eq!(allocator.frames, 3);
+    assert_eq!(
+        pool.stats(),
+        FramePoolStats {
+            allocated: 3,
+            available: 1,
+            in_flight: 2,
+            peak_in_flight: 3,
+            acquired: 4,
+            reclaimed: 2,
+            exhausted: 1,
+        }
+    );
+}
+
+#[test]
+fn reclaim_recycles_frames() {
+    let pool = OutputFramePool::new(1);
+    let mut allocator = Allocator::default();
+    let mut frame = pool
+        .acquire_for_mode(&mut allocator, DisplayMode::MODE_NTSC, BGRA)
+        .unwrap()
+        .unwrap();
+    assert_eq!((frame.get_width(), frame.get_height()), (720, 486));
+    frame.get_bytes_mut().unwrap()[0] = 42;
+    assert!(pool.reclaim(frame));
+
+    // The same frame comes back, with what was last rendered into it.
+    let mut frame = pool
+        .acquire_for_mode(&mut allocator, DisplayMode::MODE_NTSC, BGRA)
+        .unwrap()
+        .unwrap();
+    assert_eq!(frame.get_bytes().unwrap()[0], 42);
+    assert_eq!(allocator.frames, 1);
+
+    // Frames from elsewhere, or handed back twice, aren't taken.
+    let stranger = allocator.allocate_frame(720, 486, BGRA).unwrap();
+    assert!(!pool.reclaim(stranger));
+    assert!(pool.reclaim(frame));
+    assert_eq!(pool.stats().reclaimed, 2);
+    assert!(pool
+        .acquire_for_mode(&mut allocator, DisplayMode::MODE_UNKNOWN, BGRA)
+        .is_err());
+}
+
+#[test]
+fn preallocate_and_clear() {
+    let pool = OutputFramePool::new(3);
+    let mut allocator = Allocator::default();
+    pool.preallocate(&mut allocator, 16, 4, BGRA, 5).unwrap();
+    assert_eq!(allocator.frames, 3);
+    let stats = pool.stats();
+    assert_eq!((stats.allocated, stats.available), (3, 3));
+
+    let frame = pool.acquire(&mut allocator, 16, 4, BGRA).unwrap().unwrap();
+    assert_eq!(allocator.frames, 3);
+    pool.clear();
+    let stats = pool.stats();
+    assert_eq!(
+        (stats.allocated, stats.available, stats.in_flight),
+        (1, 0, 1)
+    );
+
+    // Frames in flight are still taken back, and the freed room is allocated again.
+    assert!(pool.reclaim(frame));
+    pool.preallocate(&mut allocator, 16, 4, BGRA, 3).unwrap();
+    assert_eq!(allocator.frames, 5);
+    assert_eq!(pool.stats().available, 3);
+}
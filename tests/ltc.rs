@@ -0,0 +1,202 @@
+extern crate decklink;
+
+use decklink::audio::ltc::{LtcDecoder, LtcEncoder, LtcFrame};
+use decklink::timecode::generator::TimecodeGenerator;
+use decklink::{
+    AudioPacket, DisplayMode, FrameTimecode, SmpteTimecode, TimecodeFlags, TimecodeRate,
+};
+
+const RATE: u32 = 48000;
+
+fn tc(text: &str) -> SmpteTimecode {
+    text.parse().unwrap()
+}
+
+// Encodes `words` words of LTC into channel 1 of a stereo buffer.
+fn encode(encoder: &mut LtcEncoder, samples_per_word: f64, words: usize) -> Vec<i32> {
+    let len = (samples_per_word * words as f64).round() as usize;
+    let mut samples = vec![0i32; 2 * len];
+    // Filling in uneven pieces shouldn't change the signal.
+    for chunk in samples.chunks_mut(2 * 777) {
+        encoder.fill(chunk, 2, 1).unwrap();
+    }
+    samples
+}
+
+// Decodes channel 1 of a stereo buffer in packets of 1000 sample frames, timed from `start`.
+fn decode(decoder: &mut LtcDecoder, samples: &[i32], start: i64) -> Vec<LtcFrame> {
+    let mut frames = Vec::new();
+    for (i, chunk) in samples.chunks(2 * 1000).enumerate() {
+        let packet = AudioPacket::from_i32(RATE, 2, chunk, start + 1000 * i as i64).unwrap();
+        frames.extend(decoder.process(&packet, 1).unwrap());
+    }
+    frames
+}
+
+#[test]
+fn round_trip() {
+    // Display modes, starting labels and LTC words per second.
+    let cases = [
+        (
+            DisplayMode::MODE_HD1080P2398,
+            "01:02:03:04",
+            24000.0 / 1001.0,
+        ),
+        (DisplayMode::MODE_HD1080P24, "23:59:59:20", 24.0),
+        (DisplayMode::MODE_HD1080P25, "10:00:00:00", 25.0),
+        (DisplayMode::MODE_HD1080I50, "00:09:59:21", 25.0),
+        (
+            DisplayMode::MODE_HD1080P2997,
+            "00:00:59;20",
+            30000.0 / 1001.0,
+        ),
+        (
+            DisplayMode::MODE_HD1080P2997,
+            "00:00:59:20",
+            30000.0 / 1001.0,
+        ),
+        (
+            DisplayMode::MODE_HD1080I5994,
+            "00:09:59;20",
+            30000.0 / 1001.0,
+        ),
+        (DisplayMode::MODE_HD1080P30, "12:34:56:00", 30.0),
+        (DisplayMode::MODE_HD1080P50, "09:59:59:40", 25.0),
+        (
+            DisplayMode::MODE_HD1080P5994,
+            "00:00:59;40",
+            30000.0 / 1001.0,
+        ),
+        (DisplayMode::MODE_HD1080P6000, "00:00:59:40", 30.0),
+    ];
+    for &(mode, start, words_per_second) in cases.iter() {
+        let start = tc(start);
+        let mut encoder = LtcEncoder::new(mode, RATE, start).unwrap();
+        encoder.set_user_bits(0x8765_4321);
+        let rate = encoder.rate();
+        let group = rate.frames_per_second().div_ceil(30);
+        let samples_per_word = RATE as f64 / words_per_second;
+        let samples = encode(&mut encoder, samples_per_word, 60);
+        assert_eq!(
+            encoder.peek(),
+            start.add_frames(60 * group as i64, rate).unwrap()
+        );
+
+        let mut decoder = LtcDecoder::new(RATE).unwrap();
+        let frames = decode(&mut decoder, &samples, 5000);
+        assert!(frames.len() >= 58, "{}: {} words", mode, frames.len());
+        for frame in &frames {
+            let word = ((frame.time - 5000) as f64 / samples_per_word).round() as i64;
+            assert!(
+                (frame.time as f64 - 5000.0 - word as f64 * samples_per_word).abs() <= 1.0,
+                "{}: word {} at {}",
+                mode,
+                word,
+                frame.time
+            );
+            let label = start.add_frames(word * group as i64, rate).unwrap();
+            let expected = FrameTimecode {
+                frames: label.frames() / group as u8,
+                ..FrameTimecode::from(label)
+            };
+            assert_eq!(frame.timecode, expected, "{}: word {}", mode, word);
+            assert_eq!(frame.user_bits, 0x8765_4321);
+
+            // Jam syncing to a word counts every frame again.
+            let mut generator = TimecodeGenerator::new(mode, start).unwrap();
+            generator.jam_sync(&frame.timecode, 0).unwrap();
+            assert_eq!(generator.peek(), label);
+        }
+        // The last word ends with the first transition of the next one, so it isn't decoded
+        // yet.
+        let last = frames.last().unwrap().time - 5000;
+        assert_eq!((last as f64 / samples_per_word).round(), 58.0);
+    }
+}
+
+#[test]
+fn levels_and_sample_types() {
+    let mut encoder =
+        LtcEncoder::new(DisplayMode::MODE_HD1080P25, RATE, tc("00:00:00:00")).unwrap();
+    encoder.set_level(-40.0);
+    encoder.set_color_frame(true);
+    let mut samples = vec![0i16; 1920 * 10];
+    encoder.fill(&mut samples, 1, 0).unwrap();
+    let peak = samples.iter().map(|s| s.unsigned_abs()).max().unwrap();
+    assert_eq!(peak, (32768.0 * 10f64.powf(-2.0)).round() as u16);
+
+    let mut decoder = LtcDecoder::new(RATE).unwrap();
+    let packet = AudioPacket::from_i16(RATE, 1, &samples, 0).unwrap();
+    let frames = decoder.process(&packet, 0).unwrap();
+    assert!(frames.len() >= 8);
+    for frame in &frames {
+        assert!(frame.timecode.flags.contains(TimecodeFlags::COLOR_FRAME));
+        assert!(!frame.timecode.flags.contains(TimecodeFlags::DROP_FRAME));
+        assert_eq!(frame.user_bits, 0);
+    }
+    assert_eq!(frames.last().unwrap().timecode.frames, 8);
+}
+
+#[test]
+fn varispeed() {
+    // LTC at 24 frames per second played 10% slow, then spliced to LTC played 20% fast.
+    let mut decoder = LtcDecoder::new(RATE).unwrap();
+    let (mut frames, mut time) = (Vec::new(), 0);
+    for &speed in [0.9, 1.2].iter() {
+        let sample_rate = (RATE as f64 / speed).round() as u32;
+        let mut encoder =
+            LtcEncoder::from_frame_rate(1, 24, sample_rate, tc("01:00:00:00")).unwrap();
+        let samples = encode(&mut encoder, sample_rate as f64 / 24.0, 48);
+        frames.extend(decode(&mut decoder, &samples, time));
+        time += samples.len() as i64 / 2;
+    }
+    assert!(frames.len() >= 94, "{} words", frames.len());
+    let steps: Vec<_> = frames
+        .windows(2)
+        .map(|pair| {
+            let a = SmpteTimecode::from_frame_timecode(&pair[0].timecode).unwrap();
+            let b = SmpteTimecode::from_frame_timecode(&pair[1].timecode).unwrap();
+            b.frames_since(&a, TimecodeRate::RATE_24).unwrap()
+        })
+        .collect();
+    assert_eq!(steps.iter().filter(|step| **step != 1).count(), 1);
+}
+
+#[test]
+fn gaps_and_errors() {
+    let mut encoder =
+        LtcEncoder::new(DisplayMode::MODE_HD1080P30, RATE, tc("00:00:00:00")).unwrap();
+    let samples = encode(&mut encoder, 1600.0, 20);
+
+    // Dropping a packet loses the word it fell in, and only that one.
+    let mut decoder = LtcDecoder::new(RATE).unwrap();
+    let mut frames = Vec::new();
+    for (i, chunk) in samples.chunks(2 * 1000).enumerate() {
+        if i == 10 {
+            continue;
+        }
+        let packet = AudioPacket::from_i32(RATE, 2, chunk, 1000 * i as i64).unwrap();
+        frames.extend(decoder.process(&packet, 1).unwrap());
+    }
+    let labels: Vec<_> = frames.iter().map(|f| f.timecode.frames).collect();
+    assert_eq!(
+        labels,
+        vec![0, 1, 2, 3, 4, 5, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18]
+    );
+
+    let packet = AudioPacket::from_i32(44100, 2, &samples[..2000], 0).unwrap();
+    assert!(decoder.process(&packet, 1).is_err());
+    let packet = AudioPacket::from_i32(RATE, 2, &samples[..2000], 0).unwrap();
+    assert!(decoder.process(&packet, 2).is_err());
+    assert!(LtcDecoder::new(0).is_err());
+
+    assert!(encoder.fill(&mut [0i32; 4], 2, 2).is_err());
+    assert!(encoder.fill(&mut [0i32; 3], 2, 1).is_err());
+    assert!(LtcEncoder::new(DisplayMode::MODE_HD1080P25, RATE, tc("00:00:00;00")).is_err());
+    assert!(LtcEncoder::new(DisplayMode::MODE_HD1080P25, RATE, tc("00:00:00:25")).is_err());
+    assert!(LtcEncoder::from_frame_rate(1000, 23976, RATE, tc("00:00:00:00")).is_err());
+    assert!(LtcEncoder::from_frame_rate(1000, 30000, RATE, tc("00:00:00;00")).is_err());
+    assert!(LtcEncoder::from_frame_rate(1000, 60000, RATE, tc("00:00:00;00")).is_err());
+    assert!(LtcEncoder::from_frame_rate(1001, 30000, RATE, tc("00:00:00;00")).is_ok());
+    assert!(LtcEncoder::new(DisplayMode::MODE_HD1080P25, 0, tc("00:00:00:00")).is_err());
+}
@@ -0,0 +1,198 @@
+extern crate decklink;
+
+use decklink::overlay::TextOverlay;
+use decklink::pattern::{Color, Pattern};
+use decklink::{
+    pixel, Frame, FrameTimecode, PixelFormat, TimecodeFlags, TimecodeFormat, VideoBuffer,
+    VideoFrame,
+};
+
+// FNV-1a.
+fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+// 10-bit Y' at a pixel of a v210 frame.
+fn v210_luma(frame: &mut VideoBuffer, x: usize, y: usize) -> u16 {
+    let (width, row_bytes) = (frame.get_width() as usize, frame.get_row_bytes() as usize);
+    let row = &frame.get_bytes().unwrap()[y * row_bytes..(y + 1) * row_bytes];
+    let (mut luma, mut chroma) = (vec![0; width], vec![0; width]);
+    pixel::v210_to_p210(row, row_bytes, width, 1, &mut luma, &mut chroma).unwrap();
+    luma[x] >> 6
+}
+
+#[test]
+fn size() {
+    let overlay = TextOverlay::new(2);
+    assert_eq!(overlay.size("12:34"), (62, 22));
+    assert_eq!(overlay.size("ab\nc"), (26, 42));
+    assert_eq!(TextOverlay::for_height(1080).scale, 5);
+    assert_eq!(TextOverlay::for_height(486).scale, 2);
+}
+
+#[test]
+fn glyphs_bgra() {
+    let mut frame = VideoBuffer::new(16, 14, PixelFormat::FORMAT_8BIT_BGRA).unwrap();
+    let mut overlay = TextOverlay::new(1);
+    overlay.background = Color::new(1.0, 0.0, 0.0);
+    overlay.background_opacity = 1.0;
+    overlay.draw(&mut frame, 2, 1, "Iy").unwrap();
+
+    let bytes = frame.get_bytes().unwrap();
+    let map: Vec<String> = bytes
+        .chunks(16 * 4)
+        .map(|row| {
+            row.chunks(4)
+                .map(|px| match px {
+                    [255, 255, 255, 255] => '#',
+                    [0, 0, 255, 255] => 'r',
+                    [0, 0, 0, 0] => '.',
+                    _ => '?',
+                })
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        map,
+        vec![
+            "................",
+            "..rrrrrrrrrrrrr.",
+            "..rr###rrrrrrrr.",
+            "..rrr#rrrrrrrrr.",
+            "..rrr#rrr#rrr#r.",
+            "..rrr#rrr#rrr#r.",
+            "..rrr#rrr#rrr#r.",
+            "..rrr#rrr#rrr#r.",
+            "..rr###rrr####r.",
+            "..rrrrrrrrrrr#r.",
+            "..rrrrrrrr###rr.",
+            "..rrrrrrrrrrrrr.",
+            "................",
+            "................",
+        ]
+    );
+}
+
+#[test]
+fn background_opacity() {
+    let mut frame = Pattern::Solid(Color::WHITE)
+        .buffer(16, 16, PixelFormat::FORMAT_8BIT_BGRA, 0)
+        .unwrap();
+    let mut overlay = TextOverlay::new(1);
+    overlay.background_opacity = 0.5;
+    overlay.draw(&mut frame, 0, 0, " ").unwrap();
+    let bytes = frame.get_bytes().unwrap();
+    assert_eq!(&bytes[..4], &[127, 127, 127, 255]);
+    assert_eq!(&bytes[7 * 4..8 * 4], &[255, 255, 255, 255]);
+
+    // With no box, only the text is drawn.
+    let mut frame = VideoBuffer::new(16, 16, PixelFormat::FORMAT_8BIT_BGRA).unwrap();
+    overlay.background_opacity = 0.0;
+    overlay.draw(&mut frame, 0, 0, "-").unwrap();
+    let bytes = frame.get_bytes().unwrap();
+    assert_eq!(&bytes[..4], &[0, 0, 0, 0]);
+    assert_eq!(
+        &bytes[(4 * 16 + 1) * 4..(4 * 16 + 2) * 4],
+        &[255, 255, 255, 255]
+    );
+}
+
+#[test]
+fn yuv_formats() {
+    let mut overlay = TextOverlay::new(4);
+    overlay.background_opacity = 1.0;
+
+    let mut uyvy = Pattern::EbuBars75
+        .buffer(1920, 1080, PixelFormat::FORMAT_8BIT_YUV, 0)
+        .unwrap();
+    let original = uyvy.get_bytes().unwrap().to_vec();
+    overlay.draw(&mut uyvy, 100, 100, "0").unwrap();
+    let row_bytes = uyvy.get_row_bytes() as usize;
+    let bytes = uyvy.get_bytes().unwrap();
+    let luma = |x: usize, y: usize| bytes[y * row_bytes + x * 2 + 1];
+    assert_eq!(
+        (luma(108, 104), luma(104, 104), luma(100, 100)),
+        (235, 16, 16)
+    );
+    // Only the box's rows are touched.
+    assert_eq!(&bytes[..100 * row_bytes], &original[..100 * row_bytes]);
+    assert_eq!(&bytes[144 * row_bytes..], &original[144 * row_bytes..]);
+    assert_eq!(luma(140, 120), original[120 * row_bytes + 140 * 2 + 1]);
+
+    let mut v210 = Pattern::EbuBars75
+        .buffer(1920, 1080, PixelFormat::FORMAT_10BIT_YUV, 0)
+        .unwrap();
+    overlay.draw(&mut v210, 100, 100, "0").unwrap();
+    assert_eq!(v210_luma(&mut v210, 108, 104), 940);
+    assert_eq!(v210_luma(&mut v210, 104, 104), 64);
+    assert_eq!(v210_luma(&mut v210, 140, 120), 940);
+}
+
+#[test]
+fn clipped() {
+    let mut frame = VideoBuffer::new(8, 8, PixelFormat::FORMAT_8BIT_YUV).unwrap();
+    let overlay = TextOverlay::new(3);
+    overlay.draw(&mut frame, -20, -20, "clipped").unwrap();
+    overlay.draw(&mut frame, 6, 6, "clipped").unwrap();
+    overlay.draw(&mut frame, 100, 100, "outside").unwrap();
+    assert!(overlay
+        .draw(
+            &mut VideoBuffer::new(8, 8, PixelFormat::FORMAT_10BIT_RGB).unwrap(),
+            0,
+            0,
+            "ok"
+        )
+        .is_ok());
+}
+
+#[test]
+fn burn_timecode() {
+    let mut frame = Frame::new(1920, 1080, PixelFormat::FORMAT_8BIT_YUV).unwrap();
+    let overlay = TextOverlay::for_height(1080);
+    assert_eq!(overlay.burn_timecode(&mut frame).unwrap(), None);
+    assert!(frame.get_bytes().unwrap().iter().all(|b| *b == 0));
+
+    frame.set_timecode(
+        TimecodeFormat::FORMAT_RP188_LTC,
+        FrameTimecode {
+            hours: 1,
+            minutes: 2,
+            seconds: 3,
+            frames: 6,
+            flags: TimecodeFlags::DROP_FRAME,
+        },
+    );
+    assert_eq!(
+        overlay.burn_timecode(&mut frame).unwrap(),
+        Some("01:02:03;06".to_string())
+    );
+    // The box is centered horizontally and sits above the bottom tenth of the picture.
+    let (box_width, box_height) = overlay.size("01:02:03;06");
+    let (left, top) = ((1920 - box_width) / 2, 1080 - 108 - box_height);
+    let row_bytes = frame.get_row_bytes() as usize;
+    let bytes = frame.get_bytes().unwrap();
+    let changed = |x: i32, y: i32| bytes[y as usize * row_bytes + x as usize * 2 + 1] != 0;
+    assert!(changed(left, top) && changed(left + box_width - 1, top + box_height - 1));
+    assert!(!changed(left - 1, top) && !changed(left, top - 1));
+    assert!(!changed(left + box_width, top) && !changed(left, top + box_height));
+}
+
+#[test]
+fn golden_checksums() {
+    let overlay = TextOverlay::new(2);
+    for (format, sum) in [
+        (PixelFormat::FORMAT_8BIT_YUV, 0x741ee7120c4a21adu64),
+        (PixelFormat::FORMAT_10BIT_YUV, 0x21fda1d6eab98cdd),
+        (PixelFormat::FORMAT_8BIT_BGRA, 0x4bc99376d2df4e85),
+    ]
+    .iter()
+    {
+        let mut frame = Pattern::EbuBars75.buffer(720, 576, *format, 0).unwrap();
+        overlay
+            .draw(&mut frame, 40, 40, "CAM 1 - 10:00:00:00\nLine-up {test}")
+            .unwrap();
+        assert_eq!(checksum(frame.get_bytes().unwrap()), *sum, "{:?}", format);
+    }
+}
@@ -0,0 +1,312 @@
+extern crate decklink;
+
+use decklink::pattern::{Color, Pattern};
+use decklink::{pixel, DisplayMode, PixelFormat, VideoBuffer, VideoFrame};
+
+const FORMATS: [PixelFormat; 9] = [
+    PixelFormat::FORMAT_8BIT_YUV,
+    PixelFormat::FORMAT_10BIT_YUV,
+    PixelFormat::FORMAT_8BIT_ARGB,
+    PixelFormat::FORMAT_8BIT_BGRA,
+    PixelFormat::FORMAT_10BIT_RGB,
+    PixelFormat::FORMAT_10BIT_RGBX,
+    PixelFormat::FORMAT_10BIT_RGBXLE,
+    PixelFormat::FORMAT_12BIT_RGB,
+    PixelFormat::FORMAT_12BIT_RGBLE,
+];
+
+// FNV-1a.
+fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+// 10-bit Y', Cb, Cr at a pixel of a v210 frame.
+fn v210_at(frame: &mut VideoBuffer, x: usize, y: usize) -> (u16, u16, u16) {
+    let (width, row_bytes) = (frame.get_width() as usize, frame.get_row_bytes() as usize);
+    let row = &frame.get_bytes().unwrap()[y * row_bytes..(y + 1) * row_bytes];
+    let (mut luma, mut chroma) = (vec![0; width], vec![0; width]);
+    pixel::v210_to_p210(row, row_bytes, width, 1, &mut luma, &mut chroma).unwrap();
+    let c = x / 2 * 2;
+    (luma[x] >> 6, chroma[c] >> 6, chroma[c + 1] >> 6)
+}
+
+#[test]
+fn golden_checksums() {
+    // One checksum per format, in the order of FORMATS, at 1280x720.
+    let goldens: [(Pattern, [u64; 9]); 8] = [
+        (
+            Pattern::SmpteBars,
+            [
+                0x4c0a78b3649e4305,
+                0x39d4e7333bea4abd,
+                0x96e7f7fb0026e415,
+                0x72c855096b081f35,
+                0xafbaae19beaf0105,
+                0x4f7ec26ee64f2af5,
+                0xe1e862efcc25ff05,
+                0xc3ca0489216dd3bd,
+                0x496fb76e2e8a9ddd,
+            ],
+        ),
+        (
+            Pattern::EbuBars75,
+            [
+                0x01ac77f86ff1b325,
+                0xe9c5db0af9ce93e5,
+                0x7244bf002fabc325,
+                0x039318478b46c325,
+                0x5c5ccfb4c50f7b25,
+                0x1277901f83691725,
+                0x2a289cf68fc91725,
+                0xb6fd7ed08e1fed25,
+                0xa41ad9ad8ace6b25,
+            ],
+        ),
+        (
+            Pattern::EbuBars100,
+            [
+                0xc585ba0c7ea39f25,
+                0x18092a5dfd61b8a5,
+                0x05251cd735957325,
+                0x4b713fcecedd7325,
+                0x1d626bb77f3e5b25,
+                0x4738251bc2c03325,
+                0x4db713acd586c325,
+                0x8bc29de04aaa1725,
+                0xee0180d1b0d74325,
+            ],
+        ),
+        (
+            Pattern::Pluge,
+            [
+                0x3914e0d77be51b65,
+                0xb1a8b709e73765d5,
+                0x7f63e77697218125,
+                0xd5195dcce47d1b25,
+                0x53afaef1478d60c5,
+                0x6a8a8d18577fd605,
+                0x8d60318f47cca285,
+                0xef802ccfa273c8c5,
+                0xfc478ee75fb558a5,
+            ],
+        ),
+        (
+            Pattern::LumaRamp,
+            [
+                0x11cc5356cee2ba25,
+                0x0a00354680930065,
+                0xcc36876c59d93725,
+                0x24fe1ccd297b1725,
+                0x72fe398b00c3f7e5,
+                0xadcd109e16117f25,
+                0x3a00166b21006de5,
+                0x5b763c3498361225,
+                0xce4b7c8110e38325,
+            ],
+        ),
+        (
+            Pattern::ZonePlate,
+            [
+                0xcb576e91c3719ea5,
+                0xf463d5f384bb3ba5,
+                0x5cdc56b48c4e8c05,
+                0xa309b2fccba657c5,
+                0x6f38d9460b603c35,
+                0xf3a3e3ce532edec5,
+                0xf65278ee76375345,
+                0xe2dd24830a1802dd,
+                0xf0c05ec8cf7ad245,
+            ],
+        ),
+        (
+            Pattern::MovingBox,
+            [
+                0x5a31a21e7087ac75,
+                0x63d29a4daa6d8d2d,
+                0x4af545d498b74d75,
+                0xcb74d6ccde5a8f35,
+                0x7296bc2825b6943d,
+                0x0246c57220ac7c9d,
+                0xda71625d315071fd,
+                0xfcb962dcaf085f25,
+                0x87211e42c43e4325,
+            ],
+        ),
+        (
+            Pattern::Solid(Color::new(0.25, 0.5, 0.75)),
+            [
+                0x025ea3521e36a325,
+                0x666d69d992fb5325,
+                0xca434e156a964325,
+                0x0b9995a9d4164325,
+                0x632bc4ace921c325,
+                0x767be4f94d70a325,
+                0x371a091ccdb4a325,
+                0x6acb02de624c9325,
+                0x52a772dd831a9b25,
+            ],
+        ),
+    ];
+    for (pattern, sums) in goldens.iter() {
+        for (format, sum) in FORMATS.iter().zip(sums.iter()) {
+            let mut buffer = pattern.buffer(1280, 720, *format, 10).unwrap();
+            assert_eq!(
+                checksum(buffer.get_bytes().unwrap()),
+                *sum,
+                "{:?} in {:?}",
+                pattern,
+                format
+            );
+        }
+    }
+}
+
+#[test]
+fn smpte_bars_levels() {
+    // 10-bit Rec.709 values from RP 219.
+    let mut frame = Pattern::SmpteBars
+        .buffer_for_mode(
+            DisplayMode::MODE_HD1080P25,
+            PixelFormat::FORMAT_10BIT_YUV,
+            0,
+        )
+        .unwrap();
+    // 40% gray, then 75% white, yellow, cyan, green, magenta, red and blue.
+    let top = [
+        (120, (414, 512, 512)),
+        (340, (721, 512, 512)),
+        (550, (674, 176, 543)),
+        (760, (581, 589, 176)),
+        (960, (534, 253, 207)),
+        (1170, (251, 771, 817)),
+        (1370, (204, 435, 848)),
+        (1580, (111, 848, 481)),
+        (1800, (414, 512, 512)),
+    ];
+    for (x, expected) in top.iter() {
+        assert_eq!(v210_at(&mut frame, *x, 100), *expected, "x = {}", x);
+    }
+    // 100% cyan and blue side panels around 75% white.
+    assert_eq!(v210_at(&mut frame, 100, 660), (754, 615, 64));
+    assert_eq!(v210_at(&mut frame, 960, 660), (721, 512, 512));
+    assert_eq!(v210_at(&mut frame, 1820, 660), (127, 960, 471));
+    // The luma ramp rises monotonically from black to white.
+    let ramp: Vec<u16> = (446..1474).map(|x| v210_at(&mut frame, x, 750).0).collect();
+    assert_eq!((ramp[0], ramp[ramp.len() - 1]), (64, 940));
+    assert!(ramp.windows(2).all(|w| w[0] <= w[1]));
+    // Bottom row: 15% gray, 100% white and the -2%, +2% and +4% PLUGE steps.
+    let row = 1000;
+    assert_eq!(v210_at(&mut frame, 100, row).0, 195);
+    assert_eq!(v210_at(&mut frame, 700, row).0, 940);
+    let pluge: Vec<u16> = [1190, 1260, 1330, 1400, 1460]
+        .iter()
+        .map(|x| v210_at(&mut frame, *x, row).0)
+        .collect();
+    assert_eq!(pluge, vec![46, 64, 82, 64, 99]);
+}
+
+#[test]
+fn ebu_bars_rgb() {
+    let mut frame = Pattern::EbuBars75
+        .buffer(720, 576, PixelFormat::FORMAT_8BIT_BGRA, 0)
+        .unwrap();
+    let bytes = frame.get_bytes().unwrap();
+    let bars: Vec<[u8; 4]> = (0..8)
+        .map(|i| {
+            let x = i * 90 + 45;
+            let mut px = [0; 4];
+            px.copy_from_slice(&bytes[x * 4..x * 4 + 4]);
+            px
+        })
+        .collect();
+    assert_eq!(
+        bars,
+        vec![
+            [255, 255, 255, 255],
+            [0, 191, 191, 255],
+            [191, 191, 0, 255],
+            [0, 191, 0, 255],
+            [191, 0, 191, 255],
+            [0, 0, 191, 255],
+            [191, 0, 0, 255],
+            [0, 0, 0, 255],
+        ]
+    );
+}
+
+#[test]
+fn moving_box() {
+    let (width, height) = (1920, 1080);
+    let positions: Vec<(usize, usize, usize)> = (0..3)
+        .map(|i| Pattern::moving_box(width, height, i))
+        .collect();
+    assert_eq!(
+        positions,
+        vec![(0, 472, 136), (20, 472, 136), (40, 472, 136)]
+    );
+    // It wraps rather than leaving the picture.
+    for i in 0..1000 {
+        let (left, _, side) = Pattern::moving_box(width, height, i);
+        assert!(left + side <= width as usize);
+    }
+
+    let mut frame = Pattern::MovingBox
+        .buffer(width, height, PixelFormat::FORMAT_8BIT_YUV, 2)
+        .unwrap();
+    let row_bytes = frame.get_row_bytes() as usize;
+    let row = &frame.get_bytes().unwrap()[540 * row_bytes..];
+    let luma = |x: usize| row[x * 2 + 1];
+    assert_eq!(
+        (luma(38), luma(40), luma(175), luma(176)),
+        (16, 235, 235, 16)
+    );
+}
+
+#[test]
+fn solid_pluge_clipping() {
+    // Below-black levels are kept in legal range and clipped in full range.
+    let below = Pattern::Solid(Color::gray(-0.02));
+    let mut legal = below
+        .buffer(6, 2, PixelFormat::FORMAT_10BIT_YUV, 0)
+        .unwrap();
+    assert_eq!(v210_at(&mut legal, 0, 0), (46, 512, 512));
+    let mut full = below
+        .buffer(2, 2, PixelFormat::FORMAT_8BIT_ARGB, 0)
+        .unwrap();
+    assert_eq!(&full.get_bytes().unwrap()[..4], &[255, 0, 0, 0]);
+}
+
+#[test]
+fn every_catalog_raster() {
+    let mut rasters: Vec<(i32, i32)> = DisplayMode::catalog()
+        .iter()
+        .map(|spec| (spec.width, spec.height))
+        .collect();
+    rasters.sort();
+    rasters.dedup();
+    for (width, height) in rasters {
+        let mut frame = Pattern::SmpteBars
+            .buffer(width, height, PixelFormat::FORMAT_10BIT_YUV, 0)
+            .unwrap();
+        // The bars are symmetric about the center, and the first bar is 75% white.
+        let x = (width / 8 + width * 3 / 56) as usize;
+        assert_eq!(
+            v210_at(&mut frame, x, 0),
+            (721, 512, 512),
+            "{}x{}",
+            width,
+            height
+        );
+    }
+}
+
+#[test]
+fn unsupported_format() {
+    let mut frame = VideoBuffer::new(64, 64, PixelFormat::FORMAT_8BIT_YUV).unwrap();
+    assert!(Pattern::SmpteBars.render(0, &mut frame).is_ok());
+    assert!(Pattern::SmpteBars
+        .buffer(64, 64, PixelFormat::FORMAT_H265, 0)
+        .is_err());
+}
@@ -0,0 +1,121 @@
+extern crate decklink;
+
+use decklink::{pixel, DisplayMode, PixelFormat};
+
+// Deterministic 10-bit samples, MSB-aligned in 16 bits.
+fn samples(n: usize, seed: u32) -> Vec<u16> {
+    let mut state = seed;
+    (0..n)
+        .map(|_| {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            ((state >> 22) as u16) << 6
+        })
+        .collect()
+}
+
+fn catalog_widths() -> Vec<usize> {
+    let mut widths: Vec<usize> = DisplayMode::catalog()
+        .iter()
+        .map(|spec| spec.width as usize)
+        .collect();
+    widths.sort();
+    widths.dedup();
+    widths
+}
+
+#[test]
+fn v210_reference_pixels() {
+    // Six pixels with Cb = 0x040, 0x080, 0x0c0, Y = 0x100..=0x105, Cr = 0x200, 0x240, 0x280.
+    let words: [u32; 4] = [
+        0x040 | 0x100 << 10 | 0x200 << 20,
+        0x101 | 0x080 << 10 | 0x102 << 20,
+        0x240 | 0x103 << 10 | 0x0c0 << 20,
+        0x104 | 0x280 << 10 | 0x105 << 20,
+    ];
+    let row_bytes = PixelFormat::FORMAT_10BIT_YUV.row_bytes(6).unwrap() as usize;
+    let mut src = vec![0u8; row_bytes];
+    for (i, w) in words.iter().enumerate() {
+        src[i * 4..i * 4 + 4].copy_from_slice(&w.to_le_bytes());
+    }
+
+    let (mut y, mut u, mut v) = (vec![0; 6], vec![0; 3], vec![0; 3]);
+    pixel::v210_to_yuv422p16(&src, row_bytes, 6, 1, &mut y, &mut u, &mut v).unwrap();
+    let msb = |s: &[u16]| s.iter().map(|x| x << 6).collect::<Vec<u16>>();
+    assert_eq!(y, msb(&[0x100, 0x101, 0x102, 0x103, 0x104, 0x105]));
+    assert_eq!(u, msb(&[0x040, 0x080, 0x0c0]));
+    assert_eq!(v, msb(&[0x200, 0x240, 0x280]));
+
+    let (mut py, mut puv) = (vec![0; 6], vec![0; 6]);
+    pixel::v210_to_p210(&src, row_bytes, 6, 1, &mut py, &mut puv).unwrap();
+    assert_eq!(py, y);
+    assert_eq!(puv, msb(&[0x040, 0x200, 0x080, 0x240, 0x0c0, 0x280]));
+
+    let mut packed = vec![0xffu8; row_bytes];
+    pixel::yuv422p16_to_v210(&y, &u, &v, 6, 1, &mut packed, row_bytes).unwrap();
+    assert_eq!(packed, src);
+}
+
+#[test]
+fn v210_yuv422p16_round_trip() {
+    let height = 3;
+    for width in catalog_widths().into_iter().chain(vec![2, 4, 46, 50, 94]) {
+        let row_bytes = PixelFormat::FORMAT_10BIT_YUV
+            .row_bytes(width as i32)
+            .unwrap() as usize;
+        let y = samples(width * height, 1);
+        let u = samples(width / 2 * height, 2);
+        let v = samples(width / 2 * height, 3);
+
+        let mut packed = vec![0xffu8; row_bytes * height];
+        pixel::yuv422p16_to_v210(&y, &u, &v, width, height, &mut packed, row_bytes).unwrap();
+
+        // Everything past the last used word of each row is padding and must be zeroed.
+        let used = (width * 2).div_ceil(3) * 4;
+        for row in 0..height {
+            assert!(packed[row * row_bytes + used..(row + 1) * row_bytes]
+                .iter()
+                .all(|b| *b == 0));
+        }
+
+        let (mut y2, mut u2, mut v2) = (vec![0; y.len()], vec![0; u.len()], vec![0; v.len()]);
+        pixel::v210_to_yuv422p16(&packed, row_bytes, width, height, &mut y2, &mut u2, &mut v2)
+            .unwrap();
+        assert_eq!(y, y2, "width {}", width);
+        assert_eq!(u, u2, "width {}", width);
+        assert_eq!(v, v2, "width {}", width);
+    }
+}
+
+#[test]
+fn v210_p210_round_trip() {
+    let height = 3;
+    for width in catalog_widths().into_iter().chain(vec![2, 4, 46, 50, 94]) {
+        // Exercise row padding beyond the minimum, as some drivers hand out.
+        let row_bytes = PixelFormat::FORMAT_10BIT_YUV
+            .row_bytes(width as i32)
+            .unwrap() as usize
+            + 128;
+        let y = samples(width * height, 4);
+        let uv = samples(width * height, 5);
+
+        let mut packed = vec![0u8; row_bytes * height];
+        pixel::p210_to_v210(&y, &uv, width, height, &mut packed, row_bytes).unwrap();
+
+        let (mut y2, mut uv2) = (vec![0; y.len()], vec![0; uv.len()]);
+        pixel::v210_to_p210(&packed, row_bytes, width, height, &mut y2, &mut uv2).unwrap();
+        assert_eq!(y, y2, "width {}", width);
+        assert_eq!(uv, uv2, "width {}", width);
+    }
+}
+
+#[test]
+fn v210_rejects_bad_geometry() {
+    let mut y = vec![0; 1920];
+    let mut uv = vec![0; 1920];
+    let src = vec![0u8; 5120];
+    assert!(pixel::v210_to_p210(&src, 5120, 1920, 1, &mut y, &mut uv).is_ok());
+    assert!(pixel::v210_to_p210(&src, 3840, 1920, 1, &mut y, &mut uv).is_err());
+    assert!(pixel::v210_to_p210(&src, 5120, 1920, 2, &mut y, &mut uv).is_err());
+    assert!(pixel::v210_to_p210(&src, 5120, 1919, 1, &mut y, &mut uv).is_err());
+    assert!(pixel::v210_to_p210(&src, 5120, 1920, 1, &mut y[..100], &mut uv).is_err());
+}
@@ -0,0 +1,61 @@
+extern crate decklink;
+
+use decklink::PixelFormat;
+
+// Row sizes from the pixel format section of the DeckLink SDK manual.
+#[test]
+fn row_bytes() {
+    assert_eq!(PixelFormat::FORMAT_8BIT_YUV.row_bytes(1920), Some(3840));
+    assert_eq!(PixelFormat::FORMAT_10BIT_YUV.row_bytes(1920), Some(5120));
+    assert_eq!(PixelFormat::FORMAT_10BIT_YUV.row_bytes(1280), Some(3456));
+    assert_eq!(PixelFormat::FORMAT_10BIT_YUV.row_bytes(720), Some(1920));
+    assert_eq!(PixelFormat::FORMAT_10BIT_YUV.row_bytes(3840), Some(10240));
+    assert_eq!(PixelFormat::FORMAT_8BIT_BGRA.row_bytes(1920), Some(7680));
+    assert_eq!(PixelFormat::FORMAT_10BIT_RGB.row_bytes(1920), Some(7680));
+    assert_eq!(PixelFormat::FORMAT_10BIT_RGB.row_bytes(720), Some(3072));
+    assert_eq!(PixelFormat::FORMAT_10BIT_RGBXLE.row_bytes(1280), Some(5120));
+    assert_eq!(PixelFormat::FORMAT_12BIT_RGB.row_bytes(1920), Some(8640));
+    assert_eq!(PixelFormat::FORMAT_12BIT_RGBLE.row_bytes(2048), Some(9216));
+    assert_eq!(PixelFormat::FORMAT_H265.row_bytes(1920), None);
+}
+
+#[test]
+fn frame_size() {
+    assert_eq!(
+        PixelFormat::FORMAT_10BIT_YUV.frame_size(1920, 1080),
+        Some(5120 * 1080)
+    );
+    assert_eq!(
+        PixelFormat::FORMAT_8BIT_YUV.frame_size(3840, 2160),
+        Some(7680 * 2160)
+    );
+    assert_eq!(PixelFormat::FORMAT_DNXHR.frame_size(1920, 1080), None);
+}
+
+#[test]
+fn properties() {
+    assert_eq!(PixelFormat::FORMAT_10BIT_YUV.bits_per_component(), Some(10));
+    assert_eq!(PixelFormat::FORMAT_12BIT_RGB.bits_per_component(), Some(12));
+    assert!(PixelFormat::FORMAT_10BIT_YUV.is_yuv());
+    assert!(!PixelFormat::FORMAT_10BIT_YUV.is_rgb());
+    assert!(PixelFormat::FORMAT_10BIT_RGB.is_rgb());
+    assert_eq!(PixelFormat::FORMAT_10BIT_YUV.fourcc(), Some(*b"v210"));
+    assert_eq!(PixelFormat::FORMAT_8BIT_YUV.fourcc(), Some(*b"2vuy"));
+    assert_eq!(PixelFormat::FORMAT_12BIT_RGB.fourcc(), Some(*b"R12B"));
+    assert_eq!(PixelFormat::FORMAT_8BIT_ARGB.fourcc(), None);
+}
+
+#[test]
+fn validate() {
+    assert!(PixelFormat::FORMAT_10BIT_YUV
+        .validate(1920, 1080, 5120)
+        .is_ok());
+    assert!(PixelFormat::FORMAT_10BIT_YUV
+        .validate(1920, 1080, 3840)
+        .is_err());
+    assert!(PixelFormat::FORMAT_8BIT_YUV
+        .validate(1919, 1080, 3840)
+        .is_err());
+    assert!(PixelFormat::FORMAT_8BIT_BGRA.validate(0, 1080, 0).is_err());
+    assert!(PixelFormat::FORMAT_H265.validate(1920, 1080, 0).is_err());
+}
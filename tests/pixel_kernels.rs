@@ -0,0 +1,194 @@
+extern crate decklink;
+
+use decklink::pixel::{self, Isa, Kernels};
+use decklink::PixelFormat;
+
+fn bytes(n: usize, seed: u32) -> Vec<u8> {
+    let mut state = seed;
+    (0..n)
+        .map(|_| {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (state >> 24) as u8
+        })
+        .collect()
+}
+
+fn words(n: usize, seed: u32) -> Vec<u16> {
+    bytes(n * 2, seed)
+        .chunks(2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .collect()
+}
+
+// Widths that exercise every vector size with and without a scalar tail.
+const WIDTHS: [usize; 10] = [2, 4, 6, 14, 30, 46, 48, 94, 720, 1920];
+const HEIGHT: usize = 4;
+
+fn simd_kernels() -> Vec<Kernels> {
+    Isa::ALL
+        .iter()
+        .filter(|isa| **isa != Isa::Scalar)
+        .filter_map(|isa| Kernels::new(*isa))
+        .collect()
+}
+
+fn scalar() -> Kernels {
+    Kernels::new(Isa::Scalar).unwrap()
+}
+
+#[test]
+fn detect() {
+    assert!(Isa::detect().is_supported());
+    assert_eq!(Kernels::detect().isa(), Isa::detect());
+    // Every aarch64 CPU has NEON, so the kernels below are compared against it.
+    #[cfg(all(feature = "neon", target_arch = "aarch64"))]
+    assert_eq!(Isa::detect(), Isa::Neon);
+}
+
+#[test]
+fn uyvy_to_420() {
+    for &width in WIDTHS.iter() {
+        let row_bytes = width * 2 + 64;
+        let src = bytes(row_bytes * HEIGHT, width as u32);
+        let size = width * HEIGHT * 3 / 2;
+
+        let (mut i420, mut nv12) = (vec![0; size], vec![0; size]);
+        scalar()
+            .uyvy_to_i420(&src, row_bytes, width, HEIGHT, &mut i420)
+            .unwrap();
+        scalar()
+            .uyvy_to_nv12(&src, row_bytes, width, HEIGHT, &mut nv12)
+            .unwrap();
+        for k in simd_kernels() {
+            let mut out = vec![0; size];
+            k.uyvy_to_i420(&src, row_bytes, width, HEIGHT, &mut out)
+                .unwrap();
+            assert_eq!(out, i420, "{:?} i420 width {}", k.isa(), width);
+            k.uyvy_to_nv12(&src, row_bytes, width, HEIGHT, &mut out)
+                .unwrap();
+            assert_eq!(out, nv12, "{:?} nv12 width {}", k.isa(), width);
+        }
+    }
+}
+
+#[test]
+fn uyvy_from_420() {
+    for &width in WIDTHS.iter() {
+        let row_bytes = width * 2 + 64;
+        let src = bytes(width * HEIGHT * 3 / 2, width as u32);
+
+        let (mut from_i420, mut from_nv12) = (
+            vec![0xff; row_bytes * HEIGHT],
+            vec![0xff; row_bytes * HEIGHT],
+        );
+        scalar()
+            .i420_to_uyvy(&src, width, HEIGHT, &mut from_i420, row_bytes)
+            .unwrap();
+        scalar()
+            .nv12_to_uyvy(&src, width, HEIGHT, &mut from_nv12, row_bytes)
+            .unwrap();
+        for k in simd_kernels() {
+            let mut out = vec![0xff; row_bytes * HEIGHT];
+            k.i420_to_uyvy(&src, width, HEIGHT, &mut out, row_bytes)
+                .unwrap();
+            assert_eq!(out, from_i420, "{:?} i420 width {}", k.isa(), width);
+            k.nv12_to_uyvy(&src, width, HEIGHT, &mut out, row_bytes)
+                .unwrap();
+            assert_eq!(out, from_nv12, "{:?} nv12 width {}", k.isa(), width);
+        }
+    }
+}
+
+#[test]
+fn uyvy_420_round_trip() {
+    // Identical chroma in each row pair survives the trip exactly.
+    let (width, height) = (94, 4);
+    let mut i420 = bytes(width * height * 3 / 2, 7);
+    let mut uyvy = vec![0; width * 2 * height];
+    pixel::i420_to_uyvy(&i420, width, height, &mut uyvy, width * 2).unwrap();
+    let mut back = vec![0; i420.len()];
+    pixel::uyvy_to_i420(&uyvy, width * 2, width, height, &mut back).unwrap();
+    assert_eq!(back, i420);
+
+    i420.truncate(width * height);
+    assert!(pixel::uyvy_to_i420(&uyvy, width * 2, width, height, &mut i420).is_err());
+}
+
+#[test]
+fn bgra_to_uyvy_reference() {
+    // White, black, and Rec.709 75% bars' red and blue in legal range.
+    let src: Vec<u8> = vec![
+        255, 255, 255, 255, 255, 255, 255, 255, 0, 0, 0, 255, 0, 0, 0, 255, 0, 0, 255, 255, 0, 0,
+        255, 255, 255, 0, 0, 255, 255, 0, 0, 255,
+    ];
+    let mut dst = vec![0; 16];
+    for isa in Isa::ALL.iter() {
+        if let Some(k) = Kernels::new(*isa) {
+            k.bgra_to_uyvy(&src, 32, 8, 1, &mut dst, 16).unwrap();
+            assert_eq!(
+                dst,
+                [128, 235, 128, 235, 128, 16, 128, 16, 102, 63, 240, 63, 240, 32, 118, 32],
+                "{:?}",
+                isa
+            );
+        }
+    }
+}
+
+#[test]
+fn bgra_to_uyvy() {
+    for &width in WIDTHS.iter() {
+        let (src_row_bytes, dst_row_bytes) = (width * 4 + 16, width * 2 + 32);
+        let src = bytes(src_row_bytes * HEIGHT, width as u32);
+        let mut expected = vec![0xff; dst_row_bytes * HEIGHT];
+        scalar()
+            .bgra_to_uyvy(
+                &src,
+                src_row_bytes,
+                width,
+                HEIGHT,
+                &mut expected,
+                dst_row_bytes,
+            )
+            .unwrap();
+        for k in simd_kernels() {
+            let mut out = vec![0xff; dst_row_bytes * HEIGHT];
+            k.bgra_to_uyvy(&src, src_row_bytes, width, HEIGHT, &mut out, dst_row_bytes)
+                .unwrap();
+            assert_eq!(out, expected, "{:?} width {}", k.isa(), width);
+        }
+    }
+}
+
+#[test]
+fn v210_p210() {
+    for &width in WIDTHS.iter() {
+        let row_bytes = PixelFormat::FORMAT_10BIT_YUV
+            .row_bytes(width as i32)
+            .unwrap() as usize;
+        let src = bytes(row_bytes * HEIGHT, width as u32);
+        let (mut y, mut uv) = (vec![0; width * HEIGHT], vec![0; width * HEIGHT]);
+        scalar()
+            .v210_to_p210(&src, row_bytes, width, HEIGHT, &mut y, &mut uv)
+            .unwrap();
+
+        let (py, puv) = (words(width * HEIGHT, 1), words(width * HEIGHT, 2));
+        let mut packed = vec![0xff; row_bytes * HEIGHT];
+        scalar()
+            .p210_to_v210(&py, &puv, width, HEIGHT, &mut packed, row_bytes)
+            .unwrap();
+
+        for k in simd_kernels() {
+            let (mut y2, mut uv2) = (vec![0; y.len()], vec![0; uv.len()]);
+            k.v210_to_p210(&src, row_bytes, width, HEIGHT, &mut y2, &mut uv2)
+                .unwrap();
+            assert_eq!(y2, y, "{:?} y width {}", k.isa(), width);
+            assert_eq!(uv2, uv, "{:?} uv width {}", k.isa(), width);
+
+            let mut packed2 = vec![0xff; packed.len()];
+            k.p210_to_v210(&py, &puv, width, HEIGHT, &mut packed2, row_bytes)
+                .unwrap();
+            assert_eq!(packed2, packed, "{:?} v210 width {}", k.isa(), width);
+        }
+    }
+}
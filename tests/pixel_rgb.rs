@@ -0,0 +1,155 @@
+extern crate decklink;
+
+use decklink::{pixel, PixelFormat};
+
+const RGB_FORMATS: [PixelFormat; 5] = [
+    PixelFormat::FORMAT_10BIT_RGB,
+    PixelFormat::FORMAT_10BIT_RGBX,
+    PixelFormat::FORMAT_10BIT_RGBXLE,
+    PixelFormat::FORMAT_12BIT_RGB,
+    PixelFormat::FORMAT_12BIT_RGBLE,
+];
+
+fn row_bytes(format: PixelFormat, width: usize) -> usize {
+    format.row_bytes(width as i32).unwrap() as usize
+}
+
+fn check_unpack(format: PixelFormat, src: &[u8], width: usize, expected: &[u16]) {
+    let mut row = src.to_vec();
+    row.resize(row_bytes(format, width), 0);
+
+    let mut rgb = vec![0; width * 3];
+    pixel::rgb_to_rgb16(format, &row, row.len(), width, 1, &mut rgb).unwrap();
+    assert_eq!(rgb, expected, "{:?}", format.fourcc());
+
+    let mut rgba = vec![0; width * 4];
+    pixel::rgb_to_rgba16(format, &row, row.len(), width, 1, &mut rgba).unwrap();
+    for (px, rgb) in rgba.chunks(4).zip(expected.chunks(3)) {
+        assert_eq!(&px[..3], rgb);
+        assert_eq!(px[3], 0xffff);
+    }
+
+    let mut packed = vec![0xffu8; row.len()];
+    pixel::rgb16_to_rgb(&rgb, width, 1, format, &mut packed, row.len()).unwrap();
+    assert_eq!(packed, row, "{:?}", format.fourcc());
+    let mut packed = vec![0xffu8; row.len()];
+    pixel::rgba16_to_rgb(&rgba, width, 1, format, &mut packed, row.len()).unwrap();
+    assert_eq!(packed, row, "{:?}", format.fourcc());
+}
+
+#[test]
+fn rgb10_reference_pixels() {
+    // Two pixels: R = 0x3ac, G = 0x155, B = 0x0f0 and R = 0x040, G = 0x3c0, B = 0x200.
+    let expected: Vec<u16> = [0x3ac, 0x155, 0x0f0, 0x040, 0x3c0, 0x200]
+        .iter()
+        .map(|c| c << 6)
+        .collect();
+
+    // r210: xxRRRRRR RRRRGGGG GGGGGGBB BBBBBBBB, big-endian.
+    check_unpack(
+        PixelFormat::FORMAT_10BIT_RGB,
+        &[0x3a, 0xc5, 0x54, 0xf0, 0x04, 0x0f, 0x02, 0x00],
+        2,
+        &expected,
+    );
+    // R10b: RRRRRRRR RRGGGGGG GGGGBBBB BBBBBBxx, big-endian.
+    check_unpack(
+        PixelFormat::FORMAT_10BIT_RGBX,
+        &[0xeb, 0x15, 0x53, 0xc0, 0x10, 0x3c, 0x08, 0x00],
+        2,
+        &expected,
+    );
+    // R10l: the same word, little-endian.
+    check_unpack(
+        PixelFormat::FORMAT_10BIT_RGBXLE,
+        &[0xc0, 0x53, 0x15, 0xeb, 0x00, 0x08, 0x3c, 0x10],
+        2,
+        &expected,
+    );
+}
+
+#[test]
+fn rgb12_reference_pixels() {
+    let r: Vec<u32> = (0..8).map(|i| 0xa00 | (i * 0x11)).collect();
+    let g: Vec<u32> = (0..8).map(|i| 0x5b0 | i).collect();
+    let b: Vec<u32> = (0..8).map(|i| 0x0c0 | (i << 8)).collect();
+
+    // The word layout from the 12-bit RGB section of the SDK manual.
+    let words: [u32; 9] = [
+        r[0] | g[0] << 12 | (b[0] & 0xff) << 24,
+        b[0] >> 8 | r[1] << 4 | g[1] << 16 | (b[1] & 0xf) << 28,
+        b[1] >> 4 | r[2] << 8 | g[2] << 20,
+        b[2] | r[3] << 12 | (g[3] & 0xff) << 24,
+        g[3] >> 8 | b[3] << 4 | r[4] << 16 | (g[4] & 0xf) << 28,
+        g[4] >> 4 | b[4] << 8 | r[5] << 20,
+        g[5] | b[5] << 12 | (r[6] & 0xff) << 24,
+        r[6] >> 8 | g[6] << 4 | b[6] << 16 | (r[7] & 0xf) << 28,
+        r[7] >> 4 | g[7] << 8 | b[7] << 20,
+    ];
+    let expected: Vec<u16> = (0..8)
+        .flat_map(|i| vec![r[i] as u16, g[i] as u16, b[i] as u16])
+        .map(|c| c << 4)
+        .collect();
+
+    let le: Vec<u8> = words
+        .iter()
+        .flat_map(|w| w.to_le_bytes().to_vec())
+        .collect();
+    let be: Vec<u8> = words
+        .iter()
+        .flat_map(|w| w.to_be_bytes().to_vec())
+        .collect();
+    check_unpack(PixelFormat::FORMAT_12BIT_RGBLE, &le, 8, &expected);
+    check_unpack(PixelFormat::FORMAT_12BIT_RGB, &be, 8, &expected);
+
+    // A partial group keeps the leading pixels and zeroes the unused components.
+    let mut partial = le[..5].to_vec();
+    partial[4] &= 0x0f;
+    check_unpack(PixelFormat::FORMAT_12BIT_RGBLE, &partial, 1, &expected[..3]);
+}
+
+#[test]
+fn rgb_round_trip() {
+    let height = 3;
+    for format in RGB_FORMATS.iter().cloned() {
+        let bits = format.bits_per_component().unwrap();
+        for width in vec![1, 2, 7, 8, 9, 63, 64, 65, 720, 1920, 2048] {
+            // Padding beyond the minimum, as some drivers hand out.
+            let row_bytes = row_bytes(format, width) + 64;
+            let mut state = width as u32;
+            let rgb: Vec<u16> = (0..width * height * 3)
+                .map(|_| {
+                    state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                    ((state >> (32 - bits)) as u16) << (16 - bits)
+                })
+                .collect();
+
+            let mut packed = vec![0xffu8; row_bytes * height];
+            pixel::rgb16_to_rgb(&rgb, width, height, format, &mut packed, row_bytes).unwrap();
+            let used = match bits {
+                10 => width * 4,
+                _ => width.div_ceil(8) * 36,
+            };
+            for row in packed.chunks(row_bytes) {
+                assert!(row[used..].iter().all(|b| *b == 0));
+            }
+
+            let mut rgb2 = vec![0; rgb.len()];
+            pixel::rgb_to_rgb16(format, &packed, row_bytes, width, height, &mut rgb2).unwrap();
+            assert_eq!(rgb, rgb2, "{:?} width {}", format.fourcc(), width);
+        }
+    }
+}
+
+#[test]
+fn rgb_rejects_bad_input() {
+    let src = vec![0u8; 7680];
+    let mut dst = vec![0; 1920 * 3];
+    let format = PixelFormat::FORMAT_10BIT_RGB;
+    assert!(pixel::rgb_to_rgb16(format, &src, 7680, 1920, 1, &mut dst).is_ok());
+    assert!(pixel::rgb_to_rgb16(format, &src, 5120, 1920, 1, &mut dst).is_err());
+    assert!(pixel::rgb_to_rgb16(format, &src, 7680, 1920, 2, &mut dst).is_err());
+    assert!(pixel::rgb_to_rgba16(format, &src, 7680, 1920, 1, &mut dst).is_err());
+    let yuv = PixelFormat::FORMAT_10BIT_YUV;
+    assert!(pixel::rgb_to_rgb16(yuv, &src, 5120, 1920, 1, &mut dst).is_err());
+}
@@ -0,0 +1,42 @@
+extern crate decklink;
+
+use decklink::{APIVersion, Runtime, RuntimeError, BLACKMAGIC_DECKLINK_API_VERSION};
+
+fn version(major: u8, minor: u8, sub: u8) -> APIVersion {
+    APIVersion { major, minor, sub }
+}
+
+#[test]
+fn api_versions() {
+    let headers = APIVersion::from_int(BLACKMAGIC_DECKLINK_API_VERSION as i64);
+    assert_eq!(headers, version(10, 11, 4));
+    assert_eq!(headers, APIVersion::HEADERS);
+    assert_eq!(Runtime::header_version(), headers);
+    assert_eq!(headers.to_string(), "10.11.4");
+
+    assert!(version(10, 9, 12) < headers);
+    assert!(version(10, 11, 3) < headers);
+    assert!(version(11, 0, 0) > headers);
+}
+
+#[test]
+fn old_driver_message() {
+    let error = RuntimeError::DriverOlderThanHeaders {
+        driver: version(10, 9, 12),
+        headers: APIVersion::HEADERS,
+    };
+    assert_eq!(
+        error.to_string(),
+        "the installed decklink driver (10.9.12) is older than the sdk headers (10.11.4)"
+    );
+}
+
+#[test]
+fn load_after_use() {
+    // Whether or not a driver is installed, the dispatcher has now settled on a library.
+    Runtime::is_available();
+    match Runtime::load_from("/opt/decklink/libDeckLinkAPI.so") {
+        Err(RuntimeError::LoadFailed(message)) => assert!(message.contains("already in use")),
+        result => panic!("{:?}", result),
+    }
+}
@@ -0,0 +1,61 @@
+extern crate decklink;
+
+use std::sync::{mpsc, Arc};
+
+use decklink::{PixelFormat, SharedFrame, SoftwareConversion, VideoBuffer, VideoFrame};
+
+#[test]
+fn shared_frame_validates_buffer() {
+    let bytes: Arc<[u8]> = vec![0u8; 3840 * 1080].into();
+    assert!(SharedFrame::new(
+        1920,
+        1080,
+        3840,
+        PixelFormat::FORMAT_8BIT_YUV,
+        bytes.clone()
+    )
+    .is_ok());
+    assert!(SharedFrame::new(
+        1920,
+        1080,
+        5120,
+        PixelFormat::FORMAT_8BIT_YUV,
+        bytes.clone()
+    )
+    .is_err());
+    assert!(SharedFrame::new(1920, 1080, 3840, PixelFormat::FORMAT_10BIT_YUV, bytes).is_err());
+}
+
+#[test]
+fn shared_frame_release_hook_returns_buffer() {
+    let (tx, rx) = mpsc::channel();
+    let mut frame = SharedFrame::new(
+        16,
+        2,
+        64,
+        PixelFormat::FORMAT_8BIT_BGRA,
+        vec![7u8; 128].into(),
+    )
+    .unwrap();
+    frame.set_release_hook(move |bytes| tx.send(bytes).unwrap());
+    assert!(rx.try_recv().is_err());
+    drop(frame);
+
+    // The hook holds the only reference, so the buffer can be reused.
+    let mut bytes = rx.try_recv().unwrap();
+    assert_eq!(bytes.len(), 128);
+    assert!(Arc::get_mut(&mut bytes).is_some());
+}
+
+#[test]
+fn shared_frame_is_read_only() {
+    let bytes: Arc<[u8]> = vec![255u8; 8 * 4 * 2].into();
+    let mut frame = SharedFrame::new(8, 2, 32, PixelFormat::FORMAT_8BIT_BGRA, bytes).unwrap();
+    assert!(frame.get_bytes_mut().is_err());
+
+    let mut dst = VideoBuffer::new(8, 2, PixelFormat::FORMAT_8BIT_YUV).unwrap();
+    SoftwareConversion::new()
+        .convert_frame(&mut frame, &mut dst)
+        .unwrap();
+    assert_eq!(&dst.get_bytes().unwrap()[..4], &[128, 235, 128, 235]);
+}
@@ -0,0 +1,236 @@
+extern crate decklink;
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use decklink::{DisplayMode, FrameTime, Rational, Rounding, TimeValue};
+
+fn ratio(n: i64, d: i64) -> Rational {
+    Rational::new(n, d).unwrap()
+}
+
+#[test]
+fn rationals() {
+    let r = ratio(60000, -2002);
+    assert_eq!((r.numerator(), r.denominator()), (-30000, 1001));
+    assert_eq!(r, ratio(-30000, 1001));
+    assert!(Rational::new(1, 0).is_err());
+
+    assert_eq!(
+        Rational::for_mode(DisplayMode::MODE_HD1080P5994),
+        Some(ratio(60000, 1001))
+    );
+    assert_eq!(
+        Rational::for_mode(DisplayMode::MODE_HD1080P25),
+        Some(Rational::from_integer(25))
+    );
+    assert_eq!(Rational::from_frame_rate(0, 25000), None);
+
+    assert_eq!(
+        "30000/1001".parse::<Rational>().unwrap(),
+        ratio(30000, 1001)
+    );
+    assert_eq!(
+        " 50 ".parse::<Rational>().unwrap(),
+        Rational::from_integer(50)
+    );
+    assert!("30000/".parse::<Rational>().is_err());
+    assert!("1/0".parse::<Rational>().is_err());
+    assert_eq!(ratio(48000, 1001).to_string(), "48000/1001");
+    assert_eq!(ratio(50, 2).to_string(), "25");
+
+    assert!(ratio(24000, 1001) < Rational::from_integer(24));
+    assert!(ratio(-1, 2) < ratio(1, 3));
+    assert_eq!(ratio(1, 25) + ratio(1, 30), ratio(11, 150));
+    assert_eq!(ratio(1, 25) - ratio(1, 30), ratio(1, 150));
+    assert_eq!(
+        ratio(30000, 1001) * ratio(1001, 1000),
+        Rational::from_integer(30)
+    );
+    assert_eq!(ratio(3, 4).checked_div(ratio(3, 2)), Some(ratio(1, 2)));
+    assert_eq!(ratio(3, 4).checked_div(Rational::from_integer(0)), None);
+    assert_eq!(Rational::from_integer(0).recip(), None);
+    assert_eq!(ratio(7, 2).round(Rounding::Down), Some(3));
+    assert_eq!(ratio(7, 2).round(Rounding::Nearest), Some(4));
+    assert_eq!(ratio(7, 2).round(Rounding::Exact), None);
+
+    // Results that don't fit are reported rather than wrapped.
+    let big = ratio(i64::MAX, 3);
+    assert_eq!(big.checked_mul(big), None);
+    assert_eq!(ratio(1, i64::MAX).checked_add(ratio(1, i64::MAX - 1)), None);
+    assert_eq!(
+        big.checked_mul(ratio(3, i64::MAX)),
+        Some(Rational::from_integer(1))
+    );
+    let min = Rational::from_integer(i64::MIN);
+    assert_eq!(Rational::from_integer(0).checked_sub(min), None);
+    assert_eq!(min.checked_sub(min), Some(Rational::from_integer(0)));
+    assert_eq!(
+        Rational::from_integer(-1).checked_sub(min),
+        Some(Rational::from_integer(i64::MAX))
+    );
+}
+
+#[test]
+fn rescaling() {
+    // One 29.97 frame in 90 kHz ticks, in milliseconds.
+    let frame = TimeValue::new(3003, 90000);
+    let ms = |rounding| frame.rescale(1000, rounding).map(|t| t.value);
+    assert_eq!(ms(Rounding::Down), Some(33));
+    assert_eq!(ms(Rounding::Up), Some(34));
+    assert_eq!(ms(Rounding::Nearest), Some(33));
+    assert_eq!(ms(Rounding::Exact), None);
+    assert_eq!(
+        frame.rescale(30000, Rounding::Exact),
+        Some(TimeValue::new(1001, 30000))
+    );
+
+    // Negative times round the same way along the number line.
+    let t = TimeValue::new(-1, 3);
+    assert_eq!(t.rescale(1, Rounding::Down).unwrap().value, -1);
+    assert_eq!(t.rescale(1, Rounding::Up).unwrap().value, 0);
+    assert_eq!(t.rescale(1, Rounding::Nearest).unwrap().value, 0);
+    let t = TimeValue::new(-1, 2);
+    assert_eq!(t.rescale(1, Rounding::Nearest).unwrap().value, -1);
+    assert_eq!(
+        TimeValue::new(1, 2)
+            .rescale(1, Rounding::Nearest)
+            .unwrap()
+            .value,
+        1
+    );
+
+    // Intermediate products don't overflow; results that don't fit fail.
+    let late = TimeValue::new(i64::MAX / 2, 90000);
+    assert_eq!(
+        late.rescale(90000 * 2, Rounding::Exact),
+        Some(TimeValue::new(i64::MAX / 2 * 2, 180000))
+    );
+    assert_eq!(late.rescale(1_000_000_000, Rounding::Down), None);
+    assert_eq!(frame.rescale(0, Rounding::Down), None);
+    assert_eq!(TimeValue::new(1, 0).rescale(1, Rounding::Down), None);
+}
+
+#[test]
+fn comparison_and_arithmetic() {
+    assert_eq!(TimeValue::new(1, 25), TimeValue::new(40, 1000));
+    assert!(TimeValue::new(1001, 30000) > TimeValue::new(1, 30));
+    let set: HashSet<_> = [
+        TimeValue::new(1, 25),
+        TimeValue::new(40, 1000),
+        TimeValue::new(3600, 90000),
+    ]
+    .iter()
+    .cloned()
+    .collect();
+    assert_eq!(set.len(), 1);
+
+    // A negative scale flips the sign, and a zero scale is no time at all; equal values hash
+    // equal either way.
+    assert_eq!(TimeValue::new(1, -2), TimeValue::new(-1, 2));
+    assert!(TimeValue::new(1, -2) < TimeValue::new(0, 1));
+    assert_ne!(TimeValue::new(0, 0), TimeValue::new(5, 3));
+    assert_ne!(TimeValue::new(0, 0), TimeValue::new(1, 0));
+    assert_eq!(TimeValue::new(1, 0), TimeValue::new(1, 0));
+    assert!(TimeValue::new(i64::MAX, 1) < TimeValue::new(i64::MIN, 0));
+    let set: HashSet<_> = [
+        TimeValue::new(1, -2),
+        TimeValue::new(-2, 4),
+        TimeValue::new(0, 0),
+        TimeValue::new(0, 0),
+        TimeValue::new(0, 7),
+        TimeValue::new(0, -7),
+    ]
+    .iter()
+    .cloned()
+    .collect();
+    assert_eq!(set.len(), 3);
+
+    let sum = TimeValue::new(1, 25) + TimeValue::new(1001, 30000);
+    assert_eq!((sum.value, sum.scale), (2201, 30000));
+    let difference = TimeValue::new(1, 25) - TimeValue::new(1001, 30000);
+    assert_eq!(difference, TimeValue::new(199, 30000));
+    assert_eq!(-difference, TimeValue::new(-199, 30000));
+    assert_eq!(TimeValue::new(1001, 30000) * 30, TimeValue::new(1001, 1000));
+    assert_eq!(TimeValue::new(i64::MAX, 1).checked_mul(2), None);
+    assert_eq!(
+        TimeValue::new(i64::MAX, 2).checked_add(TimeValue::new(1, 2)),
+        None
+    );
+    // No common scale fits in 64 bits.
+    assert_eq!(
+        TimeValue::new(1, i64::MAX).to_common_scale(TimeValue::new(1, i64::MAX - 1)),
+        None
+    );
+
+    let (a, b) = TimeValue::new(1, 25)
+        .to_common_scale(TimeValue::new(1, 30))
+        .unwrap();
+    assert_eq!((a.value, b.value, a.scale, b.scale), (6, 5, 150, 150));
+    assert_eq!(TimeValue::new(1001, 30000).to_string(), "1001/30000");
+    assert_eq!(TimeValue::new(3, 6).to_rational(), Some(ratio(1, 2)));
+    assert_eq!(TimeValue::new(3, 0).to_rational(), None);
+}
+
+#[test]
+fn frames() {
+    let rate = ratio(60000, 1001);
+    let t = TimeValue::from_frames(1000, rate).unwrap();
+    assert_eq!(t, TimeValue::new(1001 * 1000, 60000));
+    assert_eq!(t.to_frames(rate, Rounding::Exact), Some(1000));
+
+    // A day of 59.94 frames, in the driver's microseconds.
+    let day = 24 * 60 * 60 * 60000 / 1001;
+    let t = TimeValue::from_frames(day, rate)
+        .unwrap()
+        .rescale(1_000_000, Rounding::Nearest)
+        .unwrap();
+    assert_eq!(t.to_frames(rate, Rounding::Nearest), Some(day));
+    assert_eq!(t.to_frames(rate, Rounding::Exact), None);
+
+    // Halfway into frame 2 at 25 frames per second is still frame 2.
+    let t = TimeValue::new(100, 1000);
+    let rate = Rational::from_integer(25);
+    assert_eq!(t.to_frames(rate, Rounding::Down), Some(2));
+    assert_eq!(t.to_frames(rate, Rounding::Nearest), Some(3));
+    assert_eq!(
+        TimeValue::new(-1, 1000).to_frames(rate, Rounding::Down),
+        Some(-1)
+    );
+    assert_eq!(t.to_frames(Rational::from_integer(0), Rounding::Down), None);
+    assert_eq!(TimeValue::from_frames(1, ratio(-25, 1)), None);
+    assert_eq!(TimeValue::from_frames(i64::MAX, ratio(30000, 1001)), None);
+}
+
+#[test]
+fn durations() {
+    let t = TimeValue::from_duration(Duration::from_millis(1500), 90000, Rounding::Exact);
+    assert_eq!(t, Some(TimeValue::new(135000, 90000)));
+    assert_eq!(
+        TimeValue::from_duration(Duration::from_nanos(1), 25, Rounding::Up),
+        Some(TimeValue::new(1, 25))
+    );
+    assert_eq!(
+        TimeValue::from_duration(Duration::from_nanos(1), 25, Rounding::Exact),
+        None
+    );
+    assert_eq!(
+        TimeValue::new(1001, 30000).to_duration(),
+        Some(Duration::from_nanos(33_366_666))
+    );
+    assert_eq!(TimeValue::new(-1, 25).to_duration(), None);
+    assert!((TimeValue::new(1001, 30000).to_seconds_f64() - 0.033_366_666).abs() < 1e-9);
+
+    let time = FrameTime {
+        time: 3003,
+        duration: 1001,
+        time_scale: 30000,
+    };
+    assert_eq!(time.time_value(), TimeValue::new(1001, 10000));
+    assert_eq!(
+        time.time_value()
+            .to_frames(ratio(30000, 1001), Rounding::Exact),
+        Some(3)
+    );
+    assert_eq!(time.duration_value(), TimeValue::new(1001, 30000));
+}
@@ -0,0 +1,244 @@
+extern crate decklink;
+
+use decklink::{
+    DisplayMode, Frame, FrameTimecode, PixelFormat, SmpteTimecode, TimecodeFlags, TimecodeFormat,
+    TimecodeRate, VideoFrame,
+};
+
+const RATES: [TimecodeRate; 12] = [
+    TimecodeRate::RATE_24,
+    TimecodeRate::RATE_25,
+    TimecodeRate::RATE_29_97_DF,
+    TimecodeRate::RATE_30,
+    TimecodeRate::RATE_48,
+    TimecodeRate::RATE_50,
+    TimecodeRate::RATE_59_94_DF,
+    TimecodeRate::RATE_60,
+    TimecodeRate::RATE_96,
+    TimecodeRate::RATE_100,
+    TimecodeRate::RATE_119_88_DF,
+    TimecodeRate::RATE_120,
+];
+
+fn tc(text: &str) -> SmpteTimecode {
+    text.parse().unwrap()
+}
+
+fn components(timecode: SmpteTimecode) -> [u8; 4] {
+    [
+        timecode.hours(),
+        timecode.minutes(),
+        timecode.seconds(),
+        timecode.frames(),
+    ]
+}
+
+#[test]
+fn every_frame_of_the_day() {
+    for rate in RATES.iter() {
+        let fps = rate.frames_per_second() as u8;
+        let dropped = if rate.drop_frame() { fps / 15 } else { 0 };
+        // Count labels like a timecode generator, skipping dropped ones.
+        let mut label = [0u8; 4];
+        for n in 0..rate.frames_per_day() {
+            let timecode = SmpteTimecode::from_frames(n as i64, *rate);
+            assert_eq!(components(timecode), label, "frame {} at {}", n, rate);
+            assert_eq!(timecode.to_frames(*rate).unwrap(), n);
+
+            let [h, m, s, f] = &mut label;
+            *f += 1;
+            if *f == fps {
+                *f = 0;
+                *s += 1;
+                if *s == 60 {
+                    *s = 0;
+                    *m += 1;
+                    if *m == 60 {
+                        *m = 0;
+                        *h = (*h + 1) % 24;
+                    }
+                    if *m % 10 != 0 {
+                        *f = dropped;
+                    }
+                }
+            }
+        }
+        // The count wraps at midnight exactly.
+        assert_eq!(label, [0; 4], "{}", rate);
+        let day = rate.frames_per_day() as i64;
+        assert_eq!(components(SmpteTimecode::from_frames(day, *rate)), [0; 4]);
+        assert_eq!(
+            SmpteTimecode::from_frames(-1, *rate),
+            SmpteTimecode::from_frames(day - 1, *rate)
+        );
+    }
+}
+
+#[test]
+fn drop_frame() {
+    let rate = TimecodeRate::RATE_29_97_DF;
+    assert_eq!(rate.frames_per_day(), 2_589_408);
+    assert_eq!(SmpteTimecode::from_frames(1799, rate), tc("00:00:59;29"));
+    assert_eq!(SmpteTimecode::from_frames(1800, rate), tc("00:01:00;02"));
+    assert_eq!(SmpteTimecode::from_frames(17_982, rate), tc("00:10:00;00"));
+    assert_eq!(tc("01:00:00;00").to_frames(rate).unwrap(), 107_892);
+    assert!(tc("00:01:00;01").to_frames(rate).is_err());
+    assert!(tc("00:10:00;00").to_frames(rate).is_ok());
+
+    let rate = TimecodeRate::RATE_59_94_DF;
+    assert_eq!(rate.frames_per_day(), 5_178_816);
+    assert_eq!(SmpteTimecode::from_frames(3600, rate), tc("00:01:00;04"));
+    assert!(tc("00:01:00;03").to_frames(rate).is_err());
+
+    let rate = TimecodeRate::RATE_119_88_DF;
+    assert_eq!(SmpteTimecode::from_frames(7200, rate), tc("00:01:00;08"));
+    assert_eq!(tc("00:00:00;119").to_frames(rate).unwrap(), 119);
+}
+
+#[test]
+fn invalid_labels() {
+    // Frame numbers past the rate, and drop frame labels at non-drop rates and vice versa.
+    assert!(tc("00:00:00:25").to_frames(TimecodeRate::RATE_25).is_err());
+    assert!(tc("00:00:00:24").to_frames(TimecodeRate::RATE_25).is_ok());
+    assert!(tc("00:00:00;00").to_frames(TimecodeRate::RATE_30).is_err());
+    assert!(tc("00:00:00:00")
+        .to_frames(TimecodeRate::RATE_29_97_DF)
+        .is_err());
+
+    assert!(TimecodeRate::new(25, true).is_err());
+    assert!(TimecodeRate::new(0, false).is_err());
+    assert!(TimecodeRate::new(121, false).is_err());
+    assert_eq!(
+        TimecodeRate::new(60, true).unwrap(),
+        TimecodeRate::RATE_59_94_DF
+    );
+    assert!(SmpteTimecode::new(24, 0, 0, 0, false).is_err());
+    assert!(SmpteTimecode::new(0, 60, 0, 0, false).is_err());
+    assert!(SmpteTimecode::new(0, 0, 60, 0, false).is_err());
+}
+
+#[test]
+fn arithmetic() {
+    let rate = TimecodeRate::RATE_29_97_DF;
+    assert_eq!(
+        tc("23:59:59;29").add_frames(1, rate).unwrap(),
+        tc("00:00:00;00")
+    );
+    assert_eq!(
+        tc("00:00:59;29").add_frames(1, rate).unwrap(),
+        tc("00:01:00;02")
+    );
+    assert_eq!(
+        tc("00:01:00;02").sub_frames(1, rate).unwrap(),
+        tc("00:00:59;29")
+    );
+    assert_eq!(
+        tc("00:00:00;00").sub_frames(1, rate).unwrap(),
+        tc("23:59:59;29")
+    );
+    assert_eq!(
+        tc("01:00:00;00")
+            .frames_since(&tc("00:00:00;00"), rate)
+            .unwrap(),
+        107_892
+    );
+    assert_eq!(
+        tc("00:00:00;00")
+            .frames_since(&tc("00:00:01;00"), rate)
+            .unwrap(),
+        -30
+    );
+
+    let rate = TimecodeRate::RATE_25;
+    assert_eq!(
+        tc("10:00:00:00").add_frames(25 * 3600 * 15, rate).unwrap(),
+        tc("01:00:00:00")
+    );
+    assert!(tc("10:00:00;00").add_frames(1, rate).is_err());
+
+    assert!(tc("00:00:00:01") < tc("00:00:01:00"));
+    assert!(tc("23:00:00:00") > tc("01:59:59:29"));
+}
+
+#[test]
+fn parse_and_format() {
+    let timecode = tc("01:02:03;04");
+    assert_eq!(components(timecode), [1, 2, 3, 4]);
+    assert!(timecode.drop_frame());
+    assert_eq!(timecode.to_string(), "01:02:03;04");
+    assert_eq!(tc("01:02:03.04"), timecode);
+    assert_eq!(tc("1:2:3:4").to_string(), "01:02:03:04");
+    assert_eq!(tc("00:00:00:119").to_string(), "00:00:00:119");
+
+    for text in [
+        "",
+        "01:00:00",
+        "01:00:00:00:00",
+        "24:00:00:00",
+        "01:60:00:00",
+        "aa:00:00:00",
+        "01-00-00-00",
+        "01:00:00:",
+        "01:00:00:-1",
+        "01:00:00:256",
+        "01;00;00;00",
+    ]
+    .iter()
+    {
+        assert!(text.parse::<SmpteTimecode>().is_err(), "{:?}", text);
+    }
+
+    for rate in RATES.iter() {
+        for n in (0..rate.frames_per_day()).step_by(997) {
+            let timecode = SmpteTimecode::from_frames(n as i64, *rate);
+            assert_eq!(tc(&timecode.to_string()), timecode);
+        }
+    }
+}
+
+#[test]
+fn rates() {
+    let rate = |duration, scale| TimecodeRate::from_frame_rate(duration, scale);
+    assert_eq!(rate(1001, 24000), Some(TimecodeRate::RATE_23_98));
+    assert_eq!(rate(1000, 25000), Some(TimecodeRate::RATE_25));
+    assert_eq!(rate(1001, 30000), Some(TimecodeRate::RATE_29_97_DF));
+    assert_eq!(rate(100, 3000), Some(TimecodeRate::RATE_30));
+    assert_eq!(rate(1001, 60000), Some(TimecodeRate::RATE_59_94_DF));
+    assert_eq!(rate(1001, 120000), Some(TimecodeRate::RATE_119_88_DF));
+    assert_eq!(rate(1000, 100000), Some(TimecodeRate::RATE_100));
+    assert_eq!(rate(3, 10), None);
+    assert_eq!(rate(0, 25), None);
+
+    assert_eq!(
+        TimecodeRate::for_mode(DisplayMode::MODE_HD1080I5994),
+        Some(TimecodeRate::RATE_29_97_DF)
+    );
+    assert_eq!(
+        TimecodeRate::for_mode(DisplayMode::MODE_HD720P50),
+        Some(TimecodeRate::RATE_50)
+    );
+    for spec in DisplayMode::catalog() {
+        assert!(TimecodeRate::for_mode(spec.mode).is_some(), "{}", spec.mode);
+    }
+    assert_eq!(TimecodeRate::RATE_59_94_DF.to_string(), "60 DF");
+}
+
+#[test]
+fn sdk_timecodes() {
+    let timecode = tc("10:20:30;15");
+    let frame_timecode = FrameTimecode::from(timecode);
+    assert_eq!(frame_timecode.flags, TimecodeFlags::DROP_FRAME);
+    assert_eq!(
+        SmpteTimecode::from_frame_timecode(&frame_timecode).unwrap(),
+        timecode
+    );
+
+    let mut frame = Frame::new(16, 2, PixelFormat::FORMAT_8BIT_BGRA).unwrap();
+    frame.set_timecode(TimecodeFormat::FORMAT_RP188_VITC1, frame_timecode);
+    let sdk = frame
+        .get_timecode(TimecodeFormat::FORMAT_RP188_ANY)
+        .unwrap()
+        .unwrap();
+    assert_eq!(SmpteTimecode::from_timecode(&sdk).unwrap(), timecode);
+    assert_eq!(sdk.get_string().unwrap(), timecode.to_string());
+}
@@ -0,0 +1,195 @@
+extern crate decklink;
+
+use std::time::Duration;
+
+use decklink::timecode::generator::TimecodeGenerator;
+use decklink::{
+    DisplayMode, Frame, FrameTimecode, PixelFormat, SmpteTimecode, TimecodeFlags, TimecodeFormat,
+    TimecodeRate,
+};
+
+fn tc(text: &str) -> SmpteTimecode {
+    text.parse().unwrap()
+}
+
+fn components(text: &str, flags: TimecodeFlags) -> FrameTimecode {
+    FrameTimecode {
+        flags,
+        ..FrameTimecode::from(tc(text))
+    }
+}
+
+#[test]
+fn progressive_wraps_at_midnight() {
+    let generator = TimecodeGenerator::new(DisplayMode::MODE_HD1080P25, tc("23:59:59:23")).unwrap();
+    assert_eq!(generator.rate(), TimecodeRate::RATE_25);
+    let stamps: Vec<_> = generator.take(3).collect();
+    let labels: Vec<_> = stamps.iter().map(|s| s.timecode).collect();
+    assert_eq!(
+        labels,
+        vec![tc("23:59:59:23"), tc("23:59:59:24"), tc("00:00:00:00")]
+    );
+    let formats: Vec<_> = stamps[0].components.iter().map(|(f, _)| *f).collect();
+    assert_eq!(
+        formats,
+        vec![
+            TimecodeFormat::FORMAT_RP188_VITC1,
+            TimecodeFormat::FORMAT_RP188_LTC
+        ]
+    );
+    assert_eq!(
+        stamps[2].get(TimecodeFormat::FORMAT_RP188_LTC),
+        Some(components("00:00:00:00", TimecodeFlags::DEFAULT))
+    );
+}
+
+#[test]
+fn interlaced_field_marks() {
+    let mut generator =
+        TimecodeGenerator::new(DisplayMode::MODE_HD1080I5994, tc("00:00:59;29")).unwrap();
+    assert_eq!(generator.rate(), TimecodeRate::RATE_29_97_DF);
+    let stamp = generator.next().unwrap();
+    assert_eq!(
+        stamp.get(TimecodeFormat::FORMAT_RP188_VITC1),
+        Some(components("00:00:59;29", TimecodeFlags::DROP_FRAME))
+    );
+    assert_eq!(
+        stamp.get(TimecodeFormat::FORMAT_RP188_VITC2),
+        Some(components(
+            "00:00:59;29",
+            TimecodeFlags::DROP_FRAME | TimecodeFlags::FIELD_MARK
+        ))
+    );
+    assert_eq!(stamp.get(TimecodeFormat::FORMAT_VITC), None);
+    // Drop frame skips ;00 and ;01.
+    assert_eq!(generator.next().unwrap().timecode, tc("00:01:00;02"));
+
+    // Standard definition also carries VITC.
+    let stamp = TimecodeGenerator::new(DisplayMode::MODE_PAL, tc("10:00:00:00"))
+        .unwrap()
+        .next()
+        .unwrap();
+    assert_eq!(
+        stamp.get(TimecodeFormat::FORMAT_VITC),
+        Some(components("10:00:00:00", TimecodeFlags::DEFAULT))
+    );
+    assert_eq!(
+        stamp.get(TimecodeFormat::FORMAT_VITC_FIELD2),
+        Some(components("10:00:00:00", TimecodeFlags::FIELD_MARK))
+    );
+}
+
+#[test]
+fn frame_pairs() {
+    let generator =
+        TimecodeGenerator::new(DisplayMode::MODE_HD1080P5994, tc("00:00:59;58")).unwrap();
+    assert_eq!(generator.rate(), TimecodeRate::RATE_59_94_DF);
+    let ltc: Vec<_> = generator
+        .take(4)
+        .map(|stamp| stamp.get(TimecodeFormat::FORMAT_RP188_LTC).unwrap())
+        .collect();
+    let drop_frame = TimecodeFlags::DROP_FRAME;
+    let second = TimecodeFlags::DROP_FRAME | TimecodeFlags::FIELD_MARK;
+    assert_eq!(
+        ltc,
+        vec![
+            components("00:00:59;29", drop_frame),
+            components("00:00:59;29", second),
+            components("00:01:00;02", drop_frame),
+            components("00:01:00;02", second),
+        ]
+    );
+}
+
+#[test]
+fn jam_sync() {
+    let mut generator =
+        TimecodeGenerator::new(DisplayMode::MODE_HD1080P5994, tc("00:00:00;00")).unwrap();
+    let captured = components(
+        "01:00:00;10",
+        TimecodeFlags::DROP_FRAME | TimecodeFlags::FIELD_MARK,
+    );
+    generator.jam_sync(&captured, 1).unwrap();
+    assert_eq!(generator.peek(), tc("01:00:00;22"));
+    let stamp = generator.next().unwrap();
+    assert_eq!(
+        stamp.get(TimecodeFormat::FORMAT_RP188_VITC1),
+        Some(components("01:00:00;11", TimecodeFlags::DROP_FRAME))
+    );
+
+    // Jamming to the end of the day wraps.
+    let mut generator =
+        TimecodeGenerator::new(DisplayMode::MODE_HD1080P25, tc("00:00:00:00")).unwrap();
+    generator
+        .jam_sync(&components("23:59:59:24", TimecodeFlags::DEFAULT), 2)
+        .unwrap();
+    assert_eq!(generator.peek(), tc("00:00:00:01"));
+
+    // The captured timecode has to exist in the output mode.
+    assert!(generator
+        .jam_sync(&components("01:00:00;00", TimecodeFlags::DROP_FRAME), 1)
+        .is_err());
+    assert!(generator
+        .jam_sync(&components("01:00:00:25", TimecodeFlags::DEFAULT), 1)
+        .is_err());
+}
+
+#[test]
+fn time_of_day() {
+    let start = |mode, seconds: f64| {
+        TimecodeGenerator::from_time_of_day(mode, Duration::from_secs_f64(seconds))
+            .unwrap()
+            .peek()
+    };
+    assert_eq!(
+        start(DisplayMode::MODE_HD1080I50, 3600.5),
+        tc("01:00:00:12")
+    );
+    assert_eq!(
+        start(DisplayMode::MODE_HD1080I5994, 3600.0),
+        tc("01:00:00;00")
+    );
+    // Non-drop frame at 23.98 falls behind the clock.
+    assert_eq!(
+        start(DisplayMode::MODE_HD1080P2398, 3600.0),
+        tc("00:59:56:09")
+    );
+    assert!(TimecodeGenerator::from_system_clock(DisplayMode::MODE_HD1080P25).is_ok());
+}
+
+#[test]
+fn user_bits_and_frames() {
+    let mut generator = TimecodeGenerator::new(DisplayMode::MODE_NTSC, tc("01:00:00;00")).unwrap();
+    generator.set_user_bits(0x1234_5678);
+    let stamp = generator.next().unwrap();
+    assert_eq!(stamp.user_bits, 0x1234_5678);
+
+    let mut frame = Frame::new(720, 486, PixelFormat::FORMAT_8BIT_YUV).unwrap();
+    stamp.write_to_frame(&mut frame);
+    assert_eq!(frame.timecodes().len(), 5);
+    assert_eq!(
+        frame.timecode(TimecodeFormat::FORMAT_VITC_FIELD2),
+        Some(components(
+            "01:00:00;00",
+            TimecodeFlags::DROP_FRAME | TimecodeFlags::FIELD_MARK
+        ))
+    );
+
+    // Non-drop frame at 29.97 counts every label.
+    let mut generator =
+        TimecodeGenerator::new(DisplayMode::MODE_HD1080I5994, tc("00:00:59:29")).unwrap();
+    assert_eq!(generator.rate(), TimecodeRate::new(30, false).unwrap());
+    generator.next();
+    let stamp = generator.next().unwrap();
+    assert_eq!(stamp.timecode, tc("00:01:00:00"));
+    assert_eq!(
+        stamp.get(TimecodeFormat::FORMAT_RP188_VITC1),
+        Some(components("00:01:00:00", TimecodeFlags::DEFAULT))
+    );
+
+    assert!(TimecodeGenerator::new(DisplayMode::MODE_HD1080P25, tc("01:00:00;00")).is_err());
+    // Drop frame only makes sense at 1000/1001 rates, even where the nominal rate allows it.
+    assert!(TimecodeGenerator::new(DisplayMode::MODE_HD1080P30, tc("01:00:00;00")).is_err());
+    assert!(TimecodeGenerator::new(DisplayMode::MODE_HD1080P6000, tc("01:00:00;00")).is_err());
+    assert!(TimecodeGenerator::new(DisplayMode::MODE_UNKNOWN, tc("01:00:00:00")).is_err());
+}
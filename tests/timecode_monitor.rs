@@ -0,0 +1,259 @@
+extern crate decklink;
+
+use decklink::timecode::generator::TimecodeGenerator;
+use decklink::timecode::monitor::{TimecodeEvent, TimecodeMonitor, TimecodeStats};
+use decklink::{DisplayMode, FrameTimecode, SmpteTimecode, TimecodeFlags, TimecodeFormat};
+
+fn tc(text: &str) -> SmpteTimecode {
+    text.parse().unwrap()
+}
+
+// Feeds RP188 VITC1 labels, or `None` for frames without timecode, one frame apart in stream
+// time.
+struct Feed {
+    monitor: TimecodeMonitor,
+    frame_duration: i64,
+    frame: i64,
+}
+
+impl Feed {
+    fn new(mode: DisplayMode) -> Feed {
+        Feed {
+            monitor: TimecodeMonitor::new(mode).unwrap(),
+            frame_duration: mode.info().unwrap().frame_duration,
+            frame: 0,
+        }
+    }
+
+    fn skip(&mut self, frames: i64) {
+        self.frame += frames;
+    }
+
+    fn components(&mut self, timecode: Option<FrameTimecode>) -> Vec<TimecodeEvent> {
+        let timecode = timecode.map(|t| (TimecodeFormat::FORMAT_RP188_VITC1, t));
+        let events = self
+            .monitor
+            .observe(timecode, self.frame * self.frame_duration);
+        self.frame += 1;
+        events
+    }
+
+    fn label(&mut self, text: &str) -> Vec<TimecodeEvent> {
+        self.components(Some(FrameTimecode::from(tc(text))))
+    }
+
+    fn labels(&mut self, from: SmpteTimecode, count: i64) -> Vec<TimecodeEvent> {
+        let rate = decklink::TimecodeRate::new(25, from.drop_frame()).unwrap();
+        (0..count)
+            .flat_map(|n| {
+                let label = from.add_frames(n, rate).unwrap();
+                self.components(Some(FrameTimecode::from(label)))
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn continuous_across_midnight() {
+    let mut feed = Feed::new(DisplayMode::MODE_HD1080P25);
+    let events = feed.labels(tc("23:59:58:00"), 100);
+    assert_eq!(events, vec![TimecodeEvent::Acquired(tc("23:59:58:00"))]);
+
+    // Frames lost on capture move the stream time on too.
+    feed.skip(3);
+    assert_eq!(feed.labels(tc("00:00:02:03"), 10), vec![]);
+    let stats = feed.monitor.stats();
+    assert_eq!(stats.frames, 110);
+    assert_eq!(stats.dropped, 3);
+    assert_eq!(stats.last, Some(tc("00:00:02:12")));
+    assert_eq!(stats.discontinuities + stats.backward_jumps, 0);
+}
+
+#[test]
+fn jumps() {
+    let mut feed = Feed::new(DisplayMode::MODE_HD1080P25);
+    feed.labels(tc("10:00:00:00"), 5);
+    let events = feed.label("10:00:01:00");
+    let jump = TimecodeEvent::Discontinuity {
+        expected: tc("10:00:00:05"),
+        actual: tc("10:00:01:00"),
+        frames: 20,
+    };
+    assert_eq!(events, vec![jump]);
+    assert_eq!(
+        jump.to_string(),
+        "timecode jumped 20 frames ahead, to 10:00:01:00 instead of 10:00:00:05"
+    );
+    // The count carries on from the new label.
+    assert_eq!(feed.label("10:00:01:01"), vec![]);
+
+    let events = feed.label("09:59:59:24");
+    let back = TimecodeEvent::Backwards {
+        expected: tc("10:00:01:02"),
+        actual: tc("09:59:59:24"),
+        frames: 28,
+    };
+    assert_eq!(events, vec![back]);
+    assert_eq!(
+        back.to_string(),
+        "timecode went 28 frames back, to 09:59:59:24 instead of 10:00:01:02"
+    );
+
+    let stats = feed.monitor.stats();
+    assert_eq!((stats.discontinuities, stats.backward_jumps), (1, 1));
+}
+
+#[test]
+fn freezes() {
+    let mut feed = Feed::new(DisplayMode::MODE_HD1080P25);
+    feed.label("10:00:00:00");
+    assert_eq!(feed.label("10:00:00:01"), vec![]);
+    assert_eq!(
+        feed.label("10:00:00:01"),
+        vec![TimecodeEvent::Freeze(tc("10:00:00:01"))]
+    );
+    assert_eq!(feed.label("10:00:00:01"), vec![]);
+    assert_eq!(feed.label("10:00:00:01"), vec![]);
+    // Picking up where it stopped isn't a jump.
+    assert_eq!(feed.label("10:00:00:02"), vec![]);
+    assert_eq!(
+        feed.label("10:00:00:02"),
+        vec![TimecodeEvent::Freeze(tc("10:00:00:02"))]
+    );
+    // Picking up where it would have been is.
+    assert_eq!(
+        feed.label("10:00:00:05"),
+        vec![TimecodeEvent::Discontinuity {
+            expected: tc("10:00:00:03"),
+            actual: tc("10:00:00:05"),
+            frames: 2,
+        }]
+    );
+    let stats = feed.monitor.stats();
+    assert_eq!((stats.freezes, stats.frozen_frames), (2, 4));
+}
+
+#[test]
+fn missing_and_invalid() {
+    let mut feed = Feed::new(DisplayMode::MODE_HD1080P25);
+    assert_eq!(feed.components(None), vec![TimecodeEvent::Missing]);
+    assert_eq!(feed.components(None), vec![]);
+    assert_eq!(
+        feed.label("01:00:00:00"),
+        vec![TimecodeEvent::Acquired(tc("01:00:00:00"))]
+    );
+    assert_eq!(feed.components(None), vec![TimecodeEvent::Missing]);
+    // Timecode coming back starts the count again.
+    assert_eq!(
+        feed.label("02:00:00:00"),
+        vec![TimecodeEvent::Acquired(tc("02:00:00:00"))]
+    );
+
+    let invalid = FrameTimecode {
+        frames: 25,
+        ..FrameTimecode::from(tc("02:00:00:00"))
+    };
+    assert_eq!(
+        feed.components(Some(invalid)),
+        vec![TimecodeEvent::Invalid(invalid)]
+    );
+    assert_eq!(
+        TimecodeEvent::Invalid(invalid).to_string(),
+        "invalid timecode 02:00:00:25"
+    );
+    // Drop frame at 25 frames per second.
+    let invalid = FrameTimecode::from(tc("02:00:00;02"));
+    assert_eq!(
+        feed.components(Some(invalid)),
+        vec![TimecodeEvent::Invalid(invalid)]
+    );
+    // Invalid labels don't break the count.
+    assert_eq!(feed.label("02:00:00:03"), vec![]);
+
+    let stats = feed.monitor.stats();
+    assert_eq!((stats.missing, stats.invalid), (3, 2));
+    assert_eq!(
+        stats.to_string(),
+        "8 frames, 0 dropped, 3 without timecode, 0 discontinuities, 0 backward jumps, \
+         0 freezes over 0 frames, 0 drop frame changes, 2 invalid, last 02:00:00:03"
+    );
+    assert_eq!(
+        TimecodeStats::default().to_string(),
+        "0 frames, 0 dropped, 0 without timecode, 0 discontinuities, 0 backward jumps, \
+         0 freezes over 0 frames, 0 drop frame changes, 0 invalid, last none"
+    );
+
+    feed.monitor.reset();
+    assert_eq!(feed.monitor.stats(), TimecodeStats::default());
+}
+
+#[test]
+fn drop_frame() {
+    let mut feed = Feed::new(DisplayMode::MODE_HD1080I5994);
+    feed.label("00:00:59;29");
+    // Drop frame skips 00:01:00;00 and ;01.
+    assert_eq!(feed.label("00:01:00;02"), vec![]);
+    assert_eq!(
+        feed.components(Some(FrameTimecode::from(tc("00:01:00;01")))),
+        vec![TimecodeEvent::Invalid(FrameTimecode::from(tc(
+            "00:01:00;01"
+        )))]
+    );
+    let events = feed.label("00:01:00:04");
+    assert_eq!(
+        events,
+        vec![TimecodeEvent::DropFrameChanged(tc("00:01:00:04"))]
+    );
+    assert_eq!(
+        events[0].to_string(),
+        "timecode changed to non-drop frame at 00:01:00:04"
+    );
+    assert_eq!(feed.label("00:01:00:05"), vec![]);
+    assert_eq!(feed.monitor.stats().drop_frame_changes, 1);
+}
+
+#[test]
+fn frame_pairs() {
+    // 59.94p RP188 counts pairs of frames, with the field mark on the second of each.
+    let mode = DisplayMode::MODE_HD1080P5994;
+    let mut feed = Feed::new(mode);
+    let mut generator = TimecodeGenerator::new(mode, tc("00:00:59;50")).unwrap();
+    let mut events = Vec::new();
+    for _ in 0..20 {
+        let stamp = generator.next().unwrap();
+        events.extend(feed.components(stamp.get(TimecodeFormat::FORMAT_RP188_VITC1)));
+    }
+    assert_eq!(events, vec![TimecodeEvent::Acquired(tc("00:00:59;50"))]);
+    assert_eq!(feed.monitor.stats().last, Some(tc("00:01:00;13")));
+
+    // Losing the field mark repeats the first frame of the pair.
+    let stamp = generator.next().unwrap();
+    let first = stamp.get(TimecodeFormat::FORMAT_RP188_VITC1).unwrap();
+    let unmarked = FrameTimecode {
+        flags: first.flags & !TimecodeFlags::FIELD_MARK,
+        ..first
+    };
+    feed.components(Some(first));
+    assert_eq!(
+        feed.components(Some(unmarked)),
+        vec![TimecodeEvent::Freeze(tc("00:01:00;14"))]
+    );
+
+    // The full count in the high frame rate format is used as it is, though it's only read by
+    // default above 60 frames per second.
+    let mut monitor = TimecodeMonitor::new(mode).unwrap();
+    assert_eq!(
+        monitor.formats(),
+        &[
+            TimecodeFormat::FORMAT_RP188_VITC1,
+            TimecodeFormat::FORMAT_RP188_LTC,
+            TimecodeFormat::FORMAT_VITC,
+        ]
+    );
+    let full = FrameTimecode::from(tc("00:01:00;14"));
+    let format = TimecodeFormat::FORMAT_RP188_HIGH_FRAME_RATE;
+    monitor.observe(Some((format, full)), 0);
+    assert_eq!(monitor.stats().last, Some(tc("00:01:00;14")));
+
+    assert!(TimecodeMonitor::new(DisplayMode::MODE_UNKNOWN).is_err());
+}
@@ -0,0 +1,158 @@
+extern crate decklink;
+
+use decklink::audio::tone::{Signal, ToneGenerator, EBU_LINEUP_DBFS, SMPTE_LINEUP_DBFS};
+use decklink::{DisplayMode, Lineup, PixelFormat, VideoBuffer};
+
+fn generate(signal: Signal, channels: usize, frames: usize) -> Vec<i32> {
+    let mut generator = ToneGenerator::new(signal, 48000, channels).unwrap();
+    let mut samples = vec![0i32; frames * channels];
+    generator.fill(&mut samples).unwrap();
+    samples
+}
+
+fn channel(samples: &[i32], channels: usize, c: usize) -> Vec<f64> {
+    samples
+        .iter()
+        .skip(c)
+        .step_by(channels)
+        .map(|s| *s as f64 / 2_147_483_648.0)
+        .collect()
+}
+
+fn rms(x: &[f64]) -> f64 {
+    (x.iter().map(|x| x * x).sum::<f64>() / x.len() as f64).sqrt()
+}
+
+#[test]
+fn lineup_sine() {
+    for &level in [EBU_LINEUP_DBFS, SMPTE_LINEUP_DBFS].iter() {
+        let samples = generate(Signal::lineup(level), 3, 48000);
+        let amplitude = 10f64.powf(level / 20.0);
+        for c in 0..3 {
+            let x = channel(&samples, 3, c);
+            let peak = x.iter().fold(0.0f64, |a, b| a.max(b.abs()));
+            assert!((peak - amplitude).abs() < 1e-6);
+            assert!((rms(&x) - amplitude / 2f64.sqrt()).abs() < 1e-6);
+            // 1 kHz at 48 kHz repeats every 48 samples.
+            assert!((x[1000] - x[1048]).abs() < 1e-6);
+        }
+    }
+
+    // Buffers continue where the previous one left off, in any sample type.
+    let mut generator = ToneGenerator::new(Signal::lineup(-18.0), 48000, 2).unwrap();
+    let mut first = vec![0i16; 2 * 100];
+    let mut second = vec![0i16; 2 * 77];
+    generator.fill(&mut first).unwrap();
+    generator.fill(&mut second).unwrap();
+    generator.reset();
+    let mut whole = vec![0i16; 2 * 177];
+    generator.fill(&mut whole).unwrap();
+    assert_eq!(&whole[..200], &first[..]);
+    assert_eq!(&whole[200..], &second[..]);
+    assert!(generator.fill(&mut whole[..3]).is_err());
+}
+
+#[test]
+fn glits() {
+    let samples = generate(Signal::Glits, 2, 48000 * 4);
+    let left = channel(&samples, 2, 0);
+    let right = channel(&samples, 2, 1);
+    let ms = |x: &[f64], from: usize, to: usize| rms(&x[from * 48..to * 48]);
+    let tone = 10f64.powf(-18.0 / 20.0) / 2f64.sqrt();
+
+    assert_eq!(ms(&left, 0, 250), 0.0);
+    assert!((ms(&left, 250, 4000) - tone).abs() < 1e-3);
+    assert!((ms(&right, 0, 500) - tone).abs() < 1e-3);
+    assert_eq!(ms(&right, 500, 750), 0.0);
+    assert!((ms(&right, 750, 1000) - tone).abs() < 1e-3);
+    assert_eq!(ms(&right, 1000, 1250), 0.0);
+    assert!((ms(&right, 1250, 4000) - tone).abs() < 1e-3);
+}
+
+#[test]
+fn channel_ident() {
+    let channels = 4;
+    // One cycle: 200 ms per channel plus a second of silence.
+    let samples = generate(Signal::ChannelIdent, channels, 48 * 1800);
+    for c in 0..channels {
+        let x = channel(&samples, channels, c);
+        // Count the 10 ms windows that start a beep.
+        let on: Vec<bool> = x.chunks(480).map(|w| rms(w) > 0.01).collect();
+        let beeps = on.windows(2).filter(|w| !w[0] && w[1]).count() + on[0] as usize;
+        assert_eq!(beeps, c + 1, "channel {}", c);
+    }
+}
+
+#[test]
+fn pink_noise() {
+    let samples = generate(Signal::PinkNoise { level_dbfs: -20.0 }, 2, 48000 * 10);
+    let left = channel(&samples, 2, 0);
+    let right = channel(&samples, 2, 1);
+    for x in [&left, &right].iter() {
+        let level = 20.0 * rms(x).log10();
+        assert!((level + 20.0).abs() < 1.0, "{}", level);
+    }
+    // The channels are independent.
+    let correlation: f64 = left
+        .iter()
+        .zip(right.iter())
+        .map(|(l, r)| l * r)
+        .sum::<f64>()
+        / (rms(&left) * rms(&right) * left.len() as f64);
+    assert!(correlation.abs() < 0.05, "{}", correlation);
+
+    // Pink noise has most of its power at low frequencies, so differencing reduces it, where it
+    // would raise white noise by 3 dB.
+    let diff: Vec<f64> = left.windows(2).map(|w| w[1] - w[0]).collect();
+    assert!(rms(&diff) < rms(&left));
+
+    assert_eq!(
+        generate(Signal::PinkNoise { level_dbfs: -20.0 }, 2, 1000),
+        samples[..2000].to_vec()
+    );
+}
+
+#[test]
+fn silence() {
+    assert!(generate(Signal::Silence, 5, 100).iter().all(|s| *s == 0));
+    let mut generator = ToneGenerator::new(Signal::lineup(-18.0), 48000, 1).unwrap();
+    generator.set_signal(Signal::Silence);
+    let mut samples = vec![1i16; 10];
+    generator.fill(&mut samples).unwrap();
+    assert_eq!(samples, vec![0; 10]);
+    assert!(ToneGenerator::new(Signal::Silence, 48000, 0).is_err());
+}
+
+#[test]
+fn lineup_cadence() {
+    let tone = ToneGenerator::new(Signal::lineup(-20.0), 48000, 2).unwrap();
+    let video = VideoBuffer::new(720, 486, PixelFormat::FORMAT_8BIT_YUV).unwrap();
+    let lineup = Lineup::new(video, tone.clone(), DisplayMode::MODE_NTSC).unwrap();
+    let cadence: Vec<usize> = (0..5).map(|n| lineup.audio_frames_for(n)).collect();
+    assert_eq!(cadence, vec![1602, 1601, 1602, 1601, 1602]);
+    assert_eq!(cadence.iter().sum::<usize>(), 8008);
+    assert_eq!(lineup.next_frame(), 0);
+
+    let video = VideoBuffer::new(1920, 1080, PixelFormat::FORMAT_8BIT_YUV).unwrap();
+    assert!(Lineup::new(video, tone, DisplayMode::MODE_NTSC).is_err());
+}
+
+#[test]
+fn lineup_bars() {
+    let tone = ToneGenerator::new(Signal::lineup(-20.0), 48000, 2).unwrap();
+    let lineup = Lineup::smpte_bars(
+        tone.clone(),
+        DisplayMode::MODE_HD1080I5994,
+        PixelFormat::FORMAT_10BIT_YUV,
+    )
+    .unwrap();
+    assert_eq!(lineup.next_frame(), 0);
+    assert_eq!(lineup.dropped_audio_frames(), 0);
+    assert_eq!(lineup.audio_frames_for(0), 1602);
+    assert!(Lineup::smpte_bars(
+        tone,
+        DisplayMode::MODE_UNKNOWN,
+        PixelFormat::FORMAT_8BIT_YUV
+    )
+    .is_err());
+}